@@ -1,6 +1,6 @@
 #!/usr/bin/env python3
 """
-Bump version in all 6 locations for uti project.
+Bump version in all 7 locations for uti project.
 
 Usage:
     python bump_version.py <version>
@@ -36,6 +36,20 @@ def update_json_version(file_path: Path, version: str) -> None:
     print(f"  Updated: {file_path.relative_to(get_project_root())}")
 
 
+def update_extension_version(file_path: Path, version: str) -> None:
+    """Update version-name in the GNOME extension metadata.
+
+    The integer "version" key is reserved for extensions.gnome.org.
+    """
+    with open(file_path) as f:
+        data = json.load(f)
+    data["version-name"] = version
+    with open(file_path, "w") as f:
+        json.dump(data, f, indent=2)
+        f.write("\n")
+    print(f"  Updated: {file_path.relative_to(get_project_root())}")
+
+
 def update_cargo_version(file_path: Path, version: str) -> None:
     """Update version in a Cargo.toml file."""
     content = file_path.read_text()
@@ -99,7 +113,10 @@ def main():
     # 6. daemon/uti-daemon.spec
     update_rpm_spec_version(root / "daemon" / "uti-daemon.spec", version)
 
-    print(f"\nAll 6 files updated to version {version}")
+    # 7. gnome-extension/metadata.json
+    update_extension_version(root / "gnome-extension" / "metadata.json", version)
+
+    print(f"\nAll 7 files updated to version {version}")
 
 
 if __name__ == "__main__":
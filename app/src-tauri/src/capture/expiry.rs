@@ -0,0 +1,77 @@
+//! Clipboard history expiry
+//!
+//! With `clipboardItemTtl` set, unpinned items older than that many days
+//! are dropped from history when it's opened and then periodically. The
+//! config is reloaded whenever the file changes. Dropped items are
+//! announced with `clipboard-expired`, carrying how many were removed.
+
+use crate::config::AppConfig;
+use crate::state::AppState;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::info;
+
+/// Event emitted with the number of items dropped
+const CLIPBOARD_EXPIRED_EVENT: &str = "clipboard-expired";
+
+/// How often expired items are looked for
+const EXPIRY_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Converts the `clipboardItemTtl` option to a maximum item age
+///
+/// # Returns
+///
+/// None if items never expire (0 days)
+pub fn max_age(ttl_days: u64) -> Option<Duration> {
+    (ttl_days > 0).then(|| Duration::from_secs(ttl_days.saturating_mul(24 * 60 * 60)))
+}
+
+/// Drops expired items until the app exits
+pub async fn run(app: AppHandle) {
+    let path = AppConfig::get_config_path();
+    let mut modified: Option<SystemTime> = None;
+    let mut age: Option<Duration> = None;
+
+    loop {
+        let current = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if current != modified {
+            modified = current;
+            age = max_age(AppConfig::load().clipboard_item_ttl);
+        }
+
+        if let Some(age) = age {
+            expire(&app, age);
+        }
+
+        tokio::time::sleep(EXPIRY_INTERVAL).await;
+    }
+}
+
+/// Drops items older than `age`, emitting `clipboard-expired` if any were
+fn expire(app: &AppHandle, age: Duration) {
+    let state = app.state::<AppState>();
+    let result = state.clipboard.write().expire(age);
+    match result {
+        Ok(0) => {}
+        Ok(removed) => {
+            info!("Expired {} clipboard items", removed);
+            let _ = app.emit(CLIPBOARD_EXPIRED_EVENT, removed);
+        }
+        Err(e) => crate::error::report(
+            app,
+            "clipboard",
+            format!("Failed to expire clipboard items: {}", e),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_age() {
+        assert_eq!(max_age(0), None);
+        assert_eq!(max_age(7), Some(Duration::from_secs(7 * 24 * 60 * 60)));
+    }
+}
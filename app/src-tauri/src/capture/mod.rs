@@ -0,0 +1,260 @@
+//! Clipboard capture
+//!
+//! The [`watcher`] detects text copied in the desktop session and records
+//! it in history through [`record`], even while the window is closed.
+//! [`restore`] puts an item back on the clipboard in its stored formats.
+//! Capture can be paused with the `set_clipboard_capture_enabled` command
+//! or the tray menu, and text excluded by the `clipboardFilter` config
+//! (or copied while an excluded app is focused, see [`source_excluded`]) is
+//! never recorded. Text pushed explicitly (the CLI, the HTTP API and
+//! `AddClip` on D-Bus) is stored through [`push`], which bypasses these
+//! checks; D-Bus `Clipboard.Copy` acts like a copy and goes through
+//! [`record`]. Old items are dropped by [`expiry`].
+
+pub mod expiry;
+pub mod watcher;
+
+use crate::clipboard::filter::{Exclusion, FilterRules};
+use crate::clipboard::{ClipboardError, ClipboardItem};
+use crate::compositor;
+use crate::config::AppConfig;
+use crate::state::AppState;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::SystemTime;
+use tauri::menu::CheckMenuItem;
+use tauri::{AppHandle, Emitter, Manager, State, Wry};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::{debug, info};
+
+/// Whether copied text is recorded
+pub struct CaptureState {
+    enabled: AtomicBool,
+}
+
+impl CaptureState {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(true),
+        }
+    }
+
+    /// Checks whether capture is on (not paused)
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for CaptureState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tray menu item reflecting the paused state
+pub struct CaptureMenuItem(pub CheckMenuItem<Wry>);
+
+/// Records copied text (and its HTML, if any) in history
+///
+/// Does nothing while capture is paused or when the text is empty,
+/// excluded by the filter or skipped for its size. Emits
+/// `clipboard-changed` with the stored item.
+///
+/// # Returns
+///
+/// Whether the text was stored
+pub fn record(app: &AppHandle, text: String, html: Option<String>) -> Result<bool, ClipboardError> {
+    if text.trim().is_empty() || !app.state::<CaptureState>().is_enabled() {
+        return Ok(false);
+    }
+    if let Some(reason) = app.state::<CaptureFilter>().check(&text) {
+        debug!("Not storing clipboard item: {}", reason);
+        return Ok(false);
+    }
+
+    let state = app.state::<AppState>();
+    let item = {
+        let mut store = state.clipboard.write();
+        if !store.push_with_html(text, html)? {
+            return Ok(false);
+        }
+        store.items[0].clone()
+    };
+    let _ = app.emit("clipboard-changed", item);
+    Ok(true)
+}
+
+/// Stores text pushed explicitly by the user or a script
+///
+/// Unlike [`record`], ignores the pause state and the filter, since the
+/// text was asked for; size limits still apply. Emits `clipboard-changed`
+/// with the stored item.
+///
+/// # Returns
+///
+/// The stored item, or None if it was skipped for its size
+pub fn push(app: &AppHandle, text: String) -> Result<Option<ClipboardItem>, ClipboardError> {
+    let state = app.state::<AppState>();
+    let item = {
+        let mut store = state.clipboard.write();
+        if !store.push(text)? {
+            return Ok(None);
+        }
+        store.items[0].clone()
+    };
+    let _ = app.emit("clipboard-changed", &item);
+    Ok(Some(item))
+}
+
+/// Checks whether the focused app is excluded by `clipboardFilter.apps`
+///
+/// The focused window is only looked up when apps are excluded. A copy is
+/// attributed to the window focused when the watcher notices it, and is
+/// stored when the backend can't tell which window that is.
+pub async fn source_excluded(app: &AppHandle) -> bool {
+    let filter = app.state::<CaptureFilter>();
+    if !filter.with_rules(FilterRules::has_apps) {
+        return false;
+    }
+
+    let window = match compositor::active_window(app).await {
+        Ok(Some(window)) => window,
+        Ok(None) => return false,
+        Err(e) => {
+            debug!("Failed to get the focused window: {}", e);
+            return false;
+        }
+    };
+    match filter.with_rules(|rules| rules.check_app(&window.app_id)) {
+        Some(reason) => {
+            debug!(
+                "Not storing clipboard item from {}: {}",
+                window.app_id, reason
+            );
+            true
+        }
+        None => false,
+    }
+}
+
+/// Puts a history item back on the system clipboard
+///
+/// Items with HTML are offered as both HTML and plain text, so the target
+/// app picks the format it prefers.
+pub fn restore(
+    app: &AppHandle,
+    item: &ClipboardItem,
+) -> tauri_plugin_clipboard_manager::Result<()> {
+    match &item.html {
+        Some(html) => app
+            .clipboard()
+            .write_html(html.as_str(), Some(item.text.as_str())),
+        None => app.clipboard().write_text(item.text.as_str()),
+    }
+}
+
+/// Gets whether clipboard capture is on
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const enabled = await invoke('get_clipboard_capture_enabled');
+/// ```
+#[tauri::command]
+pub fn get_clipboard_capture_enabled(state: State<CaptureState>) -> bool {
+    state.is_enabled()
+}
+
+/// Pauses or resumes clipboard capture
+///
+/// While paused, copied text is not added to history. Emits
+/// `clipboard-capture-changed` with the new state when it changes.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether copied text should be recorded
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('set_clipboard_capture_enabled', { enabled: false });
+/// ```
+#[tauri::command]
+pub fn set_clipboard_capture_enabled(app: AppHandle, enabled: bool) {
+    set_enabled(&app, enabled);
+}
+
+/// Toggles clipboard capture (used by the tray menu)
+pub fn toggle(app: &AppHandle) {
+    let enabled = !app.state::<CaptureState>().is_enabled();
+    set_enabled(app, enabled);
+}
+
+/// Applies a capture change to the tray and frontend
+fn set_enabled(app: &AppHandle, enabled: bool) {
+    let previous = app
+        .state::<CaptureState>()
+        .enabled
+        .swap(enabled, Ordering::SeqCst);
+
+    // The tray item is checked while paused (clicking it also toggles it)
+    if let Some(item) = app.try_state::<CaptureMenuItem>() {
+        let _ = item.0.set_checked(!enabled);
+    }
+
+    if previous == enabled {
+        return;
+    }
+
+    info!(
+        "Clipboard capture {}",
+        if enabled { "resumed" } else { "paused" }
+    );
+    let _ = app.emit("clipboard-capture-changed", enabled);
+}
+
+/// Capture filter that follows the config file
+///
+/// The rules are rebuilt when config.json changes, so edits apply without
+/// a restart.
+#[derive(Default)]
+pub struct CaptureFilter {
+    cache: Mutex<Cache>,
+}
+
+#[derive(Default)]
+struct Cache {
+    modified: Option<SystemTime>,
+    rules: Option<FilterRules>,
+}
+
+impl CaptureFilter {
+    /// Checks copied text against the `clipboardFilter` rules
+    ///
+    /// # Returns
+    ///
+    /// The reason if the text must not be stored, None otherwise
+    pub fn check(&self, text: &str) -> Option<Exclusion> {
+        self.with_rules(|rules| rules.check(text))
+    }
+
+    /// Runs `f` with the current rules, rebuilding them if the config changed
+    fn with_rules<R>(&self, f: impl FnOnce(&FilterRules) -> R) -> R {
+        let modified = std::fs::metadata(AppConfig::get_config_path())
+            .and_then(|m| m.modified())
+            .ok();
+
+        let mut cache = self.cache.lock();
+        if cache.modified != modified {
+            cache.modified = modified;
+            cache.rules = None;
+        }
+        let rules = cache.rules.get_or_insert_with(|| {
+            let config = AppConfig::load().clipboard_filter;
+            FilterRules::new(config.detect_secrets, &config.patterns, &config.apps)
+        });
+        f(rules)
+    }
+}
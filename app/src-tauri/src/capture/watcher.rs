@@ -0,0 +1,158 @@
+//! Clipboard watcher
+//!
+//! On Wayland, `wl-paste --watch` (wl-clipboard) reports each clipboard
+//! change and the contents are then read with `wl-paste`. Elsewhere, or
+//! when wl-clipboard isn't installed, the clipboard is polled with arboard.
+//! Text is recorded together with its HTML when the copy offers one,
+//! unless it was copied in an app excluded by `clipboardFilter.apps`.
+
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tracing::{info, warn};
+
+/// How often the clipboard is polled without wl-clipboard
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Command run by `wl-paste --watch` on each change: drain the content
+/// and print a line as the change notification
+const WATCH_COMMAND: &str = "cat > /dev/null; echo";
+
+/// Watches the clipboard and records new text until the app exits
+pub async fn run(app: AppHandle) {
+    let mut last = None;
+
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        match watch_wayland(&app, &mut last).await {
+            Ok(()) => warn!("wl-paste exited, polling the clipboard instead"),
+            Err(e) => info!("{}, polling the clipboard instead", e),
+        }
+    }
+
+    // arboard is blocking, so poll on a blocking thread
+    let _ = tauri::async_runtime::spawn_blocking(move || poll(&app, &mut last)).await;
+}
+
+/// Polls the clipboard with arboard
+fn poll(app: &AppHandle, last: &mut Option<String>) {
+    let mut clipboard = match arboard::Clipboard::new() {
+        Ok(clipboard) => clipboard,
+        Err(e) => {
+            crate::error::report(
+                app,
+                "clipboard",
+                format!("Failed to access the clipboard: {}", e),
+            );
+            return;
+        }
+    };
+
+    loop {
+        if let Ok(text) = clipboard.get_text() {
+            if is_new(last, &text) && !tauri::async_runtime::block_on(super::source_excluded(app)) {
+                let html = clipboard.get().html().ok();
+                store(app, text, html);
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Records clipboard changes reported by `wl-paste --watch`
+///
+/// # Returns
+///
+/// Ok when wl-paste exits, Err if it can't be started or read
+async fn watch_wayland(app: &AppHandle, last: &mut Option<String>) -> Result<(), String> {
+    let mut child = Command::new("wl-paste")
+        .args(["--watch", "sh", "-c", WATCH_COMMAND])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to run wl-paste: {}", e))?;
+    let stdout = child.stdout.take().ok_or("wl-paste has no stdout")?;
+
+    info!("Watching the clipboard with wl-paste");
+    let mut lines = BufReader::new(stdout).lines();
+    while lines
+        .next_line()
+        .await
+        .map_err(|e| format!("Failed to read wl-paste output: {}", e))?
+        .is_some()
+    {
+        let Some(text) = wl_paste(&["--type", "text"]).await else {
+            continue;
+        };
+        if is_new(last, &text) && !super::source_excluded(app).await {
+            store(app, text, wayland_html().await);
+        }
+    }
+    Ok(())
+}
+
+/// Reads the clipboard HTML with wl-paste, if the copy offers it
+async fn wayland_html() -> Option<String> {
+    let types = wl_paste(&["--list-types"]).await?;
+    if !types.lines().any(|t| t == "text/html") {
+        return None;
+    }
+    wl_paste(&["--type", "text/html"]).await
+}
+
+/// Runs wl-paste without the trailing newline it adds
+///
+/// # Returns
+///
+/// None if wl-paste fails (e.g., the clipboard holds no such type)
+async fn wl_paste(args: &[&str]) -> Option<String> {
+    let output = Command::new("wl-paste")
+        .arg("--no-newline")
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Records new clipboard contents
+fn store(app: &AppHandle, text: String, html: Option<String>) {
+    if let Err(e) = super::record(app, text, html) {
+        crate::error::report(
+            app,
+            "clipboard",
+            format!("Failed to store clipboard item: {}", e),
+        );
+    }
+}
+
+/// Checks whether text differs from the last seen text, remembering it
+///
+/// Text seen while capture is paused also counts, so it isn't recorded
+/// after capture resumes.
+fn is_new(last: &mut Option<String>, text: &str) -> bool {
+    if last.as_deref() == Some(text) {
+        return false;
+    }
+    *last = Some(text.to_string());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_new_dedupes_consecutive_text() {
+        let mut last = None;
+        assert!(is_new(&mut last, "a"));
+        assert!(!is_new(&mut last, "a"));
+        assert!(is_new(&mut last, "b"));
+        assert!(is_new(&mut last, "a"));
+    }
+}
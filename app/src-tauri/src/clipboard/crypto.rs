@@ -0,0 +1,161 @@
+//! Encryption of clipboard history at rest
+//!
+//! Item texts are encrypted with AES-256-GCM under a key kept in the
+//! system keyring. Stored values are base64 of the random nonce followed
+//! by the ciphertext. Duplicate detection in the database uses an HMAC of
+//! the text instead of a plain hash, which would reveal whether a guessed
+//! text was ever copied.
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+
+/// Encryption key length in bytes
+pub const KEY_LEN: usize = 32;
+
+/// AES-GCM nonce length in bytes
+const NONCE_LEN: usize = 12;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Context mixed into the key to derive the HMAC key
+const HASH_CONTEXT: &[u8] = b"uti clipboard hash";
+
+/// Errors from decrypting stored items
+#[derive(Debug, thiserror::Error)]
+pub enum CryptoError {
+    #[error("clipboard history is encrypted and the key is unavailable")]
+    MissingKey,
+    #[error("failed to decrypt clipboard item (wrong key or corrupted data)")]
+    Decrypt,
+}
+
+/// Encryption settings for stored history
+#[derive(Debug, Clone, Default)]
+pub struct Encryption {
+    /// Key for reading encrypted items (and writing them, when enabled)
+    pub cipher: Option<Cipher>,
+    /// Whether items are written encrypted
+    pub enabled: bool,
+}
+
+impl Encryption {
+    /// Encryption with a key, on or off for new writes
+    pub fn new(cipher: Option<Cipher>, enabled: bool) -> Self {
+        Self { cipher, enabled }
+    }
+
+    /// Gets the cipher used for writing, if encryption is on
+    pub fn writer(&self) -> Option<&Cipher> {
+        self.cipher.as_ref().filter(|_| self.enabled)
+    }
+}
+
+/// Encrypts and decrypts item texts with one key
+#[derive(Clone)]
+pub struct Cipher {
+    aead: Aes256Gcm,
+    hash_key: [u8; KEY_LEN],
+}
+
+impl Cipher {
+    /// Creates a cipher from a key
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        let mut mac =
+            <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(HASH_CONTEXT);
+        Self {
+            aead: Aes256Gcm::new(key.into()),
+            hash_key: mac.finalize().into_bytes().into(),
+        }
+    }
+
+    /// Generates a random key
+    pub fn generate_key() -> [u8; KEY_LEN] {
+        Aes256Gcm::generate_key(&mut OsRng).into()
+    }
+
+    /// Encrypts text
+    ///
+    /// # Returns
+    ///
+    /// Base64 of the nonce and ciphertext
+    pub fn encrypt(&self, text: &str) -> String {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .aead
+            .encrypt(&nonce, text.as_bytes())
+            .expect("AES-GCM encryption of in-memory data can't fail");
+        let mut data = nonce.to_vec();
+        data.extend(ciphertext);
+        STANDARD.encode(data)
+    }
+
+    /// Decrypts text produced by [`encrypt`](Self::encrypt)
+    pub fn decrypt(&self, data: &str) -> Result<String, CryptoError> {
+        let data = STANDARD.decode(data).map_err(|_| CryptoError::Decrypt)?;
+        if data.len() < NONCE_LEN {
+            return Err(CryptoError::Decrypt);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let plaintext = self
+            .aead
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptoError::Decrypt)?;
+        String::from_utf8(plaintext).map_err(|_| CryptoError::Decrypt)
+    }
+
+    /// Hashes text with a key so stored hashes can't be matched against
+    /// guesses
+    ///
+    /// # Returns
+    ///
+    /// The HMAC-SHA256 as lowercase hex
+    pub fn hash(&self, text: &str) -> String {
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(&self.hash_key)
+            .expect("HMAC accepts any key length");
+        mac.update(text.as_bytes());
+        format!("{:x}", mac.finalize().into_bytes())
+    }
+}
+
+impl fmt::Debug for Cipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cipher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let cipher = Cipher::new(&Cipher::generate_key());
+        let encrypted = cipher.encrypt("secret token");
+        assert!(!encrypted.contains("secret"));
+        // A fresh nonce is used each time
+        assert_ne!(cipher.encrypt("secret token"), encrypted);
+        assert_eq!(cipher.decrypt(&encrypted).unwrap(), "secret token");
+    }
+
+    #[test]
+    fn test_wrong_key_fails() {
+        let encrypted = Cipher::new(&[1; KEY_LEN]).encrypt("secret");
+        assert!(Cipher::new(&[2; KEY_LEN]).decrypt(&encrypted).is_err());
+        assert!(Cipher::new(&[1; KEY_LEN]).decrypt("not base64!").is_err());
+    }
+
+    #[test]
+    fn test_hash_depends_on_key() {
+        let a = Cipher::new(&[1; KEY_LEN]);
+        let b = Cipher::new(&[2; KEY_LEN]);
+        assert_eq!(a.hash("text"), a.hash("text"));
+        assert_ne!(a.hash("text"), b.hash("text"));
+        assert_ne!(a.hash("text"), crate::clipboard::content_hash("text"));
+    }
+}
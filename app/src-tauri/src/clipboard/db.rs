@@ -0,0 +1,510 @@
+//! SQLite persistence for clipboard history
+//!
+//! Each change is written as a single statement instead of rewriting the
+//! whole history. Rows are ordered by their autoincrement ID (newest
+//! last), and `text` is unique. Duplicates are found through the indexed
+//! SHA-256 `hash` column, which is also loaded so items aren't rehashed.
+//! Pinned rows are neither counted nor removed when trimming to the limit.
+//! Item IDs are stored in the `uuid` column (the row ID only orders rows).
+//! With encryption on, `text` and `html` hold ciphertext and `hash` a keyed
+//! hash (see [`super::crypto`]); `encrypted` marks such rows.
+
+use super::crypto::{CryptoError, Encryption};
+use super::{content_hash, ClipboardError, ClipboardItem};
+use parking_lot::Mutex;
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Row};
+use std::path::Path;
+
+/// Current schema version (stored in `PRAGMA user_version`)
+const SCHEMA_VERSION: i32 = 6;
+
+/// Clipboard history database
+#[derive(Debug)]
+pub struct ClipboardDb {
+    /// `Connection` isn't `Sync`, so it's locked for each statement
+    conn: Mutex<Connection>,
+    /// How item texts are stored
+    encryption: Encryption,
+}
+
+impl ClipboardDb {
+    /// Opens or creates the database
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the SQLite file
+    /// * `encryption` - How item texts are stored
+    pub fn open(path: &Path, encryption: Encryption) -> rusqlite::Result<Self> {
+        Self::init(Connection::open(path)?, encryption)
+    }
+
+    /// Opens a database that lives only in memory
+    #[allow(dead_code)]
+    pub fn open_in_memory() -> rusqlite::Result<Self> {
+        Self::init(Connection::open_in_memory()?, Encryption::default())
+    }
+
+    fn init(conn: Connection, encryption: Encryption) -> rusqlite::Result<Self> {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        migrate(&conn)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+            encryption,
+        })
+    }
+
+    /// Checks whether the history is empty
+    pub fn is_empty(&self) -> rusqlite::Result<bool> {
+        let conn = self.conn.lock();
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM items", [], |row| row.get(0))?;
+        Ok(count == 0)
+    }
+
+    /// Checks whether any item is stored encrypted
+    pub fn has_encrypted(&self) -> rusqlite::Result<bool> {
+        let conn = self.conn.lock();
+        conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM items WHERE encrypted = 1)",
+            [],
+            |row| row.get(0),
+        )
+    }
+
+    /// Loads all items, newest first
+    ///
+    /// Encrypted rows are decrypted, which fails without the key.
+    pub fn load(&self) -> rusqlite::Result<Vec<ClipboardItem>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(
+            "SELECT uuid, text, hash, timestamp, pinned, html, encrypted
+             FROM items ORDER BY id DESC",
+        )?;
+        let items = stmt
+            .query_map([], |row| {
+                let encrypted: bool = row.get(6)?;
+                let text = self.read_text(row, 1, encrypted)?;
+                let html = match row.get::<_, Option<String>>(5)? {
+                    Some(_) => Some(self.read_text(row, 5, encrypted)?),
+                    None => None,
+                };
+                Ok(ClipboardItem {
+                    id: row.get(0)?,
+                    // Encrypted rows hold a keyed hash
+                    hash: if encrypted {
+                        content_hash(&text)
+                    } else {
+                        row.get(2)?
+                    },
+                    text,
+                    timestamp: row.get(3)?,
+                    pinned: row.get(4)?,
+                    html,
+                })
+            })?
+            .collect();
+        items
+    }
+
+    /// Stores an item as the newest, replacing any item with the same text
+    pub fn insert(&self, item: &ClipboardItem) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        self.insert_item(&tx, item)?;
+        tx.commit()
+    }
+
+    /// Imports items (newest first, as in `ClipboardStore::items`)
+    pub fn import(&self, items: &[ClipboardItem]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        for item in items.iter().rev() {
+            self.insert_item(&tx, item)?;
+        }
+        tx.commit()
+    }
+
+    /// Removes the item with the given text
+    pub fn delete(&self, text: &str) -> rusqlite::Result<()> {
+        self.conn
+            .lock()
+            .execute("DELETE FROM items WHERE hash = ?1", [self.hash(text)])?;
+        Ok(())
+    }
+
+    /// Pins or unpins the item with the given text
+    pub fn set_pinned(&self, text: &str, pinned: bool) -> rusqlite::Result<()> {
+        self.conn.lock().execute(
+            "UPDATE items SET pinned = ?2 WHERE hash = ?1",
+            params![self.hash(text), pinned],
+        )?;
+        Ok(())
+    }
+
+    /// Removes unpinned items copied before `timestamp`
+    pub fn delete_older_than(&self, timestamp: u64) -> rusqlite::Result<()> {
+        self.conn.lock().execute(
+            "DELETE FROM items WHERE pinned = 0 AND timestamp < ?1",
+            [timestamp],
+        )?;
+        Ok(())
+    }
+
+    /// Removes the oldest unpinned items until at most `max_items` of them remain
+    pub fn trim(&self, max_items: usize) -> rusqlite::Result<()> {
+        self.conn.lock().execute(
+            "DELETE FROM items WHERE id IN (
+                SELECT id FROM items WHERE pinned = 0 ORDER BY id
+                LIMIT max(0, (SELECT COUNT(*) FROM items WHERE pinned = 0) - ?1)
+            )",
+            [max_items as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Rewrites rows stored differently from the current setting, i.e.
+    /// encrypts plain rows when encryption is on and decrypts them when
+    /// it's off
+    ///
+    /// # Errors
+    ///
+    /// Returns error if encrypted rows exist and the key isn't available
+    ///
+    /// # Returns
+    ///
+    /// Number of rows rewritten
+    pub fn reencode(&self) -> Result<usize, ClipboardError> {
+        if self.encryption.cipher.is_none() && self.has_encrypted()? {
+            return Err(CryptoError::MissingKey.into());
+        }
+
+        let encrypt = self.encryption.writer().is_some();
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let rows: Vec<(i64, String, Option<String>)> = tx
+            .prepare("SELECT id, text, html, encrypted FROM items WHERE encrypted != ?1")?
+            .query_map([encrypt], |row| {
+                let encrypted: bool = row.get(3)?;
+                let text = self.read_text(row, 1, encrypted)?;
+                let html = match row.get::<_, Option<String>>(2)? {
+                    Some(_) => Some(self.read_text(row, 2, encrypted)?),
+                    None => None,
+                };
+                Ok((row.get(0)?, text, html))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        for (id, text, html) in &rows {
+            tx.execute(
+                "UPDATE items SET text = ?2, html = ?3, hash = ?4, encrypted = ?5 WHERE id = ?1",
+                params![
+                    id,
+                    self.write_text(text),
+                    html.as_deref().map(|html| self.write_text(html)),
+                    self.hash(text),
+                    encrypt
+                ],
+            )?;
+        }
+        tx.commit()?;
+
+        // Rewrite the file so the old contents don't linger in free pages
+        if !rows.is_empty() {
+            conn.execute_batch("VACUUM; PRAGMA wal_checkpoint(TRUNCATE);")?;
+        }
+        Ok(rows.len())
+    }
+
+    /// Deletes any row with the same text, then inserts so the item gets the newest ID
+    fn insert_item(&self, conn: &Connection, item: &ClipboardItem) -> rusqlite::Result<()> {
+        let hash = self.hash(&item.text);
+        conn.execute("DELETE FROM items WHERE hash = ?1", [&hash])?;
+        conn.execute(
+            "INSERT INTO items (text, timestamp, pinned, html, uuid, hash, encrypted)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                self.write_text(&item.text),
+                item.timestamp,
+                item.pinned,
+                item.html.as_deref().map(|html| self.write_text(html)),
+                item.id,
+                hash,
+                self.encryption.writer().is_some()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Hashes text as stored in the `hash` column
+    fn hash(&self, text: &str) -> String {
+        match self.encryption.writer() {
+            Some(cipher) => cipher.hash(text),
+            None => content_hash(text),
+        }
+    }
+
+    /// Encodes text for storage
+    fn write_text(&self, text: &str) -> String {
+        match self.encryption.writer() {
+            Some(cipher) => cipher.encrypt(text),
+            None => text.to_string(),
+        }
+    }
+
+    /// Reads a text column, decrypting it if the row is encrypted
+    fn read_text(&self, row: &Row, index: usize, encrypted: bool) -> rusqlite::Result<String> {
+        let value: String = row.get(index)?;
+        if !encrypted {
+            return Ok(value);
+        }
+        let cipher = self.encryption.cipher.as_ref().ok_or_else(|| {
+            rusqlite::Error::FromSqlConversionFailure(
+                index,
+                Type::Text,
+                Box::new(CryptoError::MissingKey),
+            )
+        })?;
+        cipher
+            .decrypt(&value)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(index, Type::Text, Box::new(e)))
+    }
+}
+
+/// Brings the schema up to date
+fn migrate(conn: &Connection) -> rusqlite::Result<()> {
+    let version: i32 = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    if version < 1 {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                text TEXT NOT NULL UNIQUE,
+                timestamp INTEGER NOT NULL
+            );",
+        )?;
+    }
+    if version < 2 {
+        conn.execute_batch("ALTER TABLE items ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;")?;
+    }
+    if version < 3 {
+        conn.execute_batch("ALTER TABLE items ADD COLUMN html TEXT;")?;
+    }
+    if version < 4 {
+        conn.execute_batch("ALTER TABLE items ADD COLUMN uuid TEXT;")?;
+        // Give existing rows an ID
+        let rows: Vec<i64> = conn
+            .prepare("SELECT id FROM items WHERE uuid IS NULL")?
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<_>>()?;
+        for row in rows {
+            conn.execute(
+                "UPDATE items SET uuid = ?2 WHERE id = ?1",
+                params![row, uuid::Uuid::new_v4().to_string()],
+            )?;
+        }
+    }
+    if version < 5 {
+        conn.execute_batch(
+            "ALTER TABLE items ADD COLUMN hash TEXT;
+             CREATE INDEX items_hash ON items (hash);",
+        )?;
+        let rows: Vec<(i64, String)> = conn
+            .prepare("SELECT id, text FROM items")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<_>>()?;
+        for (row, text) in rows {
+            conn.execute(
+                "UPDATE items SET hash = ?2 WHERE id = ?1",
+                params![row, content_hash(&text)],
+            )?;
+        }
+    }
+    if version < 6 {
+        conn.execute_batch("ALTER TABLE items ADD COLUMN encrypted INTEGER NOT NULL DEFAULT 0;")?;
+    }
+    conn.pragma_update(None, "user_version", SCHEMA_VERSION)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clipboard::crypto::{Cipher, KEY_LEN};
+
+    fn texts(db: &ClipboardDb) -> Vec<String> {
+        db.load()
+            .unwrap()
+            .into_iter()
+            .map(|item| item.text)
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_moves_duplicate_to_front() {
+        let db = ClipboardDb::open_in_memory().unwrap();
+        for text in ["a", "b", "a"] {
+            db.insert(&ClipboardItem::with_timestamp(text.to_string(), 1))
+                .unwrap();
+        }
+        assert_eq!(texts(&db), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_trim_keeps_newest() {
+        let db = ClipboardDb::open_in_memory().unwrap();
+        for text in ["a", "b", "c"] {
+            db.insert(&ClipboardItem::with_timestamp(text.to_string(), 1))
+                .unwrap();
+        }
+        db.trim(2).unwrap();
+        assert_eq!(texts(&db), vec!["c", "b"]);
+
+        db.delete("c").unwrap();
+        assert_eq!(texts(&db), vec!["b"]);
+    }
+
+    #[test]
+    fn test_trim_skips_pinned() {
+        let db = ClipboardDb::open_in_memory().unwrap();
+        for text in ["old", "a", "b", "c"] {
+            db.insert(&ClipboardItem::with_timestamp(text.to_string(), 1))
+                .unwrap();
+        }
+        db.set_pinned("old", true).unwrap();
+        db.trim(2).unwrap();
+        assert_eq!(texts(&db), vec!["c", "b", "old"]);
+        assert!(db.load().unwrap()[2].pinned);
+    }
+
+    #[test]
+    fn test_delete_older_than_skips_pinned() {
+        let db = ClipboardDb::open_in_memory().unwrap();
+        for (text, timestamp) in [("old", 1), ("pinned", 1), ("new", 3)] {
+            db.insert(&ClipboardItem::with_timestamp(text.to_string(), timestamp))
+                .unwrap();
+        }
+        db.set_pinned("pinned", true).unwrap();
+        db.delete_older_than(2).unwrap();
+        assert_eq!(texts(&db), vec!["new", "pinned"]);
+    }
+
+    #[test]
+    fn test_insert_keeps_html() {
+        let db = ClipboardDb::open_in_memory().unwrap();
+        let mut item = ClipboardItem::with_timestamp("bold".to_string(), 1);
+        item.html = Some("<b>bold</b>".to_string());
+        db.insert(&item).unwrap();
+        db.insert(&ClipboardItem::with_timestamp("plain".to_string(), 2))
+            .unwrap();
+
+        let items = db.load().unwrap();
+        assert_eq!(items[0].html, None);
+        assert_eq!(items[1], item);
+        assert_eq!(items[1].id, item.id);
+    }
+
+    #[test]
+    fn test_migration_assigns_ids() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE items (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                text TEXT NOT NULL UNIQUE,
+                timestamp INTEGER NOT NULL,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                html TEXT
+            );
+            INSERT INTO items (text, timestamp) VALUES ('a', 1), ('b', 2);
+            PRAGMA user_version = 3;",
+        )
+        .unwrap();
+
+        let db = ClipboardDb::init(conn, Encryption::default()).unwrap();
+        let items = db.load().unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(items.iter().all(|item| item.id.len() == 36));
+        assert_ne!(items[0].id, items[1].id);
+        assert_eq!(items[0].hash, content_hash("b"));
+    }
+
+    #[test]
+    fn test_encryption_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("clipboard.db");
+        let cipher = Cipher::new(&[7; KEY_LEN]);
+        let raw_texts = || -> Vec<String> {
+            let conn = Connection::open(&path).unwrap();
+            let mut stmt = conn.prepare("SELECT text FROM items").unwrap();
+            let texts = stmt
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .collect::<rusqlite::Result<_>>()
+                .unwrap();
+            texts
+        };
+
+        // A plain item written before encryption was turned on
+        ClipboardDb::open(&path, Encryption::default())
+            .unwrap()
+            .insert(&ClipboardItem::with_timestamp("plain".to_string(), 1))
+            .unwrap();
+
+        let db = ClipboardDb::open(&path, Encryption::new(Some(cipher.clone()), true)).unwrap();
+        assert_eq!(db.reencode().unwrap(), 1);
+        let mut item = ClipboardItem::with_timestamp("secret".to_string(), 2);
+        item.html = Some("<b>secret</b>".to_string());
+        db.insert(&item).unwrap();
+        db.set_pinned("secret", true).unwrap();
+        assert!(raw_texts()
+            .iter()
+            .all(|t| !t.contains("secret") && t != "plain"));
+        let items = db.load().unwrap();
+        assert_eq!(items[0].html.as_deref(), Some("<b>secret</b>"));
+        assert!(items[0].pinned);
+        assert_eq!(items[0].hash, content_hash("secret"));
+        assert_eq!(items[1].text, "plain");
+        drop(db);
+
+        // Without the key, encrypted items can't be read
+        let db = ClipboardDb::open(&path, Encryption::default()).unwrap();
+        assert!(matches!(
+            db.reencode(),
+            Err(ClipboardError::Crypto(CryptoError::MissingKey))
+        ));
+        assert!(db.load().is_err());
+        drop(db);
+
+        // Turning encryption off decrypts them again
+        let db = ClipboardDb::open(&path, Encryption::new(Some(cipher), false)).unwrap();
+        assert_eq!(db.reencode().unwrap(), 2);
+        let mut raw = raw_texts();
+        raw.sort();
+        assert_eq!(raw, vec!["plain", "secret"]);
+        db.delete("secret").unwrap();
+        assert_eq!(texts(&db), vec!["plain"]);
+    }
+
+    #[test]
+    fn test_import_keeps_order_and_reopens() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("clipboard.db");
+        {
+            let db = ClipboardDb::open(&path, Encryption::default()).unwrap();
+            assert!(db.is_empty().unwrap());
+            db.import(&[
+                ClipboardItem::with_timestamp("newest".to_string(), 2),
+                ClipboardItem::with_timestamp("oldest".to_string(), 1),
+            ])
+            .unwrap();
+        }
+
+        let db = ClipboardDb::open(&path, Encryption::default()).unwrap();
+        let items = db.load().unwrap();
+        assert_eq!(
+            items[0],
+            ClipboardItem::with_timestamp("newest".to_string(), 2)
+        );
+        assert_eq!(
+            items[1],
+            ClipboardItem::with_timestamp("oldest".to_string(), 1)
+        );
+    }
+}
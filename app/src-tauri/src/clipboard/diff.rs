@@ -0,0 +1,264 @@
+//! Clipboard item diff
+//!
+//! Computes a line diff between two texts, with word-level detail for
+//! lines that were changed rather than purely added or removed.
+
+use serde::Serialize;
+
+/// Maximum LCS table size before falling back to a full replacement
+const MAX_TABLE_SIZE: usize = 4_000_000;
+
+/// Kind of change for a line or word segment
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    /// Present in both texts
+    Equal,
+    /// Only in the new text
+    Insert,
+    /// Only in the old text
+    Delete,
+}
+
+/// A run of words within a changed line
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+/// A single line of the diff
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct DiffLine {
+    pub op: DiffOp,
+    pub text: String,
+    /// Word-level changes for a modified line (empty otherwise)
+    pub segments: Vec<DiffSegment>,
+}
+
+/// Result of diffing two texts
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct TextDiff {
+    pub lines: Vec<DiffLine>,
+    /// Number of inserted lines
+    pub added: usize,
+    /// Number of deleted lines
+    pub removed: usize,
+}
+
+/// Computes a line diff between two texts
+///
+/// Deleted lines directly followed by inserted lines are treated as
+/// modifications and paired up for a word-level diff.
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::clipboard::diff::{diff_texts, DiffOp};
+///
+/// let diff = diff_texts("a\nb", "a\nc");
+/// assert_eq!(diff.lines[0].op, DiffOp::Equal);
+/// assert_eq!((diff.added, diff.removed), (1, 1));
+/// ```
+pub fn diff_texts(old: &str, new: &str) -> TextDiff {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+
+    let mut lines: Vec<DiffLine> = diff_sequences(&old_lines, &new_lines)
+        .into_iter()
+        .map(|(op, text)| DiffLine {
+            op,
+            text: text.to_string(),
+            segments: Vec::new(),
+        })
+        .collect();
+
+    add_word_segments(&mut lines);
+
+    TextDiff {
+        added: lines.iter().filter(|l| l.op == DiffOp::Insert).count(),
+        removed: lines.iter().filter(|l| l.op == DiffOp::Delete).count(),
+        lines,
+    }
+}
+
+/// Pairs up runs of deleted and inserted lines and diffs their words
+fn add_word_segments(lines: &mut [DiffLine]) {
+    let mut i = 0;
+    while i < lines.len() {
+        let deletes = lines[i..]
+            .iter()
+            .take_while(|l| l.op == DiffOp::Delete)
+            .count();
+        let inserts = lines[i + deletes..]
+            .iter()
+            .take_while(|l| l.op == DiffOp::Insert)
+            .count();
+
+        for k in 0..deletes.min(inserts) {
+            let (old, new) = (i + k, i + deletes + k);
+            let old_words = split_words(&lines[old].text);
+            let new_words = split_words(&lines[new].text);
+            let ops = diff_sequences(&old_words, &new_words);
+
+            lines[old].segments =
+                merge_segments(ops.iter().filter(|(op, _)| *op != DiffOp::Insert));
+            lines[new].segments =
+                merge_segments(ops.iter().filter(|(op, _)| *op != DiffOp::Delete));
+        }
+
+        i += (deletes + inserts).max(1);
+    }
+}
+
+/// Joins adjacent word operations of the same kind into segments
+fn merge_segments<'a>(ops: impl Iterator<Item = &'a (DiffOp, &'a str)>) -> Vec<DiffSegment> {
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    for (op, text) in ops {
+        match segments.last_mut() {
+            Some(last) if last.op == *op => last.text.push_str(text),
+            _ => segments.push(DiffSegment {
+                op: *op,
+                text: text.to_string(),
+            }),
+        }
+    }
+    segments
+}
+
+/// Splits a line into words and the whitespace between them
+fn split_words(line: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut in_space = None;
+
+    for (i, c) in line.char_indices() {
+        let space = c.is_whitespace();
+        if in_space.is_some_and(|s| s != space) {
+            words.push(&line[start..i]);
+            start = i;
+        }
+        in_space = Some(space);
+    }
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+    words
+}
+
+/// Diffs two sequences using the longest common subsequence
+///
+/// Very large inputs fall back to deleting everything and inserting
+/// everything to keep memory bounded.
+fn diff_sequences<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(DiffOp, &'a str)> {
+    let (n, m) = (old.len(), new.len());
+
+    if (n + 1).saturating_mul(m + 1) > MAX_TABLE_SIZE {
+        return old
+            .iter()
+            .map(|t| (DiffOp::Delete, *t))
+            .chain(new.iter().map(|t| (DiffOp::Insert, *t)))
+            .collect();
+    }
+
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            result.push((DiffOp::Equal, old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push((DiffOp::Delete, old[i]));
+            i += 1;
+        } else {
+            result.push((DiffOp::Insert, new[j]));
+            j += 1;
+        }
+    }
+    result.extend(old[i..].iter().map(|t| (DiffOp::Delete, *t)));
+    result.extend(new[j..].iter().map(|t| (DiffOp::Insert, *t)));
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ops(diff: &TextDiff) -> Vec<DiffOp> {
+        diff.lines.iter().map(|l| l.op).collect()
+    }
+
+    #[test]
+    fn test_identical_texts() {
+        let diff = diff_texts("one\ntwo", "one\ntwo");
+        assert_eq!(ops(&diff), vec![DiffOp::Equal, DiffOp::Equal]);
+        assert_eq!((diff.added, diff.removed), (0, 0));
+    }
+
+    #[test]
+    fn test_added_and_removed_lines() {
+        let diff = diff_texts("a\nb\nc", "a\nc\nd");
+        assert_eq!(
+            ops(&diff),
+            vec![DiffOp::Equal, DiffOp::Delete, DiffOp::Equal, DiffOp::Insert]
+        );
+        assert_eq!((diff.added, diff.removed), (1, 1));
+        // Not adjacent, so no word-level pairing
+        assert!(diff.lines.iter().all(|l| l.segments.is_empty()));
+    }
+
+    #[test]
+    fn test_modified_line_has_word_segments() {
+        let diff = diff_texts("let x = 1;", "let y = 1;");
+        let old = &diff.lines[0];
+        let new = &diff.lines[1];
+
+        assert_eq!(old.op, DiffOp::Delete);
+        assert_eq!(
+            old.segments,
+            vec![
+                DiffSegment {
+                    op: DiffOp::Equal,
+                    text: "let ".to_string()
+                },
+                DiffSegment {
+                    op: DiffOp::Delete,
+                    text: "x".to_string()
+                },
+                DiffSegment {
+                    op: DiffOp::Equal,
+                    text: " = 1;".to_string()
+                },
+            ]
+        );
+        assert_eq!(new.segments[1].op, DiffOp::Insert);
+        assert_eq!(new.segments[1].text, "y");
+    }
+
+    #[test]
+    fn test_split_words_keeps_whitespace() {
+        assert_eq!(split_words("a  b\tc"), vec!["a", "  ", "b", "\t", "c"]);
+        assert!(split_words("").is_empty());
+    }
+
+    #[test]
+    fn test_empty_inputs() {
+        let diff = diff_texts("", "new");
+        assert_eq!(ops(&diff), vec![DiffOp::Insert]);
+        assert!(diff_texts("", "").lines.is_empty());
+    }
+}
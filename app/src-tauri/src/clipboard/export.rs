@@ -0,0 +1,140 @@
+//! Clipboard history export and import formats
+//!
+//! - JSON: an array of items (newest first) with timestamps and pinned
+//!   state. The old `clipboard.json` store format is also accepted.
+//! - Text: item texts only, separated by lines containing just `%` (the
+//!   fortune file format), so multi-line items survive a round trip.
+
+use super::{ClipboardError, ClipboardItem};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Separator line between items in the text format
+const TEXT_SEPARATOR: &str = "%";
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// JSON array of items
+    Json,
+    /// Plain text, one `%` line between items
+    Text,
+}
+
+impl ExportFormat {
+    /// Picks the format from a file extension (`.json`, anything else is text)
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Accepted JSON layouts
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonHistory {
+    Items(Vec<ClipboardItem>),
+    Store { items: Vec<ClipboardItem> },
+}
+
+/// Serializes items (newest first)
+pub fn export(items: &[ClipboardItem], format: ExportFormat) -> Result<String, ClipboardError> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_string_pretty(items)?),
+        ExportFormat::Text => Ok(items
+            .iter()
+            .map(|item| format!("{}\n", item.text))
+            .collect::<Vec<_>>()
+            .join(&format!("{}\n", TEXT_SEPARATOR))),
+    }
+}
+
+/// Parses exported history
+///
+/// Text items get the current time as their timestamp.
+///
+/// # Returns
+///
+/// Items, newest first
+pub fn parse(content: &str, format: ExportFormat) -> Result<Vec<ClipboardItem>, ClipboardError> {
+    if format == ExportFormat::Json {
+        let items = match serde_json::from_str(content)? {
+            JsonHistory::Items(items) | JsonHistory::Store { items } => items,
+        };
+        return Ok(items);
+    }
+
+    let mut items = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for line in content.lines() {
+        if line == TEXT_SEPARATOR {
+            items.push(current.join("\n"));
+            current.clear();
+        } else {
+            current.push(line);
+        }
+    }
+    items.push(current.join("\n"));
+
+    Ok(items
+        .into_iter()
+        .filter(|text| !text.trim().is_empty())
+        .map(ClipboardItem::new)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<ClipboardItem> {
+        let mut pinned = ClipboardItem::with_timestamp("first\nsecond line".to_string(), 2);
+        pinned.pinned = true;
+        vec![
+            pinned,
+            ClipboardItem::with_timestamp("plain".to_string(), 1),
+        ]
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = export(&items(), ExportFormat::Json).unwrap();
+        assert_eq!(parse(&json, ExportFormat::Json).unwrap(), items());
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let text = export(&items(), ExportFormat::Text).unwrap();
+        assert_eq!(text, "first\nsecond line\n%\nplain\n");
+
+        let texts: Vec<_> = parse(&text, ExportFormat::Text)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.text)
+            .collect();
+        assert_eq!(texts, vec!["first\nsecond line", "plain"]);
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ExportFormat::from_path(Path::new("backup.JSON")),
+            ExportFormat::Json
+        );
+        assert_eq!(
+            ExportFormat::from_path(Path::new("backup.txt")),
+            ExportFormat::Text
+        );
+    }
+
+    #[test]
+    fn test_parse_legacy_store() {
+        let json = r#"{ "items": [{ "text": "old", "timestamp": 1 }], "max_items": 50 }"#;
+        let parsed = parse(json, ExportFormat::Json).unwrap();
+        assert_eq!(parsed[0].text, "old");
+        assert!(!parsed[0].pinned);
+    }
+}
@@ -1,24 +1,43 @@
 //! Clipboard management module
 //!
 //! Provides clipboard history management with LRU eviction
-//! and JSON-based persistence.
+//! and SQLite-based persistence.
 
+pub mod crypto;
+mod db;
+pub mod diff;
+pub mod export;
+pub mod filter;
 mod store;
+pub mod transform;
 
-pub use store::ClipboardStore;
+pub use store::{ClipboardError, ClipboardStore, OversizePolicy};
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Clipboard item data structure
 ///
 /// Represents a single clipboard history entry with its text content
 /// and timestamp for LRU ordering.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipboardItem {
+    /// Unique identifier (UUID v4), kept when the same text is copied again
+    #[serde(default = "new_id")]
+    pub id: String,
     /// The text content of the clipboard item
     pub text: String,
+    /// SHA-256 of the text (hex), used to find duplicates
+    #[serde(skip)]
+    pub hash: String,
     /// Unix timestamp when this item was copied
     pub timestamp: u64,
+    /// Pinned items are never evicted by the history limit
+    #[serde(default)]
+    pub pinned: bool,
+    /// HTML representation, when the copy offered one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub html: Option<String>,
 }
 
 impl ClipboardItem {
@@ -38,8 +57,12 @@ impl ClipboardItem {
     /// ```
     pub fn new(text: String) -> Self {
         Self {
+            id: new_id(),
+            hash: content_hash(&text),
             text,
             timestamp: Self::current_timestamp(),
+            pinned: false,
+            html: None,
         }
     }
 
@@ -51,7 +74,14 @@ impl ClipboardItem {
     /// * `timestamp` - Unix timestamp
     #[allow(dead_code)]
     pub fn with_timestamp(text: String, timestamp: u64) -> Self {
-        Self { text, timestamp }
+        Self {
+            id: new_id(),
+            hash: content_hash(&text),
+            text,
+            timestamp,
+            pinned: false,
+            html: None,
+        }
     }
 
     /// Gets the current Unix timestamp
@@ -63,6 +93,30 @@ impl ClipboardItem {
     }
 }
 
+/// Hashes clipboard text for duplicate detection
+///
+/// # Returns
+///
+/// The SHA-256 digest as lowercase hex
+pub fn content_hash(text: &str) -> String {
+    format!("{:x}", Sha256::digest(text.as_bytes()))
+}
+
+/// Compares content only; the ID isn't part of equality
+impl PartialEq for ClipboardItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.text == other.text
+            && self.timestamp == other.timestamp
+            && self.pinned == other.pinned
+            && self.html == other.html
+    }
+}
+
+/// Generates a new item ID (also used for items stored without one)
+fn new_id() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -87,4 +141,29 @@ mod tests {
         let item2 = ClipboardItem::with_timestamp("test".to_string(), 123);
         assert_eq!(item1, item2);
     }
+
+    #[test]
+    fn test_items_get_unique_ids() {
+        let item1 = ClipboardItem::new("test".to_string());
+        let item2 = ClipboardItem::new("test".to_string());
+        assert_ne!(item1.id, item2.id);
+        assert_eq!(item1.id.len(), 36);
+    }
+
+    #[test]
+    fn test_new_hashes_text() {
+        let item = ClipboardItem::new("abc".to_string());
+        assert_eq!(
+            item.hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(item.hash, content_hash("abc"));
+    }
+
+    #[test]
+    fn test_deserialize_without_id_generates_one() {
+        let item: ClipboardItem =
+            serde_json::from_str(r#"{ "text": "old", "timestamp": 1 }"#).unwrap();
+        assert!(!item.id.is_empty());
+    }
 }
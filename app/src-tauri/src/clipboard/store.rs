@@ -1,12 +1,63 @@
 //! Clipboard history storage with LRU eviction
 //!
-//! Manages clipboard history persistence to JSON file with configurable
-//! maximum item limit. Uses LRU (Least Recently Used) strategy for eviction.
-
-use super::ClipboardItem;
+//! Keeps the history in memory with a configurable maximum item limit,
+//! using LRU (Least Recently Used) eviction. Pinned items are never
+//! evicted. The app persists it to SQLite
+//! (see [`ClipboardStore::open`]); the older JSON file format is still
+//! read to migrate existing histories.
+
+use super::crypto::{CryptoError, Encryption};
+use super::db::ClipboardDb;
+use super::{content_hash, ClipboardItem};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Marker appended to truncated items
+pub const TRUNCATION_MARKER: &str = "\n[… truncated]";
+
+/// What to do with items larger than the per-item size limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OversizePolicy {
+    /// Keep the beginning of the text and append [`TRUNCATION_MARKER`];
+    /// items are skipped if the limit can't even fit the marker
+    #[default]
+    Truncate,
+    /// Don't store the item
+    Skip,
+}
+
+impl OversizePolicy {
+    /// Resolves the policy from the `clipboardOversize` config option
+    ///
+    /// # Arguments
+    ///
+    /// * `setting` - "truncate" or "skip"
+    pub fn from_setting(setting: &str) -> Self {
+        match setting {
+            "skip" => Self::Skip,
+            _ => Self::Truncate,
+        }
+    }
+}
+
+/// Errors from writing the clipboard history
+#[derive(Debug, thiserror::Error)]
+pub enum ClipboardError {
+    #[error("failed to write clipboard history: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to serialize clipboard history: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("clipboard database error: {0}")]
+    Db(#[from] rusqlite::Error),
+    #[error("{0}")]
+    Crypto(#[from] CryptoError),
+    #[error("Clipboard item {0} not found")]
+    NotFound(String),
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ClipboardStore {
@@ -14,6 +65,15 @@ pub struct ClipboardStore {
     pub items: Vec<ClipboardItem>,
     /// Maximum number of items to keep
     pub max_items: usize,
+    /// Maximum size of one item in bytes (0 for no limit)
+    #[serde(skip)]
+    pub max_item_bytes: usize,
+    /// What to do with items over `max_item_bytes`
+    #[serde(skip)]
+    pub oversize: OversizePolicy,
+    /// Database that changes are written to (None for in-memory stores)
+    #[serde(skip)]
+    db: Option<ClipboardDb>,
 }
 
 impl ClipboardStore {
@@ -24,6 +84,9 @@ impl ClipboardStore {
         Self {
             items: Vec::new(),
             max_items: 50,
+            max_item_bytes: 0,
+            oversize: OversizePolicy::default(),
+            db: None,
         }
     }
 
@@ -32,17 +95,105 @@ impl ClipboardStore {
     /// # Arguments
     ///
     /// * `max_items` - Maximum number of items to store
-    #[allow(dead_code)]
     pub fn new_with_limit(max_items: usize) -> Self {
         Self {
             items: Vec::new(),
             max_items,
+            max_item_bytes: 0,
+            oversize: OversizePolicy::default(),
+            db: None,
+        }
+    }
+
+    /// Opens the SQLite-backed clipboard store without encryption
+    ///
+    /// See [`open_with_encryption`](Self::open_with_encryption).
+    #[allow(dead_code)]
+    pub fn open(path: &Path, max_items: usize) -> Result<Self, ClipboardError> {
+        Self::open_with_encryption(path, max_items, Encryption::default())
+    }
+
+    /// Opens the SQLite-backed clipboard store
+    ///
+    /// On first use, imports the history from `clipboard.json` next to the
+    /// database and renames that file to `clipboard.json.bak` (or deletes
+    /// it when encryption is on). Existing items are encrypted or decrypted
+    /// to match `encryption`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the SQLite database
+    /// * `max_items` - Maximum number of items to keep
+    /// * `encryption` - How item texts are stored
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database can't be opened or written, or
+    /// [`CryptoError::MissingKey`] if it holds encrypted items and
+    /// `encryption` has no key
+    pub fn open_with_encryption(
+        path: &Path,
+        max_items: usize,
+        encryption: Encryption,
+    ) -> Result<Self, ClipboardError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let encrypt = encryption.writer().is_some();
+        let db = ClipboardDb::open(path, encryption)?;
+
+        let legacy = path.with_extension("json");
+        if db.is_empty()? && legacy.exists() {
+            let store = Self::load(&legacy);
+            db.import(&store.items)?;
+            // Don't leave a plaintext copy behind an encrypted history
+            let result = if encrypt {
+                fs::remove_file(&legacy)
+            } else {
+                fs::rename(&legacy, legacy.with_extension("json.bak"))
+            };
+            if let Err(e) = result {
+                warn!("Failed to move {:?} aside: {}", legacy, e);
+            }
+            info!(
+                "Migrated {} clipboard items from {:?}",
+                store.items.len(),
+                legacy
+            );
+        }
+
+        let rewritten = db.reencode()?;
+        if rewritten > 0 {
+            info!(
+                "{} {} clipboard items",
+                if encrypt { "Encrypted" } else { "Decrypted" },
+                rewritten
+            );
+        }
+        db.trim(max_items)?;
+        Ok(Self {
+            items: db.load()?,
+            max_items,
+            max_item_bytes: 0,
+            oversize: OversizePolicy::default(),
+            db: Some(db),
+        })
+    }
+
+    /// Checks whether the database at `path` holds encrypted items
+    ///
+    /// Used to tell whether a key is needed before opening the store.
+    pub fn has_encrypted_items(path: &Path) -> Result<bool, ClipboardError> {
+        if !path.exists() {
+            return Ok(false);
         }
+        Ok(ClipboardDb::open(path, Encryption::default())?.has_encrypted()?)
     }
 
     /// Loads clipboard store from file
     ///
     /// Returns a new empty store if file doesn't exist or is invalid.
+    /// Items are hashed on load and duplicates (after the first) dropped.
     ///
     /// # Arguments
     ///
@@ -52,13 +203,19 @@ impl ClipboardStore {
             return Self::new();
         }
 
-        match fs::read_to_string(path) {
+        let mut store: Self = match fs::read_to_string(path) {
             Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| Self::new()),
             Err(_) => Self::new(),
-        }
+        };
+        let mut seen = HashSet::new();
+        store.items.retain_mut(|item| {
+            item.hash = content_hash(&item.text);
+            seen.insert(item.hash.clone())
+        });
+        store
     }
 
-    /// Saves clipboard store to file
+    /// Saves clipboard store to a JSON file
     ///
     /// Creates parent directories if they don't exist.
     ///
@@ -69,7 +226,8 @@ impl ClipboardStore {
     /// # Errors
     ///
     /// Returns error if file write fails
-    pub fn save(&self, path: &PathBuf) -> Result<(), std::io::Error> {
+    #[allow(dead_code)]
+    pub fn save(&self, path: &PathBuf) -> Result<(), ClipboardError> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -81,31 +239,284 @@ impl ClipboardStore {
 
     /// Adds a new item to the clipboard history
     ///
-    /// If the item already exists (same text), updates its timestamp.
-    /// Enforces max_items limit by removing oldest items.
+    /// If the item already exists (same text), updates its timestamp and
+    /// keeps its pinned state. Enforces max_items limit by removing the
+    /// oldest unpinned items. Text over `max_item_bytes` is truncated or
+    /// skipped according to `oversize`.
     ///
     /// # Arguments
     ///
     /// * `text` - The clipboard text content
-    pub fn add(&mut self, text: String) {
-        // Remove existing item with same text if present
-        self.items.retain(|item| item.text != text);
+    ///
+    /// # Returns
+    ///
+    /// false if the item was skipped for its size
+    pub fn add(&mut self, text: String) -> bool {
+        self.add_with_html(text, None)
+    }
+
+    /// Adds a new item with an optional HTML representation
+    ///
+    /// Same as [`add`](Self::add). The HTML is dropped when it's over
+    /// `max_item_bytes` or the text had to be truncated, since HTML can't
+    /// be cut safely.
+    pub fn add_with_html(&mut self, text: String, html: Option<String>) -> bool {
+        let original_len = text.len();
+        let Some(text) = self.fit_size(text) else {
+            return false;
+        };
+        let html = html.filter(|html| {
+            text.len() == original_len
+                && (self.max_item_bytes == 0 || html.len() <= self.max_item_bytes)
+        });
+
+        // Remove existing item with same text if present, keeping its ID
+        // and pinned state. Comparing hashes avoids scanning large texts.
+        let hash = content_hash(&text);
+        let existing = self
+            .items
+            .iter()
+            .position(|item| item.hash == hash)
+            .map(|index| self.items.remove(index));
 
         // Add new item at the beginning (most recent)
-        self.items.insert(0, ClipboardItem::new(text));
+        let mut item = ClipboardItem::new(text);
+        if let Some(existing) = existing {
+            item.id = existing.id;
+            item.pinned = existing.pinned;
+        }
+        item.html = html;
+        self.items.insert(0, item);
+
+        self.evict();
+        true
+    }
+
+    /// Applies the per-item size limit
+    ///
+    /// # Returns
+    ///
+    /// The text to store, or None if it must be skipped
+    fn fit_size(&self, mut text: String) -> Option<String> {
+        if self.max_item_bytes == 0 || text.len() <= self.max_item_bytes {
+            return Some(text);
+        }
+        match self.oversize {
+            OversizePolicy::Skip => {
+                info!("Skipping clipboard item of {} bytes", text.len());
+                None
+            }
+            OversizePolicy::Truncate if self.max_item_bytes < TRUNCATION_MARKER.len() => {
+                info!(
+                    "Skipping clipboard item of {} bytes, the limit is too small to truncate",
+                    text.len()
+                );
+                None
+            }
+            OversizePolicy::Truncate => {
+                let mut end = self.max_item_bytes.saturating_sub(TRUNCATION_MARKER.len());
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                text.truncate(end);
+                text.push_str(TRUNCATION_MARKER);
+                Some(text)
+            }
+        }
+    }
+
+    /// Removes the oldest unpinned items until the limit is met
+    ///
+    /// Pinned items don't count toward the limit, so pinning never pushes
+    /// out a newer copy.
+    fn evict(&mut self) {
+        let unpinned = self.items.iter().filter(|item| !item.pinned).count();
+        let mut excess = unpinned.saturating_sub(self.max_items);
+        let mut index = self.items.len();
+        while excess > 0 && index > 0 {
+            index -= 1;
+            if !self.items[index].pinned {
+                self.items.remove(index);
+                excess -= 1;
+            }
+        }
+    }
+
+    /// Adds an item and writes the change to the database
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The clipboard text content
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the database write fails (the item is still added
+    /// in memory)
+    ///
+    /// # Returns
+    ///
+    /// false if the item was skipped for its size
+    pub fn push(&mut self, text: String) -> Result<bool, ClipboardError> {
+        if !self.add(text) {
+            return Ok(false);
+        }
+        self.save_newest()?;
+        Ok(true)
+    }
+
+    /// Adds an item with an optional HTML representation and writes the
+    /// change to the database
+    ///
+    /// See [`add_with_html`](Self::add_with_html) and [`push`](Self::push).
+    pub fn push_with_html(
+        &mut self,
+        text: String,
+        html: Option<String>,
+    ) -> Result<bool, ClipboardError> {
+        if !self.add_with_html(text, html) {
+            return Ok(false);
+        }
+        self.save_newest()?;
+        Ok(true)
+    }
+
+    /// Writes the newest item to the database and applies the limit there
+    fn save_newest(&self) -> Result<(), ClipboardError> {
+        if let Some(db) = &self.db {
+            db.insert(&self.items[0])?;
+            db.trim(self.max_items)?;
+        }
+        Ok(())
+    }
+
+    /// Finds the index of the item with the given ID
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.items.iter().position(|item| item.id == id)
+    }
+
+    /// Removes the item at `index` and deletes it from the database
+    ///
+    /// Indexes shift as items are added, so prefer
+    /// [`remove_by_id`](Self::remove_by_id) when the history may have
+    /// changed since the index was read.
+    ///
+    /// # Returns
+    ///
+    /// The removed item, or None if `index` is out of range
+    pub fn remove(&mut self, index: usize) -> Result<Option<ClipboardItem>, ClipboardError> {
+        if index >= self.items.len() {
+            return Ok(None);
+        }
+        let item = self.items.remove(index);
+        if let Some(db) = &self.db {
+            db.delete(&item.text)?;
+        }
+        Ok(Some(item))
+    }
 
-        // Enforce max_items limit
-        if self.items.len() > self.max_items {
-            self.items.truncate(self.max_items);
+    /// Removes the item with the given ID and deletes it from the database
+    ///
+    /// # Returns
+    ///
+    /// The removed item, or None if no item has that ID
+    pub fn remove_by_id(&mut self, id: &str) -> Result<Option<ClipboardItem>, ClipboardError> {
+        match self.position(id) {
+            Some(index) => self.remove(index),
+            None => Ok(None),
+        }
+    }
+
+    /// Merges imported items into the history and writes it to the database
+    ///
+    /// Items with new text are added; for text already in the history the
+    /// newer timestamp wins and pinned state is kept if either is pinned.
+    /// The result is ordered by timestamp, then the size and item limits
+    /// are applied.
+    ///
+    /// # Returns
+    ///
+    /// Number of items added
+    pub fn merge(&mut self, items: Vec<ClipboardItem>) -> Result<usize, ClipboardError> {
+        let mut added = 0;
+        for mut item in items {
+            let Some(text) = self.fit_size(item.text) else {
+                continue;
+            };
+            item.hash = content_hash(&text);
+            item.text = text;
+            match self.items.iter_mut().find(|i| i.hash == item.hash) {
+                Some(existing) => {
+                    existing.timestamp = existing.timestamp.max(item.timestamp);
+                    existing.pinned |= item.pinned;
+                }
+                None => {
+                    self.items.push(item);
+                    added += 1;
+                }
+            }
+        }
+
+        self.items
+            .sort_by_key(|item| std::cmp::Reverse(item.timestamp));
+        self.evict();
+        if let Some(db) = &self.db {
+            db.import(&self.items)?;
+            db.trim(self.max_items)?;
+        }
+        Ok(added)
+    }
+
+    /// Removes unpinned items older than `max_age` and deletes them from
+    /// the database
+    ///
+    /// # Returns
+    ///
+    /// Number of items removed
+    pub fn expire(&mut self, max_age: Duration) -> Result<usize, ClipboardError> {
+        let cutoff = ClipboardItem::current_timestamp().saturating_sub(max_age.as_secs());
+        let count = self.items.len();
+        self.items
+            .retain(|item| item.pinned || item.timestamp >= cutoff);
+        let removed = count - self.items.len();
+        if removed > 0 {
+            if let Some(db) = &self.db {
+                db.delete_older_than(cutoff)?;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Pins or unpins the item at `index`
+    ///
+    /// Unpinning applies the item limit again, so the oldest unpinned item
+    /// may be evicted.
+    ///
+    /// # Returns
+    ///
+    /// False if `index` is out of range
+    pub fn set_pinned(&mut self, index: usize, pinned: bool) -> Result<bool, ClipboardError> {
+        let Some(item) = self.items.get_mut(index) else {
+            return Ok(false);
+        };
+        item.pinned = pinned;
+        if let Some(db) = &self.db {
+            db.set_pinned(&item.text, pinned)?;
+        }
+        if !pinned {
+            self.evict();
+            if let Some(db) = &self.db {
+                db.trim(self.max_items)?;
+            }
         }
+        Ok(true)
     }
 
-    /// Gets the storage file path
+    /// Gets the database path
     ///
-    /// Uses XDG Base Directory specification: ~/.config/uti/clipboard.json
+    /// Uses XDG Base Directory specification: ~/.config/uti/clipboard.db
     pub fn get_storage_path() -> PathBuf {
         let config_dir = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
-        config_dir.join("uti").join("clipboard.json")
+        config_dir.join("uti").join("clipboard.db")
     }
 }
 
@@ -165,6 +576,19 @@ mod tests {
         assert!(store.items[0].timestamp > first_timestamp);
     }
 
+    #[test]
+    fn test_add_keeps_id_of_duplicate() {
+        let mut store = ClipboardStore::new();
+        store.add("item1".to_string());
+        let id = store.items[0].id.clone();
+        store.add("item2".to_string());
+        store.add("item1".to_string());
+
+        assert_eq!(store.items[0].id, id);
+        assert_ne!(store.items[1].id, id);
+        assert_eq!(store.position(&id), Some(0));
+    }
+
     #[test]
     fn test_add_enforces_max_items_limit() {
         let mut store = ClipboardStore::new_with_limit(3);
@@ -182,6 +606,126 @@ mod tests {
         assert!(!store.items.iter().any(|i| i.text == "item1"));
     }
 
+    #[test]
+    fn test_add_keeps_pinned_items() {
+        let mut store = ClipboardStore::new_with_limit(2);
+        store.add("keep".to_string());
+        store.set_pinned(0, true).unwrap();
+        store.add("a".to_string());
+        store.add("b".to_string());
+        store.add("c".to_string());
+
+        let texts: Vec<_> = store.items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(texts, vec!["c", "b", "keep"]);
+
+        // Copying a pinned item again keeps it pinned
+        store.add("keep".to_string());
+        assert!(store.items[0].pinned);
+    }
+
+    #[test]
+    fn test_truncates_oversized_items() {
+        let mut store = ClipboardStore::new_with_limit(10);
+        store.max_item_bytes = 32;
+
+        assert!(store.add("あ".repeat(20)));
+        let text = &store.items[0].text;
+        assert!(text.len() <= 32);
+        assert!(text.starts_with("ああ"));
+        assert!(text.ends_with(TRUNCATION_MARKER));
+
+        assert!(store.add("short".to_string()));
+        assert_eq!(store.items[0].text, "short");
+    }
+
+    #[test]
+    fn test_skips_items_when_limit_is_below_marker() {
+        let mut store = ClipboardStore::new_with_limit(10);
+        store.max_item_bytes = TRUNCATION_MARKER.len() - 1;
+
+        assert!(!store.push("x".repeat(TRUNCATION_MARKER.len())).unwrap());
+        assert!(store.items.is_empty());
+
+        assert!(store.push("short".to_string()).unwrap());
+        assert_eq!(store.items[0].text, "short");
+    }
+
+    #[test]
+    fn test_skips_oversized_items() {
+        let mut store = ClipboardStore::new_with_limit(10);
+        store.max_item_bytes = 4;
+        store.oversize = OversizePolicy::Skip;
+
+        assert!(!store.push("too long".to_string()).unwrap());
+        assert!(store.items.is_empty());
+    }
+
+    #[test]
+    fn test_merge_keeps_newest_and_pinned() {
+        let mut store = ClipboardStore::new_with_limit(10);
+        store.items = vec![
+            ClipboardItem::with_timestamp("b".to_string(), 20),
+            ClipboardItem::with_timestamp("a".to_string(), 10),
+        ];
+        let mut pinned = ClipboardItem::with_timestamp("a".to_string(), 5);
+        pinned.pinned = true;
+
+        let added = store
+            .merge(vec![
+                ClipboardItem::with_timestamp("c".to_string(), 30),
+                pinned,
+            ])
+            .unwrap();
+
+        assert_eq!(added, 1);
+        let texts: Vec<_> = store.items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(texts, vec!["c", "b", "a"]);
+        assert_eq!(store.items[2].timestamp, 10);
+        assert!(store.items[2].pinned);
+    }
+
+    #[test]
+    fn test_html_dropped_when_text_truncated() {
+        let mut store = ClipboardStore::new_with_limit(10);
+        store.add_with_html("bold".to_string(), Some("<b>bold</b>".to_string()));
+        assert_eq!(store.items[0].html.as_deref(), Some("<b>bold</b>"));
+
+        store.max_item_bytes = 24;
+        store.add_with_html("x".repeat(30), Some("<i>x</i>".to_string()));
+        assert!(store.items[0].text.ends_with(TRUNCATION_MARKER));
+        assert_eq!(store.items[0].html, None);
+    }
+
+    #[test]
+    fn test_unpin_applies_limit() {
+        let mut store = ClipboardStore::new_with_limit(1);
+        store.add("a".to_string());
+        store.set_pinned(0, true).unwrap();
+        store.add("b".to_string());
+        assert_eq!(store.items.len(), 2);
+
+        assert!(store.set_pinned(1, false).unwrap());
+        assert_eq!(store.items.len(), 1);
+        assert_eq!(store.items[0].text, "b");
+        assert!(!store.set_pinned(5, true).unwrap());
+    }
+
+    #[test]
+    fn test_expire_keeps_recent_and_pinned() {
+        let mut store = ClipboardStore::new();
+        store.items = vec![
+            ClipboardItem::new("recent".to_string()),
+            ClipboardItem::with_timestamp("old".to_string(), 1),
+            ClipboardItem::with_timestamp("old pinned".to_string(), 1),
+        ];
+        store.items[2].pinned = true;
+
+        let week = Duration::from_secs(7 * 24 * 60 * 60);
+        assert_eq!(store.expire(week).unwrap(), 1);
+        let texts: Vec<_> = store.items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(texts, vec!["recent", "old pinned"]);
+    }
+
     #[test]
     fn test_load_returns_empty_store_when_file_not_exists() {
         let temp_dir = TempDir::new().unwrap();
@@ -225,6 +769,64 @@ mod tests {
         assert!(path.exists());
     }
 
+    #[test]
+    fn test_open_migrates_json_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let json_path = temp_dir.path().join("clipboard.json");
+        let db_path = temp_dir.path().join("clipboard.db");
+
+        let mut legacy = ClipboardStore::new();
+        legacy.add("old1".to_string());
+        legacy.add("old2".to_string());
+        legacy.save(&json_path).unwrap();
+
+        let store = ClipboardStore::open(&db_path, 10).unwrap();
+        assert_eq!(store.items.len(), 2);
+        assert_eq!(store.items[0].text, "old2");
+        assert!(!json_path.exists());
+        assert!(temp_dir.path().join("clipboard.json.bak").exists());
+    }
+
+    #[test]
+    fn test_push_and_remove_persist() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("clipboard.db");
+
+        let mut store = ClipboardStore::open(&path, 2).unwrap();
+        store.push("a".to_string()).unwrap();
+        store.push("b".to_string()).unwrap();
+        store.push("c".to_string()).unwrap();
+        assert_eq!(store.remove(0).unwrap().unwrap().text, "c");
+        assert!(store.remove(5).unwrap().is_none());
+        drop(store);
+
+        let reopened = ClipboardStore::open(&path, 10).unwrap();
+        let texts: Vec<_> = reopened.items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(texts, vec!["b"]);
+    }
+
+    #[test]
+    fn test_remove_by_id_persists() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("clipboard.db");
+
+        let mut store = ClipboardStore::open(&path, 10).unwrap();
+        store.push("a".to_string()).unwrap();
+        store.push("b".to_string()).unwrap();
+        let id = store.items[1].id.clone();
+        // A new item shifts indexes but not IDs
+        store.push("c".to_string()).unwrap();
+
+        assert_eq!(store.remove_by_id(&id).unwrap().unwrap().text, "a");
+        assert!(store.remove_by_id(&id).unwrap().is_none());
+        let ids: Vec<_> = store.items.iter().map(|i| i.id.clone()).collect();
+        drop(store);
+
+        let reopened = ClipboardStore::open(&path, 10).unwrap();
+        let reopened_ids: Vec<_> = reopened.items.iter().map(|i| i.id.clone()).collect();
+        assert_eq!(reopened_ids, ids);
+    }
+
     #[test]
     fn test_load_returns_empty_store_on_invalid_json() {
         let temp_dir = TempDir::new().unwrap();
@@ -235,4 +837,24 @@ mod tests {
         let store = ClipboardStore::load(&path);
         assert_eq!(store.items.len(), 0);
     }
+
+    #[test]
+    fn test_load_hashes_and_dedupes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("clipboard.json");
+        fs::write(
+            &path,
+            r#"{ "items": [
+                { "text": "a", "timestamp": 3 },
+                { "text": "b", "timestamp": 2 },
+                { "text": "a", "timestamp": 1 }
+            ], "max_items": 50 }"#,
+        )
+        .unwrap();
+
+        let store = ClipboardStore::load(&path);
+        assert_eq!(store.items.len(), 2);
+        assert_eq!(store.items[0].timestamp, 3);
+        assert_eq!(store.items[0].hash, content_hash("a"));
+    }
 }
@@ -0,0 +1,175 @@
+//! Built-in clipboard item transforms
+//!
+//! Text operations applied to history items, e.g., to clean up text before
+//! pasting it. Plugins can add more through `clipboard/transform`.
+
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+
+/// Transform operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransformOp {
+    /// Remove leading and trailing whitespace
+    Trim,
+    /// Convert to upper case
+    Upper,
+    /// Convert to lower case
+    Lower,
+    /// Reformat JSON with indentation
+    JsonPretty,
+    /// Decode percent-encoded characters (`%20` to a space)
+    UrlDecode,
+    /// Join lines into one, separated by single spaces
+    StripNewlines,
+}
+
+/// Errors from transforms that need well-formed input
+#[derive(Debug, thiserror::Error)]
+pub enum TransformError {
+    #[error("not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("decoded URL is not valid UTF-8")]
+    Utf8(#[from] std::str::Utf8Error),
+}
+
+/// Applies a transform to text
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::clipboard::transform::{transform, TransformOp};
+///
+/// assert_eq!(transform(" a%20b ", TransformOp::Trim).unwrap(), "a%20b");
+/// assert_eq!(transform("a%20b", TransformOp::UrlDecode).unwrap(), "a b");
+/// ```
+pub fn transform(text: &str, op: TransformOp) -> Result<String, TransformError> {
+    Ok(match op {
+        TransformOp::Trim => text.trim().to_string(),
+        TransformOp::Upper => text.to_uppercase(),
+        TransformOp::Lower => text.to_lowercase(),
+        TransformOp::JsonPretty => {
+            serde_json::from_str::<serde::de::IgnoredAny>(text)?;
+            pretty_json(text)
+        }
+        TransformOp::UrlDecode => percent_decode_str(text).decode_utf8()?.into_owned(),
+        TransformOp::StripNewlines => text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" "),
+    })
+}
+
+/// Indents valid JSON by two spaces, keeping key order and number formatting
+/// (which parsing into a `serde_json::Value` would change)
+fn pretty_json(json: &str) -> String {
+    let mut out = String::with_capacity(json.len() * 2);
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = json.trim().chars().peekable();
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    };
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                // Keep empty objects and arrays on one line
+                if let Some(close) = chars.next_if(|&next| matches!(next, '}' | ']')) {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_and_trim() {
+        assert_eq!(transform("  Hello\n", TransformOp::Trim).unwrap(), "Hello");
+        assert_eq!(transform("Straße", TransformOp::Upper).unwrap(), "STRASSE");
+        assert_eq!(transform("ÀB", TransformOp::Lower).unwrap(), "àb");
+    }
+
+    #[test]
+    fn test_json_pretty() {
+        assert_eq!(
+            transform(r#"{"a":[1,2]}"#, TransformOp::JsonPretty).unwrap(),
+            "{\n  \"a\": [\n    1,\n    2\n  ]\n}"
+        );
+        // Key order is kept
+        assert_eq!(
+            transform(r#"{"b":1,"a":2}"#, TransformOp::JsonPretty).unwrap(),
+            "{\n  \"b\": 1,\n  \"a\": 2\n}"
+        );
+        assert_eq!(
+            transform(
+                r#" { "s": "a, {b}: \"c\"", "e": [ ], "n": 1.50 } "#,
+                TransformOp::JsonPretty
+            )
+            .unwrap(),
+            "{\n  \"s\": \"a, {b}: \\\"c\\\"\",\n  \"e\": [],\n  \"n\": 1.50\n}"
+        );
+        assert!(transform("{oops", TransformOp::JsonPretty).is_err());
+    }
+
+    #[test]
+    fn test_url_decode() {
+        assert_eq!(
+            transform("q=caf%C3%A9%20au+lait", TransformOp::UrlDecode).unwrap(),
+            "q=café au+lait"
+        );
+        assert!(transform("%FF", TransformOp::UrlDecode).is_err());
+    }
+
+    #[test]
+    fn test_strip_newlines() {
+        assert_eq!(
+            transform(
+                "line one\r\n  line two\n\nline three\n",
+                TransformOp::StripNewlines
+            )
+            .unwrap(),
+            "line one line two line three"
+        );
+    }
+}
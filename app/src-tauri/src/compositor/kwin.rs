@@ -0,0 +1,225 @@
+//! KWin backend
+//!
+//! Runs short KWin scripts through `org.kde.kwin.Scripting`. Scripts can't
+//! return values, so the active-window script calls back into a one-off
+//! object served on the app's connection.
+
+use super::{ActiveWindow, APP_ID};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::AppHandle;
+use tokio::sync::oneshot;
+use zbus::{interface, proxy, Connection};
+
+/// How long to wait for the active-window script to report back
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Interface of the callback object
+const CALLBACK_INTERFACE: &str = "io.github.noppomario.uti.KWinCallback";
+
+/// Finds the uti window (KWin 6 `windowList`, KWin 5 `clientList`)
+const FIND_WINDOW: &str = r#"
+function utiWindow() {
+    const windows = workspace.windowList ? workspace.windowList() : workspace.clientList();
+    for (const w of windows) {
+        if (String(w.resourceClass).toLowerCase() === args.appId) {
+            return w;
+        }
+    }
+    return null;
+}
+"#;
+
+/// Moves the uti window next to the cursor (same rules as the GNOME extension)
+const PLACE_SCRIPT: &str = r#"
+const w = utiWindow();
+if (w) {
+    const cursor = workspace.cursorPos;
+    const area = workspace.clientArea(KWin.PlacementArea, w);
+    const rect = w.frameGeometry;
+    const place = (pos, len, start, extent) => {
+        if (len <= start + extent - pos) return pos;
+        if (len <= pos - start) return pos - len;
+        return Math.max(start, start + extent - len);
+    };
+    w.frameGeometry = {
+        x: place(cursor.x, rect.width, area.x, area.width),
+        y: place(cursor.y, rect.height, area.y, area.height),
+        width: rect.width,
+        height: rect.height,
+    };
+}
+"#;
+
+/// Sets keep-above on the uti window
+const KEEP_ABOVE_SCRIPT: &str = r#"
+const w = utiWindow();
+if (w) {
+    w.keepAbove = args.enabled;
+}
+"#;
+
+/// Reports the active window to the callback object
+const ACTIVE_WINDOW_SCRIPT: &str = r#"
+const w = workspace.activeWindow || workspace.activeClient;
+callDBus(args.service, args.path, args.interface, "ActiveWindow",
+    w ? String(w.caption) : "", w ? String(w.resourceClass) : "");
+"#;
+
+/// KWin scripting D-Bus proxy
+#[proxy(
+    interface = "org.kde.kwin.Scripting",
+    default_service = "org.kde.KWin",
+    default_path = "/Scripting"
+)]
+trait Scripting {
+    /// Loads a script file, returning its ID
+    #[zbus(name = "loadScript")]
+    fn load_script(&self, file_path: &str, plugin_name: &str) -> zbus::Result<i32>;
+
+    /// Unloads a script by plugin name
+    #[zbus(name = "unloadScript")]
+    fn unload_script(&self, plugin_name: &str) -> zbus::Result<bool>;
+}
+
+/// Loaded KWin script D-Bus proxy (path `/Scripting/Script{id}`)
+#[proxy(interface = "org.kde.kwin.Script", default_service = "org.kde.KWin")]
+trait Script {
+    /// Runs the script
+    #[zbus(name = "run")]
+    fn run(&self) -> zbus::Result<()>;
+}
+
+/// Receives the active-window script's report
+struct Callback {
+    tx: Mutex<Option<oneshot::Sender<ActiveWindow>>>,
+}
+
+#[interface(name = "io.github.noppomario.uti.KWinCallback")]
+impl Callback {
+    fn active_window(&self, title: String, app_id: String) {
+        if let Some(tx) = self.tx.lock().take() {
+            let _ = tx.send(ActiveWindow { title, app_id });
+        }
+    }
+}
+
+/// Moves the uti window next to the cursor
+pub async fn place_at_cursor(app: &AppHandle) -> Result<(), String> {
+    let conn = connection(app).await?;
+    run_script(&conn, PLACE_SCRIPT, json!({ "appId": APP_ID })).await
+}
+
+/// Sets or clears keep-above on the uti window
+pub async fn set_keep_above(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    let conn = connection(app).await?;
+    run_script(
+        &conn,
+        KEEP_ABOVE_SCRIPT,
+        json!({ "appId": APP_ID, "enabled": enabled }),
+    )
+    .await
+}
+
+/// Gets the active window
+pub async fn active_window(app: &AppHandle) -> Result<Option<ActiveWindow>, String> {
+    let conn = connection(app).await?;
+    let service = conn
+        .unique_name()
+        .ok_or("D-Bus connection has no unique name")?
+        .to_string();
+    let path = format!(
+        "/io/github/noppomario/uti/KWinCallback/{}",
+        uuid::Uuid::new_v4().simple()
+    );
+
+    let (tx, rx) = oneshot::channel();
+    let callback = Callback {
+        tx: Mutex::new(Some(tx)),
+    };
+    conn.object_server()
+        .at(path.as_str(), callback)
+        .await
+        .map_err(|e| format!("Failed to serve KWin callback: {}", e))?;
+
+    let args = json!({ "service": service, "path": path, "interface": CALLBACK_INTERFACE });
+    let result = match run_script(&conn, ACTIVE_WINDOW_SCRIPT, args).await {
+        Ok(()) => tokio::time::timeout(CALLBACK_TIMEOUT, rx)
+            .await
+            .map_err(|_| "KWin did not report the active window".to_string())
+            .and_then(|r| r.map_err(|_| "KWin callback dropped".to_string())),
+        Err(e) => Err(e),
+    };
+    let _ = conn
+        .object_server()
+        .remove::<Callback, _>(path.as_str())
+        .await;
+
+    let window = result?;
+    Ok((!window.title.is_empty() || !window.app_id.is_empty()).then_some(window))
+}
+
+/// Gets the app's session bus connection
+async fn connection(app: &AppHandle) -> Result<Connection, String> {
+    crate::dbus::connection(app)
+        .await
+        .map_err(|e| format!("Failed to connect to session bus: {}", e))
+}
+
+/// Loads, runs and unloads a script
+///
+/// `args` is available to the script as the `args` object.
+async fn run_script(conn: &Connection, body: &str, args: Value) -> Result<(), String> {
+    let name = format!("uti-{}", uuid::Uuid::new_v4().simple());
+    let path = script_dir().join(format!("{}.js", name));
+    std::fs::write(&path, build_script(body, &args))
+        .map_err(|e| format!("Failed to write KWin script: {}", e))?;
+
+    let result = load_and_run(conn, &path, &name).await;
+    let _ = std::fs::remove_file(&path);
+    result.map_err(|e| format!("KWin script failed: {}", e))
+}
+
+async fn load_and_run(conn: &Connection, path: &std::path::Path, name: &str) -> zbus::Result<()> {
+    let scripting = ScriptingProxy::new(conn).await?;
+    let id = scripting.load_script(&path.to_string_lossy(), name).await?;
+    let result = async {
+        ScriptProxy::builder(conn)
+            .path(format!("/Scripting/Script{}", id))?
+            .build()
+            .await?
+            .run()
+            .await
+    }
+    .await;
+    let _ = scripting.unload_script(name).await;
+    result
+}
+
+/// Directory for temporary script files
+fn script_dir() -> PathBuf {
+    dirs::runtime_dir().unwrap_or_else(std::env::temp_dir)
+}
+
+/// Prepends the arguments and the window lookup helper to a script
+fn build_script(body: &str, args: &Value) -> String {
+    format!("const args = {};\n{}{}", args, FIND_WINDOW, body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_script_embeds_args() {
+        let script = build_script(
+            KEEP_ABOVE_SCRIPT,
+            &json!({ "appId": "uti", "enabled": true }),
+        );
+        assert!(script.starts_with(r#"const args = {"appId":"uti","enabled":true};"#));
+        assert!(script.contains("function utiWindow()"));
+        assert!(script.contains("w.keepAbove = args.enabled"));
+    }
+}
@@ -0,0 +1,450 @@
+//! Compositor integration
+//!
+//! Window placement at the cursor, always-on-top and active-window info
+//! need compositor support on Wayland. Each backend provides what the
+//! compositor allows:
+//!
+//! | Backend  | Placement     | Always on top | Active window          |
+//! | -------- | ------------- | ------------- | ---------------------- |
+//! | GNOME    | uti extension | uti extension | uti extension          |
+//! | KWin     | KWin script   | KWin script   | KWin script            |
+//! | Sway     | `swaymsg`     | sticky        | `swaymsg -t get_tree`  |
+//! | Hyprland | `hyprctl`     | pin           | `hyprctl activewindow` |
+//! | None     | centered      | Tauri (X11)   | `xprop` (X11)          |
+//!
+//! The backend is detected from the session unless the `compositor`
+//! config option names one.
+
+mod kwin;
+mod wlroots;
+mod x11;
+
+use crate::error;
+use parking_lot::{Condvar, Mutex};
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, LogicalPosition, Manager, State, WebviewWindow};
+
+/// Window class / app ID of the main window
+pub const APP_ID: &str = "uti";
+
+/// How long a toggle waits for the GNOME extension's `ShowAt` call
+const SHOW_AT_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// How long a `ShowAt` call counts as the answer to the current trigger
+const SHOW_AT_TTL: Duration = Duration::from_millis(500);
+
+/// Compositor backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// GNOME Shell with the uti extension
+    Gnome,
+    /// KDE Plasma (KWin scripting)
+    KWin,
+    /// Sway (IPC via `swaymsg`)
+    Sway,
+    /// Hyprland (IPC via `hyprctl`)
+    Hyprland,
+    /// No integration
+    None,
+}
+
+impl Backend {
+    /// Resolves the backend from the `compositor` config option
+    ///
+    /// # Arguments
+    ///
+    /// * `setting` - "auto", "gnome", "kwin", "sway", "hyprland" or "none"
+    pub fn from_setting(setting: &str) -> Self {
+        match setting {
+            "gnome" => Self::Gnome,
+            "kwin" => Self::KWin,
+            "sway" => Self::Sway,
+            "hyprland" => Self::Hyprland,
+            "none" => Self::None,
+            _ => Self::detect(),
+        }
+    }
+
+    /// Detects the backend from the session environment
+    pub fn detect() -> Self {
+        Self::detect_from(
+            &std::env::var("XDG_CURRENT_DESKTOP").unwrap_or_default(),
+            std::env::var_os("SWAYSOCK").is_some(),
+            std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE").is_some(),
+        )
+    }
+
+    fn detect_from(desktop: &str, sway: bool, hyprland: bool) -> Self {
+        let desktop = desktop.to_uppercase();
+        if desktop.contains("GNOME") {
+            Self::Gnome
+        } else if desktop.contains("KDE") {
+            Self::KWin
+        } else if hyprland || desktop.contains("HYPRLAND") {
+            Self::Hyprland
+        } else if sway || desktop.contains("SWAY") {
+            Self::Sway
+        } else {
+            Self::None
+        }
+    }
+
+    /// Gets the backend name (as used in the config)
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Gnome => "gnome",
+            Self::KWin => "kwin",
+            Self::Sway => "sway",
+            Self::Hyprland => "hyprland",
+            Self::None => "none",
+        }
+    }
+}
+
+/// Information about the focused window
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveWindow {
+    /// Window title
+    pub title: String,
+    /// App ID (Wayland) or window class (X11)
+    pub app_id: String,
+}
+
+/// Focused window as last reported by the GNOME extension
+///
+/// Mutter doesn't expose the focused window to clients, so the extension
+/// reports focus changes over D-Bus (`SetActiveWindow`).
+#[derive(Default)]
+pub struct ReportedWindow(Mutex<Option<ActiveWindow>>);
+
+impl ReportedWindow {
+    /// Replaces the reported window
+    pub fn set(&self, window: Option<ActiveWindow>) {
+        *self.0.lock() = window;
+    }
+
+    fn get(&self) -> Option<ActiveWindow> {
+        self.0.lock().clone()
+    }
+}
+
+/// When the GNOME extension last showed the window with `ShowAt`
+///
+/// The extension and the app both receive `Triggered`. The extension
+/// answers with the pointer position (`ShowAt`), and the app's toggle
+/// leaves showing the window to it instead of racing it.
+#[derive(Default)]
+pub struct ShowAtReport {
+    shown: Mutex<Option<Instant>>,
+    condvar: Condvar,
+}
+
+impl ShowAtReport {
+    /// Records a `ShowAt` call and wakes a waiting toggle
+    fn report(&self) {
+        *self.shown.lock() = Some(Instant::now());
+        self.condvar.notify_all();
+    }
+
+    /// Takes a recent `ShowAt` call, waiting up to `timeout` for one
+    ///
+    /// # Returns
+    ///
+    /// true if the extension showed the window for the current trigger
+    fn take(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        let mut shown = self.shown.lock();
+        loop {
+            if shown.take().is_some_and(|at| at.elapsed() <= SHOW_AT_TTL) {
+                return true;
+            }
+            if self.condvar.wait_until(&mut shown, deadline).timed_out() {
+                return shown.take().is_some_and(|at| at.elapsed() <= SHOW_AT_TTL);
+            }
+        }
+    }
+}
+
+/// A rectangle in compositor coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Computes the window position next to the cursor
+///
+/// Prefers right of and below the cursor, flips to the other side when
+/// the window doesn't fit, and clamps to the work area when neither side
+/// fits (same rules as the GNOME extension).
+///
+/// # Arguments
+///
+/// * `cursor` - Cursor position
+/// * `size` - Window size (width, height)
+/// * `area` - Work area of the monitor under the cursor
+///
+/// # Returns
+///
+/// Top-left corner for the window
+pub fn place_near_cursor(cursor: (i32, i32), size: (i32, i32), area: Rect) -> (i32, i32) {
+    let place = |pos: i32, len: i32, start: i32, extent: i32| {
+        if len <= start + extent - pos {
+            pos
+        } else if len <= pos - start {
+            pos - len
+        } else {
+            start.max(start + extent - len)
+        }
+    };
+    (
+        place(cursor.0, size.0, area.x, area.width),
+        place(cursor.1, size.1, area.y, area.height),
+    )
+}
+
+/// Checks whether the GNOME extension shows the window for this trigger
+///
+/// The extension calls `ShowAt` when the window is hidden, so a hidden
+/// window waits briefly for that call; a visible one only checks whether
+/// the call already came. Always false on other backends.
+///
+/// # Arguments
+///
+/// * `visible` - Whether the main window is visible
+pub fn shown_by_extension(app: &AppHandle, visible: bool) -> bool {
+    if *app.state::<Backend>() != Backend::Gnome {
+        return false;
+    }
+    let timeout = if visible {
+        Duration::ZERO
+    } else {
+        SHOW_AT_TIMEOUT
+    };
+    app.state::<ShowAtReport>().take(timeout)
+}
+
+/// Shows the main window next to a pointer position
+///
+/// Called for the GNOME extension's `ShowAt`. Tauri can only move the
+/// window on X11; on Wayland the extension moves it once it's mapped.
+///
+/// # Arguments
+///
+/// * `x`, `y` - Pointer position in GNOME Shell coordinates
+pub fn show_window_at(app: &AppHandle, x: i32, y: i32) -> tauri::Result<()> {
+    let window = app
+        .get_webview_window("main")
+        .ok_or(tauri::Error::WindowNotFound)?;
+    app.state::<ShowAtReport>().report();
+
+    if !window.is_visible()? {
+        if let Some(position) = position_near(&window, x, y)? {
+            window.set_position(position)?;
+        }
+        window.show()?;
+    }
+    window.set_focus()
+}
+
+/// Computes the window position next to a point, on the monitor under it
+///
+/// # Returns
+///
+/// None if no monitor contains the point
+fn position_near(
+    window: &WebviewWindow,
+    x: i32,
+    y: i32,
+) -> tauri::Result<Option<LogicalPosition<i32>>> {
+    let Some(monitor) = window.monitor_from_point(x as f64, y as f64)? else {
+        return Ok(None);
+    };
+    let scale = monitor.scale_factor();
+    let origin = monitor.work_area().position.to_logical::<i32>(scale);
+    let extent = monitor.work_area().size.to_logical::<i32>(scale);
+    let size = window.outer_size()?.to_logical::<i32>(scale);
+
+    let area = Rect {
+        x: origin.x,
+        y: origin.y,
+        width: extent.width,
+        height: extent.height,
+    };
+    let (left, top) = place_near_cursor((x, y), (size.width, size.height), area);
+    Ok(Some(LogicalPosition::new(left, top)))
+}
+
+/// Prepares to show the main window
+///
+/// Without integration, centers it. Other backends place it after it's
+/// shown (see [`place_at_cursor`]); on GNOME the extension shows it (see
+/// [`shown_by_extension`]).
+pub fn before_show(app: &AppHandle, window: &WebviewWindow) {
+    match *app.state::<Backend>() {
+        Backend::None => {
+            if let Err(e) = window.center() {
+                tracing::error!("Failed to center window: {}", e);
+            }
+        }
+        Backend::Gnome | Backend::KWin | Backend::Sway | Backend::Hyprland => {}
+    }
+}
+
+/// Moves the shown main window next to the cursor
+///
+/// Does nothing on GNOME (the extension already did it) or without
+/// integration.
+pub async fn place_at_cursor(app: &AppHandle) -> Result<(), String> {
+    match *app.state::<Backend>() {
+        Backend::KWin => kwin::place_at_cursor(app).await,
+        Backend::Sway => wlroots::sway_place_at_cursor().await,
+        Backend::Hyprland => wlroots::hyprland_place_at_cursor().await,
+        Backend::Gnome | Backend::None => Ok(()),
+    }
+}
+
+/// Keeps the main window above other windows, or stops doing so
+///
+/// Without integration this is a no-op; callers also use Tauri's
+/// `set_always_on_top`, which works on X11.
+pub async fn set_always_on_top(app: &AppHandle, enabled: bool) -> Result<(), String> {
+    match *app.state::<Backend>() {
+        // The GNOME extension follows the App interface's Pinned property
+        Backend::Gnome => Ok(()),
+        Backend::KWin => kwin::set_keep_above(app, enabled).await,
+        Backend::Sway => wlroots::sway_set_sticky(enabled).await,
+        Backend::Hyprland => wlroots::hyprland_set_pinned(enabled).await,
+        Backend::None => Ok(()),
+    }
+}
+
+/// Gets the focused window
+///
+/// # Returns
+///
+/// Ok(None) if no window is focused or the backend can't tell (e.g.,
+/// without integration on Wayland)
+pub async fn active_window(app: &AppHandle) -> Result<Option<ActiveWindow>, String> {
+    match *app.state::<Backend>() {
+        Backend::Gnome => Ok(app.state::<ReportedWindow>().get()),
+        Backend::KWin => kwin::active_window(app).await,
+        Backend::Sway => wlroots::sway_active_window().await,
+        Backend::Hyprland => wlroots::hyprland_active_window().await,
+        Backend::None if is_x11() => x11::active_window().await,
+        Backend::None => Ok(None),
+    }
+}
+
+/// Checks whether this is an X11 session
+fn is_x11() -> bool {
+    std::env::var_os("WAYLAND_DISPLAY").is_none() && std::env::var_os("DISPLAY").is_some()
+}
+
+/// Gets the compositor backend in use
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const backend = await invoke('get_compositor'); // "kwin"
+/// ```
+#[tauri::command]
+pub fn get_compositor(backend: State<'_, Backend>) -> &'static str {
+    backend.name()
+}
+
+/// Gets the focused window
+///
+/// Returns null without compositor integration on Wayland, before the
+/// GNOME extension has reported focus, or when no window is focused.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const win = await invoke('get_active_window'); // { title, appId } | null
+/// ```
+#[tauri::command]
+pub async fn get_active_window(app: AppHandle) -> error::Result<Option<ActiveWindow>> {
+    Ok(active_window(&app).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1080,
+    };
+
+    #[test]
+    fn test_detect_from_desktop() {
+        assert_eq!(Backend::detect_from("GNOME", false, false), Backend::Gnome);
+        assert_eq!(Backend::detect_from("KDE", false, false), Backend::KWin);
+        assert_eq!(Backend::detect_from("sway", false, false), Backend::Sway);
+        assert_eq!(Backend::detect_from("", true, false), Backend::Sway);
+        assert_eq!(Backend::detect_from("", false, true), Backend::Hyprland);
+        assert_eq!(Backend::detect_from("XFCE", false, false), Backend::None);
+    }
+
+    #[test]
+    fn test_from_setting_overrides_detection() {
+        assert_eq!(Backend::from_setting("kwin"), Backend::KWin);
+        assert_eq!(Backend::from_setting("none"), Backend::None);
+    }
+
+    #[test]
+    fn test_show_at_report_is_taken_once() {
+        let report = ShowAtReport::default();
+        assert!(!report.take(Duration::ZERO));
+
+        report.report();
+        assert!(report.take(Duration::ZERO));
+        assert!(!report.take(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_show_at_report_wakes_waiting_toggle() {
+        let report = std::sync::Arc::new(ShowAtReport::default());
+        let reporter = report.clone();
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            reporter.report();
+        });
+
+        assert!(report.take(Duration::from_secs(5)));
+        thread.join().unwrap();
+    }
+
+    #[test]
+    fn test_place_right_below_cursor() {
+        assert_eq!(place_near_cursor((100, 100), (400, 300), AREA), (100, 100));
+    }
+
+    #[test]
+    fn test_place_flips_near_edges() {
+        assert_eq!(
+            place_near_cursor((1800, 1000), (400, 300), AREA),
+            (1400, 700)
+        );
+    }
+
+    #[test]
+    fn test_place_clamps_when_too_large() {
+        let area = Rect {
+            x: 0,
+            y: 0,
+            width: 500,
+            height: 400,
+        };
+        assert_eq!(place_near_cursor((250, 200), (600, 300), area), (0, 100));
+    }
+}
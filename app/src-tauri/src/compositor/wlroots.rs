@@ -0,0 +1,213 @@
+//! wlroots backends (Sway, Hyprland)
+//!
+//! wlroots protocols have no requests for placing windows or changing
+//! their stacking, so these backends use each compositor's IPC through its
+//! CLI. Sway has no always-on-top; floating windows already stay above
+//! tiled ones, so pinning makes the window sticky (shown on every
+//! workspace) instead.
+
+use super::{place_near_cursor, ActiveWindow, Rect, APP_ID};
+use serde_json::Value;
+use tokio::process::Command;
+
+/// Runs a CLI and returns its stdout
+async fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Runs a CLI and parses its stdout as JSON
+async fn run_json(program: &str, args: &[&str]) -> Result<Value, String> {
+    let stdout = run(program, args).await?;
+    serde_json::from_str(&stdout).map_err(|e| format!("Invalid {} output: {}", program, e))
+}
+
+/// Sway criteria matching the uti window
+fn sway_criteria() -> String {
+    format!("[app_id=\"^{}$\"]", APP_ID)
+}
+
+/// Floats the uti window and moves it to the cursor
+pub async fn sway_place_at_cursor() -> Result<(), String> {
+    let command = format!("{} floating enable, move position cursor", sway_criteria());
+    run("swaymsg", &[&command]).await.map(|_| ())
+}
+
+/// Makes the uti window sticky, or stops doing so
+pub async fn sway_set_sticky(enabled: bool) -> Result<(), String> {
+    let state = if enabled { "enable" } else { "disable" };
+    let command = format!("{} sticky {}", sway_criteria(), state);
+    run("swaymsg", &[&command]).await.map(|_| ())
+}
+
+/// Gets the focused window from the Sway tree
+pub async fn sway_active_window() -> Result<Option<ActiveWindow>, String> {
+    let tree = run_json("swaymsg", &["-t", "get_tree"]).await?;
+    Ok(find_sway_focused(&tree))
+}
+
+/// Finds the focused view in a Sway tree
+fn find_sway_focused(node: &Value) -> Option<ActiveWindow> {
+    let is_view = node["type"] == "con" || node["type"] == "floating_con";
+    if is_view && node["focused"] == true {
+        // XWayland windows have a class instead of an app_id
+        let app_id = node["app_id"]
+            .as_str()
+            .or_else(|| node["window_properties"]["class"].as_str())
+            .unwrap_or_default();
+        return Some(ActiveWindow {
+            title: node["name"].as_str().unwrap_or_default().to_string(),
+            app_id: app_id.to_string(),
+        });
+    }
+
+    ["nodes", "floating_nodes"]
+        .iter()
+        .filter_map(|key| node[key].as_array())
+        .flatten()
+        .find_map(find_sway_focused)
+}
+
+/// Hyprland window selector matching the uti window
+fn hyprland_selector() -> String {
+    format!("class:^({})$", APP_ID)
+}
+
+/// Finds the uti window in `hyprctl clients` output
+async fn hyprland_client() -> Result<Value, String> {
+    let clients = run_json("hyprctl", &["-j", "clients"]).await?;
+    clients
+        .as_array()
+        .and_then(|clients| clients.iter().find(|c| c["class"] == APP_ID).cloned())
+        .ok_or_else(|| "uti window not found".to_string())
+}
+
+/// Floats the uti window and moves it next to the cursor
+pub async fn hyprland_place_at_cursor() -> Result<(), String> {
+    let client = hyprland_client().await?;
+    let cursor = run_json("hyprctl", &["-j", "cursorpos"]).await?;
+    let monitors = run_json("hyprctl", &["-j", "monitors"]).await?;
+
+    let cursor = (json_i32(&cursor["x"]), json_i32(&cursor["y"]));
+    let size = (json_i32(&client["size"][0]), json_i32(&client["size"][1]));
+    let area = monitors
+        .as_array()
+        .and_then(|monitors| monitors.iter().find(|m| m["focused"] == true))
+        .map(hyprland_work_area)
+        .ok_or("No focused monitor")?;
+    let (x, y) = place_near_cursor(cursor, size, area);
+
+    let selector = hyprland_selector();
+    let batch = format!(
+        "dispatch setfloating {selector} ; dispatch movewindowpixel exact {x} {y},{selector}"
+    );
+    run("hyprctl", &["--batch", &batch]).await.map(|_| ())
+}
+
+/// Pins or unpins the uti window (Hyprland's `pin` toggles)
+pub async fn hyprland_set_pinned(enabled: bool) -> Result<(), String> {
+    let client = hyprland_client().await?;
+    if client["pinned"].as_bool() == Some(enabled) {
+        return Ok(());
+    }
+    run("hyprctl", &["dispatch", "pin", &hyprland_selector()])
+        .await
+        .map(|_| ())
+}
+
+/// Gets the focused window
+pub async fn hyprland_active_window() -> Result<Option<ActiveWindow>, String> {
+    let window = run_json("hyprctl", &["-j", "activewindow"]).await?;
+    // An empty object means nothing is focused
+    Ok(window["class"].as_str().map(|class| ActiveWindow {
+        title: window["title"].as_str().unwrap_or_default().to_string(),
+        app_id: class.to_string(),
+    }))
+}
+
+/// Computes a monitor's work area in logical coordinates
+///
+/// `reserved` is [left, top, right, bottom] space taken by bars.
+fn hyprland_work_area(monitor: &Value) -> Rect {
+    let scale = monitor["scale"]
+        .as_f64()
+        .filter(|s| *s > 0.0)
+        .unwrap_or(1.0);
+    let reserved = |i: usize| json_i32(&monitor["reserved"][i]);
+    let width = (json_i32(&monitor["width"]) as f64 / scale).round() as i32;
+    let height = (json_i32(&monitor["height"]) as f64 / scale).round() as i32;
+    Rect {
+        x: json_i32(&monitor["x"]) + reserved(0),
+        y: json_i32(&monitor["y"]) + reserved(1),
+        width: width - reserved(0) - reserved(2),
+        height: height - reserved(1) - reserved(3),
+    }
+}
+
+fn json_i32(value: &Value) -> i32 {
+    value.as_f64().unwrap_or_default() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_find_sway_focused() {
+        let tree = json!({
+            "type": "root",
+            "nodes": [{
+                "type": "workspace",
+                "nodes": [{ "type": "con", "focused": false, "name": "a", "app_id": "foot" }],
+                "floating_nodes": [{
+                    "type": "floating_con",
+                    "focused": true,
+                    "name": "Editor",
+                    "app_id": null,
+                    "window_properties": { "class": "Code" }
+                }]
+            }]
+        });
+        assert_eq!(
+            find_sway_focused(&tree),
+            Some(ActiveWindow {
+                title: "Editor".to_string(),
+                app_id: "Code".to_string(),
+            })
+        );
+        assert_eq!(
+            find_sway_focused(&json!({ "type": "root", "nodes": [] })),
+            None
+        );
+    }
+
+    #[test]
+    fn test_hyprland_work_area() {
+        let monitor = json!({
+            "x": 1920, "y": 0, "width": 3840, "height": 2160,
+            "scale": 2.0, "reserved": [0, 30, 0, 0]
+        });
+        assert_eq!(
+            hyprland_work_area(&monitor),
+            Rect {
+                x: 1920,
+                y: 30,
+                width: 1920,
+                height: 1050,
+            }
+        );
+    }
+}
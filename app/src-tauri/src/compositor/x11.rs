@@ -0,0 +1,117 @@
+//! X11 active window
+//!
+//! Without compositor integration on an X11 session, the focused window
+//! is read from the root window's `_NET_ACTIVE_WINDOW` property with
+//! `xprop`, which EWMH window managers keep up to date.
+
+use super::ActiveWindow;
+use tokio::process::Command;
+
+/// Runs xprop and returns its stdout
+async fn xprop(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("xprop")
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run xprop: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "xprop failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Gets the focused window
+pub async fn active_window() -> Result<Option<ActiveWindow>, String> {
+    let root = xprop(&["-root", "_NET_ACTIVE_WINDOW"]).await?;
+    let Some(id) = parse_window_id(&root) else {
+        return Ok(None);
+    };
+    let props = xprop(&["-id", id, "WM_CLASS", "_NET_WM_NAME"]).await?;
+    Ok(Some(parse_window(&props)))
+}
+
+/// Parses `_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007`
+///
+/// # Returns
+///
+/// None if no window is focused (id 0)
+fn parse_window_id(output: &str) -> Option<&str> {
+    let id = output.rsplit_once('#')?.1.trim();
+    let value = u64::from_str_radix(id.strip_prefix("0x")?, 16).ok()?;
+    (value != 0).then_some(id)
+}
+
+/// Parses the WM_CLASS and _NET_WM_NAME properties
+///
+/// The app ID is the class (second WM_CLASS string), as Sway reports for
+/// XWayland windows.
+fn parse_window(output: &str) -> ActiveWindow {
+    let mut window = ActiveWindow {
+        title: String::new(),
+        app_id: String::new(),
+    };
+    for line in output.lines() {
+        let Some((name, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        let mut strings = quoted_strings(value);
+        if name.starts_with("WM_CLASS") {
+            window.app_id = strings.pop().unwrap_or_default();
+        } else if name.starts_with("_NET_WM_NAME") {
+            window.title = strings.into_iter().next().unwrap_or_default();
+        }
+    }
+    window
+}
+
+/// Extracts the strings from an xprop value like `"a", "b \"c\""`
+fn quoted_strings(value: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = value.chars();
+    while chars.by_ref().any(|c| c == '"') {
+        let mut s = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => s.extend(chars.next()),
+                '"' => break,
+                c => s.push(c),
+            }
+        }
+        strings.push(s);
+    }
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_window_id() {
+        assert_eq!(
+            parse_window_id("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x3a00007\n"),
+            Some("0x3a00007")
+        );
+        assert_eq!(
+            parse_window_id("_NET_ACTIVE_WINDOW(WINDOW): window id # 0x0\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_window() {
+        let output = "WM_CLASS(STRING) = \"keepassxc\", \"KeePassXC\"\n\
+                      _NET_WM_NAME(UTF8_STRING) = \"Passwords.kdbx - \\\"KeePassXC\\\"\"\n";
+        assert_eq!(
+            parse_window(output),
+            ActiveWindow {
+                title: "Passwords.kdbx - \"KeePassXC\"".to_string(),
+                app_id: "KeePassXC".to_string(),
+            }
+        );
+    }
+}
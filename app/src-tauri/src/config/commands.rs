@@ -1,6 +1,9 @@
 //! Tauri commands for configuration management
 
-use super::AppConfig;
+use super::{AppConfig, ConfigError};
+use crate::error::Result;
+use std::path::Path;
+use tracing::info;
 
 /// Reads the application configuration
 ///
@@ -40,8 +43,8 @@ pub fn read_config() -> AppConfig {
 /// await invoke('save_config', { config: { theme: { color: 'dark' } } });
 /// ```
 #[tauri::command]
-pub fn save_config(config: AppConfig) -> Result<(), String> {
-    config.save()
+pub fn save_config(config: AppConfig) -> Result<()> {
+    Ok(config.save()?)
 }
 
 /// Opens the config folder in the default file manager
@@ -59,16 +62,15 @@ pub fn save_config(config: AppConfig) -> Result<(), String> {
 /// await invoke('open_config_folder');
 /// ```
 #[tauri::command]
-pub fn open_config_folder() -> Result<(), String> {
+pub fn open_config_folder() -> Result<()> {
     let folder = AppConfig::get_config_dir();
 
     // Ensure the directory exists
     if !folder.exists() {
-        std::fs::create_dir_all(&folder)
-            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        std::fs::create_dir_all(&folder).map_err(ConfigError::CreateDir)?;
     }
 
-    open::that(&folder).map_err(|e| format!("Failed to open folder: {}", e))
+    open_path(&folder)
 }
 
 /// Reloads the configuration from file and returns it
@@ -88,7 +90,7 @@ pub fn open_config_folder() -> Result<(), String> {
 /// ```
 #[tauri::command]
 pub fn reload_config() -> AppConfig {
-    println!("Reloading configuration...");
+    info!("Reloading configuration...");
     AppConfig::load()
 }
 
@@ -108,7 +110,7 @@ pub fn reload_config() -> AppConfig {
 /// await invoke('open_launcher_config');
 /// ```
 #[tauri::command]
-pub fn open_launcher_config() -> Result<(), String> {
+pub fn open_launcher_config() -> Result<()> {
     use crate::launcher::get_launcher_config_path;
 
     let path = get_launcher_config_path();
@@ -116,8 +118,7 @@ pub fn open_launcher_config() -> Result<(), String> {
     // Ensure the directory exists
     if let Some(parent) = path.parent() {
         if !parent.exists() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+            std::fs::create_dir_all(parent).map_err(ConfigError::CreateDir)?;
         }
     }
 
@@ -127,17 +128,19 @@ pub fn open_launcher_config() -> Result<(), String> {
   "commands": []
 }
 "#;
-        std::fs::write(&path, default_content)
-            .map_err(|e| format!("Failed to create launcher config: {}", e))?;
+        if let Err(source) = std::fs::write(&path, default_content) {
+            return Err(ConfigError::Write { path, source }.into());
+        }
     }
 
-    open::that(&path).map_err(|e| format!("Failed to open file: {}", e))
+    open_path(&path)
 }
 
 /// Opens the snippets configuration file in the default editor
 ///
-/// Opens `~/.config/uti/snippets.json` using the system's default editor.
-/// Creates the file with an empty config if it doesn't exist.
+/// Opens the snippets file (`~/.config/uti/snippets.json` or
+/// `snippetsPath`) using the system's default editor. Creates the file with
+/// an empty config if it doesn't exist.
 ///
 /// # Returns
 ///
@@ -150,16 +153,13 @@ pub fn open_launcher_config() -> Result<(), String> {
 /// await invoke('open_snippets_config');
 /// ```
 #[tauri::command]
-pub fn open_snippets_config() -> Result<(), String> {
-    use crate::snippets::SnippetsStore;
-
-    let path = SnippetsStore::get_storage_path();
+pub fn open_snippets_config() -> Result<()> {
+    let path = AppConfig::load().snippets_file();
 
     // Ensure the directory exists
     if let Some(parent) = path.parent() {
         if !parent.exists() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+            std::fs::create_dir_all(parent).map_err(ConfigError::CreateDir)?;
         }
     }
 
@@ -169,9 +169,21 @@ pub fn open_snippets_config() -> Result<(), String> {
   "items": []
 }
 "#;
-        std::fs::write(&path, default_content)
-            .map_err(|e| format!("Failed to create snippets config: {}", e))?;
+        if let Err(source) = std::fs::write(&path, default_content) {
+            return Err(ConfigError::Write { path, source }.into());
+        }
     }
 
-    open::that(&path).map_err(|e| format!("Failed to open file: {}", e))
+    open_path(&path)
+}
+
+/// Opens a file or folder with the default application
+fn open_path(path: &Path) -> Result<()> {
+    open::that(path).map_err(|source| {
+        ConfigError::Open {
+            path: path.to_path_buf(),
+            source,
+        }
+        .into()
+    })
 }
@@ -15,8 +15,62 @@ pub const DEFAULT_LANGUAGE: &str = "en";
 /// Default clipboard history limit
 pub const DEFAULT_CLIPBOARD_LIMIT: usize = 50;
 
+/// Default for skipping password-like text in clipboard history
+pub const DEFAULT_DETECT_SECRETS: bool = true;
+
+/// Default per-item clipboard size limit in bytes (1 MB)
+pub const DEFAULT_CLIPBOARD_ITEM_MAX_BYTES: usize = 1024 * 1024;
+
+/// Default for encrypting clipboard history at rest
+pub const DEFAULT_CLIPBOARD_ENCRYPTION: bool = false;
+
+/// Default clipboard item lifetime in days (0 keeps items forever)
+pub const DEFAULT_CLIPBOARD_ITEM_TTL: u64 = 0;
+
+/// Default handling of clipboard items over the size limit
+pub const DEFAULT_CLIPBOARD_OVERSIZE: &str = "truncate";
+
+/// Default auto-paste shortcut ("ctrl-shift-v" also works in terminals)
+pub const DEFAULT_PASTE_SHORTCUT: &str = "ctrl-shift-v";
+
+/// Default for pressing Enter after auto-paste
+pub const DEFAULT_PASTE_ENTER: bool = true;
+
+/// Default delay between auto-paste key events in milliseconds (0 uses the
+/// daemon's `key_delay_ms`)
+pub const DEFAULT_PASTE_KEY_DELAY_MS: u32 = 0;
+
+/// Maximum delay between auto-paste key events in milliseconds
+pub const MAX_PASTE_KEY_DELAY_MS: u32 = 1000;
+
+/// Default translation provider
+pub const DEFAULT_TRANSLATION_PROVIDER: &str = "libretranslate";
+
+/// Default LibreTranslate server URL
+pub const DEFAULT_LIBRETRANSLATE_URL: &str = "https://libretranslate.com";
+
+/// Default translation target language
+pub const DEFAULT_TRANSLATION_TARGET: &str = "en";
+
+/// Default port for the local HTTP API
+pub const DEFAULT_HTTP_API_PORT: u16 = 47917;
+
+/// Default log level
+pub const DEFAULT_LOG_LEVEL: &str = "info";
+
+/// Default compositor integration ("auto" detects it from the session)
+pub const DEFAULT_COMPOSITOR: &str = "auto";
+
+/// Default update channel
+pub const DEFAULT_UPDATE_CHANNEL: &str = "stable";
+
+/// Default world clock timezones (IANA names)
+pub const DEFAULT_WORLD_CLOCK_TIMEZONES: &[&str] =
+    &["UTC", "America/New_York", "Europe/London", "Asia/Tokyo"];
+
 // Compile-time validation of default values
 const _: () = assert!(DEFAULT_CLIPBOARD_LIMIT > 0);
+const _: () = assert!(DEFAULT_HTTP_API_PORT >= 1024);
 
 #[cfg(test)]
 mod tests {
@@ -33,6 +87,45 @@ mod tests {
         // Language must be one of the valid options
         assert!(matches!(DEFAULT_LANGUAGE, "en" | "ja"));
 
+        // Log level must be one of the valid options
+        assert!(matches!(
+            DEFAULT_LOG_LEVEL,
+            "error" | "warn" | "info" | "debug" | "trace"
+        ));
+
+        // Compositor must be one of the valid options
+        assert!(matches!(
+            DEFAULT_COMPOSITOR,
+            "auto" | "gnome" | "kwin" | "sway" | "hyprland" | "none"
+        ));
+
+        // Update channel must be one of the valid options
+        assert!(matches!(
+            DEFAULT_UPDATE_CHANNEL,
+            "stable" | "beta" | "nightly"
+        ));
+
         // Clipboard limit is validated at compile-time via const assertion above
+
+        // Oversize handling must be one of the valid options
+        assert!(matches!(DEFAULT_CLIPBOARD_OVERSIZE, "truncate" | "skip"));
+
+        // Paste shortcut must be one of the valid options
+        assert!(matches!(DEFAULT_PASTE_SHORTCUT, "ctrl-v" | "ctrl-shift-v"));
+
+        // Translation provider must be one of the valid options
+        assert!(matches!(
+            DEFAULT_TRANSLATION_PROVIDER,
+            "libretranslate" | "deepl"
+        ));
+
+        // World clock timezones must be valid IANA names
+        for tz in DEFAULT_WORLD_CLOCK_TIMEZONES {
+            assert!(
+                tz.parse::<chrono_tz::Tz>().is_ok(),
+                "invalid timezone {}",
+                tz
+            );
+        }
     }
 }
@@ -0,0 +1,146 @@
+//! Dot-path access to config keys, for `uti config get/set`
+//!
+//! Keys are the camelCase names from `config.json`, with nested sections
+//! joined by dots (e.g., `theme.color`, `paste.keyDelayMs`).
+
+use super::{AppConfig, ConfigError};
+use serde_json::Value;
+
+/// Looks up a key in the config
+///
+/// # Returns
+///
+/// The value as JSON, or an error if the key doesn't exist (unset optional
+/// keys don't exist either)
+pub fn get(config: &AppConfig, key: &str) -> Result<Value, ConfigError> {
+    let value = serde_json::to_value(config)?;
+    lookup(&value, key)
+        .cloned()
+        .ok_or_else(|| ConfigError::UnknownKey(key.to_string()))
+}
+
+/// Returns a copy of the config with a key changed
+///
+/// `value` is parsed as JSON (`100`, `true`, `null`, `["UTC"]`), falling
+/// back to a plain string, so `light` and `"light"` are the same. The result
+/// goes through [`AppConfig::validate`]; values it would replace are
+/// rejected. `null` unsets an optional key; like [`get`], keys that are
+/// already unset count as unknown.
+pub fn set(config: &AppConfig, key: &str, value: &str) -> Result<AppConfig, ConfigError> {
+    let unknown = || ConfigError::UnknownKey(key.to_string());
+    let invalid = || ConfigError::InvalidValue {
+        key: key.to_string(),
+        value: value.to_string(),
+    };
+    let new_value =
+        serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+    let unset = new_value.is_null();
+
+    let mut json = serde_json::to_value(config)?;
+    // Unsetting can't be checked after the fact, as the key is gone either way
+    if unset && lookup(&json, key).is_none() {
+        return Err(unknown());
+    }
+    let (parent, name) = match key.rsplit_once('.') {
+        Some((parent, name)) => (lookup_mut(&mut json, parent), name),
+        None => (Some(&mut json), key),
+    };
+    let Some(Value::Object(parent)) = parent else {
+        return Err(unknown());
+    };
+    parent.insert(name.to_string(), new_value);
+
+    // Unknown keys are dropped when reading the config back
+    let mut updated: AppConfig =
+        serde_json::from_value(json).map_err(|source| ConfigError::WrongType {
+            key: key.to_string(),
+            value: value.to_string(),
+            source,
+        })?;
+    let parsed = serde_json::to_value(&updated)?;
+    if lookup(&parsed, key).is_none() && !unset {
+        return Err(unknown());
+    }
+
+    // Invalid values are replaced by validate
+    updated.validate();
+    let validated = serde_json::to_value(&updated)?;
+    if lookup(&validated, key) != lookup(&parsed, key) {
+        return Err(invalid());
+    }
+    Ok(updated)
+}
+
+fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.')
+        .try_fold(value, |value, name| value.get(name))
+}
+
+fn lookup_mut<'a>(value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    key.split('.')
+        .try_fold(value, |value, name| value.get_mut(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let config = AppConfig::default();
+        assert_eq!(get(&config, "language").unwrap(), "en");
+        assert_eq!(
+            get(&config, "theme.color").unwrap(),
+            Value::String(config.theme.color.clone())
+        );
+        assert!(get(&config, "theme.colour").is_err());
+        assert!(get(&config, "updateProxy").is_err());
+    }
+
+    #[test]
+    fn test_set() {
+        let config = AppConfig::default();
+
+        let updated = set(&config, "theme.color", "light").unwrap();
+        assert_eq!(updated.theme.color, "light");
+        let updated = set(&config, "clipboardHistoryLimit", "100").unwrap();
+        assert_eq!(updated.clipboard_history_limit, 100);
+        let updated = set(&config, "updateProxy", "http://proxy:3128").unwrap();
+        assert_eq!(updated.update_proxy.as_deref(), Some("http://proxy:3128"));
+        let updated = set(&updated, "updateProxy", "null").unwrap();
+        assert_eq!(updated.update_proxy, None);
+        let updated = set(&config, "updateChannel", "nightly").unwrap();
+        assert_eq!(updated.update_channel, "nightly");
+        let updated = set(&config, "worldClockTimezones", r#"["PST","Asia/Tokyo"]"#).unwrap();
+        assert_eq!(updated.world_clock_timezones, ["PST", "Asia/Tokyo"]);
+    }
+
+    #[test]
+    fn test_set_rejects_invalid() {
+        let config = AppConfig::default();
+
+        // Replaced by validate
+        assert!(set(&config, "theme.color", "purple").is_err());
+        assert!(set(&config, "clipboardHistoryLimit", "0").is_err());
+        assert!(set(&config, "updateProxy", "ftp://proxy").is_err());
+        assert!(set(&config, "worldClockTimezones", r#"["Mars/Olympus"]"#).is_err());
+        // Wrong type
+        assert!(matches!(
+            set(&config, "clipboardHistoryLimit", "many"),
+            Err(ConfigError::WrongType { .. })
+        ));
+        // Typos
+        assert_eq!(
+            set(&config, "theme.colour", "light")
+                .unwrap_err()
+                .to_string(),
+            "Unknown config key: theme.colour"
+        );
+        assert!(set(&config, "nothing.here", "1").is_err());
+        assert_eq!(
+            set(&config, "typo", "null").unwrap_err().to_string(),
+            "Unknown config key: typo"
+        );
+        assert!(set(&config, "theme.colour", "null").is_err());
+    }
+}
@@ -4,16 +4,63 @@
 
 mod commands;
 pub mod defaults;
+pub mod keys;
 
-use defaults::{DEFAULT_CLIPBOARD_LIMIT, DEFAULT_COLOR, DEFAULT_LANGUAGE, DEFAULT_SIZE};
+use defaults::{
+    DEFAULT_CLIPBOARD_ENCRYPTION, DEFAULT_CLIPBOARD_ITEM_MAX_BYTES, DEFAULT_CLIPBOARD_ITEM_TTL,
+    DEFAULT_CLIPBOARD_LIMIT, DEFAULT_CLIPBOARD_OVERSIZE, DEFAULT_COLOR, DEFAULT_COMPOSITOR,
+    DEFAULT_DETECT_SECRETS, DEFAULT_HTTP_API_PORT, DEFAULT_LANGUAGE, DEFAULT_LIBRETRANSLATE_URL,
+    DEFAULT_LOG_LEVEL, DEFAULT_PASTE_ENTER, DEFAULT_PASTE_KEY_DELAY_MS, DEFAULT_PASTE_SHORTCUT,
+    DEFAULT_SIZE, DEFAULT_TRANSLATION_PROVIDER, DEFAULT_TRANSLATION_TARGET, DEFAULT_UPDATE_CHANNEL,
+    DEFAULT_WORLD_CLOCK_TIMEZONES, MAX_PASTE_KEY_DELAY_MS,
+};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use tracing::{info, warn};
 
 pub use commands::{
     open_config_folder, open_launcher_config, open_snippets_config, read_config, reload_config,
     save_config,
 };
 
+/// Errors from writing or opening config files and changing keys
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to create config directory: {0}")]
+    CreateDir(#[source] std::io::Error),
+
+    #[error("Failed to serialize config: {0}")]
+    Serialize(#[from] serde_json::Error),
+
+    #[error("Failed to write {}: {source}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to open {}: {source}", path.display())]
+    Open {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Unknown config key: {0}")]
+    UnknownKey(String),
+
+    #[error("Invalid value for {key}: {value}")]
+    InvalidValue { key: String, value: String },
+
+    #[error("Invalid value for {key}: {value}: {source}")]
+    WrongType {
+        key: String,
+        value: String,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
 /// Theme configuration
 ///
 /// Defines color and size theme settings.
@@ -48,6 +95,18 @@ fn default_language() -> String {
     DEFAULT_LANGUAGE.to_string()
 }
 
+fn default_log_level() -> String {
+    DEFAULT_LOG_LEVEL.to_string()
+}
+
+fn default_compositor() -> String {
+    DEFAULT_COMPOSITOR.to_string()
+}
+
+fn default_update_channel() -> String {
+    DEFAULT_UPDATE_CHANNEL.to_string()
+}
+
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
@@ -63,7 +122,7 @@ impl ThemeConfig {
     pub fn validate(&mut self) {
         // Validate color
         if !matches!(self.color.as_str(), "midnight" | "dark" | "light") {
-            eprintln!(
+            warn!(
                 "Invalid color theme '{}', falling back to '{}'",
                 self.color, DEFAULT_COLOR
             );
@@ -72,7 +131,7 @@ impl ThemeConfig {
 
         // Validate size
         if !matches!(self.size.as_str(), "minimal" | "normal" | "wide") {
-            eprintln!(
+            warn!(
                 "Invalid size theme '{}', falling back to '{}'",
                 self.size, DEFAULT_SIZE
             );
@@ -81,6 +140,244 @@ impl ThemeConfig {
     }
 }
 
+/// Translation configuration
+///
+/// API keys are stored in the system keyring, not here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslationConfig {
+    /// Provider: 'libretranslate' or 'deepl'
+    #[serde(default = "default_translation_provider")]
+    pub provider: String,
+
+    /// LibreTranslate server URL
+    #[serde(default = "default_libretranslate_url")]
+    pub libretranslate_url: String,
+
+    /// Default target language code (e.g., 'en', 'ja')
+    #[serde(default = "default_translation_target")]
+    pub target_language: String,
+}
+
+fn default_translation_provider() -> String {
+    DEFAULT_TRANSLATION_PROVIDER.to_string()
+}
+
+fn default_libretranslate_url() -> String {
+    DEFAULT_LIBRETRANSLATE_URL.to_string()
+}
+
+fn default_translation_target() -> String {
+    DEFAULT_TRANSLATION_TARGET.to_string()
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            provider: default_translation_provider(),
+            libretranslate_url: default_libretranslate_url(),
+            target_language: default_translation_target(),
+        }
+    }
+}
+
+impl TranslationConfig {
+    /// Validate translation values
+    pub fn validate(&mut self) {
+        if !matches!(self.provider.as_str(), "libretranslate" | "deepl") {
+            warn!(
+                "Invalid translation provider '{}', falling back to '{}'",
+                self.provider, DEFAULT_TRANSLATION_PROVIDER
+            );
+            self.provider = DEFAULT_TRANSLATION_PROVIDER.to_string();
+        }
+    }
+}
+
+/// Auto-paste configuration
+///
+/// How the daemon pastes into the focused window after an item is picked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PasteConfig {
+    /// Shortcut: 'ctrl-shift-v' (also works in terminals) or 'ctrl-v'
+    #[serde(default = "default_paste_shortcut")]
+    pub shortcut: String,
+
+    /// Press Enter after pasting, e.g., to run a command in a terminal
+    #[serde(default = "default_paste_enter")]
+    pub enter: bool,
+
+    /// Delay between key events in milliseconds, for targets that drop fast
+    /// input (e.g., remote desktops); 0 uses the daemon's `key_delay_ms`
+    #[serde(default = "default_paste_key_delay_ms")]
+    pub key_delay_ms: u32,
+}
+
+fn default_paste_shortcut() -> String {
+    DEFAULT_PASTE_SHORTCUT.to_string()
+}
+
+fn default_paste_enter() -> bool {
+    DEFAULT_PASTE_ENTER
+}
+
+fn default_paste_key_delay_ms() -> u32 {
+    DEFAULT_PASTE_KEY_DELAY_MS
+}
+
+impl Default for PasteConfig {
+    fn default() -> Self {
+        Self {
+            shortcut: default_paste_shortcut(),
+            enter: default_paste_enter(),
+            key_delay_ms: default_paste_key_delay_ms(),
+        }
+    }
+}
+
+impl PasteConfig {
+    /// Validate paste values
+    pub fn validate(&mut self) {
+        if !matches!(self.shortcut.as_str(), "ctrl-v" | "ctrl-shift-v") {
+            warn!(
+                "Invalid paste shortcut '{}', falling back to '{}'",
+                self.shortcut, DEFAULT_PASTE_SHORTCUT
+            );
+            self.shortcut = DEFAULT_PASTE_SHORTCUT.to_string();
+        }
+        if self.key_delay_ms > MAX_PASTE_KEY_DELAY_MS {
+            warn!(
+                "Invalid paste key delay {}ms (max {}), falling back to {}",
+                self.key_delay_ms, MAX_PASTE_KEY_DELAY_MS, DEFAULT_PASTE_KEY_DELAY_MS
+            );
+            self.key_delay_ms = DEFAULT_PASTE_KEY_DELAY_MS;
+        }
+    }
+}
+
+/// Local HTTP API configuration
+///
+/// The API token is stored in the system keyring, not here.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpApiConfig {
+    /// Whether the HTTP API server is started
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Port on 127.0.0.1 to listen on
+    #[serde(default = "default_http_api_port")]
+    pub port: u16,
+}
+
+fn default_http_api_port() -> u16 {
+    DEFAULT_HTTP_API_PORT
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_http_api_port(),
+        }
+    }
+}
+
+impl HttpApiConfig {
+    /// Validate HTTP API values
+    pub fn validate(&mut self) {
+        if self.port < 1024 {
+            warn!(
+                "Invalid HTTP API port {}, falling back to {}",
+                self.port, DEFAULT_HTTP_API_PORT
+            );
+            self.port = DEFAULT_HTTP_API_PORT;
+        }
+    }
+}
+
+/// Clipboard capture filter configuration
+///
+/// Copied text matching these rules is never stored in history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardFilterConfig {
+    /// Skip text that looks like a password, card number or private key
+    #[serde(default = "default_detect_secrets")]
+    pub detect_secrets: bool,
+
+    /// Regex patterns; text matching any of them is skipped
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// App IDs or window classes (e.g., "keepassxc") whose copies are skipped
+    #[serde(default)]
+    pub apps: Vec<String>,
+}
+
+fn default_detect_secrets() -> bool {
+    DEFAULT_DETECT_SECRETS
+}
+
+impl Default for ClipboardFilterConfig {
+    fn default() -> Self {
+        Self {
+            detect_secrets: default_detect_secrets(),
+            patterns: Vec::new(),
+            apps: Vec::new(),
+        }
+    }
+}
+
+impl ClipboardFilterConfig {
+    /// Validate filter values
+    pub fn validate(&mut self) {
+        self.patterns
+            .retain(|pattern| match regex::Regex::new(pattern) {
+                Ok(_) => true,
+                Err(e) => {
+                    warn!("Invalid clipboard filter pattern '{}': {}", pattern, e);
+                    false
+                }
+            });
+    }
+}
+
+/// Focus mode schedule
+///
+/// Focus mode is enabled automatically between `start` and `end` (local
+/// time). A schedule that ends before it starts runs overnight.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FocusSchedule {
+    /// Start time in 24-hour 'HH:MM' format
+    pub start: String,
+
+    /// End time in 24-hour 'HH:MM' format
+    pub end: String,
+}
+
+impl FocusSchedule {
+    /// Parse start and end times
+    ///
+    /// # Returns
+    ///
+    /// Some((start, end)) if both times are valid, None otherwise
+    pub fn parse(&self) -> Option<(chrono::NaiveTime, chrono::NaiveTime)> {
+        let parse = |s: &str| chrono::NaiveTime::parse_from_str(s, "%H:%M").ok();
+        Some((parse(&self.start)?, parse(&self.end)?))
+    }
+
+    /// Check whether a local time falls within the schedule
+    pub fn contains(&self, time: chrono::NaiveTime) -> bool {
+        match self.parse() {
+            Some((start, end)) if start <= end => start <= time && time < end,
+            Some((start, end)) => time >= start || time < end,
+            None => false,
+        }
+    }
+}
+
 /// Application configuration
 ///
 /// This struct represents the user's configuration for the uti application.
@@ -96,21 +393,122 @@ pub struct AppConfig {
     #[serde(default = "default_clipboard_limit")]
     pub clipboard_history_limit: usize,
 
+    /// Maximum size of one clipboard item in bytes (0 for no limit)
+    #[serde(default = "default_clipboard_item_max_bytes")]
+    pub clipboard_item_max_bytes: usize,
+
+    /// Handling of items over the size limit: 'truncate' or 'skip'
+    #[serde(default = "default_clipboard_oversize")]
+    pub clipboard_oversize: String,
+
+    /// Days after which unpinned clipboard items are dropped (0 to keep them)
+    #[serde(default = "default_clipboard_item_ttl")]
+    pub clipboard_item_ttl: u64,
+
+    /// Encrypt clipboard history on disk with a key from the system keyring
+    #[serde(default = "default_clipboard_encryption")]
+    pub clipboard_encryption: bool,
+
     /// UI language: 'en' or 'ja'
     #[serde(default = "default_language")]
     pub language: String,
+
+    /// IANA timezones shown in the world clock (e.g., 'Asia/Tokyo')
+    #[serde(default = "default_world_clock_timezones")]
+    pub world_clock_timezones: Vec<String>,
+
+    /// Translation provider settings
+    #[serde(default)]
+    pub translation: TranslationConfig,
+
+    /// Daily schedule for automatic focus mode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_schedule: Option<FocusSchedule>,
+
+    /// Local HTTP API settings
+    #[serde(default)]
+    pub http_api: HttpApiConfig,
+
+    /// Log level: 'error', 'warn', 'info', 'debug' or 'trace'
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+
+    /// Compositor integration: 'auto', 'gnome', 'kwin', 'sway', 'hyprland' or 'none'
+    #[serde(default = "default_compositor")]
+    pub compositor: String,
+
+    /// Releases offered by the updater: 'stable', 'beta' or 'nightly'
+    #[serde(default = "default_update_channel")]
+    pub update_channel: String,
+
+    /// Proxy for update checks and downloads (e.g.,
+    /// 'http://proxy.example.com:8080'); `HTTPS_PROXY` and `HTTP_PROXY` are
+    /// used when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_proxy: Option<String>,
+
+    /// Rules for text that is never stored in clipboard history
+    #[serde(default)]
+    pub clipboard_filter: ClipboardFilterConfig,
+
+    /// Auto-paste settings
+    #[serde(default)]
+    pub paste: PasteConfig,
+
+    /// Snippets file to use instead of `~/.config/uti/snippets.json`
+    /// (e.g., in a synced folder); `~/` is expanded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snippets_path: Option<String>,
 }
 
 fn default_clipboard_limit() -> usize {
     DEFAULT_CLIPBOARD_LIMIT
 }
 
+fn default_clipboard_item_max_bytes() -> usize {
+    DEFAULT_CLIPBOARD_ITEM_MAX_BYTES
+}
+
+fn default_clipboard_oversize() -> String {
+    DEFAULT_CLIPBOARD_OVERSIZE.to_string()
+}
+
+fn default_clipboard_item_ttl() -> u64 {
+    DEFAULT_CLIPBOARD_ITEM_TTL
+}
+
+fn default_clipboard_encryption() -> bool {
+    DEFAULT_CLIPBOARD_ENCRYPTION
+}
+
+fn default_world_clock_timezones() -> Vec<String> {
+    DEFAULT_WORLD_CLOCK_TIMEZONES
+        .iter()
+        .map(|tz| tz.to_string())
+        .collect()
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             theme: ThemeConfig::default(),
             clipboard_history_limit: default_clipboard_limit(),
+            clipboard_item_max_bytes: default_clipboard_item_max_bytes(),
+            clipboard_oversize: default_clipboard_oversize(),
+            clipboard_item_ttl: default_clipboard_item_ttl(),
+            clipboard_encryption: default_clipboard_encryption(),
             language: default_language(),
+            world_clock_timezones: default_world_clock_timezones(),
+            translation: TranslationConfig::default(),
+            focus_schedule: None,
+            http_api: HttpApiConfig::default(),
+            log_level: default_log_level(),
+            compositor: default_compositor(),
+            update_channel: default_update_channel(),
+            update_proxy: None,
+            clipboard_filter: ClipboardFilterConfig::default(),
+            paste: PasteConfig::default(),
+            snippets_path: None,
         }
     }
 }
@@ -135,28 +533,128 @@ impl AppConfig {
         path
     }
 
+    /// Get the path to the snippets file
+    ///
+    /// Returns `snippetsPath` if set, `~/.config/uti/snippets.json` otherwise
+    pub fn snippets_file(&self) -> PathBuf {
+        match self.snippets_path.as_deref().map(str::trim) {
+            Some(path) if !path.is_empty() => match (path.strip_prefix("~/"), dirs::home_dir()) {
+                (Some(relative), Some(home)) => home.join(relative),
+                _ => PathBuf::from(path),
+            },
+            _ => crate::snippets::SnippetsStore::get_storage_path(),
+        }
+    }
+
     /// Validate configuration values
     pub fn validate(&mut self) {
         // Validate theme
         self.theme.validate();
 
+        // Validate translation
+        self.translation.validate();
+
+        // Validate HTTP API
+        self.http_api.validate();
+
+        // Validate clipboard filter
+        self.clipboard_filter.validate();
+
+        // Validate paste
+        self.paste.validate();
+
         // Validate clipboard_history_limit
         if self.clipboard_history_limit == 0 {
-            eprintln!(
+            warn!(
                 "clipboard_history_limit cannot be 0, using default ({})",
                 DEFAULT_CLIPBOARD_LIMIT
             );
             self.clipboard_history_limit = DEFAULT_CLIPBOARD_LIMIT;
         }
 
+        // Validate clipboard_oversize
+        if !matches!(self.clipboard_oversize.as_str(), "truncate" | "skip") {
+            warn!(
+                "Invalid clipboard_oversize '{}', falling back to '{}'",
+                self.clipboard_oversize, DEFAULT_CLIPBOARD_OVERSIZE
+            );
+            self.clipboard_oversize = DEFAULT_CLIPBOARD_OVERSIZE.to_string();
+        }
+
         // Validate language
         if !matches!(self.language.as_str(), "en" | "ja") {
-            eprintln!(
+            warn!(
                 "Invalid language '{}', falling back to '{}'",
                 self.language, DEFAULT_LANGUAGE
             );
             self.language = DEFAULT_LANGUAGE.to_string();
         }
+
+        // Validate log level
+        if !matches!(
+            self.log_level.as_str(),
+            "error" | "warn" | "info" | "debug" | "trace"
+        ) {
+            warn!(
+                "Invalid log level '{}', falling back to '{}'",
+                self.log_level, DEFAULT_LOG_LEVEL
+            );
+            self.log_level = DEFAULT_LOG_LEVEL.to_string();
+        }
+
+        // Validate compositor
+        if !matches!(
+            self.compositor.as_str(),
+            "auto" | "gnome" | "kwin" | "sway" | "hyprland" | "none"
+        ) {
+            warn!(
+                "Invalid compositor '{}', falling back to '{}'",
+                self.compositor, DEFAULT_COMPOSITOR
+            );
+            self.compositor = DEFAULT_COMPOSITOR.to_string();
+        }
+
+        // Validate update channel ('prerelease' was the old name of 'beta')
+        if self.update_channel == "prerelease" {
+            self.update_channel = "beta".to_string();
+        }
+        if !matches!(self.update_channel.as_str(), "stable" | "beta" | "nightly") {
+            warn!(
+                "Invalid update channel '{}', falling back to '{}'",
+                self.update_channel, DEFAULT_UPDATE_CHANNEL
+            );
+            self.update_channel = DEFAULT_UPDATE_CHANNEL.to_string();
+        }
+
+        // Validate update proxy
+        if let Some(proxy) = &self.update_proxy {
+            let valid = reqwest::Url::parse(proxy)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https"));
+            if !valid {
+                warn!("Invalid update proxy '{}', ignoring it", proxy);
+                self.update_proxy = None;
+            }
+        }
+
+        // Validate world clock timezones the way the world clock reads them
+        self.world_clock_timezones.retain(|tz| {
+            let valid = crate::tools::parse_timezone(tz).is_some();
+            if !valid {
+                warn!("Invalid timezone '{}', removing from world clock", tz);
+            }
+            valid
+        });
+
+        // Validate focus schedule
+        if let Some(schedule) = &self.focus_schedule {
+            if schedule.parse().is_none() {
+                warn!(
+                    "Invalid focus schedule '{}'-'{}', disabling schedule",
+                    schedule.start, schedule.end
+                );
+                self.focus_schedule = None;
+            }
+        }
     }
 
     /// Load configuration from file
@@ -168,17 +666,17 @@ impl AppConfig {
         match std::fs::read_to_string(&path) {
             Ok(contents) => match serde_json::from_str::<Self>(&contents) {
                 Ok(mut config) => {
-                    println!("Loaded config from: {:?}", path);
+                    info!("Loaded config from: {:?}", path);
                     config.validate();
                     config
                 }
                 Err(e) => {
-                    eprintln!("Failed to parse config file: {}", e);
+                    warn!("Failed to parse config file: {}", e);
                     Self::default()
                 }
             },
             Err(_) => {
-                println!("Config file not found, using defaults");
+                info!("Config file not found, using defaults");
                 Self::default()
             }
         }
@@ -187,23 +685,22 @@ impl AppConfig {
     /// Save configuration to file
     ///
     /// Creates the config directory if it doesn't exist.
-    pub fn save(&self) -> Result<(), String> {
+    pub fn save(&self) -> Result<(), ConfigError> {
         let path = Self::get_config_path();
 
         // Ensure directory exists
         if let Some(parent) = path.parent() {
-            std::fs::create_dir_all(parent)
-                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+            std::fs::create_dir_all(parent).map_err(ConfigError::CreateDir)?;
         }
 
         // Write config file
-        let contents = serde_json::to_string_pretty(self)
-            .map_err(|e| format!("Failed to serialize config: {}", e))?;
+        let contents = serde_json::to_string_pretty(self)?;
 
-        std::fs::write(&path, contents)
-            .map_err(|e| format!("Failed to write config file: {}", e))?;
+        if let Err(source) = std::fs::write(&path, contents) {
+            return Err(ConfigError::Write { path, source });
+        }
 
-        println!("Config saved to: {:?}", path);
+        info!("Config saved to: {:?}", path);
         Ok(())
     }
 }
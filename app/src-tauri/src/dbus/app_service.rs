@@ -0,0 +1,180 @@
+//! App D-Bus interface
+//!
+//! Implements `io.github.noppomario.uti.App`, the integration surface for
+//! the GNOME extension, the daemon, scripts and other tools.
+//!
+//! Methods: `ToggleWindow`, `ShowWindow`, `OpenTab`, `PasteItem`, `AddClip`,
+//! `SetActiveWindow` and `ShowAt` (GNOME extension), `ReloadConfig`.
+//! Properties: `Pinned` (GNOME extension).
+//! Signals: `TypeText` and `TypeString` (daemon).
+//!
+//! Clients use `uti_dbus::app::AppProxy` and its signal types, which must
+//! match the methods and signals here.
+
+use crate::state::AppState;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use zbus::fdo;
+use zbus::interface;
+use zbus::object_server::SignalContext;
+
+/// Tabs accepted by `OpenTab` (see `TabType` in the frontend)
+const TABS: [&str; 4] = ["prompt", "clipboard", "snippets", "launcher"];
+
+/// Delay before pasting so focus returns to the previous window
+const PASTE_DELAY: Duration = Duration::from_millis(100);
+
+/// Shows the main window and switches it to a tab
+///
+/// `tab` must be one of the frontend's tabs.
+pub fn show_tab(app: &AppHandle, tab: &str) -> tauri::Result<()> {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    app.emit("open-tab", tab)
+}
+
+/// `io.github.noppomario.uti.App` implementation
+pub struct AppService {
+    app: AppHandle,
+}
+
+impl AppService {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+#[interface(name = "io.github.noppomario.uti.App")]
+impl AppService {
+    /// Toggles the main window, like a double Ctrl press
+    fn toggle_window(&self) -> fdo::Result<()> {
+        self.app
+            .emit("double-ctrl-pressed", ())
+            .map_err(|e| fdo::Error::Failed(format!("Failed to toggle window: {}", e)))
+    }
+
+    /// Shows and focuses the main window, e.g., when uti is launched again
+    fn show_window(&self) -> fdo::Result<()> {
+        let window = self
+            .app
+            .get_webview_window("main")
+            .ok_or_else(|| fdo::Error::Failed("Main window not found".to_string()))?;
+        let _ = window.show();
+        let _ = window.set_focus();
+        Ok(())
+    }
+
+    /// Shows the main window on a tab
+    fn open_tab(&self, tab: String) -> fdo::Result<()> {
+        if !TABS.contains(&tab.as_str()) {
+            return Err(fdo::Error::InvalidArgs(format!("Unknown tab: {}", tab)));
+        }
+
+        show_tab(&self.app, &tab)
+            .map_err(|e| fdo::Error::Failed(format!("Failed to open tab: {}", e)))
+    }
+
+    /// Pastes a clipboard history item into the focused window
+    ///
+    /// Items are found by id, so reordering the history between listing and
+    /// pasting can't paste the wrong one.
+    async fn paste_item(
+        &self,
+        id: String,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> fdo::Result<()> {
+        let item = {
+            let state = self.app.state::<AppState>();
+            let store = state.clipboard.read();
+            store
+                .position(&id)
+                .map(|index| store.items[index].clone())
+                .ok_or_else(|| {
+                    fdo::Error::InvalidArgs(format!("Clipboard item {} not found", id))
+                })?
+        };
+
+        crate::capture::restore(&self.app, &item)
+            .map_err(|e| fdo::Error::Failed(format!("Failed to copy item: {}", e)))?;
+
+        if let Some(window) = self.app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+        tokio::time::sleep(PASTE_DELAY).await;
+
+        let paste = crate::config::AppConfig::load().paste;
+        Self::type_text(&ctxt, &paste.shortcut, paste.enter, paste.key_delay_ms).await?;
+        Ok(())
+    }
+
+    /// Adds text to the clipboard history, see [`crate::capture::push`]
+    fn add_clip(&self, text: String) -> fdo::Result<()> {
+        crate::capture::push(&self.app, text)
+            .map(|_| ())
+            .map_err(|e| fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Records the focused window, reported by the GNOME extension
+    ///
+    /// Empty strings mean no window is focused.
+    fn set_active_window(&self, title: String, app_id: String) {
+        let window = (!title.is_empty() || !app_id.is_empty())
+            .then_some(crate::compositor::ActiveWindow { title, app_id });
+        self.app
+            .state::<crate::compositor::ReportedWindow>()
+            .set(window);
+    }
+
+    /// Shows the main window next to the pointer, reported by the GNOME
+    /// extension when it receives a trigger
+    ///
+    /// The extension only calls it while the window is hidden; the app's
+    /// own toggle for the trigger then leaves the window shown.
+    fn show_at(&self, x: i32, y: i32) -> fdo::Result<()> {
+        crate::compositor::show_window_at(&self.app, x, y)
+            .map_err(|e| fdo::Error::Failed(format!("Failed to show window: {}", e)))
+    }
+
+    /// Reloads config.json after it was changed outside the app, e.g., by
+    /// `uti config set`
+    ///
+    /// Applies the log level and sends `config_changed` to the windows, as
+    /// saving from the settings window does.
+    fn reload_config(&self) {
+        let config = crate::config::AppConfig::load();
+        let log = self.app.state::<crate::logging::LogControl>();
+        if let Err(e) = log.set_level(&config.log_level) {
+            tracing::warn!("{}", e);
+        }
+        let _ = self.app.emit("config_changed", config);
+    }
+
+    /// Whether the window is pinned (kept above other windows)
+    ///
+    /// The GNOME extension follows it to keep the window above on Mutter,
+    /// which ignores Tauri's `set_always_on_top`.
+    #[zbus(property)]
+    fn pinned(&self) -> bool {
+        self.app.state::<AppState>().pinned.load(Ordering::SeqCst)
+    }
+
+    /// Asks the daemon to paste the clipboard into the focused window
+    ///
+    /// `shortcut` is "ctrl-v" or "ctrl-shift-v"; `enter` presses Enter
+    /// after pasting; `key_delay_ms` is the time between key events, 0 for
+    /// the daemon's default.
+    #[zbus(signal)]
+    pub async fn type_text(
+        ctxt: &SignalContext<'_>,
+        shortcut: &str,
+        enter: bool,
+        key_delay_ms: u32,
+    ) -> zbus::Result<()>;
+
+    /// Asks the daemon to type text key by key into the focused window
+    #[zbus(signal)]
+    pub async fn type_string(ctxt: &SignalContext<'_>, text: &str) -> zbus::Result<()>;
+}
@@ -0,0 +1,148 @@
+//! Clipboard D-Bus interface
+//!
+//! Implements `io.github.noppomario.uti.Clipboard`, so scripts and
+//! launchers like rofi can use the history and snippets without the
+//! window:
+//!
+//! ```bash
+//! busctl --user call io.github.noppomario.uti.App \
+//!   /io/github/noppomario/uti/Clipboard \
+//!   io.github.noppomario.uti.Clipboard GetHistory u 20
+//! ```
+//!
+//! Clients use `uti_dbus::clipboard::ClipboardProxy`, which must match the
+//! methods here.
+
+use crate::clipboard::{ClipboardItem, ClipboardStore};
+use crate::snippets::{self, SnippetItem};
+use crate::state::AppState;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use zbus::fdo;
+use zbus::interface;
+
+/// Delay before pasting so focus returns to the previous window
+const PASTE_DELAY: Duration = Duration::from_millis(100);
+
+/// Gets the text of the newest `limit` history items, or all for 0
+fn history(store: &ClipboardStore, limit: u32) -> Vec<String> {
+    let limit = match limit {
+        0 => usize::MAX,
+        limit => limit as usize,
+    };
+    store
+        .items
+        .iter()
+        .take(limit)
+        .map(|item| item.text.clone())
+        .collect()
+}
+
+/// Gets a snippet's ID and label, falling back to its value
+fn snippet_entry(snippet: &SnippetItem) -> (String, String) {
+    let label = snippet
+        .label
+        .as_deref()
+        .filter(|label| !label.is_empty())
+        .unwrap_or(&snippet.value);
+    (snippet.id.clone(), label.to_string())
+}
+
+/// `io.github.noppomario.uti.Clipboard` implementation
+pub struct ClipboardService {
+    app: AppHandle,
+}
+
+impl ClipboardService {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+#[interface(name = "io.github.noppomario.uti.Clipboard")]
+impl ClipboardService {
+    /// Gets the text of the newest history items, newest first; 0 for all
+    fn get_history(&self, limit: u32) -> Vec<String> {
+        history(&self.app.state::<AppState>().clipboard.read(), limit)
+    }
+
+    /// Puts text on the system clipboard and adds it to the history
+    ///
+    /// The history follows the capture settings, like a copy in another
+    /// app.
+    fn copy(&self, text: String) -> fdo::Result<()> {
+        crate::capture::restore(&self.app, &ClipboardItem::new(text.clone()))
+            .map_err(|e| fdo::Error::Failed(format!("Failed to copy text: {}", e)))?;
+        crate::capture::record(&self.app, text, None)
+            .map(|_| ())
+            .map_err(|e| fdo::Error::Failed(e.to_string()))
+    }
+
+    /// Lists snippets as (id, label) pairs
+    fn get_snippets(&self) -> Vec<(String, String)> {
+        let state = self.app.state::<AppState>();
+        let store = state.snippets.read();
+        store.items.iter().map(snippet_entry).collect()
+    }
+
+    /// Expands a snippet and pastes it into the focused window
+    ///
+    /// Variables (`{{name:prompt}}`) expand to nothing, since there is no
+    /// window to ask for them.
+    async fn paste_snippet(&self, id: String) -> fdo::Result<()> {
+        let (value, context) = {
+            let state = self.app.state::<AppState>();
+            crate::snippet_template(&state, &id)
+                .map_err(|e| fdo::Error::InvalidArgs(e.to_string()))?
+        };
+        let rendered = snippets::render(&value, &context);
+
+        crate::capture::restore(&self.app, &ClipboardItem::new(rendered.text))
+            .map_err(|e| fdo::Error::Failed(format!("Failed to copy snippet: {}", e)))?;
+
+        if let Some(window) = self.app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+        tokio::time::sleep(PASTE_DELAY).await;
+
+        super::emit_type_text(&self.app).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interface_name() {
+        assert_eq!(
+            <ClipboardService as zbus::Interface>::name(),
+            uti_dbus::CLIPBOARD_INTERFACE
+        );
+    }
+
+    #[test]
+    fn test_history_limit() {
+        let mut store = ClipboardStore::default();
+        store.add("first".to_string());
+        store.add("second".to_string());
+        store.add("third".to_string());
+
+        assert_eq!(history(&store, 2), vec!["third", "second"]);
+        assert_eq!(history(&store, 0).len(), 3);
+        assert_eq!(history(&store, 10).len(), 3);
+    }
+
+    #[test]
+    fn test_snippet_entry_label() {
+        let mut snippet = SnippetItem::new("value".to_string(), Some("Label".to_string()));
+        assert_eq!(snippet_entry(&snippet).1, "Label");
+
+        snippet.label = Some(String::new());
+        assert_eq!(
+            snippet_entry(&snippet),
+            (snippet.id.clone(), "value".to_string())
+        );
+    }
+}
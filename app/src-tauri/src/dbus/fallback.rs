@@ -0,0 +1,88 @@
+//! Unix socket fallback to the daemon, when there is no session bus
+//!
+//! Without a session bus, the daemon serves `uti_dbus::socket::socket_path()`
+//! instead (see `uti_dbus::socket`). [`listen`] receives its gesture
+//! signals, and while it's connected, `TypeText` and `TypeString` go
+//! through [`send`] instead of D-Bus.
+
+use super::{set_daemon_status, AppBus};
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+use tracing::{debug, info, warn};
+use uti_dbus::double_tap::TriggerInfo;
+use uti_dbus::socket::Message;
+
+/// Connects to the daemon's socket and handles its signals until it
+/// disconnects
+///
+/// # Arguments
+///
+/// * `on_trigger` - Called with the name and arguments of each gesture
+///   signal
+///
+/// # Returns
+///
+/// Whether the socket was connected
+pub async fn listen(app: &AppHandle, on_trigger: impl Fn(&str, TriggerInfo)) -> bool {
+    let Some(path) = uti_dbus::socket::socket_path() else {
+        return false;
+    };
+    let stream = match UnixStream::connect(&path).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            debug!("Daemon socket {} unavailable: {}", path.display(), e);
+            return false;
+        }
+    };
+    info!("Connected to the daemon socket {}", path.display());
+
+    let (reader, writer) = stream.into_split();
+    *app.state::<AppBus>().fallback.lock().await = Some(writer);
+    set_daemon_status(app, true);
+
+    let mut lines = BufReader::new(reader).lines();
+    loop {
+        match lines.next_line().await {
+            Ok(Some(line)) => match Message::from_line(&line) {
+                Ok(Message::Trigger {
+                    name,
+                    source,
+                    gesture,
+                    timestamp_ms,
+                }) => on_trigger(
+                    &name,
+                    TriggerInfo {
+                        source,
+                        gesture,
+                        timestamp_ms,
+                    },
+                ),
+                Ok(message) => debug!("Ignoring {:?} from the daemon", message),
+                Err(e) => warn!("Invalid message from the daemon: {}", e),
+            },
+            Ok(None) => break,
+            Err(e) => {
+                warn!("Failed to read from the daemon socket: {}", e);
+                break;
+            }
+        }
+    }
+
+    app.state::<AppBus>().fallback.lock().await.take();
+    set_daemon_status(app, false);
+    warn!("Daemon socket disconnected");
+    true
+}
+
+/// Sends a message to the daemon through the socket
+///
+/// # Returns
+///
+/// None if the socket isn't connected, so the caller should use D-Bus
+pub async fn send(app: &AppHandle, message: &Message) -> Option<std::io::Result<()>> {
+    let bus = app.state::<AppBus>();
+    let mut writer = bus.fallback.lock().await;
+    let writer = writer.as_mut()?;
+    Some(writer.write_all(message.to_line().as_bytes()).await)
+}
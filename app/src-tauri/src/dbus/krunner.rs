@@ -0,0 +1,78 @@
+//! KRunner runner
+//!
+//! Implements `org.kde.krunner1` so snippets, clipboard history and
+//! launcher items show up in KRunner and the Plasma launcher search.
+
+use super::search::{ResultId, SearchSources};
+use std::collections::HashMap;
+use tauri::AppHandle;
+use tracing::warn;
+use zbus::interface;
+use zbus::zvariant::Value;
+
+/// KRunner match type for results that are likely what the user wants
+const POSSIBLE_MATCH: i32 = 30;
+
+/// A KRunner match: (id, text, icon, type, relevance, properties)
+type Match = (
+    String,
+    String,
+    String,
+    i32,
+    f64,
+    HashMap<String, Value<'static>>,
+);
+
+/// `org.kde.krunner1` implementation
+pub struct KRunner {
+    app: AppHandle,
+}
+
+impl KRunner {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+#[interface(name = "org.kde.krunner1")]
+impl KRunner {
+    /// Returns the secondary actions offered for matches (none)
+    fn actions(&self) -> Vec<(String, String, String)> {
+        Vec::new()
+    }
+
+    /// Returns matches for a query
+    #[zbus(name = "Match")]
+    fn find_matches(&self, query: String) -> Vec<Match> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_string).collect();
+        let sources = SearchSources::load(&self.app);
+        let results = sources.search(&terms);
+        let count = results.len();
+
+        results
+            .iter()
+            .enumerate()
+            .filter_map(|(rank, id)| {
+                let meta = sources.describe(id)?;
+                // Keep uti's ordering within KRunner's relevance sorting
+                let relevance = 1.0 - rank as f64 / (count as f64 * 2.0);
+                Some((
+                    id.encode(),
+                    meta.name,
+                    meta.icon.to_string(),
+                    POSSIBLE_MATCH,
+                    relevance,
+                    HashMap::from([("subtext".to_string(), Value::from(meta.description))]),
+                ))
+            })
+            .collect()
+    }
+
+    /// Runs a match (copy or launch)
+    fn run(&self, match_id: String, _action_id: String) {
+        match ResultId::parse(&match_id) {
+            Some(id) => SearchSources::load(&self.app).activate(&self.app, &id),
+            None => warn!("Unknown KRunner match: {}", match_id),
+        }
+    }
+}
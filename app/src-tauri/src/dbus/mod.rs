@@ -0,0 +1,391 @@
+//! Application D-Bus service
+//!
+//! Owns the `io.github.noppomario.uti.App` bus name on a single session
+//! connection shared by everything in the app that talks to D-Bus, and
+//! serves the app's interfaces on it: App, Clipboard, the GNOME Shell
+//! search provider and KRunner.
+
+mod app_service;
+mod clipboard_service;
+pub mod fallback;
+mod krunner;
+mod search;
+mod search_provider;
+
+use app_service::AppService;
+use clipboard_service::ClipboardService;
+use krunner::KRunner;
+use parking_lot::{Mutex, RwLock};
+use search_provider::SearchProvider;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::net::unix::OwnedWriteHalf;
+use tracing::{info, warn};
+use uti_dbus::socket::Message;
+use uti_dbus::{CLIPBOARD_PATH, DAEMON_BUS_NAME};
+use zbus::fdo::{DBusProxy, NameOwnerChangedStream};
+use zbus::names::BusName;
+use zbus::object_server::SignalContext;
+use zbus::Connection;
+
+/// Object path of the GNOME Shell search provider
+pub const SEARCH_PROVIDER_PATH: &str = "/io/github/noppomario/uti/SearchProvider";
+
+/// Object path of the KRunner runner
+pub const KRUNNER_PATH: &str = "/io/github/noppomario/uti/KRunner";
+
+pub use app_service::show_tab;
+pub use uti_dbus::{APP_BUS_NAME, APP_PATH, DOUBLE_TAP_INTERFACE, DOUBLE_TAP_PATH};
+
+/// What a daemon trigger signal asks the app to do
+///
+/// The signals (`uti_dbus::double_tap::SIGNALS`) are bound to gestures in
+/// the daemon's `daemon.toml`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    /// `Triggered`: toggle the main window
+    Toggle,
+    /// `Triggered<Tab>`: show the main window on a tab
+    OpenTab(&'static str),
+}
+
+impl Trigger {
+    /// Gets the trigger for a signal name, or None if it's unknown
+    pub fn from_signal(member: &str) -> Option<Self> {
+        match member {
+            "Triggered" => Some(Self::Toggle),
+            "TriggeredPrompt" => Some(Self::OpenTab("prompt")),
+            "TriggeredClipboard" => Some(Self::OpenTab("clipboard")),
+            "TriggeredSnippets" => Some(Self::OpenTab("snippets")),
+            "TriggeredLauncher" => Some(Self::OpenTab("launcher")),
+            _ => None,
+        }
+    }
+}
+
+/// Payload of `double-ctrl-pressed` for daemon triggers
+///
+/// Other sources (tray, `ToggleWindow`) send no payload.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerEvent {
+    /// Keyboard name, empty for the daemon's `TriggerNow`
+    pub source: String,
+    /// `double-tap`, `triple-tap`, `hold` or `manual`
+    pub gesture: String,
+    /// When the daemon recognized the gesture, in milliseconds since the
+    /// Unix epoch
+    pub timestamp_ms: u64,
+}
+
+impl From<uti_dbus::double_tap::TriggerInfo> for TriggerEvent {
+    fn from(info: uti_dbus::double_tap::TriggerInfo) -> Self {
+        Self {
+            source: info.source,
+            gesture: info.gesture,
+            timestamp_ms: info.timestamp_ms,
+        }
+    }
+}
+
+/// Event emitted when the daemon appears on or leaves the bus
+pub const DAEMON_STATUS_EVENT: &str = "daemon-status";
+
+/// Payload of `daemon-status`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct DaemonStatus {
+    /// Whether the daemon owns its bus name
+    pub connected: bool,
+}
+
+/// Application state holding the shared session connection
+#[derive(Default)]
+pub struct AppBus {
+    conn: RwLock<Option<Connection>>,
+    /// Held while connecting, so concurrent callers share one connection
+    connecting: tokio::sync::Mutex<()>,
+    /// Connection that claimed the bus name at startup, until the app's
+    /// interfaces are served on it
+    claimed: Mutex<Option<Connection>>,
+    /// Whether the daemon owns its bus name (or serves its socket), as
+    /// last seen by `listen_dbus`
+    daemon_connected: AtomicBool,
+    /// Socket to the daemon while connected through [`fallback`]
+    fallback: tokio::sync::Mutex<Option<OwnedWriteHalf>>,
+}
+
+impl AppBus {
+    /// Creates the state, reusing the connection from [`claim_instance`]
+    pub fn new(claimed: Option<Connection>) -> Self {
+        Self {
+            claimed: Mutex::new(claimed),
+            ..Self::default()
+        }
+    }
+
+    /// Checks whether the session bus connection is established
+    pub fn is_connected(&self) -> bool {
+        self.conn.read().is_some()
+    }
+
+    /// Checks whether the daemon is on the bus
+    pub fn is_daemon_connected(&self) -> bool {
+        self.daemon_connected.load(Ordering::SeqCst)
+    }
+}
+
+/// Outcome of [`claim_instance`]
+pub enum Instance {
+    /// No other instance is running; the connection owns the app bus name
+    Primary(Connection),
+    /// Another instance owns the app bus name and was asked to show itself
+    Secondary,
+}
+
+/// Claims the app bus name, so only one instance listens for signals
+///
+/// If another instance owns the name, calls `ShowWindow` on it (unless
+/// `show` is false, e.g., for autostart) and returns
+/// [`Instance::Secondary`]; the caller should exit.
+pub async fn claim_instance(show: bool) -> zbus::Result<Instance> {
+    let conn = zbus::connection::Builder::session()?.build().await?;
+    match conn.request_name(APP_BUS_NAME).await {
+        Ok(()) => {
+            info!("Registered bus name: {}", APP_BUS_NAME);
+            Ok(Instance::Primary(conn))
+        }
+        Err(zbus::Error::NameTaken) => {
+            if show {
+                let proxy = uti_dbus::app::AppProxy::new(&conn).await?;
+                if let Err(e) = proxy.show_window().await {
+                    warn!("Failed to show the running instance: {}", e);
+                }
+            }
+            Ok(Instance::Secondary)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Gets the shared session bus connection, connecting on first use
+///
+/// The first successful call also serves the app's interfaces and, unless
+/// [`claim_instance`] already did, requests the app bus name. After
+/// [`reset`], the next call connects again.
+pub async fn connection(app: &AppHandle) -> zbus::Result<Connection> {
+    let bus = app.state::<AppBus>();
+    if let Some(conn) = bus.conn.read().clone() {
+        return Ok(conn);
+    }
+
+    let _connecting = bus.connecting.lock().await;
+    if let Some(conn) = bus.conn.read().clone() {
+        return Ok(conn);
+    }
+    let conn = connect(app).await?;
+    *bus.conn.write() = Some(conn.clone());
+    Ok(conn)
+}
+
+/// Drops the shared connection after it was lost
+pub fn reset(app: &AppHandle) {
+    app.state::<AppBus>().conn.write().take();
+}
+
+/// Connects to the session bus and serves the app's interfaces
+async fn connect(app: &AppHandle) -> zbus::Result<Connection> {
+    let claimed = app.state::<AppBus>().claimed.lock().take();
+    let owns_name = claimed.is_some();
+    let conn = match claimed {
+        Some(conn) => conn,
+        None => zbus::connection::Builder::session()?.build().await?,
+    };
+
+    {
+        let server = conn.object_server();
+        server.at(APP_PATH, AppService::new(app.clone())).await?;
+        server
+            .at(CLIPBOARD_PATH, ClipboardService::new(app.clone()))
+            .await?;
+        server
+            .at(SEARCH_PROVIDER_PATH, SearchProvider::new(app.clone()))
+            .await?;
+        server.at(KRUNNER_PATH, KRunner::new(app.clone())).await?;
+    }
+
+    if !owns_name {
+        // Another instance may own the name; signals still work without it
+        match conn.request_name(APP_BUS_NAME).await {
+            Ok(()) => info!("Registered bus name: {}", APP_BUS_NAME),
+            Err(e) => warn!("Failed to register bus name {}: {}", APP_BUS_NAME, e),
+        }
+    }
+
+    Ok(conn)
+}
+
+/// Checks whether the daemon owns its bus name
+pub async fn daemon_running(conn: &Connection) -> zbus::Result<bool> {
+    let name = BusName::try_from(DAEMON_BUS_NAME)?;
+    Ok(DBusProxy::new(conn).await?.name_has_owner(name).await?)
+}
+
+/// Asks a running instance to reload config.json, e.g., after `uti config
+/// set`
+///
+/// Returns Ok(false) if uti isn't running; it isn't started through D-Bus
+/// activation.
+pub async fn reload_running_config() -> zbus::Result<bool> {
+    let conn = Connection::session().await?;
+    let name = BusName::try_from(APP_BUS_NAME)?;
+    if !DBusProxy::new(&conn).await?.name_has_owner(name).await? {
+        return Ok(false);
+    }
+    uti_dbus::app::AppProxy::new(&conn)
+        .await?
+        .reload_config()
+        .await?;
+    Ok(true)
+}
+
+/// Follows changes of the daemon's bus name owner (start, stop, restart)
+pub async fn daemon_owner_changes(
+    conn: &Connection,
+) -> zbus::Result<NameOwnerChangedStream<'static>> {
+    let proxy = DBusProxy::new(conn).await?;
+    proxy
+        .receive_name_owner_changed_with_args(&[(0, DAEMON_BUS_NAME)])
+        .await
+}
+
+/// Emits `daemon-status` when the daemon appears on or leaves the bus
+pub fn set_daemon_status(app: &AppHandle, connected: bool) {
+    let bus = app.state::<AppBus>();
+    if bus.daemon_connected.swap(connected, Ordering::SeqCst) == connected {
+        return;
+    }
+    if connected {
+        info!("Daemon connected");
+    } else {
+        warn!("Daemon disconnected");
+    }
+    let _ = app.emit(DAEMON_STATUS_EVENT, DaemonStatus { connected });
+}
+
+/// Gets whether the daemon is on the bus
+///
+/// `daemon-status` reports later changes.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const connected = await invoke('get_daemon_status');
+/// ```
+#[tauri::command]
+pub fn get_daemon_status(bus: State<AppBus>) -> bool {
+    bus.is_daemon_connected()
+}
+
+/// Emits `TypeText` so the daemon pastes into the focused window
+///
+/// The shortcut, trailing Enter and key delay come from `paste` in the app
+/// config. Goes through the daemon's socket when connected to it instead
+/// of D-Bus.
+pub async fn emit_type_text(app: &AppHandle) -> zbus::Result<()> {
+    let paste = crate::config::AppConfig::load().paste;
+    let message = Message::TypeText {
+        shortcut: paste.shortcut.clone(),
+        enter: paste.enter,
+        key_delay_ms: paste.key_delay_ms,
+    };
+    if let Some(result) = fallback::send(app, &message).await {
+        return result.map_err(zbus::Error::from);
+    }
+
+    let conn = connection(app).await?;
+    AppService::type_text(
+        &SignalContext::new(&conn, APP_PATH)?,
+        &paste.shortcut,
+        paste.enter,
+        paste.key_delay_ms,
+    )
+    .await
+}
+
+/// Emits `TypeString` so the daemon types text into the focused window
+///
+/// Like [`emit_type_text`], uses the daemon's socket when connected to it.
+pub async fn emit_type_string(app: &AppHandle, text: &str) -> zbus::Result<()> {
+    let message = Message::TypeString {
+        text: text.to_string(),
+    };
+    if let Some(result) = fallback::send(app, &message).await {
+        return result.map_err(zbus::Error::from);
+    }
+
+    let conn = connection(app).await?;
+    AppService::type_string(&SignalContext::new(&conn, APP_PATH)?, text).await
+}
+
+/// Emits `PropertiesChanged` for the App interface's `Pinned` property
+///
+/// Call after changing `AppState::pinned`.
+pub async fn emit_pinned_changed(app: &AppHandle) -> zbus::Result<()> {
+    let conn = connection(app).await?;
+    let iface = conn
+        .object_server()
+        .interface::<_, AppService>(APP_PATH)
+        .await?;
+    let service = iface.get().await;
+    service.pinned_changed(iface.signal_context()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_from_signal() {
+        assert_eq!(Trigger::from_signal("Triggered"), Some(Trigger::Toggle));
+        assert_eq!(
+            Trigger::from_signal("TriggeredSnippets"),
+            Some(Trigger::OpenTab("snippets"))
+        );
+        assert_eq!(Trigger::from_signal("TriggeredSettings"), None);
+
+        for signal in uti_dbus::double_tap::SIGNALS {
+            assert!(Trigger::from_signal(signal).is_some(), "{}", signal);
+        }
+    }
+
+    #[test]
+    fn test_trigger_event_serializes_camel_case() {
+        let event = TriggerEvent::from(uti_dbus::double_tap::TriggerInfo {
+            source: "Keychron K2".to_string(),
+            gesture: "hold".to_string(),
+            timestamp_ms: 42,
+        });
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({ "source": "Keychron K2", "gesture": "hold", "timestampMs": 42 })
+        );
+    }
+
+    #[test]
+    fn test_daemon_status_payload() {
+        assert_eq!(
+            serde_json::to_value(DaemonStatus { connected: false }).unwrap(),
+            serde_json::json!({ "connected": false })
+        );
+    }
+
+    #[test]
+    fn test_app_interface_name() {
+        assert_eq!(
+            <AppService as zbus::Interface>::name(),
+            uti_dbus::APP_INTERFACE
+        );
+    }
+}
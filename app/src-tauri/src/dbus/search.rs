@@ -0,0 +1,285 @@
+//! Search over uti's data for desktop search integrations
+//!
+//! Shared by the GNOME Shell search provider and the KRunner runner.
+//! Activating a snippet or clipboard result copies it; activating a
+//! launcher result runs it.
+
+use crate::clipboard::ClipboardItem;
+use crate::error::report;
+use crate::launcher::{self, LauncherItem};
+use crate::snippets::SnippetItem;
+use crate::state::AppState;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::info;
+
+/// Maximum number of results returned to the desktop
+const MAX_RESULTS: usize = 10;
+
+/// Maximum length of a result name before it's truncated
+const MAX_NAME_CHARS: usize = 80;
+
+/// Identifies a search result across D-Bus calls
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultId {
+    /// Snippet by id
+    Snippet(String),
+    /// Clipboard history item by id, stable while the history reorders
+    Clipboard(String),
+    /// Launcher command by id
+    Launcher(String),
+}
+
+impl ResultId {
+    pub fn parse(id: &str) -> Option<Self> {
+        let (kind, value) = id.split_once(':')?;
+        match kind {
+            "snippet" => Some(Self::Snippet(value.to_string())),
+            "clipboard" => Some(Self::Clipboard(value.to_string())),
+            "launcher" => Some(Self::Launcher(value.to_string())),
+            _ => None,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        match self {
+            Self::Snippet(id) => format!("snippet:{}", id),
+            Self::Clipboard(id) => format!("clipboard:{}", id),
+            Self::Launcher(id) => format!("launcher:{}", id),
+        }
+    }
+}
+
+/// Display information for a result
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultMeta {
+    pub name: String,
+    pub description: String,
+    /// Themed icon name
+    pub icon: &'static str,
+}
+
+/// Snapshot of the searchable data
+pub struct SearchSources {
+    snippets: Vec<SnippetItem>,
+    clipboard: Vec<ClipboardItem>,
+    commands: Vec<LauncherItem>,
+}
+
+impl SearchSources {
+    /// Takes a snapshot of snippets, clipboard history and launcher items
+    pub fn load(app: &AppHandle) -> Self {
+        let state = app.state::<AppState>();
+        let snippets = state.snippets.read().items.clone();
+        let clipboard = state.clipboard.read().items.clone();
+        Self {
+            snippets,
+            clipboard,
+            commands: launcher::load_launcher_config().commands,
+        }
+    }
+
+    /// Finds results matching all search terms (case-insensitive)
+    ///
+    /// Snippets come first, then launcher items, then clipboard history.
+    pub fn search(&self, terms: &[String]) -> Vec<ResultId> {
+        let terms: Vec<String> = terms.iter().map(|t| t.to_lowercase()).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+        let matches = |text: &str| {
+            let text = text.to_lowercase();
+            terms.iter().all(|term| text.contains(term.as_str()))
+        };
+
+        let snippets = self
+            .snippets
+            .iter()
+            .filter(|s| matches(&s.value) || s.label.as_deref().is_some_and(matches))
+            .map(|s| ResultId::Snippet(s.id.clone()));
+        let commands = self
+            .commands
+            .iter()
+            .filter(|c| matches(&c.name))
+            .map(|c| ResultId::Launcher(c.id.clone()));
+        let clipboard = self
+            .clipboard
+            .iter()
+            .filter(|item| matches(&item.text))
+            .map(|item| ResultId::Clipboard(item.id.clone()));
+
+        snippets
+            .chain(commands)
+            .chain(clipboard)
+            .take(MAX_RESULTS)
+            .collect()
+    }
+
+    /// Gets display information for a result
+    pub fn describe(&self, id: &ResultId) -> Option<ResultMeta> {
+        let (name, description, icon) = match id {
+            ResultId::Snippet(sid) => {
+                let snippet = self.snippets.iter().find(|s| &s.id == sid)?;
+                match &snippet.label {
+                    Some(label) => (label.clone(), first_line(&snippet.value), "starred"),
+                    None => (first_line(&snippet.value), "Snippet".to_string(), "starred"),
+                }
+            }
+            ResultId::Clipboard(cid) => (
+                first_line(&self.clipboard.iter().find(|c| &c.id == cid)?.text),
+                "Clipboard".to_string(),
+                "edit-paste",
+            ),
+            ResultId::Launcher(lid) => {
+                let item = self.commands.iter().find(|c| &c.id == lid)?;
+                (item.name.clone(), "Launcher".to_string(), "system-run")
+            }
+        };
+
+        Some(ResultMeta {
+            name,
+            description,
+            icon,
+        })
+    }
+
+    /// Copies or launches a result
+    pub fn activate(self, app: &AppHandle, id: &ResultId) {
+        match id {
+            ResultId::Snippet(sid) => {
+                if let Some(snippet) = self.snippets.iter().find(|s| &s.id == sid) {
+                    copy(app, &snippet.value);
+                }
+            }
+            ResultId::Clipboard(cid) => {
+                if let Some(item) = self.clipboard.iter().find(|c| &c.id == cid) {
+                    copy(app, &item.text);
+                }
+            }
+            ResultId::Launcher(lid) => {
+                if let Some(item) = self.commands.into_iter().find(|c| &c.id == lid) {
+                    launch(app, item);
+                }
+            }
+        }
+    }
+}
+
+/// Copies text to the system clipboard
+fn copy(app: &AppHandle, text: &str) {
+    match app.clipboard().write_text(text) {
+        Ok(()) => info!("Search result copied to clipboard"),
+        Err(e) => report(
+            app,
+            "search",
+            format!("Failed to copy search result: {}", e),
+        ),
+    }
+}
+
+/// Runs a launcher item's action or command
+fn launch(app: &AppHandle, item: LauncherItem) {
+    if let Some(action) = item.action {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::run_launcher_action(app.clone(), action).await {
+                report(&app, "search", e);
+            }
+        });
+    } else if let Err(e) = crate::execute_command(
+        item.command,
+        item.args,
+        Some(item.terminal),
+        Some(item.env),
+        item.cwd,
+    ) {
+        report(app, "search", e);
+    }
+}
+
+/// Gets the first non-empty line, truncated for display
+fn first_line(text: &str) -> String {
+    let line = text
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    if line.chars().count() > MAX_NAME_CHARS {
+        let truncated: String = line.chars().take(MAX_NAME_CHARS).collect();
+        format!("{}…", truncated)
+    } else {
+        line.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn sources() -> SearchSources {
+        SearchSources {
+            snippets: vec![SnippetItem::new(
+                "user@example.com".to_string(),
+                Some("Work Email".to_string()),
+            )],
+            clipboard: vec![
+                ClipboardItem::new("hello world".to_string()),
+                ClipboardItem::new("work email draft".to_string()),
+            ],
+            commands: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_result_id_roundtrip() {
+        for id in [
+            ResultId::Snippet("my-email".to_string()),
+            ResultId::Clipboard("3f1c".to_string()),
+            ResultId::Launcher("code".to_string()),
+        ] {
+            assert_eq!(ResultId::parse(&id.encode()), Some(id));
+        }
+        assert_eq!(ResultId::parse("other"), None);
+    }
+
+    #[test]
+    fn test_search_matches_all_terms() {
+        let sources = sources();
+        let results = sources.search(&terms("WORK email"));
+        assert_eq!(
+            results,
+            vec![
+                ResultId::Snippet(sources.snippets[0].id.clone()),
+                ResultId::Clipboard(sources.clipboard[1].id.clone())
+            ]
+        );
+        assert!(sources.search(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_describe() {
+        let sources = sources();
+        let meta = sources
+            .describe(&ResultId::Snippet(sources.snippets[0].id.clone()))
+            .unwrap();
+        assert_eq!(meta.name, "Work Email");
+        assert_eq!(meta.description, "user@example.com");
+        let meta = sources
+            .describe(&ResultId::Clipboard(sources.clipboard[0].id.clone()))
+            .unwrap();
+        assert_eq!(meta.name, "hello world");
+        assert!(sources
+            .describe(&ResultId::Clipboard("missing".to_string()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_first_line() {
+        assert_eq!(first_line("\n  first  \nsecond"), "first");
+        assert_eq!(first_line(&"a".repeat(100)).chars().count(), 81);
+    }
+}
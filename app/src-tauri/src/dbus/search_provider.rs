@@ -0,0 +1,81 @@
+//! GNOME Shell search provider
+//!
+//! Implements `org.gnome.Shell.SearchProvider2` so snippets, clipboard
+//! history and launcher items show up in the GNOME overview search.
+
+use super::search::{ResultId, SearchSources};
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+use zbus::interface;
+use zbus::zvariant::Value;
+
+/// `org.gnome.Shell.SearchProvider2` implementation
+pub struct SearchProvider {
+    app: AppHandle,
+}
+
+impl SearchProvider {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+
+    fn search(&self, terms: &[String]) -> Vec<String> {
+        SearchSources::load(&self.app)
+            .search(terms)
+            .iter()
+            .map(ResultId::encode)
+            .collect()
+    }
+}
+
+#[interface(name = "org.gnome.Shell.SearchProvider2")]
+impl SearchProvider {
+    /// Returns results for a new search
+    fn get_initial_result_set(&self, terms: Vec<String>) -> Vec<String> {
+        self.search(&terms)
+    }
+
+    /// Returns results for a refined search
+    fn get_subsearch_result_set(
+        &self,
+        _previous_results: Vec<String>,
+        terms: Vec<String>,
+    ) -> Vec<String> {
+        self.search(&terms)
+    }
+
+    /// Returns display metadata for results
+    fn get_result_metas(&self, identifiers: Vec<String>) -> Vec<HashMap<String, Value<'static>>> {
+        let sources = SearchSources::load(&self.app);
+
+        identifiers
+            .into_iter()
+            .filter_map(|id| {
+                let meta = sources.describe(&ResultId::parse(&id)?)?;
+                Some(HashMap::from([
+                    ("id".to_string(), Value::from(id)),
+                    ("name".to_string(), Value::from(meta.name)),
+                    ("description".to_string(), Value::from(meta.description)),
+                    ("gicon".to_string(), Value::from(meta.icon)),
+                ]))
+            })
+            .collect()
+    }
+
+    /// Activates a result (copy or launch)
+    fn activate_result(&self, identifier: String, _terms: Vec<String>, _timestamp: u32) {
+        match ResultId::parse(&identifier) {
+            Some(id) => SearchSources::load(&self.app).activate(&self.app, &id),
+            None => warn!("Unknown search result: {}", identifier),
+        }
+    }
+
+    /// Opens uti with the search terms
+    fn launch_search(&self, _terms: Vec<String>, _timestamp: u32) {
+        if let Some(window) = self.app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
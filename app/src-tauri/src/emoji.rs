@@ -0,0 +1,167 @@
+//! Emoji picker data
+//!
+//! Searches the Unicode emoji list embedded by the `emojis` crate (CLDR
+//! names plus GitHub shortcodes as keywords) with the launcher's fuzzy
+//! matcher. Emojis picked often rank higher; their usage is kept like
+//! launcher launches, in `~/.config/uti/emoji-usage.json`.
+
+use crate::launcher::{fuzzy, FrecencyStore};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Maximum number of search results
+pub const MAX_RESULTS: usize = 40;
+
+/// An emoji search result
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiMatch {
+    /// The emoji itself
+    pub emoji: String,
+    /// CLDR name (e.g., "grinning face")
+    pub name: String,
+    /// GitHub shortcodes without colons (e.g., "grinning")
+    pub shortcodes: Vec<String>,
+    /// Relevance including the usage bonus, higher is better
+    pub score: u32,
+}
+
+impl EmojiMatch {
+    fn new(emoji: &emojis::Emoji, score: u32) -> Self {
+        Self {
+            emoji: emoji.as_str().to_string(),
+            name: emoji.name().to_string(),
+            shortcodes: emoji.shortcodes().map(str::to_string).collect(),
+            score,
+        }
+    }
+}
+
+/// Get the path to the emoji usage file
+///
+/// Returns `~/.config/uti/emoji-usage.json`
+pub fn get_usage_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("uti")
+        .join("emoji-usage.json")
+}
+
+/// Scores how well an emoji matches a query by name or shortcode
+fn match_score(emoji: &emojis::Emoji, query: &str) -> Option<u32> {
+    std::iter::once(emoji.name())
+        .chain(emoji.shortcodes())
+        .filter_map(|field| fuzzy::score(query, field))
+        .max()
+}
+
+/// Searches emojis by name and shortcode
+///
+/// With an empty query, returns the most used emojis, or the first ones
+/// in Unicode order if none were used yet.
+///
+/// # Arguments
+///
+/// * `query` - Search text (e.g., "smile" or ":tada:")
+/// * `usage` - How often emojis were picked
+/// * `now` - Current time in Unix seconds
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::emoji::search;
+/// use uti_lib::launcher::FrecencyStore;
+///
+/// let results = search("rocket", &FrecencyStore::default(), 0);
+/// assert_eq!(results[0].emoji, "🚀");
+/// ```
+pub fn search(query: &str, usage: &FrecencyStore, now: i64) -> Vec<EmojiMatch> {
+    let query = query.trim().trim_matches(':');
+
+    let mut matches: Vec<EmojiMatch> = if query.is_empty() {
+        let used: Vec<EmojiMatch> = emojis::iter()
+            .filter_map(|emoji| {
+                let score = usage.score(emoji.as_str(), now);
+                (score > 0).then(|| EmojiMatch::new(emoji, score))
+            })
+            .collect();
+        if used.is_empty() {
+            emojis::iter()
+                .take(MAX_RESULTS)
+                .map(|emoji| EmojiMatch::new(emoji, 0))
+                .collect()
+        } else {
+            used
+        }
+    } else {
+        emojis::iter()
+            .filter_map(|emoji| {
+                let score = match_score(emoji, query)?;
+                Some(EmojiMatch::new(
+                    emoji,
+                    score + usage.bonus(emoji.as_str(), now),
+                ))
+            })
+            .collect()
+    };
+
+    // Stable, so equal scores keep the Unicode order
+    matches.sort_by_key(|m| std::cmp::Reverse(m.score));
+    matches.truncate(MAX_RESULTS);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn emojis_of(results: &[EmojiMatch]) -> Vec<&str> {
+        results.iter().map(|m| m.emoji.as_str()).collect()
+    }
+
+    #[test]
+    fn test_search_by_name_and_shortcode() {
+        let usage = FrecencyStore::default();
+        assert_eq!(search("party popper", &usage, NOW)[0].emoji, "🎉");
+        assert_eq!(search(":tada:", &usage, NOW)[0].emoji, "🎉");
+        assert!(emojis_of(&search("thumbs", &usage, NOW)).contains(&"👍"));
+        assert!(search("zzzqqq", &usage, NOW).is_empty());
+    }
+
+    #[test]
+    fn test_results_are_capped() {
+        let results = search("e", &FrecencyStore::default(), NOW);
+        assert_eq!(results.len(), MAX_RESULTS);
+    }
+
+    #[test]
+    fn test_usage_ranks_emojis_higher() {
+        let mut usage = FrecencyStore::default();
+        let plain = search("heart", &usage, NOW);
+        let purple = "💜";
+        assert_ne!(plain[0].emoji, purple);
+
+        for _ in 0..5 {
+            usage.record(purple, NOW);
+        }
+        assert_eq!(search("heart", &usage, NOW)[0].emoji, purple);
+    }
+
+    #[test]
+    fn test_empty_query_lists_used_emojis() {
+        let mut usage = FrecencyStore::default();
+        assert_eq!(search("", &usage, NOW).len(), MAX_RESULTS);
+
+        usage.record("🚀", NOW);
+        usage.record("🎉", NOW);
+        usage.record("🎉", NOW);
+        assert_eq!(emojis_of(&search("", &usage, NOW)), ["🎉", "🚀"]);
+    }
+
+    #[test]
+    fn test_get_usage_path() {
+        assert!(get_usage_path().ends_with("uti/emoji-usage.json"));
+    }
+}
@@ -0,0 +1,135 @@
+//! App error type and error reporting
+//!
+//! Commands return [`Error`], which the frontend receives as a message
+//! string. Failures in background work (D-Bus services, tray, plugins)
+//! have no caller to return to, so they are sent to the frontend as a
+//! `backend-error` event via [`report`]. Errors from before the frontend
+//! listens are kept with [`defer`] until it asks for them.
+
+use crate::clipboard::ClipboardError;
+use crate::config::ConfigError;
+use crate::launcher::LauncherError;
+use crate::snippets::SnippetsError;
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+use tauri::{AppHandle, Emitter};
+
+/// Event name for background errors
+pub const BACKEND_ERROR_EVENT: &str = "backend-error";
+
+/// Errors returned by app commands
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Clipboard(#[from] ClipboardError),
+    #[error(transparent)]
+    Snippets(#[from] SnippetsError),
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    #[error(transparent)]
+    Launcher(#[from] LauncherError),
+    #[error("D-Bus error: {0}")]
+    DBus(#[from] zbus::Error),
+    #[error(transparent)]
+    Tauri(#[from] tauri::Error),
+    #[error("Failed to execute {command}: {source}")]
+    Launch {
+        command: String,
+        source: std::io::Error,
+    },
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Command result type
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Lets modules that still return `String` errors use `?`
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+/// Serializes as the error message so the frontend gets a plain string
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// Payload of the `backend-error` event
+#[derive(Debug, Clone, Serialize)]
+pub struct BackendError {
+    /// Area that failed (e.g., "dbus", "tray", "plugins")
+    pub source: &'static str,
+    /// Human-readable message
+    pub message: String,
+}
+
+/// Logs a background error and sends it to the frontend
+///
+/// # Arguments
+///
+/// * `app` - App handle used to emit the event
+/// * `source` - Area that failed (e.g., "dbus", "tray", "plugins")
+/// * `error` - The error to report
+pub fn report(app: &AppHandle, source: &'static str, error: impl Display) {
+    let message = error.to_string();
+    tracing::error!(source, "{}", message);
+    let _ = app.emit(BACKEND_ERROR_EVENT, BackendError { source, message });
+}
+
+/// Startup errors waiting for the frontend, see [`defer`]
+static DEFERRED: parking_lot::Mutex<Vec<BackendError>> = parking_lot::const_mutex(Vec::new());
+
+/// Logs an error from startup and keeps it for the frontend
+///
+/// Events sent before the webview has registered its listener are lost,
+/// so the frontend collects these with [`take_deferred`] once it listens.
+pub fn defer(source: &'static str, error: impl Display) {
+    let message = error.to_string();
+    tracing::error!(source, "{}", message);
+    DEFERRED.lock().push(BackendError { source, message });
+}
+
+/// Returns the errors kept by [`defer`] and clears them
+pub fn take_deferred() -> Vec<BackendError> {
+    std::mem::take(&mut *DEFERRED.lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_deferred() {
+        defer("clipboard", "Keyring unavailable");
+        let errors = take_deferred();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].source, "clipboard");
+        assert_eq!(errors[0].message, "Keyring unavailable");
+        assert!(take_deferred().is_empty());
+    }
+
+    #[test]
+    fn test_error_serializes_as_message() {
+        let err = Error::Launch {
+            command: "nope".to_string(),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "not found"),
+        };
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#""Failed to execute nope: not found""#
+        );
+    }
+
+    #[test]
+    fn test_transparent_errors_keep_message() {
+        let err = Error::from(ClipboardError::Io(std::io::Error::other("disk full")));
+        assert_eq!(
+            err.to_string(),
+            "failed to write clipboard history: disk full"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+//! Focus mode (do-not-disturb)
+//!
+//! While focus mode is on, double Ctrl triggers are suppressed. Background
+//! work that would interrupt the user (notifications, automatic update
+//! checks) should check [`is_active`] before running.
+//!
+//! Focus mode can be toggled with the `set_focus_mode` command or the tray
+//! menu, and is switched on and off automatically at the edges of the
+//! configured `focusSchedule`. The daemon is told through its
+//! `SetFocusMode` D-Bus method so triggers are dropped at the source.
+
+use crate::config::{AppConfig, FocusSchedule};
+use crate::state::AppState;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+use tauri::menu::CheckMenuItem;
+use tauri::{AppHandle, Emitter, Manager, State, Wry};
+use tracing::info;
+use uti_dbus::daemon::DaemonProxy;
+use zbus::Connection;
+
+/// How often the schedule is evaluated
+const SCHEDULE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Focus mode state (held in [`AppState`])
+pub struct FocusState {
+    /// Whether focus mode is on
+    enabled: AtomicBool,
+    /// Whether the schedule was active at the last check
+    scheduled: AtomicBool,
+}
+
+impl FocusState {
+    pub fn new() -> Self {
+        Self {
+            enabled: AtomicBool::new(false),
+            scheduled: AtomicBool::new(false),
+        }
+    }
+
+    /// Checks whether focus mode is on
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Checks whether the schedule was active at the last check
+    pub fn is_scheduled(&self) -> bool {
+        self.scheduled.load(Ordering::SeqCst)
+    }
+}
+
+/// Tray menu item reflecting the focus mode state
+pub struct FocusMenuItem(pub CheckMenuItem<Wry>);
+
+/// Checks whether focus mode is on
+pub fn is_active(app: &AppHandle) -> bool {
+    app.try_state::<AppState>()
+        .is_some_and(|state| state.focus.is_enabled())
+}
+
+/// Gets whether focus mode is on
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const enabled = await invoke('get_focus_mode');
+/// ```
+#[tauri::command]
+pub fn get_focus_mode(state: State<AppState>) -> bool {
+    state.focus.is_enabled()
+}
+
+/// Turns focus mode on or off
+///
+/// Emits `focus-mode-changed` with the new state when it changes.
+///
+/// # Arguments
+///
+/// * `enabled` - Whether focus mode should be on
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// import { listen } from '@tauri-apps/api/event';
+/// await listen('focus-mode-changed', (event) => console.log(event.payload));
+/// await invoke('set_focus_mode', { enabled: true });
+/// ```
+#[tauri::command]
+pub async fn set_focus_mode(app: AppHandle, enabled: bool) {
+    set_enabled(&app, enabled).await;
+}
+
+/// Toggles focus mode (used by the tray menu)
+pub fn toggle(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let enabled = !is_active(&app);
+        set_enabled(&app, enabled).await;
+    });
+}
+
+/// Applies a focus mode change to the daemon, tray and frontend
+async fn set_enabled(app: &AppHandle, enabled: bool) {
+    let state = app.state::<AppState>();
+    let previous = state.focus.enabled.swap(enabled, Ordering::SeqCst);
+
+    // Keep the tray check mark in sync (clicking it also toggles it)
+    if let Some(item) = app.try_state::<FocusMenuItem>() {
+        let _ = item.0.set_checked(enabled);
+    }
+
+    if previous == enabled {
+        return;
+    }
+
+    info!(
+        "Focus mode {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    if let Err(e) = sync_daemon(enabled).await {
+        crate::error::report(
+            app,
+            "dbus",
+            format!("Failed to update daemon focus mode: {}", e),
+        );
+    }
+    let _ = app.emit("focus-mode-changed", enabled);
+}
+
+/// Sends the focus mode state to the daemon
+///
+/// Also called after reconnecting, since a restarted daemon starts with
+/// focus mode off.
+pub async fn sync_daemon(enabled: bool) -> zbus::Result<()> {
+    let conn = Connection::session().await?;
+    DaemonProxy::new(&conn).await?.set_focus_mode(enabled).await
+}
+
+/// Switches focus mode on and off following the configured schedule
+///
+/// Only the schedule's start and end change the state, so a manual toggle
+/// in between is respected until the next edge. The config is reloaded
+/// whenever the file changes.
+pub async fn run_schedule(app: AppHandle) {
+    let path = AppConfig::get_config_path();
+    let mut modified: Option<SystemTime> = None;
+    let mut schedule: Option<FocusSchedule> = None;
+
+    loop {
+        let current = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+        if current != modified {
+            modified = current;
+            schedule = AppConfig::load().focus_schedule;
+        }
+
+        let now = chrono::Local::now().time();
+        let scheduled = is_scheduled(schedule.as_ref(), now);
+
+        let state = app.state::<AppState>();
+        if state.focus.scheduled.swap(scheduled, Ordering::SeqCst) != scheduled {
+            info!(
+                "Focus schedule {}",
+                if scheduled { "started" } else { "ended" }
+            );
+            set_enabled(&app, scheduled).await;
+        }
+
+        tokio::time::sleep(SCHEDULE_INTERVAL).await;
+    }
+}
+
+/// Checks whether a local time falls within the schedule
+fn is_scheduled(schedule: Option<&FocusSchedule>, now: chrono::NaiveTime) -> bool {
+    schedule.is_some_and(|s| s.contains(now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn schedule(start: &str, end: &str) -> FocusSchedule {
+        FocusSchedule {
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule_same_day() {
+        let s = schedule("09:00", "17:30");
+        assert!(is_scheduled(Some(&s), time(9, 0)));
+        assert!(is_scheduled(Some(&s), time(12, 0)));
+        assert!(!is_scheduled(Some(&s), time(17, 30)));
+        assert!(!is_scheduled(Some(&s), time(8, 59)));
+    }
+
+    #[test]
+    fn test_schedule_overnight() {
+        let s = schedule("22:00", "07:00");
+        assert!(is_scheduled(Some(&s), time(23, 0)));
+        assert!(is_scheduled(Some(&s), time(6, 59)));
+        assert!(!is_scheduled(Some(&s), time(12, 0)));
+    }
+
+    #[test]
+    fn test_schedule_missing_or_invalid() {
+        assert!(!is_scheduled(None, time(12, 0)));
+        assert!(!is_scheduled(Some(&schedule("9am", "5pm")), time(12, 0)));
+    }
+}
@@ -0,0 +1,241 @@
+//! Local HTTP API
+//!
+//! Opt-in REST API on 127.0.0.1 for browser extensions and other tools.
+//! Every request must send `Authorization: Bearer <token>`; the token is
+//! generated on first use and kept in the system keyring.
+
+mod routes;
+
+use crate::keyring::{lookup_secret, store_secret};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{self, HeaderValue};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use std::convert::Infallible;
+use std::net::Ipv4Addr;
+use tauri::{AppHandle, Manager, State};
+use tokio::net::TcpListener;
+use tracing::{error, info};
+
+/// Keyring key for the API token
+const TOKEN_KEY: &str = "http-api.token";
+
+/// Maximum accepted request body size
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Application state for the HTTP API
+#[derive(Default)]
+pub struct HttpApiState {
+    /// Token accepted by the running server
+    token: Mutex<Option<String>>,
+}
+
+/// Gets the HTTP API token, creating one if needed
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const token = await invoke('get_http_api_token');
+/// ```
+#[tauri::command]
+pub fn get_http_api_token(state: State<'_, HttpApiState>) -> Result<String, String> {
+    let token = load_or_create_token()?;
+    *state.token.lock() = Some(token.clone());
+    Ok(token)
+}
+
+/// Replaces the HTTP API token, invalidating the old one immediately
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const token = await invoke('regenerate_http_api_token');
+/// ```
+#[tauri::command]
+pub fn regenerate_http_api_token(state: State<'_, HttpApiState>) -> Result<String, String> {
+    let token = generate_token()?;
+    *state.token.lock() = Some(token.clone());
+    info!("HTTP API token regenerated");
+    Ok(token)
+}
+
+/// Looks up the token in the keyring, generating one if missing
+fn load_or_create_token() -> Result<String, String> {
+    match lookup_secret(TOKEN_KEY) {
+        Some(token) => Ok(token),
+        None => generate_token(),
+    }
+}
+
+/// Generates and stores a new random token
+fn generate_token() -> Result<String, String> {
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    store_secret(TOKEN_KEY, "uti HTTP API token", &token)?;
+    Ok(token)
+}
+
+/// Runs the HTTP API server on 127.0.0.1
+///
+/// Runs until the app exits. Logs and returns if the token can't be set up
+/// or the port is unavailable.
+pub async fn serve(app: AppHandle, port: u16) {
+    let token = match load_or_create_token() {
+        Ok(token) => token,
+        Err(e) => {
+            error!("HTTP API disabled, failed to set up token: {}", e);
+            return;
+        }
+    };
+    *app.state::<HttpApiState>().token.lock() = Some(token);
+
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("Failed to start HTTP API on port {}: {}", port, e);
+            return;
+        }
+    };
+    info!("HTTP API listening on http://127.0.0.1:{}", port);
+
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                error!("Failed to accept HTTP API connection: {}", e);
+                continue;
+            }
+        };
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let service = service_fn(move |req| handle(app.clone(), req));
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                error!("HTTP API connection error: {}", e);
+            }
+        });
+    }
+}
+
+/// Handles a single request
+async fn handle(
+    app: AppHandle,
+    req: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    // CORS preflight (browser extensions)
+    if req.method() == Method::OPTIONS {
+        return Ok(respond(StatusCode::NO_CONTENT, None));
+    }
+
+    let expected = app.state::<HttpApiState>().token.lock().clone();
+    let provided = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    if !is_authorized(provided, expected.as_deref()) {
+        return Ok(error(StatusCode::UNAUTHORIZED, "Missing or invalid token"));
+    }
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_string();
+    let body = match Limited::new(req.into_body(), MAX_BODY_BYTES)
+        .collect()
+        .await
+    {
+        Ok(collected) => collected.to_bytes(),
+        Err(_) => {
+            return Ok(error(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "Request body too large",
+            ))
+        }
+    };
+
+    let (status, value) = routes::route(&app, &method, &path, &body).await;
+    Ok(respond(status, Some(value)))
+}
+
+/// Checks a bearer token in constant time
+fn is_authorized(header: Option<&str>, expected: Option<&str>) -> bool {
+    let (Some(header), Some(expected)) = (header, expected) else {
+        return false;
+    };
+    let Some(provided) = header.strip_prefix("Bearer ") else {
+        return false;
+    };
+
+    provided.len() == expected.len()
+        && provided
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+/// Builds an error response
+fn error(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    respond(status, Some(json!({ "error": message })))
+}
+
+/// Builds a JSON response with CORS headers
+fn respond(status: StatusCode, body: Option<Value>) -> Response<Full<Bytes>> {
+    let body = body.map(|v| v.to_string()).unwrap_or_default();
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_HEADERS,
+        HeaderValue::from_static("Authorization, Content-Type"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_METHODS,
+        HeaderValue::from_static("GET, POST, DELETE, OPTIONS"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_authorized() {
+        assert!(is_authorized(Some("Bearer abc123"), Some("abc123")));
+        assert!(!is_authorized(Some("Bearer abc124"), Some("abc123")));
+        assert!(!is_authorized(Some("abc123"), Some("abc123")));
+        assert!(!is_authorized(Some("Bearer abc"), Some("abc123")));
+        assert!(!is_authorized(None, Some("abc123")));
+        assert!(!is_authorized(Some("Bearer abc123"), None));
+    }
+
+    #[test]
+    fn test_respond_sets_cors_and_json() {
+        let response = error(StatusCode::NOT_FOUND, "nope");
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            HeaderValue::from_static("application/json")
+        );
+        assert!(response
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+}
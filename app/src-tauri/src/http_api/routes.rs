@@ -0,0 +1,172 @@
+//! HTTP API routes
+//!
+//! | Method | Path                      | Description                  |
+//! | ------ | ------------------------- | ---------------------------- |
+//! | GET    | `/api/clipboard`          | Clipboard history            |
+//! | POST   | `/api/clipboard`          | Add `{ "text" }` to history  |
+//! | DELETE | `/api/clipboard/{id}`     | Remove a history item        |
+//! | GET    | `/api/snippets`           | All snippets                 |
+//! | POST   | `/api/snippets`           | Add `{ "value", "label"? }`  |
+//! | GET    | `/api/launcher`           | Launcher commands            |
+//! | POST   | `/api/launcher/{id}/run`  | Run a launcher command       |
+
+use crate::launcher;
+use crate::snippets::{save_snippets, SnippetItem};
+use crate::state::AppState;
+use hyper::{Method, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tracing::error;
+
+/// Body of `POST /api/clipboard`
+#[derive(Deserialize)]
+struct AddClipRequest {
+    text: String,
+}
+
+/// Body of `POST /api/snippets`
+#[derive(Deserialize)]
+struct AddSnippetRequest {
+    value: String,
+    #[serde(default)]
+    label: Option<String>,
+}
+
+/// Routes a request to its handler
+///
+/// # Returns
+///
+/// The response status and JSON body
+pub(super) async fn route(
+    app: &AppHandle,
+    method: &Method,
+    path: &str,
+    body: &[u8],
+) -> (StatusCode, Value) {
+    let segments = split_path(path);
+
+    let result = match (method, segments.as_slice()) {
+        (&Method::GET, ["api", "clipboard"]) => Ok(get_clipboard(app)),
+        (&Method::POST, ["api", "clipboard"]) => parse(body).and_then(|r| add_clip(app, r)),
+        (&Method::DELETE, ["api", "clipboard", id]) => remove_clip(app, id),
+        (&Method::GET, ["api", "snippets"]) => Ok(get_snippets(app)),
+        (&Method::POST, ["api", "snippets"]) => parse(body).and_then(|r| add_snippet(app, r)),
+        (&Method::GET, ["api", "launcher"]) => Ok(json!(launcher::load_launcher_config().commands)),
+        (&Method::POST, ["api", "launcher", id, "run"]) => run_launcher(app, id).await,
+        _ => Err((StatusCode::NOT_FOUND, "Not found".to_string())),
+    };
+
+    match result {
+        Ok(value) => (StatusCode::OK, value),
+        Err((status, message)) => (status, json!({ "error": message })),
+    }
+}
+
+type RouteResult = Result<Value, (StatusCode, String)>;
+
+/// Splits a URL path into non-empty segments
+fn split_path(path: &str) -> Vec<&str> {
+    path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+/// Parses a JSON request body
+fn parse<T: for<'de> Deserialize<'de>>(body: &[u8]) -> Result<T, (StatusCode, String)> {
+    serde_json::from_slice(body).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Invalid request body: {}", e),
+        )
+    })
+}
+
+fn get_clipboard(app: &AppHandle) -> Value {
+    json!(app.state::<AppState>().clipboard.read().items)
+}
+
+fn add_clip(app: &AppHandle, request: AddClipRequest) -> RouteResult {
+    // null when the item was skipped for its size
+    let item = crate::capture::push(app, request.text).map_err(internal)?;
+    Ok(json!(item))
+}
+
+/// Removes a history item by id, so reordering can't remove another one
+fn remove_clip(app: &AppHandle, id: &str) -> RouteResult {
+    let state = app.state::<AppState>();
+    let item = state.clipboard.write().remove_by_id(id).map_err(internal)?;
+    item.map(|item| json!(item)).ok_or_else(|| {
+        (
+            StatusCode::NOT_FOUND,
+            format!("Clipboard item {} not found", id),
+        )
+    })
+}
+
+fn get_snippets(app: &AppHandle) -> Value {
+    json!(app.state::<AppState>().snippets.read().items)
+}
+
+fn add_snippet(app: &AppHandle, request: AddSnippetRequest) -> RouteResult {
+    let state = app.state::<AppState>();
+    let mut store = state.snippets.write();
+    let item = SnippetItem::new(request.value, request.label);
+    store.items.push(item.clone());
+    save_snippets(&store).map_err(internal)?;
+    Ok(json!(item))
+}
+
+async fn run_launcher(app: &AppHandle, id: &str) -> RouteResult {
+    let item = launcher::load_launcher_config()
+        .commands
+        .into_iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("Launcher item {} not found", id),
+            )
+        })?;
+
+    match item.action {
+        Some(action) => crate::run_launcher_action(app.clone(), action).await,
+        None => crate::execute_command(
+            item.command,
+            item.args,
+            Some(item.terminal),
+            Some(item.env),
+            item.cwd,
+        ),
+    }
+    .map_err(internal)?;
+    Ok(json!({ "ok": true }))
+}
+
+fn internal(error: impl std::fmt::Display) -> (StatusCode, String) {
+    error!("HTTP API error: {}", error);
+    (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_path() {
+        assert_eq!(split_path("/api/clipboard/"), vec!["api", "clipboard"]);
+        assert_eq!(
+            split_path("//api/launcher/code/run"),
+            vec!["api", "launcher", "code", "run"]
+        );
+        assert!(split_path("/").is_empty());
+    }
+
+    #[test]
+    fn test_parse_body() {
+        let request: AddSnippetRequest = parse(br#"{ "value": "v" }"#).unwrap();
+        assert_eq!(request.value, "v");
+        assert!(request.label.is_none());
+
+        let err = parse::<AddClipRequest>(b"{}").err().unwrap();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,204 @@
+//! Unix socket IPC
+//!
+//! Line-delimited JSON socket at `$XDG_RUNTIME_DIR/uti.sock` for scripts.
+//! Each request is one JSON object per line and gets one JSON line back:
+//!
+//! ```text
+//! {"cmd":"push","text":"hello"}   -> {"ok":true,"result":null}
+//! {"cmd":"history","limit":5}     -> {"ok":true,"result":[...]}
+//! {"cmd":"toggle"}                -> {"ok":true,"result":null}
+//! bad input                       -> {"ok":false,"error":"..."}
+//! ```
+
+use crate::state::AppState;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tracing::{error, info, warn};
+
+/// Socket file name inside the runtime directory
+const SOCKET_NAME: &str = "uti.sock";
+
+/// An IPC request
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum Request {
+    /// Adds text to the clipboard history
+    Push { text: String },
+    /// Returns clipboard history (newest first)
+    History {
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    /// Toggles the main window
+    Toggle,
+}
+
+/// An IPC response
+#[derive(Debug, Serialize)]
+struct Response {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl From<Result<Value, String>> for Response {
+    fn from(result: Result<Value, String>) -> Self {
+        match result {
+            Ok(value) => Self {
+                ok: true,
+                result: Some(value),
+                error: None,
+            },
+            Err(e) => Self {
+                ok: false,
+                result: None,
+                error: Some(e),
+            },
+        }
+    }
+}
+
+/// Gets the socket path
+///
+/// # Returns
+///
+/// `$XDG_RUNTIME_DIR/uti.sock`, or None if there is no runtime directory
+pub fn socket_path() -> Option<PathBuf> {
+    dirs::runtime_dir().map(|dir| dir.join(SOCKET_NAME))
+}
+
+/// Runs the IPC socket server
+///
+/// Runs until the app exits. Logs and returns if the socket can't be created.
+pub async fn serve(app: AppHandle) {
+    let Some(path) = socket_path() else {
+        warn!("XDG_RUNTIME_DIR not set, IPC socket disabled");
+        return;
+    };
+
+    // A leftover socket from a crashed instance refuses connections
+    if path.exists() {
+        if UnixStream::connect(&path).await.is_ok() {
+            warn!("IPC socket {} already in use", path.display());
+            return;
+        }
+        let _ = std::fs::remove_file(&path);
+    }
+
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            error!("Failed to bind IPC socket {}: {}", path.display(), e);
+            return;
+        }
+    };
+    if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+        error!("Failed to restrict IPC socket permissions: {}", e);
+    }
+    info!("IPC socket listening on {}", path.display());
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = handle_connection(&app, stream).await {
+                        error!("IPC connection error: {}", e);
+                    }
+                });
+            }
+            Err(e) => error!("Failed to accept IPC connection: {}", e),
+        }
+    }
+}
+
+/// Answers requests on a connection until the client closes it
+async fn handle_connection(app: &AppHandle, stream: UnixStream) -> std::io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let result = parse_request(&line).and_then(|request| handle_request(app, request));
+        let mut response = serde_json::to_string(&Response::from(result))?;
+        response.push('\n');
+        writer.write_all(response.as_bytes()).await?;
+    }
+
+    Ok(())
+}
+
+/// Parses a request line
+fn parse_request(line: &str) -> Result<Request, String> {
+    serde_json::from_str(line).map_err(|e| format!("Invalid request: {}", e))
+}
+
+/// Runs a request
+fn handle_request(app: &AppHandle, request: Request) -> Result<Value, String> {
+    match request {
+        Request::Push { text } => {
+            crate::capture::push(app, text).map_err(|e| e.to_string())?;
+            Ok(Value::Null)
+        }
+        Request::History { limit } => {
+            let state = app.state::<AppState>();
+            let store = state.clipboard.read();
+            let limit = limit.unwrap_or(store.items.len());
+            Ok(json!(&store.items[..limit.min(store.items.len())]))
+        }
+        Request::Toggle => {
+            // Same path as a double Ctrl press, so pin and positioning rules apply
+            app.emit("double-ctrl-pressed", ())
+                .map_err(|e| format!("Failed to toggle window: {}", e))?;
+            Ok(Value::Null)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        assert_eq!(
+            parse_request(r#"{"cmd":"push","text":"hi"}"#).unwrap(),
+            Request::Push {
+                text: "hi".to_string()
+            }
+        );
+        assert_eq!(
+            parse_request(r#"{"cmd":"history"}"#).unwrap(),
+            Request::History { limit: None }
+        );
+        assert_eq!(
+            parse_request(r#"{"cmd":"history","limit":3}"#).unwrap(),
+            Request::History { limit: Some(3) }
+        );
+        assert_eq!(
+            parse_request(r#"{"cmd":"toggle"}"#).unwrap(),
+            Request::Toggle
+        );
+        assert!(parse_request(r#"{"cmd":"explode"}"#).is_err());
+        assert!(parse_request("not json").is_err());
+    }
+
+    #[test]
+    fn test_response_serialization() {
+        let ok = serde_json::to_string(&Response::from(Ok(json!([1, 2])))).unwrap();
+        assert_eq!(ok, r#"{"ok":true,"result":[1,2]}"#);
+
+        let err = serde_json::to_string(&Response::from(Err("nope".to_string()))).unwrap();
+        assert_eq!(err, r#"{"ok":false,"error":"nope"}"#);
+    }
+}
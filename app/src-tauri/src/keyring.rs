@@ -0,0 +1,108 @@
+//! System keyring access
+//!
+//! Stores and looks up secrets (API keys, encryption keys) in the
+//! Secret Service (GNOME Keyring, KWallet) via the `secret-tool` CLI.
+
+use crate::clipboard::crypto::{Cipher, KEY_LEN};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use tracing::error;
+
+/// Service attribute used for all uti secrets
+const SERVICE: &str = "io.github.noppomario.uti";
+
+/// Key of the clipboard history encryption key (base64)
+const CLIPBOARD_KEY: &str = "clipboard.encryption";
+
+/// Looks up a secret by key
+///
+/// Returns None if the secret doesn't exist or the keyring is unavailable.
+///
+/// # Arguments
+///
+/// * `key` - Secret identifier (e.g., "translation.deepl")
+pub fn lookup_secret(key: &str) -> Option<String> {
+    let output = Command::new("secret-tool")
+        .args(["lookup", "service", SERVICE, "key", key])
+        .output()
+        .map_err(|e| error!("Failed to run secret-tool: {}", e))
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let secret = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!secret.is_empty()).then_some(secret)
+}
+
+/// Stores a secret, replacing any existing value for the key
+///
+/// The secret is passed via stdin so it never appears in the process list.
+///
+/// # Arguments
+///
+/// * `key` - Secret identifier (e.g., "translation.deepl")
+/// * `label` - Human-readable label shown in keyring managers
+/// * `secret` - The secret value
+pub fn store_secret(key: &str, label: &str, secret: &str) -> Result<(), String> {
+    let mut child = Command::new("secret-tool")
+        .args(["store", "--label", label, "service", SERVICE, "key", key])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run secret-tool: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(secret.as_bytes())
+            .map_err(|e| format!("Failed to write secret: {}", e))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for secret-tool: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("secret-tool store failed: {}", stderr.trim()));
+    }
+
+    Ok(())
+}
+
+/// Gets the clipboard history encryption key
+///
+/// # Arguments
+///
+/// * `create` - Generate and store a key if there's none. Only pass true
+///   when no history is encrypted yet, since a lookup also fails while
+///   the keyring is unavailable and the existing key must not be replaced.
+///
+/// # Returns
+///
+/// The cipher, or None if there's no key and `create` is false
+pub fn clipboard_cipher(create: bool) -> Result<Option<Cipher>, String> {
+    if let Some(encoded) = lookup_secret(CLIPBOARD_KEY) {
+        let key: [u8; KEY_LEN] = STANDARD
+            .decode(encoded)
+            .ok()
+            .and_then(|key| key.try_into().ok())
+            .ok_or("Invalid clipboard encryption key in the keyring")?;
+        return Ok(Some(Cipher::new(&key)));
+    }
+    if !create {
+        return Ok(None);
+    }
+
+    let key = Cipher::generate_key();
+    store_secret(
+        CLIPBOARD_KEY,
+        "uti clipboard history key",
+        &STANDARD.encode(key),
+    )?;
+    Ok(Some(Cipher::new(&key)))
+}
@@ -1,12 +1,45 @@
 //! Desktop file search functionality
 //!
 //! Searches .desktop files in standard XDG directories and parses them
-//! to provide application search results.
-
+//! to provide application search results. Names, generic names and
+//! keywords are fuzzy-matched (see [`super::fuzzy`]), and frequently
+//! launched apps get a frecency bonus (see [`super::frecency`]). Names,
+//! comments and keywords are read in the UI language when the entry is
+//! translated (e.g., `Name[ja]`).
+//!
+//! Exec lines keep their field codes (`%f`, `%U`, ...), which are
+//! substituted with the files to open when an app is launched, as
+//! described in the Desktop Entry Specification. Flatpak apps are run
+//! with `flatpak run` instead, since their Exec lines may point at
+//! wrappers that only exist inside the Flatpak installation.
+
+use super::frecency::FrecencyStore;
+use super::fuzzy;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Errors from an invalid Exec line
+#[derive(Debug, thiserror::Error)]
+pub enum ExecError {
+    #[error("Exec line {0:?} has an unterminated quote")]
+    UnterminatedQuote(String),
+
+    #[error("Exec line {0:?} has no program")]
+    NoProgram(String),
+}
+
+/// Where a desktop app is installed
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AppSource {
+    /// System package or local install
+    #[default]
+    Native,
+    /// Flatpak, run with `flatpak run`
+    Flatpak,
+}
 
 /// A desktop application entry parsed from .desktop file
 #[derive(Debug, Clone, Serialize)]
@@ -16,12 +49,254 @@ pub struct DesktopApp {
     pub id: String,
     /// Display name
     pub name: String,
-    /// Exec command (cleaned of field codes)
+    /// Exec command line as written, including field codes
     pub exec: String,
     /// Icon name or path
     pub icon: Option<String>,
     /// Short description
     pub comment: Option<String>,
+    /// Generic name (e.g., "Web Browser")
+    pub generic_name: Option<String>,
+    /// Search keywords
+    pub keywords: Vec<String>,
+    /// Whether the app runs in a terminal (`Terminal=true`)
+    pub terminal: bool,
+    /// Where the app is installed
+    pub source: AppSource,
+    /// Flatpak application id (e.g., "org.mozilla.firefox")
+    pub flatpak_id: Option<String>,
+    /// Relevance for the query including the frecency bonus, higher is
+    /// better
+    pub score: u32,
+    /// Path of the desktop file
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// Maximum number of search results
+const MAX_RESULTS: usize = 20;
+
+impl DesktopApp {
+    /// Scores how well the app matches a query
+    ///
+    /// The name counts most; generic name and keyword matches are scored
+    /// lower so that an app named like the query ranks first.
+    ///
+    /// # Returns
+    ///
+    /// None if neither the name, the generic name nor a keyword matches
+    pub fn match_score(&self, query: &str) -> Option<u32> {
+        let secondary = self
+            .generic_name
+            .iter()
+            .chain(&self.keywords)
+            .filter_map(|field| fuzzy::score(query, field))
+            .max()
+            .map(|score| score * 2 / 3);
+        fuzzy::score(query, &self.name).max(secondary)
+    }
+
+    /// Builds the command lines launching the app with the given files
+    ///
+    /// Field codes are substituted: `%F`/`%U` with all files, `%f`/`%u`
+    /// with one file (the app is launched once per file when given
+    /// several), `%i` with the icon, `%c` with the name and `%k` with the
+    /// desktop file path. `%f` and `%F` turn `file://` URIs into paths.
+    /// Flatpak apps get `flatpak run` with the files forwarded instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - Paths or URIs to open, may be empty
+    ///
+    /// # Returns
+    ///
+    /// One argument list per process to start, program first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Exec line has an unterminated quote or no
+    /// program
+    pub fn commands(&self, files: &[String]) -> Result<Vec<Vec<String>>, ExecError> {
+        let words = split_exec(&self.exec)?;
+        if let Some(app_id) = &self.flatpak_id {
+            return Ok(vec![flatpak_command(app_id, &words, files)]);
+        }
+        let single_file = words
+            .iter()
+            .any(|word| field_codes(word).any(|code| code == 'f' || code == 'u'));
+
+        let commands: Vec<Vec<String>> = if single_file && files.len() > 1 {
+            files
+                .iter()
+                .map(|file| self.expand(&words, std::slice::from_ref(file)))
+                .collect()
+        } else {
+            vec![self.expand(&words, files)]
+        };
+
+        if commands
+            .iter()
+            .any(|argv| argv.first().is_none_or(String::is_empty))
+        {
+            return Err(ExecError::NoProgram(self.exec.clone()));
+        }
+        Ok(commands)
+    }
+
+    /// Substitutes the field codes in an Exec line split into words
+    fn expand(&self, words: &[String], files: &[String]) -> Vec<String> {
+        let mut argv = Vec::new();
+        for word in words {
+            match word.as_str() {
+                "%F" => argv.extend(files.iter().map(|f| file_path(f))),
+                "%U" => argv.extend(files.iter().cloned()),
+                "%i" => {
+                    if let Some(icon) = &self.icon {
+                        argv.push("--icon".to_string());
+                        argv.push(icon.clone());
+                    }
+                }
+                _ => {
+                    let expanded = self.expand_word(word, files.first());
+                    // A code with nothing to substitute removes the argument
+                    if !expanded.is_empty() || !word.starts_with('%') {
+                        argv.push(expanded);
+                    }
+                }
+            }
+        }
+        argv
+    }
+
+    /// Substitutes the field codes within a single word
+    fn expand_word(&self, word: &str, file: Option<&String>) -> String {
+        let mut result = String::new();
+        let mut chars = word.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                result.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('%') => result.push('%'),
+                Some('f') => result.extend(file.map(|f| file_path(f))),
+                Some('u') => result.extend(file.cloned()),
+                Some('c') => result.push_str(&self.name),
+                Some('k') => result.push_str(&self.path.to_string_lossy()),
+                // Deprecated or unknown codes are removed
+                _ => {}
+            }
+        }
+        result
+    }
+}
+
+/// Splits an Exec line into words, handling double-quoted arguments
+fn split_exec(exec: &str) -> Result<Vec<String>, ExecError> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = exec.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => word.extend(chars.next()),
+                        Some(c) => word.push(c),
+                        None => return Err(ExecError::UnterminatedQuote(exec.to_string())),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Field code letters in a word, skipping escaped `%%`
+fn field_codes(word: &str) -> impl Iterator<Item = char> + '_ {
+    let mut chars = word.chars();
+    std::iter::from_fn(move || loop {
+        match chars.next()? {
+            '%' => match chars.next()? {
+                '%' => continue,
+                code => return Some(code),
+            },
+            _ => continue,
+        }
+    })
+}
+
+/// Builds the `flatpak run` command for a Flatpak app
+///
+/// Files are forwarded into the sandbox (`--file-forwarding`), as URIs
+/// if the Exec line takes URIs and as paths otherwise.
+fn flatpak_command(app_id: &str, words: &[String], files: &[String]) -> Vec<String> {
+    let mut argv = vec!["flatpak".to_string(), "run".to_string()];
+    if files.is_empty() {
+        argv.push(app_id.to_string());
+        return argv;
+    }
+    let uris = words
+        .iter()
+        .any(|word| field_codes(word).any(|code| code == 'u' || code == 'U'));
+    argv.push("--file-forwarding".to_string());
+    argv.push(app_id.to_string());
+    if uris {
+        argv.push("@@u".to_string());
+        argv.extend(files.iter().cloned());
+    } else {
+        argv.push("@@".to_string());
+        argv.extend(files.iter().map(|f| file_path(f)));
+    }
+    argv.push("@@".to_string());
+    argv
+}
+
+/// Turns a `file://` URI into a local path; other values are kept
+fn file_path(file: &str) -> String {
+    match file.strip_prefix("file://") {
+        Some(path) => percent_encoding::percent_decode_str(path)
+            .decode_utf8_lossy()
+            .into_owned(),
+        None => file.to_string(),
+    }
+}
+
+/// Finds a desktop entry by id
+///
+/// Looks in the same directories as [`search_desktop_files`], in priority
+/// order. Hidden and NoDisplay entries aren't found.
+///
+/// # Arguments
+///
+/// * `id` - Desktop file name without extension (e.g., "org.gnome.Nautilus")
+/// * `language` - Language for the name and comment (e.g., "ja")
+pub fn find_desktop_entry(id: &str, language: Option<&str>) -> Option<DesktopApp> {
+    if id.is_empty() || id.contains('/') {
+        return None;
+    }
+    get_desktop_dirs()
+        .iter()
+        .map(|dir| dir.join(format!("{}.desktop", id)))
+        .find(|path| path.is_file())
+        .and_then(|path| parse_desktop_file(&path, language))
 }
 
 /// Search for desktop applications matching the query
@@ -31,13 +306,25 @@ pub struct DesktopApp {
 /// - /usr/local/share/applications
 /// - ~/.local/share/applications
 ///
-/// Results are sorted by relevance (exact match > starts with > contains)
-pub fn search_desktop_files(query: &str) -> Vec<DesktopApp> {
-    let query_lower = query.to_lowercase();
-    if query_lower.is_empty() {
+/// Results are sorted by fuzzy match score plus frecency bonus, then by name
+///
+/// # Arguments
+///
+/// * `query` - Search text
+/// * `language` - Language for names, comments and keywords (e.g., "ja");
+///   untranslated values are used when an entry has no translation
+/// * `launches` - Launch history for the frecency bonus
+pub fn search_desktop_files(
+    query: &str,
+    language: Option<&str>,
+    launches: &FrecencyStore,
+) -> Vec<DesktopApp> {
+    let query = query.trim();
+    if query.is_empty() {
         return Vec::new();
     }
 
+    let now = chrono::Utc::now().timestamp();
     let mut apps: Vec<DesktopApp> = Vec::new();
     let mut seen_ids: HashMap<String, usize> = HashMap::new();
 
@@ -52,30 +339,16 @@ pub fn search_desktop_files(query: &str) -> Vec<DesktopApp> {
                     continue;
                 }
 
-                if let Some(app) = parse_desktop_file(&path) {
-                    // Skip hidden or no-display apps
-                    let name_lower = app.name.to_lowercase();
-
-                    // Check if name matches query
-                    if !name_lower.contains(&query_lower) {
-                        // Also check comment
-                        if let Some(ref comment) = app.comment {
-                            if !comment.to_lowercase().contains(&query_lower) {
-                                continue;
-                            }
-                        } else {
-                            continue;
-                        }
-                    }
+                if let Some(mut app) = parse_desktop_file(&path, language) {
+                    let Some(score) = app.match_score(query) else {
+                        continue;
+                    };
+                    app.score = score + launches.bonus(&app.id, now);
 
-                    // Deduplicate by id (keep first seen - higher priority dir)
+                    // Deduplicate by id (keep first seen - higher priority
+                    // dir - unless another entry matches better)
                     if let Some(&existing_idx) = seen_ids.get(&app.id) {
-                        // Update if this is a better match
-                        let existing = &apps[existing_idx];
-                        let existing_name_lower = existing.name.to_lowercase();
-
-                        // Prefer exact name match
-                        if name_lower == query_lower && existing_name_lower != query_lower {
+                        if app.score > apps[existing_idx].score {
                             apps[existing_idx] = app;
                         }
                     } else {
@@ -87,33 +360,18 @@ pub fn search_desktop_files(query: &str) -> Vec<DesktopApp> {
         }
     }
 
-    // Sort by relevance
-    apps.sort_by(|a, b| {
-        let a_lower = a.name.to_lowercase();
-        let b_lower = b.name.to_lowercase();
-
-        // Exact match first
-        let a_exact = a_lower == query_lower;
-        let b_exact = b_lower == query_lower;
-        if a_exact != b_exact {
-            return b_exact.cmp(&a_exact);
-        }
-
-        // Starts with query
-        let a_starts = a_lower.starts_with(&query_lower);
-        let b_starts = b_lower.starts_with(&query_lower);
-        if a_starts != b_starts {
-            return b_starts.cmp(&a_starts);
-        }
+    rank(&mut apps);
+    apps
+}
 
-        // Alphabetical
-        a_lower.cmp(&b_lower)
+/// Sorts apps by score (best first), then by name, and limits the results
+fn rank(apps: &mut Vec<DesktopApp>) {
+    apps.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
     });
-
-    // Limit results
-    apps.truncate(20);
-
-    apps
+    apps.truncate(MAX_RESULTS);
 }
 
 /// Get list of directories to search for .desktop files
@@ -142,14 +400,90 @@ fn get_desktop_dirs() -> Vec<PathBuf> {
     dirs
 }
 
+/// A localizable value: the untranslated one and the best translation
+#[derive(Default)]
+struct LocaleString {
+    default: Option<String>,
+    /// (match quality, value), see [`locale_rank`]
+    translated: Option<(u8, String)>,
+}
+
+impl LocaleString {
+    /// Stores the value of a `Key` or `Key[locale]` line
+    fn set(&mut self, locale: Option<&str>, language: Option<&str>, value: &str) {
+        let Some(locale) = locale else {
+            self.default = Some(value.to_string());
+            return;
+        };
+        let rank = locale_rank(locale, language);
+        if rank > 0
+            && self
+                .translated
+                .as_ref()
+                .is_none_or(|(best, _)| rank > *best)
+        {
+            self.translated = Some((rank, value.to_string()));
+        }
+    }
+
+    /// The translation if there is one, else the untranslated value
+    fn value(&self) -> Option<&str> {
+        self.translated
+            .as_ref()
+            .map(|(_, value)| value.as_str())
+            .or(self.default.as_deref())
+    }
+}
+
+/// How well a key's locale (e.g., "ja" or "pt_BR") fits the language
+///
+/// 2 for an exact match, 1 for the same language with a country, encoding
+/// or modifier ("ja_JP", "sr@latin"), 0 otherwise.
+fn locale_rank(locale: &str, language: Option<&str>) -> u8 {
+    let Some(language) = language.filter(|l| !l.is_empty()) else {
+        return 0;
+    };
+    if locale == language {
+        2
+    } else if locale.split(['_', '.', '@']).next() == Some(language) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Splits a key like `Name[ja]` into the key and its locale
+fn split_locale(key: &str) -> (&str, Option<&str>) {
+    match key.split_once('[') {
+        Some((base, rest)) => (base, rest.strip_suffix(']')),
+        None => (key, None),
+    }
+}
+
+/// Splits a `;`-separated list such as Keywords
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Parse a .desktop file and return DesktopApp if valid
-fn parse_desktop_file(path: &PathBuf) -> Option<DesktopApp> {
+///
+/// Localized keys in `language` are preferred over untranslated ones.
+fn parse_desktop_file(path: &Path, language: Option<&str>) -> Option<DesktopApp> {
     let content = fs::read_to_string(path).ok()?;
     let mut in_desktop_entry = false;
-    let mut name: Option<String> = None;
+    let mut name = LocaleString::default();
     let mut exec: Option<String> = None;
     let mut icon: Option<String> = None;
-    let mut comment: Option<String> = None;
+    let mut comment = LocaleString::default();
+    let mut generic_name = LocaleString::default();
+    let mut keywords = LocaleString::default();
+    let mut terminal = false;
+    let mut flatpak_id: Option<String> = None;
     let mut no_display = false;
     let mut hidden = false;
     let mut app_type: Option<String> = None;
@@ -172,11 +506,19 @@ fn parse_desktop_file(path: &PathBuf) -> Option<DesktopApp> {
             let key = key.trim();
             let value = value.trim();
 
+            let (key, locale) = split_locale(key);
+
             match key {
-                "Name" => name = Some(value.to_string()),
-                "Exec" => exec = Some(clean_exec(value)),
+                "Name" => name.set(locale, language, value),
+                "Comment" => comment.set(locale, language, value),
+                "GenericName" => generic_name.set(locale, language, value),
+                "Keywords" => keywords.set(locale, language, value),
+                // Other keys aren't translated
+                _ if locale.is_some() => {}
+                "Exec" => exec = Some(value.to_string()),
                 "Icon" => icon = Some(value.to_string()),
-                "Comment" => comment = Some(value.to_string()),
+                "Terminal" => terminal = value.eq_ignore_ascii_case("true"),
+                "X-Flatpak" => flatpak_id = Some(value.to_string()),
                 "NoDisplay" => no_display = value.eq_ignore_ascii_case("true"),
                 "Hidden" => hidden = value.eq_ignore_ascii_case("true"),
                 "Type" => app_type = Some(value.to_string()),
@@ -195,8 +537,13 @@ fn parse_desktop_file(path: &PathBuf) -> Option<DesktopApp> {
     }
 
     // Must have name and exec
-    let name = name?;
     let exec = exec?;
+    let mut keywords = split_list(keywords.value().unwrap_or_default());
+    // Keep the untranslated name searchable (e.g., "Files" for "ファイル")
+    if name.translated.is_some() {
+        keywords.extend(name.default.clone());
+    }
+    let name = name.value()?.to_string();
 
     // Get id from filename
     let id = path
@@ -204,50 +551,292 @@ fn parse_desktop_file(path: &PathBuf) -> Option<DesktopApp> {
         .and_then(|s| s.to_str())
         .map(|s| s.to_string())?;
 
+    // Entries exported by Flatpak name their app; fall back to the file
+    // name for ones in a Flatpak exports directory without the key
+    let flatpak_id = flatpak_id.or_else(|| {
+        path.to_string_lossy()
+            .contains("/flatpak/exports/")
+            .then(|| id.clone())
+    });
+
     Some(DesktopApp {
         id,
         name,
         exec,
         icon,
-        comment,
+        comment: comment.value().map(str::to_string),
+        generic_name: generic_name.value().map(str::to_string),
+        keywords,
+        terminal,
+        source: if flatpak_id.is_some() {
+            AppSource::Flatpak
+        } else {
+            AppSource::Native
+        },
+        flatpak_id,
+        score: 0,
+        path: path.to_path_buf(),
     })
 }
 
-/// Clean exec string by removing field codes (%f, %F, %u, %U, etc.)
-fn clean_exec(exec: &str) -> String {
-    let mut result = String::new();
-    let mut chars = exec.chars().peekable();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    while let Some(c) = chars.next() {
-        if c == '%' {
-            // Skip the field code character
-            chars.next();
-        } else {
-            result.push(c);
+    fn entry(exec: &str) -> DesktopApp {
+        DesktopApp {
+            exec: exec.to_string(),
+            icon: Some("code".to_string()),
+            path: PathBuf::from("/usr/share/applications/code.desktop"),
+            ..app("Code", None, &[])
         }
     }
 
-    result.trim().to_string()
-}
+    fn files(files: &[&str]) -> Vec<String> {
+        files.iter().map(|f| f.to_string()).collect()
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_commands_without_files() {
+        assert_eq!(entry("firefox %u").commands(&[]).unwrap(), [["firefox"]]);
+        assert_eq!(entry("nautilus %U").commands(&[]).unwrap(), [["nautilus"]]);
+        assert_eq!(
+            entry("code --new-window %F").commands(&[]).unwrap(),
+            [["code", "--new-window"]]
+        );
+        assert_eq!(
+            entry("gnome-terminal").commands(&[]).unwrap(),
+            [["gnome-terminal"]]
+        );
+    }
+
+    #[test]
+    fn test_commands_substitute_files() {
+        let paths = files(&["file:///home/me/My%20Notes.txt", "/tmp/b.txt"]);
+        assert_eq!(
+            entry("code %F").commands(&paths).unwrap(),
+            [["code", "/home/me/My Notes.txt", "/tmp/b.txt"]]
+        );
+        assert_eq!(
+            entry("app %U").commands(&paths).unwrap(),
+            [["app", "file:///home/me/My%20Notes.txt", "/tmp/b.txt"]]
+        );
+        // One process per file for single-file codes
+        assert_eq!(
+            entry("viewer --file=%f").commands(&paths).unwrap(),
+            [
+                ["viewer", "--file=/home/me/My Notes.txt"],
+                ["viewer", "--file=/tmp/b.txt"]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_commands_other_field_codes() {
+        assert_eq!(
+            entry("code %i --title %c %k 100%% %d")
+                .commands(&[])
+                .unwrap(),
+            [vec![
+                "code",
+                "--icon",
+                "code",
+                "--title",
+                "Code",
+                "/usr/share/applications/code.desktop",
+                "100%"
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_commands_quoting() {
+        assert_eq!(
+            entry(r#"sh -c "echo \"hi\" %f" x"#).commands(&[]).unwrap(),
+            [["sh", "-c", "echo \"hi\" ", "x"]]
+        );
+        assert!(matches!(
+            entry(r#"sh -c "unterminated"#).commands(&[]),
+            Err(ExecError::UnterminatedQuote(_))
+        ));
+        assert!(matches!(
+            entry("  ").commands(&[]),
+            Err(ExecError::NoProgram(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_terminal_app() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("htop.desktop");
+        fs::write(
+            &path,
+            "[Desktop Entry]\nType=Application\nName=Htop\nExec=htop\nTerminal=true\n",
+        )
+        .unwrap();
+        let app = parse_desktop_file(&path, None).unwrap();
+        assert_eq!(app.id, "htop");
+        assert!(app.terminal);
+    }
+
+    #[test]
+    fn test_parse_localized_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("org.gnome.Nautilus.desktop");
+        fs::write(
+            &path,
+            "[Desktop Entry]\nType=Application\nExec=nautilus --new-window %U\n\
+             Name=Files\nName[ja]=ファイル\nName[de]=Dateien\n\
+             Comment=Access and organize files\nComment[ja_JP]=ファイルにアクセス\n\
+             Keywords=folder;manager;\nKeywords[ja]=フォルダー;\n",
+        )
+        .unwrap();
+
+        let app = parse_desktop_file(&path, Some("ja")).unwrap();
+        assert_eq!(app.name, "ファイル");
+        assert_eq!(app.comment.as_deref(), Some("ファイルにアクセス"));
+        assert_eq!(app.keywords, ["フォルダー", "Files"]);
+        assert!(app.match_score("files").is_some());
+
+        let app = parse_desktop_file(&path, Some("en")).unwrap();
+        assert_eq!(app.name, "Files");
+        assert_eq!(app.keywords, ["folder", "manager"]);
+        assert_eq!(parse_desktop_file(&path, None).unwrap().name, "Files");
+    }
+
+    #[test]
+    fn test_locale_rank() {
+        assert_eq!(locale_rank("ja", Some("ja")), 2);
+        assert_eq!(locale_rank("ja_JP", Some("ja")), 1);
+        assert_eq!(locale_rank("sr@latin", Some("sr")), 1);
+        assert_eq!(locale_rank("jv", Some("ja")), 0);
+        assert_eq!(locale_rank("ja", None), 0);
+    }
 
     #[test]
-    fn test_clean_exec() {
-        assert_eq!(clean_exec("firefox %u"), "firefox");
-        assert_eq!(clean_exec("nautilus %U"), "nautilus");
-        assert_eq!(clean_exec("code --new-window %F"), "code --new-window");
-        assert_eq!(clean_exec("gnome-terminal"), "gnome-terminal");
+    fn test_parse_flatpak_app() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("org.mozilla.firefox.desktop");
+        fs::write(
+            &path,
+            "[Desktop Entry]\nType=Application\nName=Firefox\n\
+             Exec=/usr/bin/flatpak run --branch=stable --command=firefox \
+             --file-forwarding org.mozilla.firefox @@u %u @@\n\
+             X-Flatpak=org.mozilla.firefox\n",
+        )
+        .unwrap();
+        let app = parse_desktop_file(&path, None).unwrap();
+        assert_eq!(app.source, AppSource::Flatpak);
+        assert_eq!(app.flatpak_id.as_deref(), Some("org.mozilla.firefox"));
+        assert_eq!(
+            app.commands(&[]).unwrap(),
+            [["flatpak", "run", "org.mozilla.firefox"]]
+        );
+        assert_eq!(
+            app.commands(&files(&["https://example.com"])).unwrap(),
+            [[
+                "flatpak",
+                "run",
+                "--file-forwarding",
+                "org.mozilla.firefox",
+                "@@u",
+                "https://example.com",
+                "@@"
+            ]]
+        );
+    }
+
+    #[test]
+    fn test_flatpak_exports_dir_marks_app() {
+        let dir = tempfile::tempdir().unwrap();
+        let exports = dir.path().join("flatpak/exports/share/applications");
+        fs::create_dir_all(&exports).unwrap();
+        let path = exports.join("org.gnome.Maps.desktop");
+        fs::write(
+            &path,
+            "[Desktop Entry]\nType=Application\nName=Maps\nExec=gnome-maps %F\n",
+        )
+        .unwrap();
+        let app = parse_desktop_file(&path, None).unwrap();
+        assert_eq!(app.flatpak_id.as_deref(), Some("org.gnome.Maps"));
+        assert_eq!(
+            app.commands(&files(&["file:///tmp/a%20b.geojson"]))
+                .unwrap()[0][3..],
+            ["org.gnome.Maps", "@@", "/tmp/a b.geojson", "@@"]
+        );
     }
 
     #[test]
     fn test_search_empty_query() {
-        let results = search_desktop_files("");
+        let results = search_desktop_files("", None, &FrecencyStore::default());
         assert!(results.is_empty());
     }
 
+    fn app(name: &str, generic_name: Option<&str>, keywords: &[&str]) -> DesktopApp {
+        DesktopApp {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            exec: name.to_lowercase(),
+            icon: None,
+            comment: None,
+            generic_name: generic_name.map(str::to_string),
+            keywords: keywords.iter().map(|k| k.to_string()).collect(),
+            terminal: false,
+            source: AppSource::Native,
+            flatpak_id: None,
+            score: 0,
+            path: PathBuf::new(),
+        }
+    }
+
+    #[test]
+    fn test_match_score_fields() {
+        let firefox = app("Firefox", Some("Web Browser"), &["Internet", "WWW"]);
+        assert!(firefox.match_score("ffox").is_some());
+        assert!(firefox.match_score("browser").is_some());
+        assert!(firefox.match_score("internet").is_some());
+        assert_eq!(firefox.match_score("editor"), None);
+
+        // A name match outranks the same text in another field
+        let browser = app("Browser", None, &[]);
+        assert!(browser.match_score("browser") > firefox.match_score("browser"));
+    }
+
+    #[test]
+    fn test_rank() {
+        let mut apps: Vec<DesktopApp> = ["Tetris Remake", "GNOME Terminal", "Terminal"]
+            .into_iter()
+            .map(|name| {
+                let mut app = app(name, None, &[]);
+                app.score = app.match_score("term").unwrap();
+                app
+            })
+            .collect();
+        rank(&mut apps);
+        let names: Vec<&str> = apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["Terminal", "GNOME Terminal", "Tetris Remake"]);
+    }
+
+    #[test]
+    fn test_frecency_bonus_lifts_used_apps() {
+        let now = chrono::Utc::now().timestamp();
+        let mut launches = FrecencyStore::default();
+        for _ in 0..5 {
+            launches.record("tetris remake", now);
+        }
+
+        let mut apps: Vec<DesktopApp> = ["Tetris Remake", "Terminal"]
+            .into_iter()
+            .map(|name| {
+                let mut app = app(name, None, &[]);
+                app.score = app.match_score("term").unwrap() + launches.bonus(&app.id, now);
+                app
+            })
+            .collect();
+        rank(&mut apps);
+        assert_eq!(apps[0].name, "Tetris Remake");
+    }
+
     #[test]
     fn test_get_desktop_dirs() {
         let dirs = get_desktop_dirs();
@@ -0,0 +1,243 @@
+//! File search
+//!
+//! Finds files by name with whichever search tool is installed: `fd`
+//! (`fdfind` on Debian/Ubuntu) searching the home directory, or the
+//! system-wide `plocate`/`locate` index. Output is read as the tool
+//! produces it and the tool is stopped once enough results are in.
+
+use super::find_program;
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tracing::{debug, warn};
+
+/// Maximum number of results
+pub const MAX_RESULTS: usize = 50;
+
+/// How long a search may take before the results so far are returned
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Errors from file search
+#[derive(Debug, thiserror::Error)]
+pub enum FileSearchError {
+    #[error("no file search tool found; install fd or plocate")]
+    NotInstalled,
+
+    #[error("failed to run {tool}: {source}")]
+    Run {
+        tool: &'static str,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// An installed file search tool
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTool {
+    /// fd, searching the home directory
+    Fd(PathBuf),
+    /// plocate, searching its system-wide index
+    Plocate(PathBuf),
+    /// locate (mlocate), searching its system-wide index
+    Locate(PathBuf),
+}
+
+impl SearchTool {
+    /// Finds the preferred installed tool: fd, then plocate, then locate
+    pub fn detect() -> Option<Self> {
+        Self::detect_in(&std::env::var_os("PATH")?)
+    }
+
+    /// Finds the preferred tool in the given `PATH` value
+    fn detect_in(path: &OsStr) -> Option<Self> {
+        find_program("fd", path)
+            .or_else(|| find_program("fdfind", path))
+            .map(Self::Fd)
+            .or_else(|| find_program("plocate", path).map(Self::Plocate))
+            .or_else(|| find_program("locate", path).map(Self::Locate))
+    }
+
+    /// Tool name for messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Fd(_) => "fd",
+            Self::Plocate(_) => "plocate",
+            Self::Locate(_) => "locate",
+        }
+    }
+
+    /// Builds the command searching for file names containing `query`
+    fn command(&self, query: &str) -> Command {
+        let limit = MAX_RESULTS.to_string();
+        match self {
+            Self::Fd(program) => {
+                let mut cmd = Command::new(program);
+                cmd.args(["--absolute-path", "--color", "never", "--fixed-strings"])
+                    .args(["--max-results", &limit, "--", query]);
+                if let Some(home) = dirs::home_dir() {
+                    cmd.arg(home);
+                }
+                cmd
+            }
+            Self::Plocate(program) | Self::Locate(program) => {
+                let mut cmd = Command::new(program);
+                cmd.args([
+                    "--ignore-case",
+                    "--basename",
+                    "--limit",
+                    &limit,
+                    "--",
+                    query,
+                ]);
+                cmd
+            }
+        }
+    }
+}
+
+/// A file found by [`search_files`]
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileMatch {
+    /// Absolute path, to pass to an opener such as `xdg-open`
+    pub path: String,
+    /// File name
+    pub name: String,
+    /// Whether the path is a directory
+    pub is_dir: bool,
+}
+
+impl FileMatch {
+    /// Builds a match from a line of search output
+    fn from_line(line: &str) -> Option<Self> {
+        let path = line.trim_end_matches(['\r', '\n']);
+        if path.is_empty() {
+            return None;
+        }
+        let trimmed = path.trim_end_matches('/');
+        let name = Path::new(trimmed)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| trimmed.to_string());
+        Some(Self {
+            path: trimmed.to_string(),
+            name,
+            is_dir: Path::new(trimmed).is_dir(),
+        })
+    }
+}
+
+/// Searches for files whose names contain `query`
+///
+/// Returns at most [`MAX_RESULTS`] matches in the order the tool finds
+/// them. A search running longer than a few seconds is stopped and the
+/// matches so far are returned.
+///
+/// # Errors
+///
+/// Returns an error if no search tool is installed or it can't be started
+pub async fn search_files(query: &str) -> Result<Vec<FileMatch>, FileSearchError> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tool = SearchTool::detect().ok_or(FileSearchError::NotInstalled)?;
+    debug!("Searching files for {:?} with {}", query, tool.name());
+
+    let mut child = tool
+        .command(query)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|source| FileSearchError::Run {
+            tool: tool.name(),
+            source,
+        })?;
+
+    let mut matches = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let mut lines = BufReader::new(stdout).lines();
+        let read = async {
+            while matches.len() < MAX_RESULTS {
+                match lines.next_line().await {
+                    Ok(Some(line)) => matches.extend(FileMatch::from_line(&line)),
+                    Ok(None) => break,
+                    Err(e) => {
+                        // e.g., a file name that isn't UTF-8
+                        warn!("Stopped reading {} output: {}", tool.name(), e);
+                        break;
+                    }
+                }
+            }
+        };
+        if tokio::time::timeout(SEARCH_TIMEOUT, read).await.is_err() {
+            warn!("{} search for {:?} timed out", tool.name(), query);
+        }
+    }
+
+    // Stop the tool if it's still searching
+    let _ = child.kill().await;
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn install(dir: &Path, name: &str) {
+        let path = dir.join(name);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_detect_prefers_fd() {
+        let bin = tempfile::tempdir().unwrap();
+        let path = bin.path().as_os_str();
+        assert_eq!(SearchTool::detect_in(path), None);
+
+        install(bin.path(), "locate");
+        assert_eq!(
+            SearchTool::detect_in(path),
+            Some(SearchTool::Locate(bin.path().join("locate")))
+        );
+
+        install(bin.path(), "plocate");
+        assert_eq!(SearchTool::detect_in(path).unwrap().name(), "plocate");
+
+        install(bin.path(), "fdfind");
+        assert_eq!(
+            SearchTool::detect_in(path),
+            Some(SearchTool::Fd(bin.path().join("fdfind")))
+        );
+    }
+
+    #[test]
+    fn test_non_executable_files_are_skipped() {
+        let bin = tempfile::tempdir().unwrap();
+        std::fs::write(bin.path().join("fd"), "").unwrap();
+        assert_eq!(SearchTool::detect_in(bin.path().as_os_str()), None);
+    }
+
+    #[test]
+    fn test_file_match_from_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let line = format!("{}/", dir.path().display());
+        let found = FileMatch::from_line(&line).unwrap();
+        assert_eq!(found.path, dir.path().to_string_lossy());
+        assert!(found.is_dir);
+
+        let file = FileMatch::from_line("/nonexistent/report.pdf").unwrap();
+        assert_eq!(file.name, "report.pdf");
+        assert!(!file.is_dir);
+
+        assert_eq!(FileMatch::from_line(""), None);
+    }
+}
@@ -0,0 +1,199 @@
+//! Launch history for frecency ranking
+//!
+//! Records when launcher entries and desktop apps are launched, stored in
+//! `~/.config/uti/launches.json`. Frecency combines how often and how
+//! recently something was launched: each of the latest launches counts
+//! more the more recent it is, so an app opened daily outranks one opened
+//! many times months ago. The emoji picker keeps its usage the same way.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+
+/// Launches remembered per entry
+const MAX_LAUNCHES: usize = 10;
+
+/// Launches older than this (in seconds) are forgotten (90 days)
+const MAX_AGE: i64 = 90 * 24 * 60 * 60;
+
+/// Launch weights by age: (maximum age in seconds, weight)
+const WEIGHTS: [(i64, u32); 4] = [
+    (24 * 60 * 60, 100),
+    (7 * 24 * 60 * 60, 70),
+    (30 * 24 * 60 * 60, 50),
+    (MAX_AGE, 30),
+];
+
+/// Divisor turning a frecency score into a search ranking bonus
+///
+/// Ten launches within a day add 100, about as much as a good fuzzy match.
+const BONUS_DIVISOR: u32 = 10;
+
+/// Launch timestamps (Unix seconds) by entry id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrecencyStore {
+    /// Latest launches per id, oldest first
+    #[serde(default)]
+    pub launches: HashMap<String, Vec<i64>>,
+}
+
+impl FrecencyStore {
+    /// Records a launch
+    ///
+    /// Keeps only the latest launches per id and drops ids whose launches
+    /// have all expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Launcher entry or desktop app id
+    /// * `now` - Launch time in Unix seconds
+    pub fn record(&mut self, id: &str, now: i64) {
+        let launches = self.launches.entry(id.to_string()).or_default();
+        launches.push(now);
+        if launches.len() > MAX_LAUNCHES {
+            launches.drain(..launches.len() - MAX_LAUNCHES);
+        }
+        self.launches
+            .retain(|_, launches| launches.iter().any(|&t| now - t < MAX_AGE));
+    }
+
+    /// Frecency score of an id, 0 if it was never launched
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time in Unix seconds
+    pub fn score(&self, id: &str, now: i64) -> u32 {
+        self.launches
+            .get(id)
+            .map(|launches| launches.iter().map(|&t| weight(now - t)).sum())
+            .unwrap_or(0)
+    }
+
+    /// Search ranking bonus for an id, to add to its fuzzy match score
+    pub fn bonus(&self, id: &str, now: i64) -> u32 {
+        self.score(id, now) / BONUS_DIVISOR
+    }
+}
+
+/// Weight of a launch `age` seconds ago
+fn weight(age: i64) -> u32 {
+    WEIGHTS
+        .iter()
+        .find(|&&(max_age, _)| age < max_age)
+        .map(|&(_, weight)| weight)
+        .unwrap_or(0)
+}
+
+/// Get the path to the launch history file
+///
+/// Returns `~/.config/uti/launches.json`
+pub fn get_frecency_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("uti")
+        .join("launches.json")
+}
+
+/// Load the launch history
+///
+/// Returns an empty history if the file doesn't exist or is invalid.
+pub fn load_frecency() -> FrecencyStore {
+    load_frecency_from(&get_frecency_path())
+}
+
+/// Save the launch history
+pub fn save_frecency(store: &FrecencyStore) -> std::io::Result<()> {
+    save_frecency_to(store, &get_frecency_path())
+}
+
+/// Load a usage history from a file
+///
+/// Returns an empty history if the file doesn't exist or is invalid.
+pub fn load_frecency_from(path: &Path) -> FrecencyStore {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => match serde_json::from_str(&contents) {
+            Ok(store) => {
+                info!("Loaded usage history from: {:?}", path);
+                store
+            }
+            Err(e) => {
+                error!("Failed to parse usage history {:?}: {}", path, e);
+                FrecencyStore::default()
+            }
+        },
+        Err(_) => FrecencyStore::default(),
+    }
+}
+
+/// Save a usage history to a file
+pub fn save_frecency_to(store: &FrecencyStore, path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(store)?;
+    std::fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 24 * 60 * 60;
+    const NOW: i64 = 1_700_000_000;
+
+    #[test]
+    fn test_score_weights_recent_launches() {
+        let mut store = FrecencyStore::default();
+        store.record("daily", NOW - DAY / 2);
+        store.record("daily", NOW - 3 * DAY / 2);
+        store.record("old", NOW - 60 * DAY);
+        store.record("old", NOW - 50 * DAY);
+        store.record("old", NOW - 40 * DAY);
+
+        assert_eq!(store.score("daily", NOW), 100 + 70);
+        assert_eq!(store.score("old", NOW), 3 * 30);
+        assert_eq!(store.score("never", NOW), 0);
+        assert_eq!(store.bonus("daily", NOW), 17);
+    }
+
+    #[test]
+    fn test_record_keeps_latest_launches() {
+        let mut store = FrecencyStore::default();
+        for i in 0..15 {
+            store.record("app", NOW + i);
+        }
+        let launches = &store.launches["app"];
+        assert_eq!(launches.len(), MAX_LAUNCHES);
+        assert_eq!(launches.first(), Some(&(NOW + 5)));
+    }
+
+    #[test]
+    fn test_record_forgets_expired_apps() {
+        let mut store = FrecencyStore::default();
+        store.record("gone", NOW - 100 * DAY);
+        store.record("recent", NOW);
+        assert!(!store.launches.contains_key("gone"));
+        assert!(store.launches.contains_key("recent"));
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("usage.json");
+        let mut store = FrecencyStore::default();
+        store.record("app", NOW);
+        save_frecency_to(&store, &path).unwrap();
+
+        let loaded = load_frecency_from(&path);
+        assert_eq!(loaded.launches["app"], vec![NOW]);
+        assert!(load_frecency_from(&dir.path().join("missing.json"))
+            .launches
+            .is_empty());
+    }
+
+    #[test]
+    fn test_get_frecency_path() {
+        assert!(get_frecency_path().ends_with("uti/launches.json"));
+    }
+}
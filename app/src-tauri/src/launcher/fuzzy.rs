@@ -0,0 +1,170 @@
+//! Fuzzy matching for launcher search
+//!
+//! The query's characters must appear in the candidate in order, but not
+//! necessarily next to each other, so "ffox" matches "Firefox". Matches are
+//! scored so that consecutive characters, word starts and prefixes rank
+//! higher than scattered ones.
+
+/// Score for each matched character
+const SCORE_MATCH: i32 = 16;
+
+/// Bonus for a character directly following the previous match
+const BONUS_CONSECUTIVE: i32 = 4;
+
+/// Bonus for a match at the start of a word ("Studio" in "Visual Studio")
+const BONUS_WORD_START: i32 = 10;
+
+/// Bonus for a match at the very start of the candidate
+const BONUS_FIRST_CHAR: i32 = 8;
+
+/// Penalty for skipping candidate characters between matches
+const PENALTY_GAP_START: i32 = 5;
+
+/// Additional penalty for each further skipped character
+const PENALTY_GAP_EXTENSION: i32 = 1;
+
+/// Penalty for each candidate character after the last match
+const PENALTY_TRAILING: i32 = 1;
+
+/// Scores how well a query fuzzy-matches a candidate (case-insensitive)
+///
+/// # Returns
+///
+/// None if the query's characters don't all appear in order, otherwise
+/// a score where higher is better. An empty query matches with score 0.
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::launcher::fuzzy::score;
+///
+/// assert!(score("ffox", "Firefox").is_some());
+/// assert!(score("fire", "Firefox") > score("ffox", "Firefox"));
+/// assert_eq!(score("xof", "Firefox"), None);
+/// ```
+pub fn score(query: &str, candidate: &str) -> Option<u32> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let original: Vec<char> = candidate.chars().collect();
+    let lower: Vec<char> = original
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    if lower.len() < query.len() {
+        return None;
+    }
+
+    let bonuses: Vec<i32> = (0..original.len())
+        .map(|j| position_bonus(&original, j))
+        .collect();
+
+    // best[j]: best score for the query so far with its last character
+    // matched at candidate position j
+    let mut best: Vec<Option<i32>> = lower
+        .iter()
+        .enumerate()
+        .map(|(j, &c)| (c == query[0]).then(|| SCORE_MATCH + bonuses[j]))
+        .collect();
+
+    for &q in &query[1..] {
+        let mut next = vec![None; lower.len()];
+        for j in 1..lower.len() {
+            if lower[j] != q {
+                continue;
+            }
+            let consecutive = best[j - 1].map(|s| s + BONUS_CONSECUTIVE.max(bonuses[j]));
+            let gapped = jump_from(&best, j).map(|s| s + bonuses[j]);
+            next[j] = match (consecutive, gapped) {
+                (Some(a), Some(b)) => Some(a.max(b)),
+                (a, b) => a.or(b),
+            }
+            .map(|s| s + SCORE_MATCH);
+        }
+        best = next;
+    }
+
+    // Unmatched trailing characters cost a little, so "Files" ranks above
+    // "Files and Folders"
+    best.into_iter()
+        .enumerate()
+        .filter_map(|(j, s)| s.map(|s| s - PENALTY_TRAILING * (lower.len() - j - 1) as i32))
+        .max()
+        .map(|s| s.max(1) as u32)
+}
+
+/// Best score for reaching position `j` from a match before `j - 1`, with
+/// the skipped characters' penalty applied
+fn jump_from(best: &[Option<i32>], j: usize) -> Option<i32> {
+    best[..j.saturating_sub(1)]
+        .iter()
+        .enumerate()
+        .filter_map(|(k, s)| s.map(|s| s - gap_penalty(j - k - 1)))
+        .max()
+}
+
+fn gap_penalty(skipped: usize) -> i32 {
+    PENALTY_GAP_START + PENALTY_GAP_EXTENSION * (skipped as i32 - 1)
+}
+
+/// Bonus for matching the character at `j`, by where it is in its word
+fn position_bonus(chars: &[char], j: usize) -> i32 {
+    if j == 0 {
+        return BONUS_WORD_START + BONUS_FIRST_CHAR;
+    }
+    let (prev, current) = (chars[j - 1], chars[j]);
+    let word_start = !prev.is_alphanumeric() && current.is_alphanumeric()
+        || prev.is_lowercase() && current.is_uppercase()
+        || !prev.is_ascii_digit() && current.is_ascii_digit();
+    if word_start {
+        BONUS_WORD_START
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsequence_matching() {
+        assert!(score("ffox", "Firefox").is_some());
+        assert!(score("FIREFOX", "firefox").is_some());
+        assert!(score("vsc", "Visual Studio Code").is_some());
+        assert_eq!(score("foxfire", "Firefox"), None);
+        assert_eq!(score("firefoxes", "Firefox"), None);
+        assert_eq!(score("", "Firefox"), Some(0));
+    }
+
+    #[test]
+    fn test_ranking() {
+        // Exact > prefix or whole word > scattered
+        let exact = score("files", "Files");
+        let prefix = score("files", "Files and Folders");
+        let word = score("files", "GNOME Files");
+        let scattered = score("files", "Find Illegal Letters");
+        assert!(exact > prefix, "{:?} {:?}", exact, prefix);
+        assert!(exact > word, "{:?} {:?}", exact, word);
+        assert!(word > scattered, "{:?} {:?}", word, scattered);
+        assert!(prefix > scattered, "{:?} {:?}", prefix, scattered);
+
+        // Word initials beat characters buried in a word
+        assert!(score("vsc", "Visual Studio Code") > score("vsc", "Avscan"));
+        // Consecutive characters beat the same characters spread out
+        assert!(score("term", "Terminal") > score("term", "Tetris Remake"));
+    }
+
+    #[test]
+    fn test_best_alignment_is_used() {
+        // The first "code" is inside a word; the second starts one
+        assert!(score("code", "Xcode Code") > score("code", "Xcode"));
+        assert!(score("ode", "VS Code").is_some());
+    }
+
+    #[test]
+    fn test_camel_case_word_starts() {
+        assert!(score("lo", "LibreOffice") > score("lo", "Calo"));
+    }
+}
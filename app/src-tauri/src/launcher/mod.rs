@@ -1,16 +1,69 @@
 //! Launcher module
 //!
-//! Provides launcher configuration and recent files functionality.
+//! Provides launcher configuration, desktop app and file search, launch
+//! history, systemd user services and recent files functionality.
 
 pub mod desktop;
+pub mod file_search;
+pub mod frecency;
+pub mod fuzzy;
 pub mod recent_files;
+pub mod script_filter;
 mod store;
+pub mod systemd;
+pub mod terminal;
+pub mod web_search;
 
-pub use desktop::{search_desktop_files, DesktopApp};
-pub use recent_files::RecentFile;
+pub use desktop::{find_desktop_entry, search_desktop_files, DesktopApp, ExecError};
+pub use file_search::{search_files, FileMatch, FileSearchError};
+pub use frecency::{
+    load_frecency, load_frecency_from, save_frecency, save_frecency_to, FrecencyStore,
+};
+pub use recent_files::{RecentFile, XbelError};
+pub use script_filter::{ScriptAction, ScriptProvider, ScriptResult};
 pub use store::{get_launcher_config_path, load_launcher_config};
+pub use systemd::{SystemdError, SystemdUnit, UnitAction};
+pub use web_search::SearchProvider;
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// Errors from launching apps, searches and services
+#[derive(Debug, thiserror::Error)]
+pub enum LauncherError {
+    #[error("Desktop entry {0} not found")]
+    EntryNotFound(String),
+
+    #[error(transparent)]
+    Exec(#[from] ExecError),
+
+    #[error("No terminal emulator found to run {0}; set \"terminal\" in launcher.json")]
+    NoTerminal(String),
+
+    #[error("No search provider for keyword {0}")]
+    NoSearchProvider(String),
+
+    #[error("Failed to open {target}: {source}")]
+    Open {
+        target: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to save launch history: {0}")]
+    Frecency(#[source] std::io::Error),
+
+    #[error(transparent)]
+    FileSearch(#[from] FileSearchError),
+
+    #[error(transparent)]
+    Systemd(#[from] SystemdError),
+
+    #[error(transparent)]
+    RecentFiles(#[from] XbelError),
+}
 
 /// History source for retrieving recent files
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,9 +79,64 @@ pub enum HistorySource {
         /// Custom path to XBEL file (default: ~/.local/share/recently-used.xbel)
         #[serde(default)]
         path: Option<String>,
+        /// Show files that no longer exist, marked as missing
+        #[serde(rename = "includeMissing", default)]
+        include_missing: bool,
     },
     /// Use VSCode history from specified path
-    Vscode { path: String },
+    Vscode {
+        path: String,
+        /// Show files that no longer exist, marked as missing
+        #[serde(rename = "includeMissing", default)]
+        include_missing: bool,
+    },
+    /// Use Sublime Text's session file from specified path
+    Sublime {
+        path: String,
+        /// Show files that no longer exist, marked as missing
+        #[serde(rename = "includeMissing", default)]
+        include_missing: bool,
+    },
+    /// Run a custom query against any app's SQLite recents database
+    Sqlite {
+        path: String,
+        /// SELECT statement returning recent files, most recent first
+        query: String,
+        /// Column holding file:// URIs or paths (default: the first column)
+        #[serde(rename = "uriColumn", default)]
+        uri_column: Option<String>,
+        /// Show files that no longer exist, marked as missing
+        #[serde(rename = "includeMissing", default)]
+        include_missing: bool,
+    },
+    /// Use frecent directories from zoxide
+    Zoxide {
+        /// Keywords to filter directories by, as for `zoxide query` (optional)
+        #[serde(default)]
+        query: Option<String>,
+    },
+}
+
+/// Built-in action run by a launcher entry instead of an external command
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum LauncherAction {
+    /// Capture a screenshot via the XDG portal
+    Screenshot {
+        /// Capture mode: 'region', 'window' or 'full'
+        #[serde(default = "default_screenshot_mode")]
+        mode: String,
+    },
+    /// Recognize text in a screen region and copy it
+    Ocr {
+        /// Tesseract language(s), e.g. 'eng+jpn' (default: 'eng')
+        #[serde(default)]
+        language: Option<String>,
+    },
+}
+
+fn default_screenshot_mode() -> String {
+    "region".to_string()
 }
 
 /// A command entry in the launcher configuration
@@ -39,7 +147,8 @@ pub struct LauncherItem {
     pub id: String,
     /// Display name
     pub name: String,
-    /// Command to execute
+    /// Command to execute (unused when `action` is set)
+    #[serde(default)]
     pub command: String,
     /// Additional arguments (optional)
     #[serde(default)]
@@ -48,6 +157,21 @@ pub struct LauncherItem {
     /// If not specified, will try to auto-detect from recently-used.xbel
     #[serde(default)]
     pub history_source: Option<HistorySource>,
+    /// Built-in action to run instead of `command` (optional)
+    #[serde(default)]
+    pub action: Option<LauncherAction>,
+    /// Run `command` inside a terminal emulator (optional)
+    #[serde(default)]
+    pub terminal: bool,
+    /// Extra environment variables for `command` (optional)
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Working directory for `command`, `~/` expanded (optional)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Number of recent files in the jump list (optional, default 10)
+    #[serde(default)]
+    pub max_items: Option<usize>,
 }
 
 /// Launcher configuration file structure
@@ -56,6 +180,46 @@ pub struct LauncherConfig {
     /// List of launcher commands
     #[serde(default)]
     pub commands: Vec<LauncherItem>,
+    /// Web search shortcuts
+    #[serde(default, rename = "searchProviders")]
+    pub search_providers: Vec<SearchProvider>,
+    /// Scripts providing extra launcher results
+    #[serde(default, rename = "scriptProviders")]
+    pub script_providers: Vec<ScriptProvider>,
+    /// Terminal emulator command for terminal apps (optional)
+    /// If not specified, `$TERMINAL` or a known installed terminal is used
+    #[serde(default)]
+    pub terminal: Option<String>,
+}
+
+impl LauncherConfig {
+    /// Drops invalid entries (see [`web_search::validate_providers`] and
+    /// [`script_filter::validate_providers`])
+    pub fn validate(&mut self) {
+        web_search::validate_providers(&mut self.search_providers);
+        script_filter::validate_providers(&mut self.script_providers);
+    }
+
+    /// Finds the search provider for a keyword (case-insensitive)
+    pub fn search_provider(&self, keyword: &str) -> Option<&SearchProvider> {
+        self.search_providers
+            .iter()
+            .find(|p| p.keyword.eq_ignore_ascii_case(keyword))
+    }
+}
+
+/// Finds the first file named `name` in a `PATH` value that's executable
+pub(crate) fn find_program(name: &str, path: &OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
 }
 
 #[cfg(test)]
@@ -76,6 +240,44 @@ mod tests {
         assert_eq!(item.command, "nautilus");
         assert!(item.args.is_empty());
         assert!(item.history_source.is_none());
+        assert!(item.action.is_none());
+        assert!(!item.terminal);
+        assert!(item.env.is_empty());
+        assert!(item.cwd.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_launcher_item_with_env_and_cwd() {
+        let json = r#"{
+            "id": "code-project",
+            "name": "Code (project)",
+            "command": "code",
+            "args": ["."],
+            "env": { "PATH": "/opt/node/bin:/usr/bin" },
+            "cwd": "~/src/project"
+        }"#;
+
+        let item: LauncherItem = serde_json::from_str(json).unwrap();
+        assert_eq!(item.env["PATH"], "/opt/node/bin:/usr/bin");
+        assert_eq!(item.cwd.as_deref(), Some("~/src/project"));
+    }
+
+    #[test]
+    fn test_deserialize_launcher_item_with_action() {
+        let json = r#"{
+            "id": "screenshot",
+            "name": "Screenshot",
+            "action": { "type": "screenshot" }
+        }"#;
+
+        let item: LauncherItem = serde_json::from_str(json).unwrap();
+        assert!(item.command.is_empty());
+        assert_eq!(
+            item.action,
+            Some(LauncherAction::Screenshot {
+                mode: "region".to_string()
+            })
+        );
     }
 
     #[test]
@@ -86,7 +288,8 @@ mod tests {
             "command": "code",
             "historySource": {
                 "type": "vscode",
-                "path": "~/.config/Code/User/History"
+                "path": "~/.config/Code/User/History",
+                "includeMissing": true
             }
         }"#;
 
@@ -94,7 +297,10 @@ mod tests {
         assert_eq!(item.id, "vscode");
         assert!(matches!(
             item.history_source,
-            Some(HistorySource::Vscode { .. })
+            Some(HistorySource::Vscode {
+                include_missing: true,
+                ..
+            })
         ));
     }
 
@@ -113,7 +319,67 @@ mod tests {
         let item: LauncherItem = serde_json::from_str(json).unwrap();
         assert!(matches!(
             item.history_source,
-            Some(HistorySource::RecentlyUsed { app_name: Some(ref name), path: _ }) if name == "org.gnome.Nautilus"
+            Some(HistorySource::RecentlyUsed { app_name: Some(ref name), .. }) if name == "org.gnome.Nautilus"
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_launcher_item_with_sublime() {
+        let json = r#"{
+            "id": "sublime",
+            "name": "Sublime Text",
+            "command": "subl",
+            "historySource": {
+                "type": "sublime",
+                "path": "~/.config/sublime-text/Local/Session.sublime_session"
+            }
+        }"#;
+
+        let item: LauncherItem = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            item.history_source,
+            Some(HistorySource::Sublime { ref path, include_missing: false })
+                if path.ends_with("Session.sublime_session")
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_launcher_item_with_sqlite() {
+        let json = r#"{
+            "id": "dbeaver",
+            "name": "DBeaver",
+            "command": "dbeaver",
+            "maxItems": 30,
+            "historySource": {
+                "type": "sqlite",
+                "path": "~/.local/share/app/recent.db",
+                "query": "SELECT uri FROM recent ORDER BY time DESC",
+                "uriColumn": "uri"
+            }
+        }"#;
+
+        let item: LauncherItem = serde_json::from_str(json).unwrap();
+        assert_eq!(item.max_items, Some(30));
+        assert!(matches!(
+            item.history_source,
+            Some(HistorySource::Sqlite { ref uri_column, include_missing: false, .. })
+                if uri_column.as_deref() == Some("uri")
+        ));
+    }
+
+    #[test]
+    fn test_deserialize_launcher_item_with_zoxide() {
+        let json = r#"{
+            "id": "files",
+            "name": "Files",
+            "command": "nautilus",
+            "historySource": { "type": "zoxide" }
+        }"#;
+
+        let item: LauncherItem = serde_json::from_str(json).unwrap();
+        assert!(matches!(
+            item.history_source,
+            Some(HistorySource::Zoxide { query: None })
         ));
     }
 
@@ -138,6 +404,7 @@ mod tests {
         assert_eq!(config.commands.len(), 2);
         assert_eq!(config.commands[0].id, "nautilus");
         assert_eq!(config.commands[1].id, "gnome-text-editor");
+        assert!(config.terminal.is_none());
     }
 
     #[test]
@@ -146,6 +413,23 @@ mod tests {
 
         let config: LauncherConfig = serde_json::from_str(json).unwrap();
         assert!(config.commands.is_empty());
+        assert!(config.search_providers.is_empty());
+    }
+
+    #[test]
+    fn test_search_providers() {
+        let json = r#"{
+            "searchProviders": [
+                { "name": "GitHub", "keyword": "gh", "url": "https://github.com/search?q={query}" },
+                { "name": "Broken", "keyword": "x", "url": "https://example.com/" }
+            ]
+        }"#;
+
+        let mut config: LauncherConfig = serde_json::from_str(json).unwrap();
+        config.validate();
+        assert_eq!(config.search_providers.len(), 1);
+        assert_eq!(config.search_provider("GH").unwrap().name, "GitHub");
+        assert!(config.search_provider("x").is_none());
     }
 
     #[test]
@@ -158,7 +442,13 @@ mod tests {
             history_source: Some(HistorySource::RecentlyUsed {
                 app_name: Some("org.test.App".to_string()),
                 path: None,
+                include_missing: false,
             }),
+            action: None,
+            terminal: false,
+            env: HashMap::new(),
+            cwd: None,
+            max_items: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -181,7 +471,7 @@ mod tests {
 
         let item: LauncherItem = serde_json::from_str(json).unwrap();
         match item.history_source {
-            Some(HistorySource::RecentlyUsed { app_name, path }) => {
+            Some(HistorySource::RecentlyUsed { app_name, path, .. }) => {
                 assert_eq!(app_name, Some("gnome-text-editor".to_string()));
                 assert_eq!(
                     path,
@@ -207,8 +497,13 @@ mod tests {
 
         let item: LauncherItem = serde_json::from_str(json).unwrap();
         match item.history_source {
-            Some(HistorySource::RecentlyUsed { app_name, path }) => {
+            Some(HistorySource::RecentlyUsed {
+                app_name,
+                path,
+                include_missing,
+            }) => {
                 assert_eq!(app_name, None);
+                assert!(!include_missing);
                 assert_eq!(
                     path,
                     Some("~/.local/share/org.gnome.TextEditor/recently-used.xbel".to_string())
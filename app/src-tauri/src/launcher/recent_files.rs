@@ -3,16 +3,89 @@
 //! Reads recent files from:
 //! - ~/.local/share/recently-used.xbel (freedesktop standard)
 //! - VSCode storage.json (openedPathsList)
+//! - Sublime Text's Session.sublime_session (file and folder history)
+//! - Any SQLite database, with a user-supplied query
+//! - zoxide's frecent directories (`zoxide query --list`)
+//!
+//! History lists files that were since deleted or live on drives that
+//! aren't mounted; [`check_existence`] drops or marks them.
+//!
+//! Files opened through uti are written back to the XBEL file with
+//! [`record_visit`], so jump lists stay accurate.
 
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use quick_xml::de::from_str;
+use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::warn;
 
-/// Maximum number of files to return per command
+/// Default number of files to return per command
 const MAX_FILES_PER_COMMAND: usize = 10;
 
+/// Upper bound for a launcher item's `maxItems`
+const MAX_ITEMS_LIMIT: usize = 100;
+
+/// Maximum number of rows read from a user-supplied SQLite query
+const MAX_SQLITE_ROWS: usize = 500;
+
+/// How long to wait for a file's metadata before assuming it exists
+///
+/// Keeps a slow network mount from holding up the jump list.
+const STAT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// How long zoxide may take to list directories
+const ZOXIDE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Characters left as-is in file:// URIs (as GLib's `g_filename_to_uri`)
+const URI_PATH_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Owner of the freedesktop metadata block in a bookmark
+const FREEDESKTOP_OWNER: &str = "http://freedesktop.org";
+
+/// Content of a new XBEL file
+const EMPTY_XBEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xbel version="1.0"
+      xmlns:bookmark="http://www.freedesktop.org/standards/desktop-bookmarks"
+      xmlns:mime="http://www.freedesktop.org/standards/shared-mime-info"
+>
+</xbel>"#;
+
+/// Errors from writing an XBEL file
+#[derive(Debug, thiserror::Error)]
+pub enum XbelError {
+    #[error("failed to access {path:?}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("invalid XBEL: {0}")]
+    Xml(#[from] quick_xml::Error),
+}
+
+impl From<quick_xml::events::attributes::AttrError> for XbelError {
+    fn from(e: quick_xml::events::attributes::AttrError) -> Self {
+        Self::Xml(e.into())
+    }
+}
+
+impl From<std::io::Error> for XbelError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Xml(e.into())
+    }
+}
+
 /// Recent file entry returned to frontend
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -20,6 +93,8 @@ pub struct RecentFile {
     pub path: String,
     pub name: String,
     pub timestamp: String,
+    /// Whether the file no longer exists (see [`check_existence`])
+    pub missing: bool,
 }
 
 // XBEL XML structures for deserialization
@@ -107,7 +182,8 @@ fn get_filename(path: &str) -> String {
 ///
 /// # Returns
 ///
-/// Vector of recent files, sorted by timestamp (newest first), limited to MAX_FILES_PER_COMMAND
+/// Vector of recent files, sorted by timestamp (newest first). Not limited;
+/// [`check_existence`] limits it.
 pub fn get_recent_files_from_xbel(
     app_name: Option<&str>,
     custom_path: Option<&str>,
@@ -120,7 +196,7 @@ pub fn get_recent_files_from_xbel(
     let content = match fs::read_to_string(&xbel_path) {
         Ok(c) => c,
         Err(e) => {
-            eprintln!("Failed to read recently-used.xbel: {}", e);
+            warn!("Failed to read recently-used.xbel: {}", e);
             return Vec::new();
         }
     };
@@ -128,7 +204,7 @@ pub fn get_recent_files_from_xbel(
     let xbel: Xbel = match from_str(&content) {
         Ok(x) => x,
         Err(e) => {
-            eprintln!("Failed to parse recently-used.xbel: {}", e);
+            warn!("Failed to parse recently-used.xbel: {}", e);
             return Vec::new();
         }
     };
@@ -160,6 +236,7 @@ pub fn get_recent_files_from_xbel(
                 path,
                 name,
                 timestamp,
+                missing: false,
             })
         })
         .collect();
@@ -167,12 +244,227 @@ pub fn get_recent_files_from_xbel(
     // Sort by timestamp (newest first)
     files.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
 
-    // Limit to MAX_FILES_PER_COMMAND
-    files.truncate(MAX_FILES_PER_COMMAND);
-
     files
 }
 
+/// Record that a file was opened, per the freedesktop bookmark spec
+///
+/// Updates the file's bookmark (`modified` and `visited`) and the
+/// application's entry in it (`modified` and `count`), adding either if
+/// missing. The rest of the file is kept as-is.
+///
+/// # Arguments
+///
+/// * `file_path` - File that was opened (supports ~ expansion)
+/// * `app_name` - Application name to record (e.g., "org.gnome.TextEditor")
+/// * `command` - Command the application runs with, for its `exec`
+/// * `custom_path` - Optional custom path to XBEL file (supports ~ expansion)
+///
+/// # Errors
+///
+/// Returns an error if the XBEL file can't be parsed or written
+pub fn record_visit(
+    file_path: &str,
+    app_name: &str,
+    command: &str,
+    custom_path: Option<&str>,
+) -> Result<(), XbelError> {
+    let xbel_path = match custom_path {
+        Some(p) => expand_tilde(p),
+        None => get_xbel_path(),
+    };
+    let io_error = |source| XbelError::Io {
+        path: xbel_path.clone(),
+        source,
+    };
+
+    let content = match fs::read_to_string(&xbel_path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => EMPTY_XBEL.to_string(),
+        Err(e) => return Err(io_error(e)),
+    };
+
+    let visit = Visit {
+        href: file_uri(&expand_tilde(file_path)),
+        app_name,
+        exec: format!("'{} %u'", command),
+        now: chrono::Utc::now()
+            .format("%Y-%m-%dT%H:%M:%S%.6fZ")
+            .to_string(),
+    };
+    let updated = visit.apply(&content)?;
+
+    // Write a temporary file and rename it, so readers never see a partial file
+    if let Some(dir) = xbel_path.parent() {
+        fs::create_dir_all(dir).map_err(io_error)?;
+    }
+    let tmp_path = xbel_path.with_extension("xbel.tmp");
+    fs::write(&tmp_path, updated).map_err(io_error)?;
+    fs::rename(&tmp_path, &xbel_path).map_err(io_error)
+}
+
+/// Encode a path as a file:// URI
+fn file_uri(path: &Path) -> String {
+    format!(
+        "file://{}",
+        utf8_percent_encode(&path.to_string_lossy(), URI_PATH_SET)
+    )
+}
+
+/// A file opened by an application, to record in an XBEL file
+struct Visit<'a> {
+    href: String,
+    app_name: &'a str,
+    exec: String,
+    now: String,
+}
+
+impl Visit<'_> {
+    /// Rewrites XBEL content with the visit recorded
+    fn apply(&self, content: &str) -> Result<String, XbelError> {
+        let mut reader = Reader::from_str(content);
+        let mut writer = Writer::new(Vec::new());
+        // Inside the visited file's bookmark
+        let mut in_bookmark = false;
+        let mut found_bookmark = false;
+        let mut recorded_app = false;
+
+        loop {
+            match reader.read_event()? {
+                Event::Eof => break,
+                Event::Start(e) if e.name().as_ref() == b"bookmark" && self.is_visited(&e)? => {
+                    in_bookmark = true;
+                    found_bookmark = true;
+                    writer.write_event(Event::Start(self.touch(&e, &[])?))?;
+                }
+                Event::Empty(e) if e.name().as_ref() == b"bookmark" && self.is_visited(&e)? => {
+                    found_bookmark = true;
+                    writer.write_event(Event::Start(self.touch(&e, &[])?))?;
+                    self.write_info(&mut writer)?;
+                    writer.write_event(Event::End(BytesEnd::new("bookmark")))?;
+                }
+                Event::Empty(e)
+                    if in_bookmark
+                        && e.name().as_ref() == b"bookmark:application"
+                        && attribute(&e, b"name")?.as_deref() == Some(self.app_name) =>
+                {
+                    let count = attribute(&e, b"count")?
+                        .and_then(|c| c.parse::<u32>().ok())
+                        .unwrap_or(0);
+                    let count = (count + 1).to_string();
+                    writer.write_event(Event::Empty(self.touch(&e, &[("count", &count)])?))?;
+                    recorded_app = true;
+                }
+                Event::End(e) if in_bookmark && e.name().as_ref() == b"bookmark:applications" => {
+                    if !recorded_app {
+                        writer.write_event(Event::Empty(self.application()))?;
+                        recorded_app = true;
+                    }
+                    writer.write_event(Event::End(e))?;
+                }
+                Event::End(e) if in_bookmark && e.name().as_ref() == b"bookmark" => {
+                    // Bookmarks without an applications list get a new info block
+                    if !recorded_app {
+                        self.write_info(&mut writer)?;
+                    }
+                    in_bookmark = false;
+                    writer.write_event(Event::End(e))?;
+                }
+                Event::End(e) if !found_bookmark && e.name().as_ref() == b"xbel" => {
+                    self.write_bookmark(&mut writer)?;
+                    writer.write_event(Event::End(e))?;
+                }
+                event => writer.write_event(event)?,
+            }
+        }
+
+        // Events are copied from a &str, so the output is valid UTF-8
+        Ok(String::from_utf8_lossy(&writer.into_inner()).into_owned())
+    }
+
+    /// Whether a bookmark element is the visited file's
+    fn is_visited(&self, bookmark: &BytesStart) -> Result<bool, XbelError> {
+        Ok(attribute(bookmark, b"href")?.as_deref() == Some(self.href.as_str()))
+    }
+
+    /// Copies an element with `modified` (and `visited` for bookmarks) set
+    /// to now and the given attributes replaced
+    fn touch(
+        &self,
+        element: &BytesStart,
+        replace: &[(&str, &str)],
+    ) -> Result<BytesStart<'static>, XbelError> {
+        let is_bookmark = element.name().as_ref() == b"bookmark";
+        let mut updated: Vec<(&str, &str)> = vec![("modified", &self.now)];
+        if is_bookmark {
+            updated.push(("visited", &self.now));
+        }
+        updated.extend_from_slice(replace);
+
+        let name = String::from_utf8_lossy(element.name().as_ref()).into_owned();
+        let mut touched = BytesStart::new(name);
+        for attr in element.attributes() {
+            let attr = attr?;
+            if !updated
+                .iter()
+                .any(|(key, _)| key.as_bytes() == attr.key.as_ref())
+            {
+                touched.push_attribute(attr);
+            }
+        }
+        touched.extend_attributes(updated);
+        Ok(touched)
+    }
+
+    /// The application's entry for a bookmark
+    fn application(&self) -> BytesStart<'static> {
+        BytesStart::new("bookmark:application").with_attributes([
+            ("name", self.app_name),
+            ("exec", &self.exec),
+            ("modified", &self.now),
+            ("count", "1"),
+        ])
+    }
+
+    /// Writes an info block listing the application
+    fn write_info(&self, writer: &mut Writer<Vec<u8>>) -> Result<(), XbelError> {
+        writer.write_event(Event::Start(BytesStart::new("info")))?;
+        writer.write_event(Event::Start(
+            BytesStart::new("metadata").with_attributes([("owner", FREEDESKTOP_OWNER)]),
+        ))?;
+        writer.write_event(Event::Start(BytesStart::new("bookmark:applications")))?;
+        writer.write_event(Event::Empty(self.application()))?;
+        writer.write_event(Event::End(BytesEnd::new("bookmark:applications")))?;
+        writer.write_event(Event::End(BytesEnd::new("metadata")))?;
+        writer.write_event(Event::End(BytesEnd::new("info")))?;
+        Ok(())
+    }
+
+    /// Writes a new bookmark for the visited file
+    fn write_bookmark(&self, writer: &mut Writer<Vec<u8>>) -> Result<(), XbelError> {
+        let bookmark = BytesStart::new("bookmark").with_attributes([
+            ("href", self.href.as_str()),
+            ("added", &self.now),
+            ("modified", &self.now),
+            ("visited", &self.now),
+        ]);
+        writer.write_event(Event::Text(BytesText::new("  ")))?;
+        writer.write_event(Event::Start(bookmark))?;
+        self.write_info(writer)?;
+        writer.write_event(Event::End(BytesEnd::new("bookmark")))?;
+        writer.write_event(Event::Text(BytesText::new("\n")))?;
+        Ok(())
+    }
+}
+
+/// Gets an attribute's unescaped value
+fn attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>, XbelError> {
+    match element.try_get_attribute(name)? {
+        Some(attr) => Ok(Some(attr.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
 /// Get all available application names from recently-used.xbel
 ///
 /// Useful for debugging and discovering what applications are tracked
@@ -208,7 +500,7 @@ pub fn get_available_apps() -> Vec<String> {
 }
 
 /// Expand tilde to home directory
-fn expand_tilde(path: &str) -> PathBuf {
+pub fn expand_tilde(path: &str) -> PathBuf {
     if let Some(stripped) = path.strip_prefix("~/") {
         if let Some(home) = dirs::home_dir() {
             return home.join(stripped);
@@ -230,7 +522,8 @@ fn expand_tilde(path: &str) -> PathBuf {
 ///
 /// # Returns
 ///
-/// Vector of recent files, limited to MAX_FILES_PER_COMMAND
+/// Vector of recent files, most recent first. Not limited;
+/// [`check_existence`] limits it.
 pub fn get_recent_files_from_vscode(vscdb_path: &str) -> Vec<RecentFile> {
     let path = expand_tilde(vscdb_path);
 
@@ -241,7 +534,7 @@ pub fn get_recent_files_from_vscode(vscdb_path: &str) -> Vec<RecentFile> {
     ) {
         Ok(c) => c,
         Err(e) => {
-            eprintln!("Failed to open VSCode state.vscdb at {:?}: {}", path, e);
+            warn!("Failed to open VSCode state.vscdb at {:?}: {}", path, e);
             return Vec::new();
         }
     };
@@ -256,7 +549,7 @@ pub fn get_recent_files_from_vscode(vscdb_path: &str) -> Vec<RecentFile> {
     let json_str = match query_result {
         Ok(s) => s,
         Err(e) => {
-            eprintln!("Failed to query VSCode recent files: {}", e);
+            warn!("Failed to query VSCode recent files: {}", e);
             return Vec::new();
         }
     };
@@ -264,7 +557,7 @@ pub fn get_recent_files_from_vscode(vscdb_path: &str) -> Vec<RecentFile> {
     let json: Value = match serde_json::from_str(&json_str) {
         Ok(v) => v,
         Err(e) => {
-            eprintln!("Failed to parse VSCode recent files JSON: {}", e);
+            warn!("Failed to parse VSCode recent files JSON: {}", e);
             return Vec::new();
         }
     };
@@ -273,12 +566,12 @@ pub fn get_recent_files_from_vscode(vscdb_path: &str) -> Vec<RecentFile> {
     let entries = match json.get("entries").and_then(|v| v.as_array()) {
         Some(arr) => arr,
         None => {
-            eprintln!("No entries found in VSCode recent files");
+            warn!("No entries found in VSCode recent files");
             return Vec::new();
         }
     };
 
-    let mut files: Vec<RecentFile> = entries
+    entries
         .iter()
         .filter_map(|entry| {
             // Try fileUri first (individual files), then folderUri (folders),
@@ -296,14 +589,289 @@ pub fn get_recent_files_from_vscode(vscdb_path: &str) -> Vec<RecentFile> {
                 path,
                 name,
                 timestamp: String::new(), // VSCode doesn't store timestamps
+                missing: false,
             })
         })
-        .collect();
+        .collect()
+}
 
-    // Limit to MAX_FILES_PER_COMMAND
-    files.truncate(MAX_FILES_PER_COMMAND);
+/// Get recent files from any SQLite database with a custom query
+///
+/// Lets a launcher item use an app's own recents database without code
+/// for each app. The database is opened read-only and only read-only
+/// statements are run.
+///
+/// # Arguments
+///
+/// * `db_path` - Path to the database (supports ~ expansion)
+/// * `query` - SELECT statement returning recent files, most recent first
+/// * `uri_column` - Column holding file:// URIs or absolute paths
+///   (default: the first column)
+///
+/// # Returns
+///
+/// Vector of recent files in query order. Not limited beyond
+/// MAX_SQLITE_ROWS; [`check_existence`] limits it.
+pub fn get_recent_files_from_sqlite(
+    db_path: &str,
+    query: &str,
+    uri_column: Option<&str>,
+) -> Vec<RecentFile> {
+    let path = expand_tilde(db_path);
 
-    files
+    let conn = match rusqlite::Connection::open_with_flags(
+        &path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+    ) {
+        Ok(c) => c,
+        Err(e) => {
+            warn!("Failed to open SQLite database at {:?}: {}", path, e);
+            return Vec::new();
+        }
+    };
+
+    match query_recent_files(&conn, query, uri_column) {
+        Ok(files) => files,
+        Err(e) => {
+            warn!("Failed to query recent files from {:?}: {}", path, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Runs a recent files query and converts the URI column to files
+fn query_recent_files(
+    conn: &rusqlite::Connection,
+    query: &str,
+    uri_column: Option<&str>,
+) -> Result<Vec<RecentFile>, String> {
+    let mut stmt = conn.prepare(query).map_err(|e| e.to_string())?;
+    if !stmt.readonly() {
+        return Err("query must not modify the database".to_string());
+    }
+    let column = match uri_column {
+        Some(name) => stmt.column_index(name).map_err(|e| e.to_string())?,
+        None => 0,
+    };
+
+    let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+    let mut seen = std::collections::HashSet::new();
+    let mut files = Vec::new();
+    let mut count = 0;
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        count += 1;
+        if count > MAX_SQLITE_ROWS {
+            break;
+        }
+        // NULLs and non-text values are skipped
+        let Ok(Some(value)) = row.get::<_, Option<String>>(column) else {
+            continue;
+        };
+        let Some(path) = uri_to_path(&value) else {
+            continue;
+        };
+        if seen.insert(path.clone()) {
+            files.push(RecentFile {
+                name: get_filename(&path),
+                path,
+                timestamp: String::new(),
+                missing: false,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// Converts a file:// URI or absolute (or ~) path to a path
+///
+/// Other URIs (e.g., https://) are not files and give None.
+fn uri_to_path(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.starts_with("file://") {
+        decode_file_uri(value)
+    } else if value.starts_with('/') || value.starts_with("~/") {
+        Some(expand_tilde(value).to_string_lossy().into_owned())
+    } else {
+        None
+    }
+}
+
+/// Get recent files and folders from a Sublime Text session file
+///
+/// Reads `file_history` then `folder_history` from
+/// `Session.sublime_session`, each most recent first.
+///
+/// # Arguments
+///
+/// * `session_path` - Path to Session.sublime_session (supports ~ expansion)
+///
+/// # Returns
+///
+/// Vector of recent files and folders. Not limited; [`check_existence`]
+/// limits it.
+pub fn get_recent_files_from_sublime(session_path: &str) -> Vec<RecentFile> {
+    let path = expand_tilde(session_path);
+    let content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) => {
+            warn!("Failed to read Sublime Text session at {:?}: {}", path, e);
+            return Vec::new();
+        }
+    };
+
+    match serde_json::from_str::<Value>(&content) {
+        Ok(session) => parse_sublime_session(&session),
+        Err(e) => {
+            warn!("Failed to parse Sublime Text session: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Extracts the file and folder history from a Sublime Text session
+fn parse_sublime_session(session: &Value) -> Vec<RecentFile> {
+    let mut seen = std::collections::HashSet::new();
+    ["file_history", "folder_history"]
+        .iter()
+        .filter_map(|key| session.get(key).and_then(Value::as_array))
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter(|path| !path.is_empty() && seen.insert(path.to_string()))
+        .map(|path| RecentFile {
+            path: path.to_string(),
+            name: get_filename(path),
+            timestamp: String::new(), // Sublime Text doesn't store timestamps
+            missing: false,
+        })
+        .collect()
+}
+
+/// Number of files to return for a launcher item's `maxItems`
+///
+/// Defaults to MAX_FILES_PER_COMMAND, and is kept between 1 and
+/// MAX_ITEMS_LIMIT.
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::launcher::recent_files::item_limit;
+///
+/// assert_eq!(item_limit(None), 10);
+/// assert_eq!(item_limit(Some(30)), 30);
+/// assert_eq!(item_limit(Some(0)), 1);
+/// ```
+pub fn item_limit(max_items: Option<usize>) -> usize {
+    max_items
+        .unwrap_or(MAX_FILES_PER_COMMAND)
+        .clamp(1, MAX_ITEMS_LIMIT)
+}
+
+/// Drops or marks recent files that no longer exist
+///
+/// Files are checked in order, a batch at a time, until enough existing
+/// ones are found. A file whose metadata takes too long (e.g., on a slow
+/// network mount) is assumed to exist.
+///
+/// # Arguments
+///
+/// * `files` - Recent files, most relevant first
+/// * `include_missing` - Keep missing files, with `missing` set, instead
+///   of dropping them
+/// * `limit` - Maximum number of files to return (see [`item_limit`])
+///
+/// # Returns
+///
+/// At most `limit` files
+pub async fn check_existence(
+    files: Vec<RecentFile>,
+    include_missing: bool,
+    limit: usize,
+) -> Vec<RecentFile> {
+    let mut checked = Vec::new();
+    let mut files = files.into_iter();
+
+    while checked.len() < limit {
+        let batch: Vec<RecentFile> = files.by_ref().take(limit).collect();
+        if batch.is_empty() {
+            break;
+        }
+        let exists =
+            futures_util::future::join_all(batch.iter().map(|file| path_exists(&file.path))).await;
+
+        for (mut file, exists) in batch.into_iter().zip(exists) {
+            if !exists {
+                if !include_missing {
+                    continue;
+                }
+                file.missing = true;
+            }
+            checked.push(file);
+        }
+    }
+
+    checked.truncate(limit);
+    checked
+}
+
+/// Checks whether a path exists, assuming it does if the check is slow
+async fn path_exists(path: &str) -> bool {
+    match tokio::time::timeout(STAT_TIMEOUT, tokio::fs::metadata(path)).await {
+        Ok(result) => result.is_ok(),
+        Err(_) => true,
+    }
+}
+
+/// Get frecent directories from zoxide
+///
+/// # Arguments
+///
+/// * `query` - Keywords to filter by, as for `zoxide query` (optional)
+/// * `limit` - Maximum number of directories (see [`item_limit`])
+///
+/// # Returns
+///
+/// Directories, most frecent first. Empty if zoxide isn't installed or
+/// doesn't answer within [`ZOXIDE_TIMEOUT`].
+pub async fn get_recent_dirs_from_zoxide(query: Option<&str>, limit: usize) -> Vec<RecentFile> {
+    let keywords: Vec<&str> = query
+        .map(|q| q.split_whitespace().collect())
+        .unwrap_or_default();
+    let output = tokio::process::Command::new("zoxide")
+        .args(["query", "--list", "--"])
+        .args(&keywords)
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = match tokio::time::timeout(ZOXIDE_TIMEOUT, output).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => {
+            warn!("Failed to run zoxide: {}", e);
+            return Vec::new();
+        }
+        Err(_) => {
+            warn!("zoxide timed out after {:?}", ZOXIDE_TIMEOUT);
+            return Vec::new();
+        }
+    };
+
+    // zoxide exits with 1 when nothing matches
+    parse_zoxide_output(&String::from_utf8_lossy(&output.stdout), limit)
+}
+
+/// Parses `zoxide query --list` output, one directory per line
+fn parse_zoxide_output(output: &str, limit: usize) -> Vec<RecentFile> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .take(limit)
+        .map(|path| RecentFile {
+            path: path.to_string(),
+            name: get_filename(path),
+            timestamp: String::new(), // zoxide lists by frecency, not time
+            missing: false,
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -412,6 +980,210 @@ mod tests {
         assert_eq!(xbel.bookmarks[0].href, "file:///home/user/test.txt");
     }
 
+    fn visit(href: &str, app_name: &'static str) -> Visit<'static> {
+        Visit {
+            href: href.to_string(),
+            app_name,
+            exec: format!("'{} %u'", app_name),
+            now: "2025-02-01T09:00:00.000000Z".to_string(),
+        }
+    }
+
+    fn applications(xbel: &str, href: &str) -> Vec<String> {
+        let xbel: Xbel = from_str(xbel).unwrap();
+        let bookmark = xbel.bookmarks.iter().find(|b| b.href == href).unwrap();
+        bookmark
+            .info
+            .as_ref()
+            .and_then(|i| i.metadata.as_ref())
+            .and_then(|m| m.applications.as_ref())
+            .map(|apps| apps.apps.iter().map(|a| a.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    const VISITED_XBEL: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xbel version="1.0">
+  <bookmark href="file:///home/user/test.txt" added="2025-01-01T12:00:00Z" modified="2025-01-01T12:00:00Z" visited="2025-01-01T12:00:00Z">
+    <info>
+      <metadata owner="http://freedesktop.org">
+        <mime:mime-type type="text/plain"/>
+        <bookmark:applications>
+          <bookmark:application name="org.gnome.TextEditor" exec="&apos;gnome-text-editor %u&apos;" modified="2025-01-01T12:00:00Z" count="2"/>
+        </bookmark:applications>
+      </metadata>
+    </info>
+  </bookmark>
+</xbel>"#;
+
+    #[test]
+    fn test_record_visit_updates_existing_application() {
+        let updated = visit("file:///home/user/test.txt", "org.gnome.TextEditor")
+            .apply(VISITED_XBEL)
+            .unwrap();
+
+        assert!(updated.contains(r#"count="3""#));
+        assert!(updated.contains(r#"<mime:mime-type type="text/plain"/>"#));
+        assert!(updated.contains(r#"added="2025-01-01T12:00:00Z""#));
+        assert_eq!(
+            updated
+                .matches(r#"visited="2025-02-01T09:00:00.000000Z""#)
+                .count(),
+            1
+        );
+        assert_eq!(
+            applications(&updated, "file:///home/user/test.txt"),
+            ["org.gnome.TextEditor"]
+        );
+    }
+
+    #[test]
+    fn test_record_visit_adds_application_and_bookmark() {
+        let href = "file:///home/user/test.txt";
+        let updated = visit(href, "uti").apply(VISITED_XBEL).unwrap();
+        assert_eq!(
+            applications(&updated, href),
+            ["org.gnome.TextEditor", "uti"]
+        );
+
+        let new_href = "file:///home/user/My%20Notes.md";
+        let updated = visit(new_href, "uti").apply(&updated).unwrap();
+        assert_eq!(applications(&updated, new_href), ["uti"]);
+        assert_eq!(
+            applications(&updated, href),
+            ["org.gnome.TextEditor", "uti"]
+        );
+    }
+
+    #[test]
+    fn test_record_visit_creates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let xbel = dir.path().join("app").join("recently-used.xbel");
+        let xbel = xbel.to_str().unwrap();
+
+        record_visit("/tmp/My Notes.md", "notes", "notes", Some(xbel)).unwrap();
+        record_visit("/tmp/My Notes.md", "notes", "notes", Some(xbel)).unwrap();
+
+        let files = get_recent_files_from_xbel(Some("notes"), Some(xbel));
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "/tmp/My Notes.md");
+        let content = fs::read_to_string(xbel).unwrap();
+        assert!(content.contains("file:///tmp/My%20Notes.md"));
+        assert!(content.contains(r#"count="2""#));
+    }
+
+    fn recent(path: &str) -> RecentFile {
+        RecentFile {
+            path: path.to_string(),
+            name: get_filename(path),
+            timestamp: String::new(),
+            missing: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_sublime_session() {
+        let session = serde_json::json!({
+            "file_history": ["/home/user/notes.md", "/home/user/src/main.rs", "/home/user/notes.md"],
+            "folder_history": ["/home/user/src"],
+            "windows": []
+        });
+        let files = parse_sublime_session(&session);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "/home/user/notes.md",
+                "/home/user/src/main.rs",
+                "/home/user/src"
+            ]
+        );
+        assert_eq!(files[1].name, "main.rs");
+
+        assert!(parse_sublime_session(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_query_recent_files_from_sqlite() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE recent (title TEXT, location TEXT, opened INTEGER);
+             INSERT INTO recent VALUES
+                 ('Notes', 'file:///home/user/My%20Notes.md', 3),
+                 ('Site', 'https://example.com', 2),
+                 ('Query', '/home/user/query.sql', 1),
+                 ('Empty', NULL, 0),
+                 ('Again', 'file:///home/user/My%20Notes.md', 0);",
+        )
+        .unwrap();
+
+        let query = "SELECT title, location FROM recent ORDER BY opened DESC";
+        let files = query_recent_files(&conn, query, Some("location")).unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["/home/user/My Notes.md", "/home/user/query.sql"]);
+        assert_eq!(files[0].name, "My Notes.md");
+
+        // Without uriColumn the first column is used
+        assert!(query_recent_files(&conn, query, None).unwrap().is_empty());
+        assert!(query_recent_files(&conn, query, Some("missing")).is_err());
+        assert!(query_recent_files(&conn, "DELETE FROM recent", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_existence() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("notes.txt");
+        fs::write(&existing, "").unwrap();
+        let existing = existing.to_string_lossy().into_owned();
+        let gone = dir
+            .path()
+            .join("deleted.txt")
+            .to_string_lossy()
+            .into_owned();
+        let files = vec![recent(&gone), recent(&existing)];
+
+        let kept = check_existence(files.clone(), false, MAX_FILES_PER_COMMAND).await;
+        assert_eq!(kept.len(), 1);
+        assert_eq!(kept[0].path, existing);
+
+        let marked = check_existence(files, true, MAX_FILES_PER_COMMAND).await;
+        assert_eq!(marked.len(), 2);
+        assert!(marked[0].missing);
+        assert!(!marked[1].missing);
+    }
+
+    #[tokio::test]
+    async fn test_check_existence_fills_limit_past_missing_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut files: Vec<RecentFile> = (0..MAX_FILES_PER_COMMAND)
+            .map(|i| recent(&dir.path().join(format!("gone{}", i)).to_string_lossy()))
+            .collect();
+        for i in 0..MAX_FILES_PER_COMMAND + 5 {
+            let path = dir.path().join(format!("file{}", i));
+            fs::write(&path, "").unwrap();
+            files.push(recent(&path.to_string_lossy()));
+        }
+
+        let kept = check_existence(files.clone(), false, MAX_FILES_PER_COMMAND).await;
+        assert_eq!(kept.len(), MAX_FILES_PER_COMMAND);
+        assert!(kept[0].path.ends_with("file0"));
+
+        let few = check_existence(files, false, 3).await;
+        let names: Vec<&str> = few.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["file0", "file1", "file2"]);
+    }
+
+    #[test]
+    fn test_parse_zoxide_output() {
+        let output = "/home/user/src/uti\n/home/user/Documents\n\n";
+        let dirs = parse_zoxide_output(output, MAX_FILES_PER_COMMAND);
+        assert_eq!(dirs.len(), 2);
+        assert_eq!(dirs[0].path, "/home/user/src/uti");
+        assert_eq!(dirs[0].name, "uti");
+
+        let many: String = (0..20).map(|i| format!("/tmp/d{}\n", i)).collect();
+        assert_eq!(parse_zoxide_output(&many, 5).len(), 5);
+    }
+
     #[test]
     fn test_expand_tilde() {
         let path = expand_tilde("~/test");
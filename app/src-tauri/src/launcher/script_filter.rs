@@ -0,0 +1,276 @@
+//! Script filter providers
+//!
+//! `scriptProviders` in launcher.json lists scripts that extend the
+//! launcher, like Alfred script filters: uti runs each script with the
+//! query as its last argument and reads JSON results from its standard
+//! output, either a list or `{ "items": [...] }`:
+//!
+//! ```json
+//! [{ "title": "Open uti", "subtitle": "GitHub", "action": { "type": "open", "target": "https://github.com/noppomario/uti" } }]
+//! ```
+//!
+//! A provider with a `keyword` only runs for queries starting with it
+//! ("gh tokio"), and gets the rest of the query.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::process::Command;
+use tracing::{error, warn};
+
+/// How long a script may take before its results are dropped
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Maximum number of results taken from each script
+pub const MAX_RESULTS: usize = 20;
+
+/// A script providing launcher results
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptProvider {
+    /// Display name, also identifies the provider
+    pub name: String,
+    /// Program to run (`~/` is expanded)
+    pub command: String,
+    /// Arguments before the query
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Word the query must start with for the script to run (optional)
+    /// If not specified, the script runs for every query
+    #[serde(default)]
+    pub keyword: Option<String>,
+}
+
+impl ScriptProvider {
+    /// Gets the query to pass to the script, if it should run for `query`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uti_lib::launcher::script_filter::ScriptProvider;
+    ///
+    /// let provider = ScriptProvider {
+    ///     name: "Notes".to_string(),
+    ///     command: "notes-search".to_string(),
+    ///     args: vec![],
+    ///     keyword: Some("n".to_string()),
+    /// };
+    /// assert_eq!(provider.script_query("n groceries"), Some("groceries"));
+    /// assert_eq!(provider.script_query("nautilus"), None);
+    /// ```
+    pub fn script_query<'a>(&self, query: &'a str) -> Option<&'a str> {
+        let query = query.trim();
+        let script_query = match &self.keyword {
+            Some(keyword) => {
+                let (word, rest) = query.split_once(char::is_whitespace)?;
+                word.eq_ignore_ascii_case(keyword).then_some(rest.trim())?
+            }
+            None => query,
+        };
+        (!script_query.is_empty()).then_some(script_query)
+    }
+}
+
+/// What happens when a script result is selected
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ScriptAction {
+    /// Open a URL or file with the default app
+    Open { target: String },
+    /// Copy text to the clipboard
+    Copy { text: String },
+    /// Run a program
+    Run {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// A launcher result returned by a script
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptResult {
+    /// Provider that returned the result (filled in by uti)
+    #[serde(default)]
+    pub provider: String,
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    /// Icon name or path
+    #[serde(default)]
+    pub icon: Option<String>,
+    pub action: ScriptAction,
+}
+
+/// Script output: a list of results or Alfred's `{ "items": [...] }`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScriptOutput {
+    List(Vec<ScriptResult>),
+    Items { items: Vec<ScriptResult> },
+}
+
+/// Parses a script's output
+fn parse_output(output: &str) -> Result<Vec<ScriptResult>, serde_json::Error> {
+    let mut results = match serde_json::from_str(output)? {
+        ScriptOutput::List(results) | ScriptOutput::Items { items: results } => results,
+    };
+    results.truncate(MAX_RESULTS);
+    Ok(results)
+}
+
+/// Drops providers without a name or command and later duplicates of a
+/// name, with warnings
+pub fn validate_providers(providers: &mut Vec<ScriptProvider>) {
+    let mut names = HashSet::new();
+    providers.retain(|provider| {
+        if provider.name.trim().is_empty() || provider.command.trim().is_empty() {
+            warn!(
+                "Ignoring script provider {:?}: name and command are required",
+                provider.name
+            );
+            return false;
+        }
+        if !names.insert(provider.name.clone()) {
+            warn!(
+                "Ignoring script provider {:?}: name is already used",
+                provider.name
+            );
+            return false;
+        }
+        true
+    });
+}
+
+/// Runs one provider's script and parses its results
+async fn run_provider(provider: &ScriptProvider, query: &str) -> Result<Vec<ScriptResult>, String> {
+    let program = super::recent_files::expand_tilde(&provider.command);
+    let child = Command::new(&program)
+        .args(&provider.args)
+        .arg(query)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("failed to run {:?}: {}", program, e))?;
+
+    let output = tokio::time::timeout(SCRIPT_TIMEOUT, child.wait_with_output())
+        .await
+        .map_err(|_| format!("timed out after {:?}", SCRIPT_TIMEOUT))?
+        .map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        return Err(format!(
+            "exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    parse_output(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|e| format!("invalid output: {}", e))
+}
+
+/// Runs the providers that match a query and merges their results
+///
+/// Scripts run concurrently. A failing script is logged and skipped so
+/// it can't break the launcher.
+pub async fn search(providers: &[ScriptProvider], query: &str) -> Vec<ScriptResult> {
+    let runs = providers.iter().filter_map(|provider| {
+        let script_query = provider.script_query(query)?;
+        Some(async move { (provider, run_provider(provider, script_query).await) })
+    });
+
+    let mut results = Vec::new();
+    for (provider, result) in futures_util::future::join_all(runs).await {
+        match result {
+            Ok(items) => results.extend(items.into_iter().map(|mut item| {
+                item.provider = provider.name.clone();
+                item
+            })),
+            Err(e) => error!("Script provider {:?}: {}", provider.name, e),
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(name: &str, command: &str, keyword: Option<&str>) -> ScriptProvider {
+        ScriptProvider {
+            name: name.to_string(),
+            command: command.to_string(),
+            args: Vec::new(),
+            keyword: keyword.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_script_query() {
+        let any = provider("Any", "any", None);
+        assert_eq!(any.script_query("  todo "), Some("todo"));
+        assert_eq!(any.script_query(""), None);
+
+        let keyword = provider("GitHub", "gh-search", Some("gh"));
+        assert_eq!(keyword.script_query("GH tokio rt"), Some("tokio rt"));
+        assert_eq!(keyword.script_query("gh "), None);
+        assert_eq!(keyword.script_query("ghostty"), None);
+    }
+
+    #[test]
+    fn test_parse_output() {
+        let list = r#"[{ "title": "Copy", "action": { "type": "copy", "text": "hi" } }]"#;
+        let results = parse_output(list).unwrap();
+        assert_eq!(results[0].title, "Copy");
+        assert_eq!(
+            results[0].action,
+            ScriptAction::Copy {
+                text: "hi".to_string()
+            }
+        );
+
+        let items = r#"{ "items": [{
+            "title": "Top", "subtitle": "htop", "icon": "utilities-terminal",
+            "action": { "type": "run", "command": "htop" }
+        }] }"#;
+        let results = parse_output(items).unwrap();
+        assert_eq!(results[0].subtitle.as_deref(), Some("htop"));
+        assert!(matches!(&results[0].action, ScriptAction::Run { args, .. } if args.is_empty()));
+
+        assert!(parse_output("not json").is_err());
+        assert!(parse_output(r#"[{ "title": "No action" }]"#).is_err());
+    }
+
+    #[test]
+    fn test_validate_providers() {
+        let mut providers = vec![
+            provider("Notes", "notes", None),
+            provider("", "x", None),
+            provider("Empty", " ", None),
+            provider("Notes", "other", None),
+        ];
+        validate_providers(&mut providers);
+        assert_eq!(providers, [provider("Notes", "notes", None)]);
+    }
+
+    #[tokio::test]
+    async fn test_search_runs_scripts() {
+        let echo = ScriptProvider {
+            args: vec![
+                "-c".to_string(),
+                r#"printf '[{"title":"%s","action":{"type":"open","target":"x"}}]' "$0""#
+                    .to_string(),
+            ],
+            ..provider("Echo", "sh", None)
+        };
+        let failing = provider("Broken", "/nonexistent/script", None);
+
+        let results = search(&[echo, failing], "hello").await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].title, "hello");
+        assert_eq!(results[0].provider, "Echo");
+    }
+}
@@ -4,6 +4,7 @@
 
 use super::LauncherConfig;
 use std::path::PathBuf;
+use tracing::{error, info};
 
 /// Get the path to the launcher configuration file
 ///
@@ -24,17 +25,18 @@ pub fn load_launcher_config() -> LauncherConfig {
 
     match std::fs::read_to_string(&path) {
         Ok(contents) => match serde_json::from_str::<LauncherConfig>(&contents) {
-            Ok(config) => {
-                println!("Loaded launcher config from: {:?}", path);
+            Ok(mut config) => {
+                info!("Loaded launcher config from: {:?}", path);
+                config.validate();
                 config
             }
             Err(e) => {
-                eprintln!("Failed to parse launcher config: {}", e);
+                error!("Failed to parse launcher config: {}", e);
                 empty_config()
             }
         },
         Err(_) => {
-            println!(
+            info!(
                 "Launcher config not found at {:?}. Create it to add launcher commands.",
                 path
             );
@@ -45,7 +47,7 @@ pub fn load_launcher_config() -> LauncherConfig {
 
 /// Create empty launcher configuration
 fn empty_config() -> LauncherConfig {
-    LauncherConfig { commands: vec![] }
+    LauncherConfig::default()
 }
 
 #[cfg(test)]
@@ -0,0 +1,309 @@
+//! systemd user services
+//!
+//! Lists the user's systemd services with `systemctl --user` so they can
+//! be started, stopped or restarted from the launcher (e.g., bouncing
+//! syncthing). Loaded units come with their state; installed services
+//! that aren't loaded yet are listed as inactive.
+
+use super::fuzzy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tokio::process::Command;
+
+/// Maximum number of search results
+pub const MAX_RESULTS: usize = 20;
+
+/// Errors from systemctl
+#[derive(Debug, thiserror::Error)]
+pub enum SystemdError {
+    #[error("invalid unit name: {0:?}")]
+    InvalidUnit(String),
+
+    #[error("failed to run systemctl: {0}")]
+    Run(#[from] std::io::Error),
+
+    #[error("systemctl --user {command} failed: {message}")]
+    Failed { command: String, message: String },
+}
+
+/// A systemd user service
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemdUnit {
+    /// Unit name (e.g., "syncthing.service")
+    pub name: String,
+    /// Description from the unit file, empty if not loaded
+    pub description: String,
+    /// High-level state: "active", "inactive", "failed", ...
+    pub active_state: String,
+    /// Detailed state: "running", "exited", "dead", ...
+    pub sub_state: String,
+}
+
+impl SystemdUnit {
+    /// Whether the unit is running (or starting/reloading)
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self.active_state.as_str(),
+            "active" | "activating" | "reloading"
+        )
+    }
+
+    /// Scores how well the unit matches a query by name or description
+    fn match_score(&self, query: &str) -> Option<u32> {
+        let name = self.name.strip_suffix(".service").unwrap_or(&self.name);
+        let description = fuzzy::score(query, &self.description).map(|score| score * 2 / 3);
+        fuzzy::score(query, name).max(description)
+    }
+}
+
+/// Action on a unit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnitAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl UnitAction {
+    /// systemctl verb for the action
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Restart => "restart",
+        }
+    }
+}
+
+/// Splits the first `n` whitespace-separated fields off a line
+///
+/// Returns the fields and the rest of the line, or None if the line has
+/// fewer fields.
+fn split_fields(line: &str, n: usize) -> Option<(Vec<&str>, &str)> {
+    let mut fields = Vec::with_capacity(n);
+    let mut rest = line.trim_start();
+    for _ in 0..n {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        if end == 0 {
+            return None;
+        }
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some((fields, rest.trim_end()))
+}
+
+/// Parses `systemctl list-units --plain --no-legend` output
+fn parse_list_units(output: &str) -> Vec<SystemdUnit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (fields, description) = split_fields(line, 4)?;
+            let [name, load, active, sub] = fields[..] else {
+                return None;
+            };
+            (load != "not-found").then(|| SystemdUnit {
+                name: name.to_string(),
+                description: description.to_string(),
+                active_state: active.to_string(),
+                sub_state: sub.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `systemctl list-unit-files --plain --no-legend` output
+///
+/// Returns the names of services that can be started: templates and
+/// masked or static units are skipped.
+fn parse_list_unit_files(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (fields, _) = split_fields(line, 2)?;
+            let [name, state] = fields[..] else {
+                return None;
+            };
+            let startable = matches!(state, "enabled" | "disabled" | "linked");
+            (startable && !name.contains("@.")).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Runs `systemctl --user` with the given arguments
+///
+/// # Returns
+///
+/// The standard output
+async fn systemctl(args: &[&str]) -> Result<String, SystemdError> {
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(SystemdError::Failed {
+            command: args.join(" "),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Lists user services matching a query
+///
+/// An empty query lists all services: active ones first, then by name.
+///
+/// # Errors
+///
+/// Returns an error if systemctl can't be run or fails
+pub async fn list_units(query: &str) -> Result<Vec<SystemdUnit>, SystemdError> {
+    let list = ["--type=service", "--plain", "--no-legend", "--no-pager"];
+    let loaded = systemctl(&[&["list-units", "--all"], &list[..]].concat()).await?;
+    let files = systemctl(&[&["list-unit-files"], &list[..]].concat()).await?;
+
+    let mut units = parse_list_units(&loaded);
+    let names: HashSet<String> = units.iter().map(|u| u.name.clone()).collect();
+    units.extend(
+        parse_list_unit_files(&files)
+            .into_iter()
+            .filter(|name| !names.contains(name))
+            .map(|name| SystemdUnit {
+                name,
+                description: String::new(),
+                active_state: "inactive".to_string(),
+                sub_state: "dead".to_string(),
+            }),
+    );
+    Ok(search(units, query))
+}
+
+/// Filters and ranks units by a query
+fn search(units: Vec<SystemdUnit>, query: &str) -> Vec<SystemdUnit> {
+    let query = query.trim();
+    let mut scored: Vec<(u32, SystemdUnit)> = units
+        .into_iter()
+        .filter_map(|unit| {
+            if query.is_empty() {
+                return Some((u32::from(unit.is_active()), unit));
+            }
+            Some((unit.match_score(query)?, unit))
+        })
+        .collect();
+    scored.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then(a.name.cmp(&b.name)));
+    scored.truncate(MAX_RESULTS);
+    scored.into_iter().map(|(_, unit)| unit).collect()
+}
+
+/// Checks that a unit name is safe to pass to systemctl
+fn validate_unit(name: &str) -> Result<(), SystemdError> {
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || ":_.@-\\".contains(c));
+    if name.is_empty() || name.starts_with('-') || !valid_chars {
+        return Err(SystemdError::InvalidUnit(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Starts, stops or restarts a user service
+///
+/// # Errors
+///
+/// Returns an error if the unit name is invalid or systemctl fails
+pub async fn run_action(unit: &str, action: UnitAction) -> Result<(), SystemdError> {
+    validate_unit(unit)?;
+    systemctl(&[action.verb(), "--", unit]).await.map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST_UNITS: &str = "\
+syncthing.service                 loaded    active   running Syncthing - Open Source Continuous File Synchronization
+pipewire.service                  loaded    active   running PipeWire Multimedia Service
+gone.service                      not-found inactive dead    gone.service
+backup.service                    loaded    failed   failed  Nightly backup
+";
+
+    const LIST_UNIT_FILES: &str = "\
+syncthing.service                 enabled   enabled
+ssh-agent.service                 disabled  enabled
+dbus-:1.2-org.a11y.Bus@.service   static    -
+emacs@.service                    disabled  enabled
+pulseaudio.service                masked    enabled
+";
+
+    fn unit(name: &str, description: &str, active: &str) -> SystemdUnit {
+        SystemdUnit {
+            name: name.to_string(),
+            description: description.to_string(),
+            active_state: active.to_string(),
+            sub_state: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_list_units() {
+        let units = parse_list_units(LIST_UNITS);
+        let names: Vec<&str> = units.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["syncthing.service", "pipewire.service", "backup.service"]
+        );
+        assert_eq!(
+            units[0].description,
+            "Syncthing - Open Source Continuous File Synchronization"
+        );
+        assert!(units[0].is_active());
+        assert_eq!(units[2].sub_state, "failed");
+        assert!(!units[2].is_active());
+    }
+
+    #[test]
+    fn test_parse_list_unit_files() {
+        assert_eq!(
+            parse_list_unit_files(LIST_UNIT_FILES),
+            ["syncthing.service", "ssh-agent.service"]
+        );
+    }
+
+    #[test]
+    fn test_search() {
+        let units = vec![
+            unit("pipewire.service", "PipeWire Multimedia Service", "active"),
+            unit("ssh-agent.service", "", "inactive"),
+            unit("syncthing.service", "File Synchronization", "active"),
+        ];
+
+        let found = search(units.clone(), "sync");
+        assert_eq!(found[0].name, "syncthing.service");
+        assert!(search(units.clone(), "zzz").is_empty());
+
+        // Without a query, active units come first
+        let all: Vec<String> = search(units, "").into_iter().map(|u| u.name).collect();
+        assert_eq!(
+            all,
+            ["pipewire.service", "syncthing.service", "ssh-agent.service"]
+        );
+    }
+
+    #[test]
+    fn test_validate_unit() {
+        assert!(validate_unit("syncthing.service").is_ok());
+        assert!(validate_unit("app-org.gnome.Terminal@1.service").is_ok());
+        assert!(validate_unit("").is_err());
+        assert!(validate_unit("--now").is_err());
+        assert!(validate_unit("a.service; rm -rf ~").is_err());
+    }
+
+    #[test]
+    fn test_unit_action_deserialize() {
+        let action: UnitAction = serde_json::from_str("\"restart\"").unwrap();
+        assert_eq!(action, UnitAction::Restart);
+        assert_eq!(action.verb(), "restart");
+    }
+}
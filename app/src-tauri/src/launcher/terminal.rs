@@ -0,0 +1,160 @@
+//! Terminal emulator detection
+//!
+//! Apps with `Terminal=true` in their desktop entry (and launcher entries
+//! with `"terminal": true`) have no window of their own, so they're run
+//! inside a terminal emulator: the `terminal` command from launcher.json,
+//! else `$TERMINAL`, else the first known terminal found in `PATH`.
+
+use super::find_program;
+use std::ffi::OsStr;
+use std::path::Path;
+
+/// Known terminals with the arguments placed before the command to run
+const KNOWN_TERMINALS: &[(&str, &[&str])] = &[
+    ("ptyxis", &["--"]),
+    ("gnome-terminal", &["--"]),
+    ("kgx", &["-e"]),
+    ("konsole", &["-e"]),
+    ("xfce4-terminal", &["-x"]),
+    ("tilix", &["-e"]),
+    ("alacritty", &["-e"]),
+    ("kitty", &[]),
+    ("foot", &[]),
+    ("wezterm", &["start", "--"]),
+    ("xterm", &["-e"]),
+];
+
+/// Arguments a terminal takes before the command, by program name
+///
+/// Unknown terminals get the xterm convention, `-e`.
+fn exec_args(program: &str) -> Vec<String> {
+    let name = Path::new(program)
+        .file_name()
+        .and_then(OsStr::to_str)
+        .unwrap_or(program);
+    KNOWN_TERMINALS
+        .iter()
+        .find(|(known, _)| *known == name)
+        .map(|(_, args)| args.iter().map(|a| a.to_string()).collect())
+        .unwrap_or_else(|| vec!["-e".to_string()])
+}
+
+/// Finds a terminal emulator: `$TERMINAL`, then a known one in `PATH`
+///
+/// # Returns
+///
+/// The terminal program and the arguments to put before the command
+pub fn detect() -> Option<(String, Vec<String>)> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    detect_in(std::env::var("TERMINAL").ok().as_deref(), &path)
+}
+
+/// Finds a terminal from a `$TERMINAL` value and a `PATH` value
+fn detect_in(env_terminal: Option<&str>, path: &OsStr) -> Option<(String, Vec<String>)> {
+    if let Some(terminal) = env_terminal.map(str::trim).filter(|t| !t.is_empty()) {
+        return Some((terminal.to_string(), exec_args(terminal)));
+    }
+    KNOWN_TERMINALS.iter().find_map(|(name, _)| {
+        let program = find_program(name, path)?;
+        Some((program.to_string_lossy().into_owned(), exec_args(name)))
+    })
+}
+
+/// Wraps a command to run inside a terminal emulator
+///
+/// # Arguments
+///
+/// * `configured` - `terminal` from launcher.json, a command line used
+///   as-is before the command (e.g., "foot" or "kitty --single-instance")
+/// * `command` - Program to run in the terminal
+/// * `args` - Its arguments
+///
+/// # Returns
+///
+/// The terminal program and its full argument list, or None if no
+/// terminal is configured or installed
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::launcher::terminal::wrap;
+///
+/// let (program, args) = wrap(Some("alacritty -e"), "htop", &["-d".to_string()]).unwrap();
+/// assert_eq!(program, "alacritty");
+/// assert_eq!(args, ["-e", "htop", "-d"]);
+/// ```
+pub fn wrap(
+    configured: Option<&str>,
+    command: &str,
+    args: &[String],
+) -> Option<(String, Vec<String>)> {
+    let (program, mut terminal_args) = match configured {
+        Some(line) if !line.trim().is_empty() => {
+            let mut words = line.split_whitespace().map(str::to_string);
+            (words.next()?, words.collect())
+        }
+        _ => detect()?,
+    };
+    terminal_args.push(command.to_string());
+    terminal_args.extend(args.iter().cloned());
+    Some((program, terminal_args))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn install(dir: &Path, name: &str) {
+        let path = dir.join(name);
+        std::fs::write(&path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_detect_in_path() {
+        let bin = tempfile::tempdir().unwrap();
+        let path = bin.path().as_os_str();
+        assert_eq!(detect_in(None, path), None);
+
+        install(bin.path(), "xterm");
+        install(bin.path(), "wezterm");
+        let (program, args) = detect_in(None, path).unwrap();
+        assert_eq!(program, bin.path().join("wezterm").to_string_lossy());
+        assert_eq!(args, ["start", "--"]);
+    }
+
+    #[test]
+    fn test_detect_prefers_terminal_env() {
+        let bin = tempfile::tempdir().unwrap();
+        install(bin.path(), "xterm");
+        let path = bin.path().as_os_str();
+
+        assert_eq!(
+            detect_in(Some("/usr/bin/kitty"), path),
+            Some(("/usr/bin/kitty".to_string(), vec![]))
+        );
+        assert_eq!(
+            detect_in(Some("st"), path),
+            Some(("st".to_string(), vec!["-e".to_string()]))
+        );
+        assert_eq!(detect_in(Some(" "), path).unwrap().1, ["-e"]);
+    }
+
+    #[test]
+    fn test_wrap_with_configured_terminal() {
+        let args = vec!["-d".to_string(), "5".to_string()];
+        assert_eq!(
+            wrap(Some("kitty --single-instance"), "htop", &args),
+            Some((
+                "kitty".to_string(),
+                vec![
+                    "--single-instance".to_string(),
+                    "htop".to_string(),
+                    "-d".to_string(),
+                    "5".to_string()
+                ]
+            ))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Web search providers
+//!
+//! `searchProviders` in launcher.json maps keywords to search URLs, like
+//! browser bang shortcuts: with `{ "keyword": "gh", "url":
+//! "https://github.com/search?q={query}" }`, typing "gh tokio" in the
+//! launcher searches GitHub for "tokio".
+
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::warn;
+
+/// Placeholder replaced by the search terms in provider URLs
+pub const QUERY_PLACEHOLDER: &str = "{query}";
+
+/// A web search shortcut
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchProvider {
+    /// Display name (e.g., "GitHub")
+    pub name: String,
+    /// Keyword typed before the search terms (e.g., "gh")
+    pub keyword: String,
+    /// URL template containing `{query}`
+    pub url: String,
+}
+
+impl SearchProvider {
+    /// Checks that the provider can be used
+    ///
+    /// # Errors
+    ///
+    /// Returns a description of the problem if the keyword is empty or
+    /// contains whitespace, or the URL isn't http(s) or lacks `{query}`
+    pub fn validate(&self) -> Result<(), String> {
+        if self.keyword.is_empty() || self.keyword.chars().any(char::is_whitespace) {
+            return Err(format!(
+                "keyword {:?} must be one word without spaces",
+                self.keyword
+            ));
+        }
+        if !(self.url.starts_with("https://") || self.url.starts_with("http://")) {
+            return Err(format!("URL {:?} must start with http(s)://", self.url));
+        }
+        if !self.url.contains(QUERY_PLACEHOLDER) {
+            return Err(format!(
+                "URL {:?} must contain {}",
+                self.url, QUERY_PLACEHOLDER
+            ));
+        }
+        Ok(())
+    }
+
+    /// Builds the search URL for the given terms (percent-encoded)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uti_lib::launcher::SearchProvider;
+    ///
+    /// let github = SearchProvider {
+    ///     name: "GitHub".to_string(),
+    ///     keyword: "gh".to_string(),
+    ///     url: "https://github.com/search?q={query}".to_string(),
+    /// };
+    /// assert_eq!(github.url_for("tokio rt"), "https://github.com/search?q=tokio%20rt");
+    /// ```
+    pub fn url_for(&self, query: &str) -> String {
+        let encoded = utf8_percent_encode(query.trim(), NON_ALPHANUMERIC).to_string();
+        self.url.replace(QUERY_PLACEHOLDER, &encoded)
+    }
+}
+
+/// Drops invalid providers and later duplicates of a keyword, with warnings
+///
+/// Keywords are compared case-insensitively.
+pub fn validate_providers(providers: &mut Vec<SearchProvider>) {
+    let mut keywords = HashSet::new();
+    providers.retain(|provider| {
+        if let Err(e) = provider.validate() {
+            warn!("Ignoring search provider {:?}: {}", provider.name, e);
+            return false;
+        }
+        if !keywords.insert(provider.keyword.to_lowercase()) {
+            warn!(
+                "Ignoring search provider {:?}: keyword {:?} is already used",
+                provider.name, provider.keyword
+            );
+            return false;
+        }
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(keyword: &str, url: &str) -> SearchProvider {
+        SearchProvider {
+            name: keyword.to_uppercase(),
+            keyword: keyword.to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_url_for_encodes_query() {
+        let ddg = provider("d", "https://duckduckgo.com/?q={query}&ia=web");
+        assert_eq!(
+            ddg.url_for(" c++ & rust "),
+            "https://duckduckgo.com/?q=c%2B%2B%20%26%20rust&ia=web"
+        );
+        assert_eq!(
+            ddg.url_for("日本"),
+            "https://duckduckgo.com/?q=%E6%97%A5%E6%9C%AC&ia=web"
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(provider("gh", "https://github.com/search?q={query}")
+            .validate()
+            .is_ok());
+        assert!(provider("", "https://example.com/{query}")
+            .validate()
+            .is_err());
+        assert!(provider("g h", "https://example.com/{query}")
+            .validate()
+            .is_err());
+        assert!(provider("x", "file:///etc/{query}").validate().is_err());
+        assert!(provider("x", "https://example.com/").validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_providers_drops_invalid_and_duplicates() {
+        let mut providers = vec![
+            provider("gh", "https://github.com/search?q={query}"),
+            provider("bad", "https://example.com/"),
+            provider("GH", "https://gitlab.com/search?search={query}"),
+            provider("w", "https://en.wikipedia.org/w/index.php?search={query}"),
+        ];
+        validate_providers(&mut providers);
+        let keywords: Vec<&str> = providers.iter().map(|p| p.keyword.as_str()).collect();
+        assert_eq!(keywords, ["gh", "w"]);
+    }
+}
@@ -2,8 +2,10 @@
 ///
 /// This library provides:
 /// - Clipboard history management with LRU eviction
+/// - Emoji search
 /// - Launcher command configuration and recent files
 /// - Snippets storage for quick text access
 pub mod clipboard;
+pub mod emoji;
 pub mod launcher;
 pub mod snippets;
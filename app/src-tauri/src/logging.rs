@@ -0,0 +1,234 @@
+//! Logging
+//!
+//! Sets up `tracing` with output to stderr and a daily rotating log file
+//! under `~/.local/state/uti/logs/`. The level applies to uti's own
+//! modules (dependencies log warnings only) and can be changed at runtime.
+
+use crate::error;
+use parking_lot::Mutex;
+use std::path::{Path, PathBuf};
+use tauri::State;
+use tracing::info;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, Registry};
+
+/// Log file name prefix (files are named `uti.YYYY-MM-DD.log`)
+const LOG_PREFIX: &str = "uti";
+
+/// Number of daily log files to keep
+const MAX_LOG_FILES: usize = 7;
+
+/// Default number of lines returned by `get_recent_logs`
+const DEFAULT_RECENT_LINES: usize = 200;
+
+/// Application state for runtime log control
+pub struct LogControl {
+    handle: reload::Handle<Targets, Registry>,
+    level: Mutex<LevelFilter>,
+    dir: PathBuf,
+}
+
+/// Gets the log directory
+///
+/// # Returns
+///
+/// `~/.local/state/uti/logs/`
+pub fn get_log_dir() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("uti")
+        .join("logs")
+}
+
+/// Installs the global subscriber at info level
+///
+/// Falls back to stderr only if the log directory can't be created.
+pub fn init() -> LogControl {
+    let level = LevelFilter::INFO;
+    let dir = get_log_dir();
+
+    let (filter, handle) = reload::Layer::new(targets(level));
+    let file_appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir);
+
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(std::io::stderr));
+
+    match file_appender {
+        Ok(appender) => registry
+            .with(fmt::layer().with_ansi(false).with_writer(appender))
+            .init(),
+        Err(e) => {
+            registry.init();
+            tracing::warn!("Failed to open log directory {:?}: {}", dir, e);
+        }
+    }
+
+    LogControl {
+        handle,
+        level: Mutex::new(level),
+        dir,
+    }
+}
+
+impl LogControl {
+    /// Changes the log level until the app restarts
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - "error", "warn", "info", "debug" or "trace"
+    pub fn set_level(&self, level: &str) -> Result<(), String> {
+        let filter = parse_level(level).ok_or_else(|| format!("Invalid log level: {}", level))?;
+        self.handle
+            .reload(targets(filter))
+            .map_err(|e| format!("Failed to change log level: {}", e))?;
+        *self.level.lock() = filter;
+        Ok(())
+    }
+
+    /// Gets the current log level name (e.g., "info")
+    pub fn level(&self) -> String {
+        self.level.lock().to_string().to_lowercase()
+    }
+}
+
+/// Builds the filter: `level` for uti, warnings for everything else
+fn targets(level: LevelFilter) -> Targets {
+    Targets::new()
+        .with_default(LevelFilter::WARN.min(level))
+        .with_target("uti", level)
+        .with_target("uti_lib", level)
+}
+
+/// Parses a level name
+fn parse_level(level: &str) -> Option<LevelFilter> {
+    match level {
+        "error" => Some(LevelFilter::ERROR),
+        "warn" => Some(LevelFilter::WARN),
+        "info" => Some(LevelFilter::INFO),
+        "debug" => Some(LevelFilter::DEBUG),
+        "trace" => Some(LevelFilter::TRACE),
+        _ => None,
+    }
+}
+
+/// Gets the current log level
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const level = await invoke('get_log_level'); // "info"
+/// ```
+#[tauri::command]
+pub fn get_log_level(state: State<'_, LogControl>) -> String {
+    state.level()
+}
+
+/// Changes the log level until the app restarts
+///
+/// # Arguments
+///
+/// * `level` - "error", "warn", "info", "debug" or "trace"
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('set_log_level', { level: 'debug' });
+/// ```
+#[tauri::command]
+pub fn set_log_level(state: State<'_, LogControl>, level: String) -> error::Result<()> {
+    state.set_level(&level)?;
+    info!("Log level set to {}", level);
+    Ok(())
+}
+
+/// Gets the last lines of the current log file
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of lines (default 200)
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const lines = await invoke('get_recent_logs', { limit: 100 });
+/// ```
+#[tauri::command]
+pub fn get_recent_logs(
+    state: State<'_, LogControl>,
+    limit: Option<usize>,
+) -> error::Result<Vec<String>> {
+    let Some(path) = latest_log_file(&state.dir) else {
+        return Ok(Vec::new());
+    };
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read log file {:?}: {}", path, e))?;
+    Ok(tail(&content, limit.unwrap_or(DEFAULT_RECENT_LINES)))
+}
+
+/// Finds the newest log file (date-stamped names sort chronologically)
+fn latest_log_file(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(LOG_PREFIX) && n.ends_with(".log"))
+        })
+        .max()
+}
+
+/// Returns the last `limit` lines of `content`
+fn tail(content: &str, limit: usize) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.len().saturating_sub(limit);
+    lines[start..].iter().map(|l| l.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("debug"), Some(LevelFilter::DEBUG));
+        assert_eq!(parse_level("warn"), Some(LevelFilter::WARN));
+        assert_eq!(parse_level("verbose"), None);
+    }
+
+    #[test]
+    fn test_tail() {
+        let content = "a\nb\nc\n";
+        assert_eq!(tail(content, 2), vec!["b", "c"]);
+        assert_eq!(tail(content, 10), vec!["a", "b", "c"]);
+        assert!(tail("", 5).is_empty());
+    }
+
+    #[test]
+    fn test_latest_log_file() {
+        let dir = TempDir::new().unwrap();
+        assert!(latest_log_file(dir.path()).is_none());
+
+        for name in ["uti.2026-01-01.log", "uti.2026-01-03.log", "other.txt"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        assert_eq!(
+            latest_log_file(dir.path()).unwrap(),
+            dir.path().join("uti.2026-01-03.log")
+        );
+    }
+}
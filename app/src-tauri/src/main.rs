@@ -5,31 +5,62 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod capture;
 mod clipboard;
+mod compositor;
 mod config;
+mod dbus;
+mod emoji;
+mod error;
+mod focus;
+mod http_api;
+mod ipc;
+mod keyring;
 mod launcher;
+mod logging;
+mod plugins;
 mod settings;
+mod shell;
+mod snippet_sync;
 mod snippets;
+mod state;
+mod tools;
 mod tray;
 mod updater;
 
 use clap::{Parser, Subcommand};
-use clipboard::{ClipboardItem, ClipboardStore};
+use clipboard::crypto::Encryption;
+use clipboard::diff::{diff_texts, TextDiff};
+use clipboard::export::ExportFormat;
+use clipboard::transform::{transform, TransformOp};
+use clipboard::{ClipboardError, ClipboardItem, ClipboardStore, OversizePolicy};
 use config::{
     open_config_folder, open_launcher_config, open_snippets_config, read_config, reload_config,
     save_config, AppConfig,
 };
-use launcher::{LauncherConfig, RecentFile};
+use launcher::{LauncherAction, LauncherConfig, LauncherError, RecentFile};
 use settings::{
     apply_window_size, check_for_updates, check_for_updates_with_dialog, get_autostart_status,
-    get_version, open_github, set_autostart, set_window_mode,
+    get_version, install_update, open_github, remind_update_later, set_autostart, set_window_mode,
+    skip_update,
 };
-use snippets::{load_snippets, save_snippets, SnippetItem, SnippetsStore};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use tauri::{Emitter, Manager, State, WebviewWindow};
+use snippets::import::ImportFormat;
+use snippets::{
+    load_snippets, save_snippets, RenderedSnippet, SnippetItem, SnippetVariable, TemplateContext,
+};
+use state::{get_state_snapshot, AppState};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use tauri::{AppHandle, Emitter, Manager, State, WebviewWindow};
 use tauri_plugin_autostart::MacosLauncher;
-use zbus::Connection;
+use tools::{
+    convert_time, define_clipboard_selection, define_word, get_network_info, get_public_ip,
+    get_system_stats, get_world_clock, inspect_text, kill_process, ocr_screen_region,
+    search_processes, set_translation_api_key, take_screenshot, terminate_process,
+    translate_and_paste, translate_text,
+};
+use tracing::{debug, error, info, warn};
 
 /// uti - Double Ctrl hotkey desktop tool
 #[derive(Parser)]
@@ -56,182 +87,1172 @@ enum Commands {
         /// Check only, don't install
         #[arg(long)]
         check: bool,
+        /// Print the check result as JSON (with --check)
+        #[arg(long, requires = "check")]
+        json: bool,
+        /// Stop offering the latest release (newer ones are offered again)
+        #[arg(long, conflicts_with_all = ["check", "remind_in"])]
+        skip: bool,
+        /// Stop offering updates for DAYS days
+        #[arg(long, value_name = "DAYS", conflicts_with = "check")]
+        remind_in: Option<u32>,
+        /// Update only these components (comma-separated; default: all)
+        #[arg(long, value_enum, value_delimiter = ',', conflicts_with_all = ["skip", "remind_in"])]
+        only: Vec<updater::Component>,
+    },
+    /// Check that uti, the daemon and the GNOME extension versions match
+    Doctor,
+    /// Read or change config.json
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
     },
 }
 
-/// Application state for window pin functionality
-///
-/// Tracks whether the window is pinned (always-on-top with auto-hide disabled).
-struct PinState {
-    is_pinned: Arc<AtomicBool>,
-}
-
-impl PinState {
-    fn new() -> Self {
-        Self {
-            is_pinned: Arc::new(AtomicBool::new(false)),
-        }
-    }
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Print a config value, e.g. `uti config get theme.color`
+    Get {
+        /// Dot-separated key, as in config.json
+        key: String,
+    },
+    /// Change a config value, e.g. `uti config set theme.color light`
+    Set {
+        /// Dot-separated key, as in config.json
+        key: String,
+        /// New value: JSON (100, true, null, ["UTC"]) or a plain string
+        value: String,
+    },
 }
 
 /// Gets the clipboard history
 ///
 /// Returns a list of clipboard items sorted by timestamp (newest first).
 #[tauri::command]
-fn get_clipboard_history(store: State<Mutex<ClipboardStore>>) -> Vec<ClipboardItem> {
-    let store = store.lock().unwrap();
-    store.items.clone()
+fn get_clipboard_history(state: State<AppState>) -> Vec<ClipboardItem> {
+    state.clipboard.read().items.clone()
 }
 
 /// Adds a new item to the clipboard history
 ///
 /// If the item already exists, its timestamp will be updated.
-/// Enforces the maximum item limit via LRU eviction.
+/// Enforces the maximum item limit via LRU eviction. Copies are recorded
+/// by the backend watcher, so this is only needed for text the frontend
+/// captures itself. Follows the same rules as the watcher (see
+/// [`capture::record`]).
 #[tauri::command]
-fn add_clipboard_item(text: String, store: State<Mutex<ClipboardStore>>) {
-    let mut store = store.lock().unwrap();
-    store.add(text);
-
-    // Save to file
-    let path = ClipboardStore::get_storage_path();
-    if let Err(e) = store.save(&path) {
-        eprintln!("Failed to save clipboard store: {}", e);
-    }
+fn add_clipboard_item(text: String, html: Option<String>, app: AppHandle) -> error::Result<()> {
+    capture::record(&app, text, html)?;
+    Ok(())
 }
 
 /// Sets the system clipboard to the specified text
+///
+/// When the history has HTML for this text, both formats are offered and
+/// the target app picks the one it prefers.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('paste_item', { text: item.text });
+/// ```
+#[tauri::command]
+fn paste_item(text: String, app: AppHandle, state: State<AppState>) -> error::Result<()> {
+    let item = state
+        .clipboard
+        .read()
+        .items
+        .iter()
+        .find(|item| item.text == text)
+        .cloned()
+        .unwrap_or_else(|| ClipboardItem::new(text));
+    capture::restore(&app, &item).map_err(|e| format!("Failed to set clipboard: {}", e))?;
+    Ok(())
+}
+
+/// Searches emojis by name and shortcode
+///
+/// Frequently picked emojis rank higher; an empty query lists them.
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const results = await invoke<EmojiMatch[]>('search_emoji', { query: 'tada' });
+/// ```
+#[tauri::command]
+fn search_emoji(query: String, state: State<AppState>) -> Vec<emoji::EmojiMatch> {
+    let now = chrono::Utc::now().timestamp();
+    emoji::search(&query, &state.emoji_usage.read(), now)
+}
+
+/// Copies an emoji to the clipboard and records its use
+///
+/// # Errors
+///
+/// Returns an error if the text isn't an emoji or the clipboard can't be
+/// set
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('copy_emoji', { emoji: '🎉' });
+/// ```
 #[tauri::command]
-async fn paste_item(text: String) -> Result<(), String> {
-    println!("Would paste: {}", text);
+fn copy_emoji(emoji: String, app: AppHandle, state: State<AppState>) -> error::Result<()> {
+    let found = emojis::get(&emoji).ok_or_else(|| format!("Not an emoji: {}", emoji))?;
+    capture::restore(&app, &ClipboardItem::new(found.as_str().to_string()))
+        .map_err(|e| format!("Failed to set clipboard: {}", e))?;
+
+    let mut usage = state.emoji_usage.write();
+    usage.record(found.as_str(), chrono::Utc::now().timestamp());
+    if let Err(e) = launcher::save_frecency_to(&usage, &emoji::get_usage_path()) {
+        // The emoji is copied; only its ranking is lost
+        warn!("Failed to save emoji usage: {}", e);
+    }
     Ok(())
 }
 
 /// Gets recent files from recently-used.xbel
+///
+/// Files that no longer exist are dropped, or marked `missing` if
+/// `include_missing` is set. Returns at most `max_items` files (default 10).
+#[tauri::command]
+async fn get_recent_files(
+    app_name: Option<String>,
+    xbel_path: Option<String>,
+    include_missing: Option<bool>,
+    max_items: Option<usize>,
+) -> Vec<RecentFile> {
+    let files = launcher::recent_files::get_recent_files_from_xbel(
+        app_name.as_deref(),
+        xbel_path.as_deref(),
+    );
+    launcher::recent_files::check_existence(
+        files,
+        include_missing.unwrap_or(false),
+        launcher::recent_files::item_limit(max_items),
+    )
+    .await
+}
+
+/// Records a file opened from a jump list in recently-used.xbel
+///
+/// Updates the file's visited time and the application's entry, so the
+/// jump list stays accurate even if the app doesn't record files itself.
+///
+/// # Arguments
+///
+/// * `file_path` - File that was opened
+/// * `app_name` - Application name to record it for
+/// * `command` - Command the application was launched with
+/// * `xbel_path` - Per-app XBEL file (optional, defaults to the system one)
+///
+/// # Errors
+///
+/// Returns an error if the XBEL file can't be parsed or written
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('record_recent_file', {
+///   filePath: '/home/user/notes.txt',
+///   appName: 'org.gnome.TextEditor',
+///   command: 'gnome-text-editor',
+///   xbelPath: null,
+/// });
+/// ```
 #[tauri::command]
-fn get_recent_files(app_name: Option<String>, xbel_path: Option<String>) -> Vec<RecentFile> {
-    launcher::recent_files::get_recent_files_from_xbel(app_name.as_deref(), xbel_path.as_deref())
+fn record_recent_file(
+    file_path: String,
+    app_name: String,
+    command: String,
+    xbel_path: Option<String>,
+) -> error::Result<()> {
+    launcher::recent_files::record_visit(&file_path, &app_name, &command, xbel_path.as_deref())
+        .map_err(LauncherError::from)?;
+    Ok(())
 }
 
 /// Gets recent files from VSCode state.vscdb SQLite database
+///
+/// Files that no longer exist are dropped, or marked `missing` if
+/// `include_missing` is set. Returns at most `max_items` files (default 10).
 #[tauri::command]
-fn get_vscode_recent_files(storage_path: String) -> Vec<RecentFile> {
-    launcher::recent_files::get_recent_files_from_vscode(&storage_path)
+async fn get_vscode_recent_files(
+    storage_path: String,
+    include_missing: Option<bool>,
+    max_items: Option<usize>,
+) -> Vec<RecentFile> {
+    let files = launcher::recent_files::get_recent_files_from_vscode(&storage_path);
+    launcher::recent_files::check_existence(
+        files,
+        include_missing.unwrap_or(false),
+        launcher::recent_files::item_limit(max_items),
+    )
+    .await
+}
+
+/// Gets recent files and folders from a Sublime Text session file
+///
+/// Files that no longer exist are dropped, or marked `missing` if
+/// `include_missing` is set. Returns at most `max_items` files (default 10).
+#[tauri::command]
+async fn get_sublime_recent_files(
+    session_path: String,
+    include_missing: Option<bool>,
+    max_items: Option<usize>,
+) -> Vec<RecentFile> {
+    let files = launcher::recent_files::get_recent_files_from_sublime(&session_path);
+    launcher::recent_files::check_existence(
+        files,
+        include_missing.unwrap_or(false),
+        launcher::recent_files::item_limit(max_items),
+    )
+    .await
+}
+
+/// Gets recent files from any SQLite database with a custom query
+///
+/// Files that no longer exist are dropped, or marked `missing` if
+/// `include_missing` is set. Returns at most `max_items` files (default 10).
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const files = await invoke<RecentFile[]>('get_sqlite_recent_files', {
+///   dbPath: '~/.local/share/app/recent.db',
+///   query: 'SELECT uri FROM recent ORDER BY time DESC',
+///   uriColumn: null,
+///   includeMissing: false,
+///   maxItems: null,
+/// });
+/// ```
+#[tauri::command]
+async fn get_sqlite_recent_files(
+    db_path: String,
+    query: String,
+    uri_column: Option<String>,
+    include_missing: Option<bool>,
+    max_items: Option<usize>,
+) -> Vec<RecentFile> {
+    let files = launcher::recent_files::get_recent_files_from_sqlite(
+        &db_path,
+        &query,
+        uri_column.as_deref(),
+    );
+    launcher::recent_files::check_existence(
+        files,
+        include_missing.unwrap_or(false),
+        launcher::recent_files::item_limit(max_items),
+    )
+    .await
+}
+
+/// Gets frecent directories from zoxide, at most `max_items` (default 10)
+///
+/// Async so that running zoxide doesn't block the main thread; gives up
+/// on zoxide after a timeout.
+#[tauri::command]
+async fn get_zoxide_dirs(query: Option<String>, max_items: Option<usize>) -> Vec<RecentFile> {
+    launcher::recent_files::get_recent_dirs_from_zoxide(
+        query.as_deref(),
+        launcher::recent_files::item_limit(max_items),
+    )
+    .await
 }
 
 /// Executes a command with optional arguments
+///
+/// # Arguments
+///
+/// * `command` - Program to run
+/// * `args` - Its arguments
+/// * `terminal` - Run it inside a terminal emulator (for `Terminal=true`
+///   apps); the `terminal` setting in launcher.json, `$TERMINAL` or a
+///   known installed terminal is used
+/// * `env` - Extra environment variables
+/// * `cwd` - Working directory (`~/` is expanded)
+///
+/// # Errors
+///
+/// Returns an error if the command can't be started or no terminal
+/// emulator is found
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('execute_command', { command: 'htop', args: [], terminal: true });
+/// await invoke('execute_command', { command: 'code', args: ['.'], cwd: '~/src/uti' });
+/// ```
 #[tauri::command]
-fn execute_command(command: String, args: Vec<String>) -> Result<(), String> {
-    std::process::Command::new(&command)
-        .args(&args)
-        .spawn()
-        .map_err(|e| format!("Failed to execute {}: {}", command, e))?;
+fn execute_command(
+    command: String,
+    args: Vec<String>,
+    terminal: Option<bool>,
+    env: Option<HashMap<String, String>>,
+    cwd: Option<String>,
+) -> error::Result<()> {
+    let (command, args) = if terminal.unwrap_or(false) {
+        let configured = launcher::load_launcher_config().terminal;
+        launcher::terminal::wrap(configured.as_deref(), &command, &args)
+            .ok_or_else(|| LauncherError::NoTerminal(command.clone()))?
+    } else {
+        (command, args)
+    };
+    let mut process = std::process::Command::new(&command);
+    process.args(&args).envs(env.unwrap_or_default());
+    if let Some(cwd) = cwd.filter(|cwd| !cwd.is_empty()) {
+        process.current_dir(launcher::recent_files::expand_tilde(&cwd));
+    }
+    match process.spawn() {
+        Ok(_) => Ok(()),
+        Err(source) => Err(error::Error::Launch { command, source }),
+    }
+}
+
+/// Launches a desktop app, optionally opening files with it
+///
+/// The entry's Exec field codes are substituted with the files, so apps
+/// taking a single file are started once per file.
+///
+/// # Arguments
+///
+/// * `id` - Desktop entry id (file name without `.desktop`)
+/// * `files` - Paths or URIs to open
+///
+/// # Errors
+///
+/// Returns an error if the entry isn't found, its Exec line is invalid
+/// or the app can't be started
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('execute_desktop_entry', { id: 'org.gnome.TextEditor', files: ['/tmp/notes.txt'] });
+/// ```
+#[tauri::command]
+fn execute_desktop_entry(id: String, files: Vec<String>) -> error::Result<()> {
+    let language = AppConfig::load().language;
+    let app = launcher::find_desktop_entry(&id, Some(&language))
+        .ok_or_else(|| LauncherError::EntryNotFound(id.clone()))?;
+    for mut argv in app.commands(&files).map_err(LauncherError::from)? {
+        let command = argv.remove(0);
+        execute_command(command, argv, Some(app.terminal), None, None)?;
+    }
+    Ok(())
+}
+
+/// Runs a shell command, streaming its output as events
+///
+/// # Arguments
+///
+/// * `cmd` - Command line, run with `sh -c`
+///
+/// # Returns
+///
+/// The run id carried by the `shell-output` and `shell-exit` events
+///
+/// # Errors
+///
+/// Returns an error if the command is empty or the shell can't be started
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const id = await invoke<number>('run_shell_command', { cmd: 'ls -la' });
+/// await listen<{ id: number; stream: string; line: string }>('shell-output', e => { ... });
+/// ```
+#[tauri::command]
+async fn run_shell_command(cmd: String, app: AppHandle) -> error::Result<u64> {
+    shell::run(app, &cmd)
+}
+
+/// Kills a shell command started with `run_shell_command`
+///
+/// Everything the command started is killed too; its `shell-exit` event
+/// follows with a null code.
+///
+/// # Returns
+///
+/// false if the command already finished
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('kill_shell_command', { id });
+/// ```
+#[tauri::command]
+fn kill_shell_command(id: u64) -> bool {
+    shell::kill(id)
+}
+
+/// Returns errors from startup, before the frontend was listening
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const errors = await invoke<BackendError[]>('take_backend_errors');
+/// ```
+#[tauri::command]
+fn take_backend_errors() -> Vec<error::BackendError> {
+    error::take_deferred()
+}
+
+/// Runs a built-in launcher action
+#[tauri::command]
+async fn run_launcher_action(app: AppHandle, action: LauncherAction) -> error::Result<()> {
+    match action {
+        LauncherAction::Screenshot { mode } => {
+            take_screenshot(app, Some(mode)).await.map(|_| ())?
+        }
+        LauncherAction::Ocr { language } => ocr_screen_region(app, language).await.map(|_| ())?,
+    }
     Ok(())
 }
 
 /// Gets the launcher configuration
+///
+/// Commands are ordered by frecency; ones never launched keep their
+/// configured order after the rest.
+#[tauri::command]
+fn get_launcher_config(state: State<AppState>) -> LauncherConfig {
+    let mut config = launcher::load_launcher_config();
+    let launches = state.launches.read();
+    let now = chrono::Utc::now().timestamp();
+    config
+        .commands
+        .sort_by_cached_key(|item| std::cmp::Reverse(launches.score(&item.id, now)));
+    config
+}
+
+/// Opens a web search using the provider configured for a keyword
+///
+/// # Arguments
+///
+/// * `keyword` - Provider keyword from `searchProviders` (e.g., "gh")
+/// * `query` - Search terms
+///
+/// # Errors
+///
+/// Returns an error if no provider has the keyword or the browser can't be
+/// opened
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('open_web_search', { keyword: 'gh', query: 'tokio' });
+/// ```
+#[tauri::command]
+fn open_web_search(keyword: String, query: String) -> error::Result<()> {
+    let config = launcher::load_launcher_config();
+    let provider = config
+        .search_provider(&keyword)
+        .ok_or_else(|| LauncherError::NoSearchProvider(keyword.clone()))?;
+    let url = provider.url_for(&query);
+    info!("Opening {} search: {}", provider.name, url);
+    open::that(&url).map_err(|source| LauncherError::Open {
+        target: url.clone(),
+        source,
+    })?;
+    Ok(())
+}
+
+/// Runs the `scriptProviders` from launcher.json for a query
+///
+/// # Returns
+///
+/// The results of all scripts that ran; failing scripts are logged and
+/// skipped
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const results = await invoke<ScriptResult[]>('search_script_providers', { query: 'todo' });
+/// ```
 #[tauri::command]
-fn get_launcher_config() -> LauncherConfig {
-    launcher::load_launcher_config()
+async fn search_script_providers(query: String) -> Vec<launcher::ScriptResult> {
+    let config = launcher::load_launcher_config();
+    launcher::script_filter::search(&config.script_providers, &query).await
+}
+
+/// Runs the action of a script provider result
+///
+/// # Errors
+///
+/// Returns an error if the target can't be opened, the clipboard can't be
+/// set or the command can't be started
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('activate_script_result', { action: result.action });
+/// ```
+#[tauri::command]
+fn activate_script_result(action: launcher::ScriptAction, app: AppHandle) -> error::Result<()> {
+    match action {
+        launcher::ScriptAction::Open { target } => {
+            open::that(&target).map_err(|source| LauncherError::Open {
+                target: target.clone(),
+                source,
+            })?
+        }
+        launcher::ScriptAction::Copy { text } => capture::restore(&app, &ClipboardItem::new(text))
+            .map_err(|e| format!("Failed to set clipboard: {}", e))?,
+        launcher::ScriptAction::Run { command, args } => {
+            execute_command(command, args, None, None, None)?
+        }
+    }
+    Ok(())
+}
+
+/// Records a launch of a launcher entry or desktop app for frecency ranking
+///
+/// # Arguments
+///
+/// * `id` - Launcher entry or desktop app id
+///
+/// # Errors
+///
+/// Returns an error if the launch history can't be saved
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('record_launch', { id: item.id });
+/// ```
+#[tauri::command]
+fn record_launch(id: String, state: State<AppState>) -> error::Result<()> {
+    let mut launches = state.launches.write();
+    launches.record(&id, chrono::Utc::now().timestamp());
+    launcher::save_frecency(&launches).map_err(LauncherError::Frecency)?;
+    Ok(())
 }
 
 /// Gets all snippets
 #[tauri::command]
-fn get_snippets(store: State<Mutex<SnippetsStore>>) -> Vec<SnippetItem> {
-    let store = store.lock().unwrap();
-    store.items.clone()
+fn get_snippets(state: State<AppState>) -> Vec<SnippetItem> {
+    state.snippets.read().items.clone()
 }
 
 /// Adds a new snippet (used when pinning from clipboard)
+///
+/// `category` optionally files it under a category.
 #[tauri::command]
 fn add_snippet(
     value: String,
     label: Option<String>,
-    store: State<Mutex<SnippetsStore>>,
-) -> SnippetItem {
-    let mut store = store.lock().unwrap();
-    let item = SnippetItem::new(value, label);
+    category: Option<String>,
+    state: State<AppState>,
+) -> error::Result<SnippetItem> {
+    let mut store = state.snippets.write();
+    let mut item = SnippetItem::new(value, label);
+    item.set_category(category);
     store.items.push(item.clone());
+    save_snippets(&store)?;
+    Ok(item)
+}
+
+/// Updates a snippet's value and label
+///
+/// # Arguments
+///
+/// * `id` - ID of the snippet
+/// * `value` - New text
+/// * `label` - New label (empty or null to display the value instead)
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const snippet = await invoke('update_snippet', {
+///   id: snippet.id, value: 'text', label: 'Greeting',
+/// });
+/// ```
+#[tauri::command]
+fn update_snippet(
+    id: String,
+    value: String,
+    label: Option<String>,
+    state: State<AppState>,
+) -> error::Result<SnippetItem> {
+    let mut store = state.snippets.write();
+    let item = store
+        .update(&id, value, label)
+        .cloned()
+        .ok_or_else(|| format!("Snippet {} not found", id))?;
+    save_snippets(&store)?;
+    Ok(item)
+}
 
-    if let Err(e) = save_snippets(&store) {
-        eprintln!("Failed to save snippets: {}", e);
+/// Deletes a snippet
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('delete_snippet', { id: snippet.id });
+/// ```
+#[tauri::command]
+fn delete_snippet(id: String, state: State<AppState>) -> error::Result<()> {
+    let mut store = state.snippets.write();
+    if store.remove(&id).is_none() {
+        return Err(format!("Snippet {} not found", id).into());
     }
-    item
+    save_snippets(&store)?;
+    Ok(())
 }
 
-/// Removes a clipboard item by index (used when pinning to snippets)
+/// Imports snippets from another text expander
+///
+/// Snippets with the same value and label as an existing one are skipped.
+///
+/// # Arguments
+///
+/// * `path` - File to read
+/// * `format` - "espanso" (YAML match file), "csv" or "json"; picked from
+///   the file extension when omitted
+///
+/// # Returns
+///
+/// Number of snippets added
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const added = await invoke('import_snippets', {
+///   path: '~/.config/espanso/match/base.yml', format: 'espanso',
+/// });
+/// ```
 #[tauri::command]
-fn remove_clipboard_item(index: usize, store: State<Mutex<ClipboardStore>>) {
-    let mut store = store.lock().unwrap();
-    if index < store.items.len() {
-        store.items.remove(index);
-        let path = ClipboardStore::get_storage_path();
-        if let Err(e) = store.save(&path) {
-            eprintln!("Failed to save clipboard store: {}", e);
-        }
+fn import_snippets(
+    path: PathBuf,
+    format: Option<ImportFormat>,
+    state: State<AppState>,
+) -> error::Result<usize> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let format = format.unwrap_or_else(|| ImportFormat::from_path(&path));
+    let items = snippets::import::parse(&content, format).map_err(|e| e.to_string())?;
+
+    let mut store = state.snippets.write();
+    let added = store.merge(items);
+    save_snippets(&store)?;
+    info!("Imported {} snippets from {:?}", added, path);
+    Ok(added)
+}
+
+/// Expands the placeholders in a snippet for pasting
+///
+/// Supports `{{date}}`, `{{time}}`, `{{date:FORMAT}}`, `{{clipboard}}`
+/// (latest history item), `{{env:NAME}}` and `{{cursor}}`. Variables
+/// (`{{name:prompt}}`) are left as they are; see
+/// `render_snippet_with_vars`.
+///
+/// # Returns
+///
+/// The expanded text and the cursor position (characters from the start)
+/// if the snippet marks one
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const { text, cursor } = await invoke('render_snippet', { id: snippet.id });
+/// ```
+#[tauri::command]
+fn render_snippet(id: String, state: State<AppState>) -> error::Result<RenderedSnippet> {
+    let (value, context) = snippet_template(&state, &id)?;
+    Ok(snippets::render(&value, &context))
+}
+
+/// Lists the variables (`{{name:prompt}}`) a snippet asks for
+///
+/// # Returns
+///
+/// Variables with their prompt text, in order of appearance
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const vars = await invoke('get_snippet_variables', { id: snippet.id });
+/// // [{ name: 'name', prompt: 'Recipient name' }]
+/// ```
+#[tauri::command]
+fn get_snippet_variables(
+    id: String,
+    state: State<AppState>,
+) -> error::Result<Vec<SnippetVariable>> {
+    let (value, _) = snippet_template(&state, &id)?;
+    Ok(snippets::variables(&value))
+}
+
+/// Expands a snippet's placeholders, filling in its variables
+///
+/// # Arguments
+///
+/// * `id` - ID of the snippet
+/// * `values` - Variable values by name; missing ones expand to nothing
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const { text, cursor } = await invoke('render_snippet_with_vars', {
+///   id: snippet.id, values: { name: 'Ann' },
+/// });
+/// ```
+#[tauri::command]
+fn render_snippet_with_vars(
+    id: String,
+    values: HashMap<String, String>,
+    state: State<AppState>,
+) -> error::Result<RenderedSnippet> {
+    let (value, context) = snippet_template(&state, &id)?;
+    Ok(snippets::render_with_vars(&value, &context, &values))
+}
+
+/// Gets a snippet's value and the context to expand it in
+fn snippet_template(state: &AppState, id: &str) -> error::Result<(String, TemplateContext)> {
+    let value = state
+        .snippets
+        .read()
+        .items
+        .iter()
+        .find(|item| item.id == id)
+        .map(|item| item.value.clone())
+        .ok_or_else(|| format!("Snippet {} not found", id))?;
+    let clipboard = state
+        .clipboard
+        .read()
+        .items
+        .first()
+        .map(|item| item.text.clone());
+    Ok((value, TemplateContext::new(clipboard)))
+}
+
+/// Assigns a quick-access slot (1-9) to a snippet, or clears it
+///
+/// The snippet previously in the slot loses it.
+///
+/// # Arguments
+///
+/// * `id` - ID of the snippet
+/// * `slot` - Slot number, or null to clear the snippet's slot
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('set_snippet_slot', { id: snippet.id, slot: 3 });
+/// ```
+#[tauri::command]
+fn set_snippet_slot(
+    id: String,
+    slot: Option<u8>,
+    state: State<AppState>,
+) -> error::Result<SnippetItem> {
+    if let Some(slot) = slot.filter(|slot| !snippets::SLOTS.contains(slot)) {
+        return Err(format!("Invalid snippet slot {} (expected 1-9)", slot).into());
     }
+    let mut store = state.snippets.write();
+    let item = store
+        .set_slot(&id, slot)
+        .cloned()
+        .ok_or_else(|| format!("Snippet {} not found", id))?;
+    save_snippets(&store)?;
+    Ok(item)
 }
 
-/// Emits a TypeText D-Bus signal to trigger auto-paste via daemon
+/// Gets the snippet in a quick-access slot
+///
+/// Returns null if no snippet is assigned to the slot.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const snippet = await invoke('get_snippet_by_slot', { n: 3 });
+/// ```
 #[tauri::command]
-async fn type_text() {
-    match Connection::session().await {
-        Ok(conn) => {
-            if let Err(e) = conn
-                .emit_signal(
-                    None::<()>,
-                    "/io/github/noppomario/uti/DoubleTap",
-                    "io.github.noppomario.uti.DoubleTap",
-                    "TypeText",
-                    &(),
-                )
-                .await
-            {
-                eprintln!("Failed to emit TypeText signal: {}", e);
-            } else {
-                println!("TypeText signal emitted");
-            }
-        }
-        Err(e) => {
-            eprintln!("Failed to connect to D-Bus: {}", e);
-        }
+fn get_snippet_by_slot(n: u8, state: State<AppState>) -> Option<SnippetItem> {
+    state.snippets.read().by_slot(n).cloned()
+}
+
+/// Lists snippet categories, sorted
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const categories = await invoke('get_snippet_categories'); // ["Work", ...]
+/// ```
+#[tauri::command]
+fn get_snippet_categories(state: State<AppState>) -> Vec<String> {
+    state.snippets.read().categories()
+}
+
+/// Gets the snippets in a category
+///
+/// # Arguments
+///
+/// * `category` - Category name, or null for uncategorized snippets
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const work = await invoke('get_snippets_by_category', { category: 'Work' });
+/// ```
+#[tauri::command]
+fn get_snippets_by_category(category: Option<String>, state: State<AppState>) -> Vec<SnippetItem> {
+    state.snippets.read().in_category(category.as_deref())
+}
+
+/// Moves a snippet to a category
+///
+/// # Arguments
+///
+/// * `id` - ID of the snippet
+/// * `category` - Category name, or null/empty to uncategorize it
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('set_snippet_category', { id: snippet.id, category: 'Work' });
+/// ```
+#[tauri::command]
+fn set_snippet_category(
+    id: String,
+    category: Option<String>,
+    state: State<AppState>,
+) -> error::Result<SnippetItem> {
+    let mut store = state.snippets.write();
+    let item = store
+        .set_category(&id, category)
+        .cloned()
+        .ok_or_else(|| format!("Snippet {} not found", id))?;
+    save_snippets(&store)?;
+    Ok(item)
+}
+
+/// Reorders snippets
+///
+/// # Arguments
+///
+/// * `ids` - Snippet IDs in the new order; snippets not listed keep their
+///   relative order after the listed ones
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('reorder_snippets', { ids: snippets.map((s) => s.id) });
+/// ```
+#[tauri::command]
+fn reorder_snippets(ids: Vec<String>, state: State<AppState>) -> error::Result<()> {
+    let mut store = state.snippets.write();
+    store.reorder(&ids);
+    save_snippets(&store)?;
+    Ok(())
+}
+
+/// Removes a clipboard item by index
+///
+/// Indexes shift when a copy is recorded, so use
+/// `remove_clipboard_item_by_id` instead where possible.
+#[tauri::command]
+fn remove_clipboard_item(index: usize, state: State<AppState>) -> error::Result<()> {
+    state.clipboard.write().remove(index)?;
+    Ok(())
+}
+
+/// Removes a clipboard item by ID (used when pinning to snippets)
+///
+/// Removing an item that's already gone is not an error.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('remove_clipboard_item_by_id', { id: item.id });
+/// ```
+#[tauri::command]
+fn remove_clipboard_item_by_id(id: String, state: State<AppState>) -> error::Result<()> {
+    state.clipboard.write().remove_by_id(&id)?;
+    Ok(())
+}
+
+/// Pins a clipboard item so the history limit never evicts it
+#[tauri::command]
+fn pin_clipboard_item(id: String, state: State<AppState>) -> error::Result<()> {
+    set_clipboard_item_pinned(&state, &id, true)
+}
+
+/// Unpins a clipboard item, making it subject to the history limit again
+#[tauri::command]
+fn unpin_clipboard_item(id: String, state: State<AppState>) -> error::Result<()> {
+    set_clipboard_item_pinned(&state, &id, false)
+}
+
+fn set_clipboard_item_pinned(state: &AppState, id: &str, pinned: bool) -> error::Result<()> {
+    let mut store = state.clipboard.write();
+    let found = match store.position(id) {
+        Some(index) => store.set_pinned(index, pinned)?,
+        None => false,
+    };
+    if !found {
+        return Err(format!("Clipboard item {} not found", id).into());
+    }
+    Ok(())
+}
+
+/// Exports the clipboard history to a file
+///
+/// # Arguments
+///
+/// * `path` - File to write
+/// * `format` - "json" (items with timestamps and pinned state) or "text"
+///   (texts separated by `%` lines)
+///
+/// # Returns
+///
+/// Number of items exported
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('export_clipboard_history', { path: '/tmp/clips.json', format: 'json' });
+/// ```
+#[tauri::command]
+fn export_clipboard_history(
+    path: PathBuf,
+    format: ExportFormat,
+    state: State<AppState>,
+) -> error::Result<usize> {
+    let items = state.clipboard.read().items.clone();
+    let content = clipboard::export::export(&items, format)?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    info!("Exported {} clipboard items to {:?}", items.len(), path);
+    Ok(items.len())
+}
+
+/// Imports clipboard history from a file, merging it with the current one
+///
+/// `.json` files are read as JSON, anything else as text (see
+/// `export_clipboard_history`). Text already in the history isn't
+/// duplicated, and the history limit still applies.
+///
+/// # Returns
+///
+/// Number of items added
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const added = await invoke('import_clipboard_history', { path: '/tmp/clips.json' });
+/// ```
+#[tauri::command]
+fn import_clipboard_history(path: PathBuf, state: State<AppState>) -> error::Result<usize> {
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let items = clipboard::export::parse(&content, ExportFormat::from_path(&path))?;
+    let added = state.clipboard.write().merge(items)?;
+    info!("Imported {} clipboard items from {:?}", added, path);
+    Ok(added)
+}
+
+/// Compares two clipboard items by ID
+///
+/// Returns a line diff from item `a` (old) to item `b` (new), with
+/// word-level segments for modified lines.
+#[tauri::command]
+fn diff_clipboard_items(a: String, b: String, state: State<AppState>) -> error::Result<TextDiff> {
+    let store = state.clipboard.read();
+    let get = |id: &str| {
+        store
+            .items
+            .iter()
+            .find(|item| item.id == id)
+            .map(|item| item.text.as_str())
+            .ok_or_else(|| ClipboardError::NotFound(id.to_string()))
+    };
+    Ok(diff_texts(get(&a)?, get(&b)?))
+}
+
+/// Applies a built-in transform to a clipboard item
+///
+/// # Arguments
+///
+/// * `id` - ID of the history item
+/// * `op` - "trim", "upper", "lower", "jsonPretty", "urlDecode" or
+///   "stripNewlines"
+/// * `copy` - Also put the result on the system clipboard
+///
+/// # Returns
+///
+/// The transformed text
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const text = await invoke('transform_clipboard_item', {
+///   id: item.id, op: 'jsonPretty', copy: true,
+/// });
+/// ```
+#[tauri::command]
+fn transform_clipboard_item(
+    id: String,
+    op: TransformOp,
+    copy: Option<bool>,
+    app: AppHandle,
+    state: State<AppState>,
+) -> error::Result<String> {
+    let text = state
+        .clipboard
+        .read()
+        .items
+        .iter()
+        .find(|item| item.id == id)
+        .map(|item| item.text.clone())
+        .ok_or_else(|| format!("Clipboard item {} not found", id))?;
+    let result = transform(&text, op).map_err(|e| e.to_string())?;
+    if copy.unwrap_or(false) {
+        capture::restore(&app, &ClipboardItem::new(result.clone()))
+            .map_err(|e| format!("Failed to set clipboard: {}", e))?;
     }
+    Ok(result)
+}
+
+/// Joins clipboard items and puts the result on the system clipboard
+///
+/// # Arguments
+///
+/// * `ids` - IDs of the history items, in the order they are joined
+/// * `separator` - Text between items (default: a newline)
+///
+/// # Returns
+///
+/// The merged text
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const text = await invoke('merge_clipboard_items', {
+///   ids: [first.id, second.id], separator: '\n\n',
+/// });
+/// ```
+#[tauri::command]
+fn merge_clipboard_items(
+    ids: Vec<String>,
+    separator: Option<String>,
+    app: AppHandle,
+    state: State<AppState>,
+) -> error::Result<String> {
+    if ids.is_empty() {
+        return Err("No clipboard items to merge".to_string().into());
+    }
+
+    let merged = {
+        let store = state.clipboard.read();
+        ids.iter()
+            .map(|id| {
+                store
+                    .items
+                    .iter()
+                    .find(|item| item.id == *id)
+                    .map(|item| item.text.as_str())
+                    .ok_or_else(|| format!("Clipboard item {} not found", id))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join(separator.as_deref().unwrap_or("\n"))
+    };
+    capture::restore(&app, &ClipboardItem::new(merged.clone()))
+        .map_err(|e| format!("Failed to set clipboard: {}", e))?;
+    Ok(merged)
+}
+
+/// Emits a TypeText D-Bus signal to trigger auto-paste via daemon
+#[tauri::command]
+async fn type_text(app: AppHandle) -> error::Result<()> {
+    dbus::emit_type_text(&app).await?;
+    info!("TypeText signal emitted");
+    Ok(())
+}
+
+/// Types text into the focused window key by key via the daemon
+///
+/// For apps that block pasting. The text is also put on the clipboard,
+/// which the daemon pastes instead if the keyboard layout can't type it.
+///
+/// # Arguments
+///
+/// * `text` - Text to type
+///
+/// # Errors
+///
+/// Returns an error if the clipboard can't be set or the signal can't be
+/// sent
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('type_string', { text: 'hunter2' });
+/// ```
+#[tauri::command]
+async fn type_string(app: AppHandle, text: String) -> error::Result<()> {
+    capture::restore(&app, &ClipboardItem::new(text.clone()))
+        .map_err(|e| format!("Failed to set clipboard: {}", e))?;
+    dbus::emit_type_string(&app, &text).await?;
+    info!("TypeString signal emitted");
+    Ok(())
 }
 
 /// Toggles the window visibility state
 #[tauri::command]
-fn toggle_window(window: WebviewWindow, pin_state: State<'_, PinState>) {
+fn toggle_window(window: WebviewWindow, state: State<'_, AppState>) {
     // Ignore toggle when window is pinned to prevent unexpected behavior
-    if pin_state.is_pinned.load(Ordering::SeqCst) {
-        println!("Window is pinned, toggle_window ignored");
+    if state.is_pinned() {
+        info!("Window is pinned, toggle_window ignored");
         return;
     }
 
     let is_visible = window.is_visible().unwrap_or(false);
-    println!("Current window state: visible={}", is_visible);
+    debug!("Current window state: visible={}", is_visible);
+
+    // The GNOME extension answers the same trigger with ShowAt, which
+    // shows the window at the pointer
+    let app = window.app_handle().clone();
+    if compositor::shown_by_extension(&app, is_visible) {
+        info!("Window shown by the GNOME extension");
+        return;
+    }
 
     if is_visible {
         let _ = window.hide();
-        println!("Window hidden");
+        info!("Window hidden");
     } else {
-        // On GNOME, the extension handles positioning at cursor location.
-        // On other environments, center the window as fallback.
-        let is_gnome = std::env::var("XDG_CURRENT_DESKTOP")
-            .map(|v| v.to_uppercase().contains("GNOME"))
-            .unwrap_or(false);
-
-        if is_gnome {
-            // Wait for GNOME extension to position the window before showing.
-            std::thread::sleep(std::time::Duration::from_millis(50));
-        } else {
-            match window.center() {
-                Ok(_) => println!("Window centered on screen"),
-                Err(e) => eprintln!("Failed to center window: {}", e),
-            }
-        }
+        // KWin and wlroots compositors can only move the window once it's
+        // mapped
+        compositor::before_show(&app, &window);
 
         let _ = window.show();
         let _ = window.set_focus();
-        println!("Window shown");
+        info!("Window shown");
+
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = compositor::place_at_cursor(&app).await {
+                warn!("Failed to place window at cursor: {}", e);
+            }
+        });
     }
 }
 
@@ -239,7 +1260,7 @@ fn toggle_window(window: WebviewWindow, pin_state: State<'_, PinState>) {
 #[tauri::command]
 fn hide_for_paste(window: WebviewWindow) {
     let _ = window.hide();
-    println!("Window hidden for paste (PIN state ignored)");
+    info!("Window hidden for paste (PIN state ignored)");
 }
 
 /// Show window (for re-showing after paste when pinned)
@@ -247,114 +1268,210 @@ fn hide_for_paste(window: WebviewWindow) {
 fn show_window(window: WebviewWindow) {
     let _ = window.show();
     let _ = window.set_focus();
-    println!("Window shown");
+    info!("Window shown");
 }
 
 /// Set window pinned state (always-on-top with auto-hide disabled)
 #[tauri::command]
 async fn set_pinned(
     window: WebviewWindow,
-    pin_state: State<'_, PinState>,
+    state: State<'_, AppState>,
     pinned: bool,
-) -> Result<(), String> {
-    pin_state.is_pinned.store(pinned, Ordering::SeqCst);
-
-    // Emit D-Bus signal for GNOME extension to handle always-on-top
-    if let Ok(conn) = zbus::Connection::session().await {
-        let _ = conn
-            .emit_signal(
-                None::<()>,
-                "/io/github/noppomario/uti/DoubleTap",
-                "io.github.noppomario.uti.DoubleTap",
-                "SetAlwaysOnTop",
-                &(pinned,),
-            )
-            .await;
-        println!("D-Bus SetAlwaysOnTop signal emitted: {}", pinned);
+) -> error::Result<()> {
+    state.pinned.store(pinned, Ordering::SeqCst);
+
+    // Ask the compositor to keep the window above others.
+    // Not fatal: the Tauri call below still works on X11.
+    match compositor::set_always_on_top(window.app_handle(), pinned).await {
+        Ok(()) => info!("Compositor always-on-top set: {}", pinned),
+        Err(e) => error::report(window.app_handle(), "compositor", e),
+    }
+
+    // Announce the App interface's Pinned property; the GNOME extension
+    // keeps the window above while it's set
+    if let Err(e) = dbus::emit_pinned_changed(window.app_handle()).await {
+        error::report(
+            window.app_handle(),
+            "dbus",
+            format!("Failed to announce pin state: {}", e),
+        );
     }
 
     // Also call Tauri API (works on non-GNOME environments)
-    window
-        .set_always_on_top(pinned)
-        .map_err(|e| e.to_string())?;
-    println!("Window pinned: {}", pinned);
+    window.set_always_on_top(pinned)?;
+    info!("Window pinned: {}", pinned);
     Ok(())
 }
 
 /// Search for desktop applications matching the query
+///
+/// Frequently launched apps rank higher (see `record_launch`). Names are
+/// shown in the configured UI language when the app is translated.
 #[tauri::command]
-fn search_desktop_files(query: String) -> Vec<launcher::DesktopApp> {
-    launcher::search_desktop_files(&query)
+fn search_desktop_files(query: String, state: State<AppState>) -> Vec<launcher::DesktopApp> {
+    let language = AppConfig::load().language;
+    launcher::search_desktop_files(&query, Some(&language), &state.launches.read())
+}
+
+/// Searches for files by name with fd or plocate/locate
+///
+/// # Returns
+///
+/// Up to 50 matches; open one with `execute_command('xdg-open', [path])`
+///
+/// # Errors
+///
+/// Returns an error if no search tool is installed or it can't be started
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const files = await invoke<FileMatch[]>('search_files', { query: 'report' });
+/// ```
+#[tauri::command]
+async fn search_files(query: String) -> error::Result<Vec<launcher::FileMatch>> {
+    Ok(launcher::search_files(&query)
+        .await
+        .map_err(LauncherError::from)?)
+}
+
+/// Lists systemd user services matching a query
+///
+/// # Returns
+///
+/// Up to 20 services; an empty query lists active ones first
+///
+/// # Errors
+///
+/// Returns an error if systemctl can't be run
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const units = await invoke<SystemdUnit[]>('list_systemd_units', { query: 'sync' });
+/// ```
+#[tauri::command]
+async fn list_systemd_units(query: String) -> error::Result<Vec<launcher::SystemdUnit>> {
+    Ok(launcher::systemd::list_units(&query)
+        .await
+        .map_err(LauncherError::from)?)
+}
+
+/// Starts, stops or restarts a systemd user service
+///
+/// # Arguments
+///
+/// * `unit` - Unit name (e.g., "syncthing.service")
+/// * `action` - "start", "stop" or "restart"
+///
+/// # Errors
+///
+/// Returns an error if the unit name is invalid or systemctl fails
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('systemd_unit_action', { unit: 'syncthing.service', action: 'restart' });
+/// ```
+#[tauri::command]
+async fn systemd_unit_action(unit: String, action: launcher::UnitAction) -> error::Result<()> {
+    launcher::systemd::run_action(&unit, action)
+        .await
+        .map_err(LauncherError::from)?;
+    info!("systemd user unit {}: {}", unit, action.verb());
+    Ok(())
 }
 
 /// Listens for D-Bus signals from the daemon and forwards them to the frontend.
 ///
+/// `Triggered` toggles the window; the other trigger signals open it on a
+/// tab (see [`dbus::Trigger`]). The keyboard, gesture and time sent with
+/// `Triggered` are forwarded to the frontend as [`dbus::TriggerEvent`].
+///
 /// Uses exponential backoff retry (1s -> 2s -> 4s -> ... -> max 30s) for:
 /// - D-Bus session connection
-/// - Proxy creation (daemon may not be running yet)
 /// - Signal stream acquisition
 ///
-/// Automatically reconnects when the stream ends (e.g., daemon restart).
+/// Automatically reconnects when the stream ends (the session bus
+/// connection was lost). Without a session bus, listens on the daemon's
+/// Unix socket instead (see [`dbus::fallback`]), with the same backoff
+/// between connections. Follows the daemon's bus name, emitting
+/// `daemon-status` when it appears or leaves, and restores focus mode
+/// when it comes back (e.g., after a restart).
 async fn listen_dbus(window: WebviewWindow) {
     use futures_util::stream::StreamExt;
     use std::time::Duration;
-    use zbus::proxy;
 
-    /// D-Bus proxy interface for receiving DoubleTap signals
-    #[proxy(
-        interface = "io.github.noppomario.uti.DoubleTap",
-        default_path = "/io/github/noppomario/uti/DoubleTap"
-    )]
-    trait DoubleTap {
-        /// Signal emitted when double Ctrl press is detected
-        #[zbus(signal, name = "Triggered")]
-        fn triggered(&self) -> zbus::Result<()>;
-    }
+    let rule = zbus::MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .interface(dbus::DOUBLE_TAP_INTERFACE)
+        .and_then(|rule| rule.path(dbus::DOUBLE_TAP_PATH))
+        .expect("valid match rule")
+        .build();
 
     let mut retry_delay = Duration::from_secs(1);
     let max_delay = Duration::from_secs(30);
+    let mut reported = false;
 
     loop {
-        // Connect to D-Bus session bus
-        let conn = match Connection::session().await {
+        // Connect to D-Bus session bus (shared with the app's D-Bus service)
+        let conn = match dbus::connection(window.app_handle()).await {
             Ok(c) => {
-                println!("Connected to D-Bus session bus");
+                info!("Connected to D-Bus session bus");
                 c
             }
             Err(e) => {
-                eprintln!(
-                    "D-Bus connection failed: {}, retrying in {:?}...",
-                    e, retry_delay
-                );
+                // Without a session bus, the daemon serves a socket instead
+                let started = std::time::Instant::now();
+                let connected = dbus::fallback::listen(window.app_handle(), |signal, info| {
+                    handle_trigger(&window, signal, Some(info));
+                })
+                .await;
+                if connected {
+                    // Only a connection that lasted resets the backoff, so a
+                    // socket that keeps dropping clients isn't retried in a loop
+                    if started.elapsed() >= max_delay {
+                        retry_delay = Duration::from_secs(1);
+                    }
+                    warn!(
+                        "Daemon socket connection closed, reconnecting in {:?}...",
+                        retry_delay
+                    );
+                } else {
+                    warn!(
+                        "D-Bus connection failed: {}, retrying in {:?}...",
+                        e, retry_delay
+                    );
+                }
+                // Tell the user once retries reach the maximum delay
+                if !connected && retry_delay == max_delay && !reported {
+                    error::report(
+                        window.app_handle(),
+                        "dbus",
+                        format!("Can't connect to the D-Bus session bus: {}", e),
+                    );
+                    reported = true;
+                }
                 tokio::time::sleep(retry_delay).await;
                 retry_delay = (retry_delay * 2).min(max_delay);
                 continue;
             }
         };
 
-        // Create proxy (may fail if daemon not running)
-        let proxy = match DoubleTapProxy::new(&conn, "io.github.noppomario.uti").await {
-            Ok(p) => p,
-            Err(e) => {
-                eprintln!(
-                    "D-Bus proxy creation failed: {}, retrying in {:?}...",
-                    e, retry_delay
-                );
-                tokio::time::sleep(retry_delay).await;
-                retry_delay = (retry_delay * 2).min(max_delay);
-                continue;
-            }
+        // Get signal streams - success, reset retry delay
+        let streams = async {
+            let signals = zbus::MessageStream::for_match_rule(rule.clone(), &conn, None).await?;
+            let daemon_changes = dbus::daemon_owner_changes(&conn).await?;
+            zbus::Result::Ok((signals, daemon_changes))
         };
-
-        // Get signal stream - success, reset retry delay
-        let mut stream = match proxy.receive_triggered().await {
+        let (mut stream, mut daemon_changes) = match streams.await {
             Ok(s) => {
-                println!("Listening for D-Bus signals...");
+                info!("Listening for D-Bus signals...");
                 retry_delay = Duration::from_secs(1);
                 s
             }
             Err(e) => {
-                eprintln!(
+                warn!(
                     "Failed to receive signals: {}, retrying in {:?}...",
                     e, retry_delay
                 );
@@ -364,14 +1481,36 @@ async fn listen_dbus(window: WebviewWindow) {
             }
         };
 
-        // Process signals until stream ends
-        while let Some(_signal) = stream.next().await {
-            println!("D-Bus signal received!");
-            let _ = window.emit("double-ctrl-pressed", ());
+        match dbus::daemon_running(&conn).await {
+            Ok(running) => daemon_status_changed(window.app_handle(), running).await,
+            Err(e) => warn!("Failed to check whether the daemon is running: {}", e),
         }
 
-        // Stream ended (connection lost), retry with backoff
-        eprintln!(
+        // Process signals until a stream ends
+        loop {
+            tokio::select! {
+                msg = stream.next() => match msg {
+                    Some(Ok(msg)) => handle_daemon_signal(&window, &msg),
+                    Some(Err(_)) => continue,
+                    None => break,
+                },
+                change = daemon_changes.next() => match change {
+                    Some(change) => {
+                        let Ok(args) = change.args() else {
+                            continue;
+                        };
+                        let running = args.new_owner().is_some();
+                        daemon_status_changed(window.app_handle(), running).await;
+                    }
+                    None => break,
+                },
+            }
+        }
+
+        // Stream ended (connection lost), reconnect with backoff
+        dbus::set_daemon_status(window.app_handle(), false);
+        dbus::reset(window.app_handle());
+        warn!(
             "D-Bus signal stream ended, reconnecting in {:?}...",
             retry_delay
         );
@@ -380,35 +1519,269 @@ async fn listen_dbus(window: WebviewWindow) {
     }
 }
 
+/// Forwards a daemon trigger signal to the frontend
+fn handle_daemon_signal(window: &WebviewWindow, msg: &zbus::Message) {
+    let header = msg.header();
+    let Some(member) = header.member() else {
+        return;
+    };
+    let info = uti_dbus::double_tap::TriggerInfo::from_message(msg);
+    handle_trigger(window, member.as_str(), info);
+}
+
+/// Acts on a daemon trigger signal, received over D-Bus or the socket
+fn handle_trigger(
+    window: &WebviewWindow,
+    signal: &str,
+    info: Option<uti_dbus::double_tap::TriggerInfo>,
+) {
+    debug!("Daemon signal received: {}", signal);
+    if focus::is_active(window.app_handle()) {
+        info!("Focus mode on, signal ignored");
+        return;
+    }
+    match dbus::Trigger::from_signal(signal) {
+        Some(dbus::Trigger::Toggle) => {
+            let _ = window.emit("double-ctrl-pressed", info.map(dbus::TriggerEvent::from));
+        }
+        Some(dbus::Trigger::OpenTab(tab)) => {
+            let _ = dbus::show_tab(window.app_handle(), tab);
+        }
+        None => warn!("Unknown daemon signal: {}", signal),
+    }
+}
+
+/// Reports the daemon's status, restoring focus mode when it (re)appears
+///
+/// Also warns when the daemon or extension don't match the app's version.
+async fn daemon_status_changed(app: &AppHandle, running: bool) {
+    dbus::set_daemon_status(app, running);
+    if running {
+        let mismatches = updater::version_mismatches().await;
+        if !mismatches.is_empty() {
+            error::report(app, "update", updater::describe_mismatches(&mismatches));
+        }
+    }
+    if running && focus::is_active(app) {
+        if let Err(e) = focus::sync_daemon(true).await {
+            error::report(
+                app,
+                "dbus",
+                format!("Failed to restore daemon focus mode: {}", e),
+            );
+        }
+    }
+}
+
+/// Draws update progress as a progress bar on stderr
+///
+/// Redraws the current line while a phase runs and starts a new line when
+/// the next phase begins.
+fn print_progress(progress: updater::UpdateProgress) {
+    static PHASE: parking_lot::Mutex<Option<updater::UpdatePhase>> = parking_lot::const_mutex(None);
+
+    let mut phase = PHASE.lock();
+    if phase.is_some_and(|phase| phase != progress.phase) {
+        eprintln!();
+    }
+    *phase = Some(progress.phase);
+    eprint!("\r\x1b[2K{}", progress.bar());
+}
+
+/// Records a decision to skip or postpone the latest release
+fn record_update_decision(result: &updater::UpdateCheckResult, skip: bool, remind_in: Option<u32>) {
+    let mut decisions = updater::UpdateDecisions::load();
+    if skip {
+        decisions.skip(&result.latest_version);
+    }
+    if let Some(days) = remind_in {
+        decisions.remind_in(days, chrono::Utc::now().timestamp());
+    }
+    if let Err(e) = decisions.save() {
+        eprintln!("Failed to save update decision: {}", e);
+        std::process::exit(1);
+    }
+
+    if skip {
+        println!("Skipping version {}.", result.latest_version);
+    }
+    if let Some(days) = remind_in {
+        println!("Updates postponed for {} days.", days);
+    }
+}
+
+/// Handle CLI config command
+///
+/// `set` validates the value, saves config.json and tells a running
+/// instance to reload it.
+async fn handle_config_command(action: ConfigAction) {
+    let config = AppConfig::load();
+    match action {
+        ConfigAction::Get { key } => match config::keys::get(&config, &key) {
+            Ok(serde_json::Value::String(value)) => println!("{}", value),
+            Ok(value) => println!("{}", value),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        ConfigAction::Set { key, value } => {
+            let saved = config::keys::set(&config, &key, &value).and_then(|updated| updated.save());
+            if let Err(e) = saved {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            match dbus::reload_running_config().await {
+                Ok(true) => println!("Updated {} (uti reloaded the config)", key),
+                Ok(false) => println!("Updated {}", key),
+                Err(e) => println!("Updated {} (restart uti to apply: {})", key, e),
+            }
+        }
+    }
+}
+
+/// Handle CLI doctor command
+///
+/// Prints each component's version and exits with an error if they don't
+/// work together.
+async fn handle_doctor_command() {
+    use updater::Component;
+
+    let show = |version: Option<semver::Version>, missing: &str| {
+        version.map_or_else(|| missing.to_string(), |v| v.to_string())
+    };
+    println!("uti:             {}", env!("CARGO_PKG_VERSION"));
+    println!(
+        "daemon:          {}",
+        show(updater::running_daemon_version().await, "not running")
+    );
+    println!(
+        "GNOME extension: {}",
+        show(Component::Extension.installed_version(), "not found")
+    );
+    println!();
+
+    let mismatches = updater::version_mismatches().await;
+    if mismatches.is_empty() {
+        println!("All components are compatible.");
+    } else {
+        eprintln!("{}", updater::describe_mismatches(&mismatches));
+        std::process::exit(1);
+    }
+}
+
+/// Prints an update check result as JSON, for scripts
+///
+/// Failures print `{"error": "..."}` and exit with status 1.
+fn print_update_check_json(result: Result<updater::UpdateCheckResult, updater::UpdateError>) {
+    match result {
+        Ok(result) => match serde_json::to_string_pretty(&result) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize the update check: {}", e);
+                std::process::exit(1);
+            }
+        },
+        Err(e) => {
+            println!("{}", serde_json::json!({ "error": e.to_string() }));
+            std::process::exit(1);
+        }
+    }
+}
+
 /// Handle CLI update command
-async fn handle_update_command(check_only: bool) {
+///
+/// `skip` and `remind_in` record a decision instead of installing. A
+/// skipped or postponed release is still installed when asked explicitly.
+/// `only` limits the update to some components; empty updates all.
+async fn handle_update_command(
+    check_only: bool,
+    json: bool,
+    skip: bool,
+    remind_in: Option<u32>,
+    only: &[updater::Component],
+) {
+    use updater::Component;
+
+    let components = if only.is_empty() {
+        &Component::ALL[..]
+    } else {
+        only
+    };
     let current_version = env!("CARGO_PKG_VERSION");
+    let channel = updater::UpdateChannel::configured();
+    if json {
+        print_update_check_json(updater::check_for_updates(current_version, channel).await);
+        return;
+    }
+
     println!("Current version: {}", current_version);
     println!("Checking for updates...");
 
-    match updater::check_for_updates(current_version).await {
+    match updater::check_for_updates(current_version, channel).await {
         Ok(result) => {
-            if result.update_available {
+            let newer = result.update_available || result.declined;
+            if newer && (skip || remind_in.is_some()) {
+                record_update_decision(&result, skip, remind_in);
+                return;
+            }
+
+            if newer {
+                if result.declined {
+                    println!(
+                        "Version {} was skipped or postponed.",
+                        result.latest_version
+                    );
+                }
                 println!(
                     "Update available: {} -> {}",
                     result.current_version, result.latest_version
                 );
 
+                if let Some(ref notes) = result.release_notes {
+                    println!();
+                    println!("What's new in {}:", result.latest_version);
+                    println!("{}", notes);
+                    println!();
+                }
+
                 if check_only {
-                    println!("Run 'uti update' to install the update.");
+                    println!(
+                        "Run '{}' to install the update.",
+                        updater::manual_update_command()
+                    );
                     return;
                 }
 
-                if result.uti_rpm_url.is_none() && result.daemon_rpm_url.is_none() {
+                let flatpak = updater::flatpak_app_id().is_some();
+                let wants_rpm = components.iter().any(|c| *c != Component::Extension);
+                if !flatpak
+                    && wants_rpm
+                    && result.uti_rpm_url.is_none()
+                    && result.daemon_rpm_url.is_none()
+                {
                     println!("No RPM packages found in the release.");
                     return;
                 }
 
-                println!("Installing update...");
-                match updater::perform_update(&result).await {
-                    Ok(()) => {
+                let names: Vec<_> = components.iter().map(|c| c.label()).collect();
+                println!("Installing update ({})...", names.join(", "));
+                let update =
+                    updater::perform_update_with_progress(&result, components, &print_progress)
+                        .await;
+                // End the progress bar line
+                eprintln!();
+                match update {
+                    Ok(extension_updated) => {
+                        if extension_updated {
+                            println!("GNOME extension updated.");
+                        }
                         println!("Update installed successfully!");
                         println!();
+                        if components == [Component::App] {
+                            println!("Restart uti to use the new version.");
+                            return;
+                        }
                         // Red bold warning box
                         println!("\x1b[1;31m+--------------------------------------------------------------+\x1b[0m");
                         println!("\x1b[1;31m|  WARNING: YOU MUST LOG OUT AND LOG BACK IN TO APPLY CHANGES  |\x1b[0m");
@@ -439,10 +1812,26 @@ fn main() {
     // Handle subcommands
     if let Some(command) = cli.command {
         match command {
-            Commands::Update { check } => {
+            Commands::Update {
+                check,
+                json,
+                skip,
+                remind_in,
+                only,
+            } => {
                 // Run update check in a tokio runtime
                 let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
-                rt.block_on(handle_update_command(check));
+                rt.block_on(handle_update_command(check, json, skip, remind_in, &only));
+                return;
+            }
+            Commands::Config { action } => {
+                let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+                rt.block_on(handle_config_command(action));
+                return;
+            }
+            Commands::Doctor => {
+                let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+                rt.block_on(handle_doctor_command());
                 return;
             }
         }
@@ -452,21 +1841,77 @@ fn main() {
     run_gui(cli.minimized);
 }
 
+/// Opens clipboard history, respecting the config limit and encryption
+///
+/// The encryption key comes from the system keyring and is created when
+/// encryption is first turned on. After encryption is turned off, the key
+/// is still used to decrypt the existing history. Falls back to an
+/// in-memory history so the app still starts, and tells the user that
+/// history won't be kept.
+fn open_clipboard_history(config: &AppConfig) -> ClipboardStore {
+    let path = ClipboardStore::get_storage_path();
+    let limit = config.clipboard_history_limit;
+
+    let open = || -> Result<ClipboardStore, String> {
+        let encrypted = ClipboardStore::has_encrypted_items(&path).map_err(|e| e.to_string())?;
+        let cipher = if config.clipboard_encryption || encrypted {
+            keyring::clipboard_cipher(!encrypted)?
+        } else {
+            None
+        };
+        ClipboardStore::open_with_encryption(
+            &path,
+            limit,
+            Encryption::new(cipher, config.clipboard_encryption),
+        )
+        .map_err(|e| e.to_string())
+    };
+    open().unwrap_or_else(|e| {
+        error::defer(
+            "clipboard",
+            format!(
+                "Failed to open clipboard history {}: {}. History is kept in memory only \
+                 and will be lost on exit.",
+                path.display(),
+                e
+            ),
+        );
+        ClipboardStore::new_with_limit(limit)
+    })
+}
+
 /// Run the Tauri GUI application
+///
+/// Exits right away if another instance owns the app bus name, after
+/// asking it to show its window.
 fn run_gui(start_minimized: bool) {
+    let log_control = logging::init();
+
+    let claimed = match tauri::async_runtime::block_on(dbus::claim_instance(!start_minimized)) {
+        Ok(dbus::Instance::Primary(conn)) => Some(conn),
+        Ok(dbus::Instance::Secondary) => {
+            info!("uti is already running, exiting");
+            return;
+        }
+        Err(e) => {
+            // Without a session bus there is no other instance to find
+            warn!("Failed to claim bus name {}: {}", dbus::APP_BUS_NAME, e);
+            None
+        }
+    };
+
     // Load config to get clipboard history limit
     let app_config = AppConfig::load();
+    if let Err(e) = log_control.set_level(&app_config.log_level) {
+        warn!("{}", e);
+    }
 
-    // Load clipboard store from file, respecting config limit
-    let path = ClipboardStore::get_storage_path();
-    let mut store = ClipboardStore::load(&path);
-
-    // Apply config limit (in case it changed since last save)
-    if store.max_items != app_config.clipboard_history_limit {
-        store.max_items = app_config.clipboard_history_limit;
-        // Save updated limit to file
-        if let Err(e) = store.save(&path) {
-            eprintln!("Failed to save updated max_items: {}", e);
+    let mut store = open_clipboard_history(&app_config);
+    store.max_item_bytes = app_config.clipboard_item_max_bytes;
+    store.oversize = OversizePolicy::from_setting(&app_config.clipboard_oversize);
+    if let Some(age) = capture::expiry::max_age(app_config.clipboard_item_ttl) {
+        if let Err(e) = store.expire(age) {
+            error!("Failed to expire clipboard items: {}", e);
         }
     }
 
@@ -476,9 +1921,21 @@ fn run_gui(start_minimized: bool) {
             Some(vec!["--minimized"]),
         ))
         .plugin(tauri_plugin_clipboard_manager::init())
-        .manage(Mutex::new(store))
-        .manage(Mutex::new(load_snippets()))
-        .manage(PinState::new())
+        .manage(AppState::new(
+            store,
+            load_snippets(&app_config.snippets_file()),
+            launcher::load_frecency(),
+            launcher::load_frecency_from(&emoji::get_usage_path()),
+        ))
+        .manage(compositor::Backend::from_setting(&app_config.compositor))
+        .manage(compositor::ReportedWindow::default())
+        .manage(compositor::ShowAtReport::default())
+        .manage(capture::CaptureState::new())
+        .manage(capture::CaptureFilter::default())
+        .manage(plugins::PluginHost::default())
+        .manage(dbus::AppBus::new(claimed))
+        .manage(http_api::HttpApiState::default())
+        .manage(log_control)
         .invoke_handler(tauri::generate_handler![
             // Window commands
             toggle_window,
@@ -487,11 +1944,32 @@ fn run_gui(start_minimized: bool) {
             set_pinned,
             set_window_mode,
             type_text,
+            type_string,
+            // Compositor commands
+            compositor::get_compositor,
+            compositor::get_active_window,
+            // Focus mode commands
+            focus::get_focus_mode,
+            focus::set_focus_mode,
+            // Daemon commands
+            dbus::get_daemon_status,
             // Clipboard commands
             get_clipboard_history,
             add_clipboard_item,
             remove_clipboard_item,
+            remove_clipboard_item_by_id,
+            pin_clipboard_item,
+            unpin_clipboard_item,
+            capture::get_clipboard_capture_enabled,
+            capture::set_clipboard_capture_enabled,
+            export_clipboard_history,
+            import_clipboard_history,
+            diff_clipboard_items,
+            transform_clipboard_item,
+            merge_clipboard_items,
             paste_item,
+            search_emoji,
+            copy_emoji,
             // Config commands
             read_config,
             save_config,
@@ -501,20 +1979,84 @@ fn run_gui(start_minimized: bool) {
             reload_config,
             // Launcher commands
             get_recent_files,
+            record_recent_file,
             get_vscode_recent_files,
+            get_sublime_recent_files,
+            get_sqlite_recent_files,
+            get_zoxide_dirs,
             execute_command,
+            execute_desktop_entry,
+            run_shell_command,
+            kill_shell_command,
+            take_backend_errors,
+            run_launcher_action,
             get_launcher_config,
+            record_launch,
+            open_web_search,
+            search_script_providers,
+            activate_script_result,
             search_desktop_files,
+            search_files,
+            list_systemd_units,
+            systemd_unit_action,
+            // Plugin commands
+            plugins::list_plugins,
+            plugins::reload_plugins,
+            plugins::search_plugins,
+            plugins::activate_plugin_item,
+            plugins::transform_with_plugin,
+            plugins::run_plugin_command,
             // Snippets commands
             get_snippets,
             add_snippet,
+            update_snippet,
+            delete_snippet,
+            reorder_snippets,
+            get_snippet_categories,
+            get_snippets_by_category,
+            set_snippet_category,
+            render_snippet,
+            get_snippet_variables,
+            render_snippet_with_vars,
+            import_snippets,
+            set_snippet_slot,
+            get_snippet_by_slot,
+            // Logging commands
+            logging::get_log_level,
+            logging::set_log_level,
+            logging::get_recent_logs,
+            // Debug commands
+            get_state_snapshot,
+            // HTTP API commands
+            http_api::get_http_api_token,
+            http_api::regenerate_http_api_token,
             // Settings commands
             get_version,
             get_autostart_status,
             set_autostart,
             check_for_updates,
             check_for_updates_with_dialog,
+            install_update,
+            skip_update,
+            remind_update_later,
             open_github,
+            // Tool commands
+            get_world_clock,
+            convert_time,
+            define_word,
+            define_clipboard_selection,
+            translate_text,
+            translate_and_paste,
+            set_translation_api_key,
+            take_screenshot,
+            ocr_screen_region,
+            search_processes,
+            terminate_process,
+            kill_process,
+            get_system_stats,
+            get_network_info,
+            get_public_ip,
+            inspect_text,
         ])
         .setup(move |app| {
             let window = app.get_webview_window("main").unwrap();
@@ -530,17 +2072,17 @@ fn run_gui(start_minimized: bool) {
 
             // Setup tray icon
             if let Err(e) = tray::setup_tray(app) {
-                eprintln!("Failed to setup tray: {}", e);
+                error!("Failed to setup tray: {}", e);
             }
 
             // Auto-hide window when it loses focus (unless pinned)
             let window_for_blur = window.clone();
-            let is_pinned = app.state::<PinState>().is_pinned.clone();
+            let is_pinned = app.state::<AppState>().pinned.clone();
             window.on_window_event(move |event| {
                 if let tauri::WindowEvent::Focused(focused) = event {
                     if !focused && !is_pinned.load(Ordering::SeqCst) {
                         let _ = window_for_blur.hide();
-                        println!("Window lost focus, hiding");
+                        info!("Window lost focus, hiding");
                     }
                 }
             });
@@ -550,6 +2092,51 @@ fn run_gui(start_minimized: bool) {
                 listen_dbus(window_clone).await;
             });
 
+            // Start plugins in the background
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                app_handle.state::<plugins::PluginHost>().load().await;
+            });
+
+            // Record clipboard changes
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                capture::watcher::run(app_handle).await;
+            });
+
+            // Drop clipboard items past their TTL
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                capture::expiry::run(app_handle).await;
+            });
+
+            // Reload snippets edited elsewhere (e.g., a synced folder)
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                snippet_sync::run(app_handle).await;
+            });
+
+            // Follow the focus mode schedule
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                focus::run_schedule(app_handle).await;
+            });
+
+            // Serve the scripting socket
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                ipc::serve(app_handle).await;
+            });
+
+            // Start the local HTTP API if enabled
+            if config.http_api.enabled {
+                let app_handle = app.handle().clone();
+                let port = config.http_api.port;
+                tauri::async_runtime::spawn(async move {
+                    http_api::serve(app_handle, port).await;
+                });
+            }
+
             Ok(())
         })
         .run(tauri::generate_context!())
@@ -0,0 +1,165 @@
+//! Plugin manifest and discovery
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tracing::warn;
+
+/// Manifest file name inside each plugin directory
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// Plugin manifest (`~/.config/uti/plugins/<name>/plugin.json`)
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    /// Plugin name (unique, used to address the plugin)
+    pub name: String,
+
+    /// Executable to run (relative paths resolve against the plugin directory)
+    pub command: String,
+
+    /// Arguments passed to the executable
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Short description shown in the plugin list
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A discovered plugin
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredPlugin {
+    pub manifest: PluginManifest,
+    /// Directory containing the manifest (used as working directory)
+    pub dir: PathBuf,
+}
+
+impl DiscoveredPlugin {
+    /// Resolves the executable path
+    pub fn executable(&self) -> PathBuf {
+        let command = Path::new(&self.manifest.command);
+        if command.is_absolute() || !self.manifest.command.contains('/') {
+            // Absolute path or bare name looked up in PATH
+            command.to_path_buf()
+        } else {
+            self.dir.join(command)
+        }
+    }
+}
+
+/// Get the plugins directory
+///
+/// Returns `~/.config/uti/plugins/`
+pub fn get_plugins_dir() -> PathBuf {
+    let mut path = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    path.push("uti");
+    path.push("plugins");
+    path
+}
+
+/// Discovers plugins in a directory
+///
+/// Each subdirectory with a valid `plugin.json` is a plugin. Invalid
+/// manifests and duplicate names are skipped with a warning.
+pub fn discover_plugins(dir: &Path) -> Vec<DiscoveredPlugin> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+
+    let mut plugins: Vec<DiscoveredPlugin> = Vec::new();
+    for dir in dirs {
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let Ok(contents) = std::fs::read_to_string(&manifest_path) else {
+            continue;
+        };
+
+        let manifest = match serde_json::from_str::<PluginManifest>(&contents) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                warn!("Invalid plugin manifest {:?}: {}", manifest_path, e);
+                continue;
+            }
+        };
+
+        if plugins.iter().any(|p| p.manifest.name == manifest.name) {
+            warn!(
+                "Duplicate plugin name '{}' in {:?}, skipping",
+                manifest.name, dir
+            );
+            continue;
+        }
+
+        plugins.push(DiscoveredPlugin { manifest, dir });
+    }
+
+    plugins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_manifest(root: &Path, dir: &str, contents: &str) {
+        let dir = root.join(dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(MANIFEST_FILE), contents).unwrap();
+    }
+
+    #[test]
+    fn test_discover_plugins() {
+        let temp_dir = TempDir::new().unwrap();
+        write_manifest(
+            temp_dir.path(),
+            "b-plugin",
+            r#"{ "name": "b", "command": "./run.sh", "args": ["--stdio"] }"#,
+        );
+        write_manifest(
+            temp_dir.path(),
+            "a-plugin",
+            r#"{ "name": "a", "command": "a" }"#,
+        );
+        write_manifest(temp_dir.path(), "broken", "{ not json");
+        write_manifest(temp_dir.path(), "dup", r#"{ "name": "a", "command": "x" }"#);
+
+        let plugins = discover_plugins(temp_dir.path());
+        let names: Vec<&str> = plugins.iter().map(|p| p.manifest.name.as_str()).collect();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(plugins[1].manifest.args, vec!["--stdio"]);
+    }
+
+    #[test]
+    fn test_discover_plugins_missing_dir() {
+        assert!(discover_plugins(Path::new("/nonexistent/uti/plugins")).is_empty());
+    }
+
+    #[test]
+    fn test_executable_resolution() {
+        let plugin = |command: &str| DiscoveredPlugin {
+            manifest: PluginManifest {
+                name: "p".to_string(),
+                command: command.to_string(),
+                args: Vec::new(),
+                description: None,
+            },
+            dir: PathBuf::from("/plugins/p"),
+        };
+
+        assert_eq!(
+            plugin("./run.sh").executable(),
+            PathBuf::from("/plugins/p/./run.sh")
+        );
+        assert_eq!(plugin("python3").executable(), PathBuf::from("python3"));
+        assert_eq!(
+            plugin("/usr/bin/node").executable(),
+            PathBuf::from("/usr/bin/node")
+        );
+    }
+}
@@ -0,0 +1,327 @@
+//! Plugin system
+//!
+//! Runs external plugins discovered in `~/.config/uti/plugins/` as child
+//! processes speaking JSON-RPC over stdio. A plugin can provide launcher
+//! search results, clipboard transforms and commands. See
+//! `docs/plugins.md` for the protocol.
+
+mod manifest;
+mod rpc;
+
+use manifest::{discover_plugins, get_plugins_dir};
+use parking_lot::Mutex;
+use rpc::PluginProcess;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tauri::State;
+use tracing::{error, info};
+
+/// A transform or command registered by a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginEntry {
+    pub id: String,
+    pub label: String,
+}
+
+/// What a plugin registers in its `initialize` response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginCapabilities {
+    /// Whether the plugin answers `launcher/search`
+    #[serde(default)]
+    pub launcher: bool,
+    /// Clipboard transforms (`clipboard/transform`)
+    #[serde(default)]
+    pub transforms: Vec<PluginEntry>,
+    /// Commands (`command/run`)
+    #[serde(default)]
+    pub commands: Vec<PluginEntry>,
+}
+
+/// A loaded plugin as shown to the frontend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    pub name: String,
+    pub description: Option<String>,
+    pub capabilities: PluginCapabilities,
+}
+
+/// A launcher result provided by a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginSearchResult {
+    /// Plugin that returned the result (filled in by uti)
+    #[serde(default)]
+    pub plugin: String,
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A running plugin
+struct LoadedPlugin {
+    info: PluginInfo,
+    process: tokio::sync::Mutex<PluginProcess>,
+}
+
+/// Application state holding the running plugins
+#[derive(Default)]
+pub struct PluginHost {
+    plugins: Mutex<Vec<Arc<LoadedPlugin>>>,
+}
+
+impl PluginHost {
+    /// Discovers and starts all plugins, replacing running ones
+    pub async fn load(&self) -> Vec<PluginInfo> {
+        let mut loaded = Vec::new();
+
+        for plugin in discover_plugins(&get_plugins_dir()) {
+            let name = plugin.manifest.name.clone();
+            match start_plugin(&plugin).await {
+                Ok(process) => {
+                    info!("Loaded plugin: {}", name);
+                    loaded.push(Arc::new(process));
+                }
+                Err(e) => error!("Failed to load plugin '{}': {}", name, e),
+            }
+        }
+
+        let infos = loaded.iter().map(|p| p.info.clone()).collect();
+        // Dropping the previous plugins kills their processes
+        *self.plugins.lock() = loaded;
+        infos
+    }
+
+    /// Gets the names of the running plugins
+    pub fn names(&self) -> Vec<String> {
+        self.plugins
+            .lock()
+            .iter()
+            .map(|p| p.info.name.clone())
+            .collect()
+    }
+
+    /// Gets the running plugins
+    fn plugins(&self) -> Vec<Arc<LoadedPlugin>> {
+        self.plugins.lock().clone()
+    }
+
+    /// Finds a running plugin by name
+    fn find(&self, name: &str) -> Result<Arc<LoadedPlugin>, String> {
+        self.plugins()
+            .into_iter()
+            .find(|p| p.info.name == name)
+            .ok_or_else(|| format!("Plugin '{}' not found", name))
+    }
+}
+
+/// Starts a plugin process and performs the `initialize` handshake
+async fn start_plugin(plugin: &manifest::DiscoveredPlugin) -> Result<LoadedPlugin, String> {
+    let mut process = PluginProcess::spawn(plugin)?;
+    let result = process
+        .call(
+            "initialize",
+            json!({ "version": env!("CARGO_PKG_VERSION") }),
+        )
+        .await?;
+    let capabilities: PluginCapabilities = serde_json::from_value(result)
+        .map_err(|e| format!("Invalid initialize response: {}", e))?;
+
+    Ok(LoadedPlugin {
+        info: PluginInfo {
+            name: plugin.manifest.name.clone(),
+            description: plugin.manifest.description.clone(),
+            capabilities,
+        },
+        process: tokio::sync::Mutex::new(process),
+    })
+}
+
+impl LoadedPlugin {
+    async fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        self.process.lock().await.call(method, params).await
+    }
+}
+
+/// Lists the running plugins
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const plugins = await invoke('list_plugins');
+/// ```
+#[tauri::command]
+pub fn list_plugins(host: State<'_, PluginHost>) -> Vec<PluginInfo> {
+    host.plugins().iter().map(|p| p.info.clone()).collect()
+}
+
+/// Restarts all plugins, picking up added or removed ones
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const plugins = await invoke('reload_plugins');
+/// ```
+#[tauri::command]
+pub async fn reload_plugins(host: State<'_, PluginHost>) -> Result<Vec<PluginInfo>, String> {
+    info!("Reloading plugins...");
+    Ok(host.load().await)
+}
+
+/// Searches all launcher plugins
+///
+/// Plugins are queried concurrently. A failing plugin is logged and
+/// skipped so it can't break the launcher.
+///
+/// # Arguments
+///
+/// * `query` - Search query typed in the launcher
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const results = await invoke('search_plugins', { query: 'todo' });
+/// ```
+#[tauri::command]
+pub async fn search_plugins(
+    query: String,
+    host: State<'_, PluginHost>,
+) -> Result<Vec<PluginSearchResult>, String> {
+    let searches = host
+        .plugins()
+        .into_iter()
+        .filter(|p| p.info.capabilities.launcher)
+        .map(|plugin| {
+            let query = query.clone();
+            async move {
+                let result = plugin
+                    .call("launcher/search", json!({ "query": query }))
+                    .await
+                    .and_then(|value| {
+                        serde_json::from_value::<Vec<PluginSearchResult>>(value)
+                            .map_err(|e| format!("Invalid search response: {}", e))
+                    });
+                (plugin, result)
+            }
+        });
+
+    let mut results = Vec::new();
+    for (plugin, result) in futures_util::future::join_all(searches).await {
+        match result {
+            Ok(items) => results.extend(items.into_iter().map(|mut item| {
+                item.plugin = plugin.info.name.clone();
+                item
+            })),
+            Err(e) => error!("{}", e),
+        }
+    }
+    Ok(results)
+}
+
+/// Activates a launcher result returned by `search_plugins`
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('activate_plugin_item', { plugin: result.plugin, id: result.id });
+/// ```
+#[tauri::command]
+pub async fn activate_plugin_item(
+    plugin: String,
+    id: String,
+    host: State<'_, PluginHost>,
+) -> Result<(), String> {
+    let plugin = host.find(&plugin)?;
+    plugin
+        .call("launcher/activate", json!({ "id": id }))
+        .await?;
+    Ok(())
+}
+
+/// Transforms text with a plugin's clipboard transform
+///
+/// # Returns
+///
+/// Ok with the transformed text, Err with error message if failed
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const text = await invoke('transform_with_plugin', {
+///   plugin: 'case-tools', transform: 'upper', text: 'hello',
+/// });
+/// ```
+#[tauri::command]
+pub async fn transform_with_plugin(
+    plugin: String,
+    transform: String,
+    text: String,
+    host: State<'_, PluginHost>,
+) -> Result<String, String> {
+    let plugin = host.find(&plugin)?;
+    let result = plugin
+        .call(
+            "clipboard/transform",
+            json!({ "id": transform, "text": text }),
+        )
+        .await?;
+
+    result
+        .get("text")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| "Invalid transform response: missing 'text'".to_string())
+}
+
+/// Runs a plugin command
+///
+/// # Returns
+///
+/// Ok with the plugin's result value, Err with error message if failed
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const result = await invoke('run_plugin_command', {
+///   plugin: 'notes', command: 'new-note', args: { title: 'Idea' },
+/// });
+/// ```
+#[tauri::command]
+pub async fn run_plugin_command(
+    plugin: String,
+    command: String,
+    args: Option<Value>,
+    host: State<'_, PluginHost>,
+) -> Result<Value, String> {
+    let plugin = host.find(&plugin)?;
+    plugin
+        .call(
+            "command/run",
+            json!({ "id": command, "args": args.unwrap_or(Value::Null) }),
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_defaults() {
+        let caps: PluginCapabilities =
+            serde_json::from_value(json!({ "transforms": [{ "id": "upper", "label": "UPPER" }] }))
+                .unwrap();
+        assert!(!caps.launcher);
+        assert_eq!(caps.transforms[0].id, "upper");
+        assert!(caps.commands.is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! JSON-RPC over plugin stdio
+//!
+//! Plugins are child processes that read one JSON-RPC 2.0 request per line
+//! on stdin and write one response per line on stdout. Anything a plugin
+//! writes to stderr ends up in uti's log.
+
+use super::manifest::DiscoveredPlugin;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tracing::warn;
+
+/// Maximum time to wait for a plugin response
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// JSON-RPC response (only the fields uti reads)
+#[derive(Debug, Deserialize)]
+struct Response {
+    id: Option<u64>,
+    #[serde(default)]
+    result: Value,
+    error: Option<ResponseError>,
+}
+
+/// JSON-RPC error object
+#[derive(Debug, Deserialize)]
+struct ResponseError {
+    message: String,
+}
+
+/// A running plugin process
+pub struct PluginProcess {
+    name: String,
+    // Held so the process is killed when dropped
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    next_id: u64,
+}
+
+impl PluginProcess {
+    /// Starts a plugin process
+    pub fn spawn(plugin: &DiscoveredPlugin) -> Result<Self, String> {
+        let name = &plugin.manifest.name;
+        let mut child = Command::new(plugin.executable())
+            .args(&plugin.manifest.args)
+            .current_dir(&plugin.dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to start plugin '{}': {}", name, e))?;
+
+        let stdin = child.stdin.take().ok_or("Plugin stdin unavailable")?;
+        let stdout = child.stdout.take().ok_or("Plugin stdout unavailable")?;
+
+        Ok(Self {
+            name: name.clone(),
+            _child: child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+            next_id: 1,
+        })
+    }
+
+    /// Sends a request and waits for its response
+    ///
+    /// # Returns
+    ///
+    /// Ok with the `result` value, Err with error message if the plugin
+    /// returned an error, exited or timed out
+    pub async fn call(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        });
+        let mut line = request.to_string();
+        line.push('\n');
+
+        self.stdin
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to plugin '{}': {}", self.name, e))?;
+        self.stdin
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to write to plugin '{}': {}", self.name, e))?;
+
+        tokio::time::timeout(REQUEST_TIMEOUT, self.read_response(id))
+            .await
+            .map_err(|_| format!("Plugin '{}' timed out on '{}'", self.name, method))?
+    }
+
+    /// Reads lines until the response with the given id arrives
+    async fn read_response(&mut self, id: u64) -> Result<Value, String> {
+        loop {
+            let line = self
+                .stdout
+                .next_line()
+                .await
+                .map_err(|e| format!("Failed to read from plugin '{}': {}", self.name, e))?
+                .ok_or_else(|| format!("Plugin '{}' exited", self.name))?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let response = match serde_json::from_str::<Response>(&line) {
+                Ok(response) => response,
+                Err(e) => {
+                    warn!("Ignoring invalid output from plugin '{}': {}", self.name, e);
+                    continue;
+                }
+            };
+
+            // Skip notifications and stale responses
+            if response.id != Some(id) {
+                continue;
+            }
+
+            return match response.error {
+                Some(error) => Err(format!("Plugin '{}' error: {}", self.name, error.message)),
+                None => Ok(response.result),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::manifest::PluginManifest;
+    use std::path::PathBuf;
+
+    /// Plugin that answers each request with a fixed line from `script`
+    fn shell_plugin(script: &str) -> DiscoveredPlugin {
+        DiscoveredPlugin {
+            manifest: PluginManifest {
+                name: "test".to_string(),
+                command: "sh".to_string(),
+                args: vec!["-c".to_string(), script.to_string()],
+                description: None,
+            },
+            dir: PathBuf::from("/"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_call_returns_result() {
+        let plugin = shell_plugin(
+            r#"read line; echo 'not json'; echo '{"jsonrpc":"2.0","method":"log"}'; echo '{"jsonrpc":"2.0","id":1,"result":{"ok":true}}'"#,
+        );
+        let mut process = PluginProcess::spawn(&plugin).unwrap();
+        let result = process.call("initialize", json!({})).await.unwrap();
+        assert_eq!(result, json!({ "ok": true }));
+    }
+
+    #[tokio::test]
+    async fn test_call_returns_error() {
+        let plugin = shell_plugin(
+            r#"read line; echo '{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"nope"}}'"#,
+        );
+        let mut process = PluginProcess::spawn(&plugin).unwrap();
+        let err = process.call("missing", json!({})).await.unwrap_err();
+        assert!(err.contains("nope"));
+    }
+
+    #[tokio::test]
+    async fn test_call_plugin_exited() {
+        let mut process = PluginProcess::spawn(&shell_plugin("exit 0")).unwrap();
+        assert!(process.call("initialize", json!({})).await.is_err());
+    }
+}
@@ -4,8 +4,9 @@
 //! and provides commands for settings UI functionality.
 
 use serde::Serialize;
-use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+use tauri::{AppHandle, Emitter, Manager, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
 use tauri_plugin_autostart::ManagerExt;
+use tracing::{error, info};
 
 /// Window size constants for each theme
 pub mod window_size {
@@ -42,9 +43,9 @@ pub fn apply_window_size(window: &WebviewWindow, size: &str) {
     let (width, height) = window_size::by_theme(size);
 
     if let Err(e) = window.set_size(tauri::LogicalSize::new(width, height)) {
-        eprintln!("Failed to set window size: {}", e);
+        error!("Failed to set window size: {}", e);
     } else {
-        println!("Window size set to {}x{} ({})", width, height, size);
+        info!("Window size set to {}x{} ({})", width, height, size);
     }
 }
 
@@ -58,7 +59,7 @@ pub fn apply_window_size(window: &WebviewWindow, size: &str) {
 /// * `window` - The Tauri window instance
 /// * `mode` - The window mode: "prompt" or "default"
 #[tauri::command]
-pub fn set_window_mode(window: WebviewWindow, mode: String) {
+pub fn set_window_mode(window: WebviewWindow, mode: String) -> crate::error::Result<()> {
     use crate::config::AppConfig;
 
     let (width, height) = match mode.as_str() {
@@ -70,11 +71,9 @@ pub fn set_window_mode(window: WebviewWindow, mode: String) {
         }
     };
 
-    if let Err(e) = window.set_size(tauri::LogicalSize::new(width, height)) {
-        eprintln!("Failed to set window mode: {}", e);
-    } else {
-        println!("Window mode set to {} ({}x{})", mode, width, height);
-    }
+    window.set_size(tauri::LogicalSize::new(width, height))?;
+    info!("Window mode set to {} ({}x{})", mode, width, height);
+    Ok(())
 }
 
 /// Gets the current application version
@@ -140,12 +139,12 @@ pub fn set_autostart(app: AppHandle, enabled: bool) -> Result<(), String> {
         autostart_manager
             .enable()
             .map_err(|e| format!("Failed to enable autostart: {}", e))?;
-        println!("Auto-start enabled");
+        info!("Auto-start enabled");
     } else {
         autostart_manager
             .disable()
             .map_err(|e| format!("Failed to disable autostart: {}", e))?;
-        println!("Auto-start disabled");
+        info!("Auto-start disabled");
     }
 
     Ok(())
@@ -157,6 +156,10 @@ pub struct UpdateCheckResult {
     pub current_version: String,
     pub latest_version: String,
     pub update_available: bool,
+    /// What changed in the latest release (markdown)
+    pub release_notes: Option<String>,
+    /// A newer release was skipped or postponed by the user
+    pub declined: bool,
 }
 
 /// Checks for updates from settings UI
@@ -182,7 +185,7 @@ pub async fn check_for_updates() -> Result<UpdateCheckResult, String> {
 
     let current_version = env!("CARGO_PKG_VERSION");
 
-    let result = updater::check_for_updates(current_version)
+    let result = updater::check_for_updates(current_version, updater::UpdateChannel::configured())
         .await
         .map_err(|e| format!("{}", e))?;
 
@@ -190,9 +193,28 @@ pub async fn check_for_updates() -> Result<UpdateCheckResult, String> {
         current_version: result.current_version,
         latest_version: result.latest_version,
         update_available: result.update_available,
+        release_notes: result.release_notes,
+        declined: result.declined,
     })
 }
 
+/// How to install an available update, shown in the update dialog
+pub fn install_hint() -> String {
+    format!(
+        "Install it now, or run '{}' in terminal.",
+        crate::updater::manual_update_command()
+    )
+}
+
+/// Message for a newer release the user skipped or postponed
+pub fn declined_message(latest_version: &str) -> String {
+    format!(
+        "Version {} was skipped or postponed.\n\n{}",
+        latest_version,
+        install_hint()
+    )
+}
+
 /// URL-encode a string for use in query parameters
 fn urlencoding(s: &str) -> String {
     percent_encoding::utf8_percent_encode(s, percent_encoding::NON_ALPHANUMERIC).to_string()
@@ -218,31 +240,49 @@ pub async fn check_for_updates_with_dialog(app: AppHandle) {
 
     let current_version = env!("CARGO_PKG_VERSION").to_string();
 
-    let (title, message, kind) = match updater::check_for_updates(&current_version).await {
-        Ok(result) => {
-            if result.update_available {
-                (
-                    "Update Available".to_string(),
-                    format!(
-                        "Update available: {} -> {}\n\nRun 'uti update' in terminal to install.",
-                        result.current_version, result.latest_version
-                    ),
-                    "info".to_string(),
-                )
-            } else {
-                (
-                    "No Update".to_string(),
-                    "You are running the latest version.".to_string(),
-                    "info".to_string(),
-                )
-            }
-        }
-        Err(e) => (
-            "Update Check Failed".to_string(),
-            format!("{}", e),
-            "error".to_string(),
-        ),
-    };
+    let channel = updater::UpdateChannel::configured();
+
+    // `version` is only set for updates the user can still skip or postpone
+    let (title, message, kind, notes, installable, version) =
+        match updater::check_for_updates(&current_version, channel).await {
+            Ok(result) if result.update_available => (
+                "Update Available".to_string(),
+                format!(
+                    "Update available: {} -> {}\n\n{}",
+                    result.current_version,
+                    result.latest_version,
+                    install_hint()
+                ),
+                "info".to_string(),
+                result.release_notes.unwrap_or_default(),
+                true,
+                result.latest_version,
+            ),
+            Ok(result) if result.declined => (
+                "Update Skipped".to_string(),
+                declined_message(&result.latest_version),
+                "info".to_string(),
+                result.release_notes.unwrap_or_default(),
+                true,
+                String::new(),
+            ),
+            Ok(_) => (
+                "No Update".to_string(),
+                "You are running the latest version.".to_string(),
+                "info".to_string(),
+                String::new(),
+                false,
+                String::new(),
+            ),
+            Err(e) => (
+                "Update Check Failed".to_string(),
+                format!("{}", e),
+                "error".to_string(),
+                String::new(),
+                false,
+                String::new(),
+            ),
+        };
 
     // Close existing dialog window if any
     if let Some(existing) = app.get_webview_window("dialog") {
@@ -251,15 +291,21 @@ pub async fn check_for_updates_with_dialog(app: AppHandle) {
 
     // Create new dialog window with URL parameters
     let url = format!(
-        "update-dialog.html?title={}&message={}&kind={}",
+        "update-dialog.html?title={}&message={}&kind={}&notes={}&installable={}&version={}",
         urlencoding(&title),
         urlencoding(&message),
-        urlencoding(&kind)
+        urlencoding(&kind),
+        urlencoding(&notes),
+        installable,
+        urlencoding(&version)
     );
 
+    // Taller dialog to show the release notes
+    let height = if notes.is_empty() { 200.0 } else { 420.0 };
+
     match WebviewWindowBuilder::new(&app, "dialog", WebviewUrl::App(url.into()))
         .title("uti")
-        .inner_size(420.0, 200.0)
+        .inner_size(420.0, height)
         .resizable(false)
         .decorations(false)
         .transparent(true)
@@ -268,9 +314,95 @@ pub async fn check_for_updates_with_dialog(app: AppHandle) {
         .focused(true)
         .build()
     {
-        Ok(_) => println!("Update dialog created"),
-        Err(e) => eprintln!("Failed to create update dialog: {:?}", e),
+        Ok(_) => info!("Update dialog created"),
+        Err(e) => error!("Failed to create update dialog: {:?}", e),
+    }
+}
+
+/// Event with [`UpdateProgress`](crate::updater::UpdateProgress) while
+/// [`install_update`] runs
+pub const UPDATE_PROGRESS_EVENT: &str = "update-progress";
+
+/// Downloads and installs the latest update, from the update dialog
+///
+/// Emits `update-progress` events with the phase and downloaded bytes.
+/// Installing the RPMs asks for authentication through pkexec.
+///
+/// # Returns
+///
+/// Ok(false) if there is no update to install. A skipped or postponed
+/// release is still installed, since the user asked for it explicitly.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// import { listen } from '@tauri-apps/api/event';
+/// await listen('update-progress', (e) => console.log(e.payload)); // { phase, downloaded, total }
+/// const installed = await invoke<boolean>('install_update');
+/// ```
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<bool, String> {
+    use crate::updater::{self, UpdatePhase, UpdateProgress};
+
+    let emit = |progress: UpdateProgress| {
+        let _ = app.emit(UPDATE_PROGRESS_EVENT, progress);
+    };
+
+    emit(UpdateProgress::start(UpdatePhase::Checking));
+    let channel = updater::UpdateChannel::configured();
+    let result = updater::check_for_updates(env!("CARGO_PKG_VERSION"), channel)
+        .await
+        .map_err(|e| e.to_string())?;
+    if !result.update_available && !result.declined {
+        return Ok(false);
     }
+
+    updater::perform_update_with_progress(&result, &updater::Component::ALL, &emit)
+        .await
+        .map_err(|e| e.to_string())?;
+    info!("Update {} installed", result.latest_version);
+    Ok(true)
+}
+
+/// Skips a release, so update checks stop offering it
+///
+/// Newer releases are offered again.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('skip_update', { version: '0.9.0' });
+/// ```
+#[tauri::command]
+pub fn skip_update(version: String) -> Result<(), String> {
+    let mut decisions = crate::updater::UpdateDecisions::load();
+    decisions.skip(&version);
+    decisions
+        .save()
+        .map_err(|e| format!("Failed to save update decision: {}", e))?;
+    info!("Skipping update {}", version);
+    Ok(())
+}
+
+/// Postpones update offers for a number of days
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('remind_update_later', { days: 7 });
+/// ```
+#[tauri::command]
+pub fn remind_update_later(days: u32) -> Result<(), String> {
+    let mut decisions = crate::updater::UpdateDecisions::load();
+    decisions.remind_in(days, chrono::Utc::now().timestamp());
+    decisions
+        .save()
+        .map_err(|e| format!("Failed to save update decision: {}", e))?;
+    info!("Postponing updates for {} days", days);
+    Ok(())
 }
 
 /// Opens the GitHub repository in the default browser
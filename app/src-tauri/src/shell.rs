@@ -0,0 +1,219 @@
+//! Shell commands from the launcher
+//!
+//! Typing `>` followed by a command in the launcher runs it through `sh -c`.
+//! Output is read line by line as the command runs and streamed to the
+//! frontend as `shell-output` events, followed by one `shell-exit` event.
+//! Events carry the run id returned by [`run`], so output of an older
+//! command can be told apart, and [`kill`] stops a run that hangs.
+
+use crate::error;
+use serde::Serialize;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::Command;
+use tokio::sync::oneshot;
+use tracing::{info, warn};
+
+/// Event carrying a line of output
+pub const OUTPUT_EVENT: &str = "shell-output";
+
+/// Event sent when a command has finished
+pub const EXIT_EVENT: &str = "shell-exit";
+
+/// Id of the next run
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Runs still going, with the sender that asks each one to stop
+static RUNNING: parking_lot::Mutex<Vec<(u64, oneshot::Sender<()>)>> =
+    parking_lot::const_mutex(Vec::new());
+
+/// A line a command wrote
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ShellOutput {
+    /// Run id
+    id: u64,
+    /// `stdout` or `stderr`
+    stream: &'static str,
+    /// Line without its line break
+    line: String,
+}
+
+/// How a command finished
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ShellExit {
+    /// Run id
+    id: u64,
+    /// Exit code, None if killed by a signal or not waited for
+    code: Option<i32>,
+}
+
+/// Stops a running command and everything it started
+///
+/// # Returns
+///
+/// false if the run already finished
+pub fn kill(id: u64) -> bool {
+    let mut running = RUNNING.lock();
+    let Some(index) = running.iter().position(|(run, _)| *run == id) else {
+        return false;
+    };
+    let (_, stop) = running.swap_remove(index);
+    stop.send(()).is_ok()
+}
+
+/// Starts a shell command and streams its output as events
+///
+/// Must be called from within the async runtime, which drives the child
+/// process.
+///
+/// # Returns
+///
+/// The run id used in the command's events
+///
+/// # Errors
+///
+/// Returns an error if the command is empty or the shell can't be started
+pub fn run(app: AppHandle, command: &str) -> error::Result<u64> {
+    let command = command.trim();
+    if command.is_empty() {
+        return Err("Shell command is empty".to_string().into());
+    }
+
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Own process group, so killing the run also kills what it started
+        .process_group(0)
+        .spawn()
+        .map_err(|source| error::Error::Launch {
+            command: command.to_string(),
+            source,
+        })?;
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    info!("Running shell command {}: {}", id, command);
+
+    let stdout = child
+        .stdout
+        .take()
+        .map(|out| tauri::async_runtime::spawn(stream_lines(app.clone(), id, "stdout", out)));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|err| tauri::async_runtime::spawn(stream_lines(app.clone(), id, "stderr", err)));
+
+    let (stop, stopped) = oneshot::channel();
+    RUNNING.lock().push((id, stop));
+
+    tauri::async_runtime::spawn(async move {
+        let status = tokio::select! {
+            status = child.wait() => status,
+            Ok(()) = stopped => {
+                info!("Killing shell command {}", id);
+                if let Some(pid) = child.id() {
+                    // SAFETY: signals the process group the child leads
+                    unsafe { libc::kill(-(pid as i32), libc::SIGKILL) };
+                }
+                child.wait().await
+            }
+        };
+        RUNNING.lock().retain(|(run, _)| *run != id);
+        let code = match status {
+            Ok(status) => status.code(),
+            Err(e) => {
+                warn!("Failed to wait for shell command {}: {}", id, e);
+                None
+            }
+        };
+        // Read all output before reporting the exit
+        for task in [stdout, stderr].into_iter().flatten() {
+            let _ = task.await;
+        }
+        info!("Shell command {} exited with {:?}", id, code);
+        let _ = app.emit(EXIT_EVENT, ShellExit { id, code });
+    });
+
+    Ok(id)
+}
+
+/// Emits each line of a command's output stream
+async fn stream_lines(
+    app: AppHandle,
+    id: u64,
+    stream: &'static str,
+    output: impl AsyncRead + Unpin,
+) {
+    read_lines(output, |line| {
+        let _ = app.emit(OUTPUT_EVENT, ShellOutput { id, stream, line });
+    })
+    .await;
+}
+
+/// Calls `on_line` for each line until the end of the output
+///
+/// Output that isn't UTF-8 is converted lossily. Reading continues past
+/// errors so the pipe keeps draining and the command can't block on it.
+async fn read_lines(output: impl AsyncRead + Unpin, mut on_line: impl FnMut(String)) {
+    let mut reader = BufReader::new(output);
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        match reader.read_until(b'\n', &mut buf).await {
+            Ok(0) => break,
+            Ok(_) => {
+                if buf.ends_with(b"\n") {
+                    buf.pop();
+                    if buf.ends_with(b"\r") {
+                        buf.pop();
+                    }
+                }
+                on_line(String::from_utf8_lossy(&buf).into_owned());
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => {
+                warn!("Failed to read shell output: {}", e);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn lines_of(output: &[u8]) -> Vec<String> {
+        let mut lines = Vec::new();
+        read_lines(output, |line| lines.push(line)).await;
+        lines
+    }
+
+    #[tokio::test]
+    async fn test_read_lines() {
+        assert_eq!(
+            lines_of(b"one\r\ntwo\nthree").await,
+            ["one", "two", "three"]
+        );
+        assert!(lines_of(b"").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_lines_keeps_reading_past_invalid_utf8() {
+        assert_eq!(
+            lines_of(b"bad \xff byte\nafter").await,
+            ["bad \u{FFFD} byte", "after"]
+        );
+    }
+
+    #[test]
+    fn test_kill_finished_run() {
+        assert!(!kill(u64::MAX));
+    }
+}
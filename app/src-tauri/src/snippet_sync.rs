@@ -0,0 +1,99 @@
+//! Snippets file sync
+//!
+//! The snippets file can live outside the config directory
+//! (`snippetsPath`), e.g., in a Syncthing or Nextcloud folder. It's checked
+//! for changes every few seconds, and edits made elsewhere (another
+//! machine, a text editor) replace the snippets in memory and emit
+//! `snippets-changed`. Changing `snippetsPath` switches files without a
+//! restart; when the new file doesn't exist yet, the current snippets are
+//! written there.
+
+use crate::config::AppConfig;
+use crate::snippets::{read_snippets, save_snippets, SnippetsError};
+use crate::state::AppState;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+/// How often the snippets file is checked for changes
+const SYNC_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Follows the snippets file until the app exits
+pub async fn run(app: AppHandle) {
+    let config_path = AppConfig::get_config_path();
+    let mut config_modified: Option<SystemTime> = None;
+    let mut path = app.state::<AppState>().snippets.read().file_path();
+    let mut modified = modified_time(&path);
+
+    loop {
+        let current = modified_time(&config_path);
+        if current != config_modified {
+            config_modified = current;
+            let configured = AppConfig::load().snippets_file();
+            if configured != path {
+                info!("Snippets file changed to {:?}", configured);
+                path = configured;
+                modified = None;
+                if !path.exists() {
+                    move_to(&app, &path);
+                }
+            }
+        }
+
+        let current = modified_time(&path);
+        if current.is_some() && current != modified {
+            modified = current;
+            reload(&app, &path);
+        }
+
+        tokio::time::sleep(SYNC_INTERVAL).await;
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Writes the current snippets to a new file and saves there from now on
+fn move_to(app: &AppHandle, path: &Path) {
+    let state = app.state::<AppState>();
+    let mut store = state.snippets.write();
+    store.path = Some(path.to_path_buf());
+    if let Err(e) = save_snippets(&store) {
+        crate::error::report(
+            app,
+            "snippets",
+            format!("Failed to write snippets to {}: {}", path.display(), e),
+        );
+    }
+}
+
+/// Replaces the snippets with the file's, emitting `snippets-changed` if
+/// they differ
+///
+/// An unreadable file (e.g., one a sync tool is still writing) is skipped;
+/// it's read again on its next change.
+fn reload(app: &AppHandle, path: &Path) {
+    let loaded = match read_snippets(path) {
+        Ok(loaded) => loaded,
+        Err(SnippetsError::Read(e)) if e.kind() == ErrorKind::NotFound => return,
+        Err(e) => {
+            warn!("Not reloading snippets from {:?}: {}", path, e);
+            return;
+        }
+    };
+
+    let changed = {
+        let state = app.state::<AppState>();
+        let mut store = state.snippets.write();
+        let changed = store.items != loaded.items;
+        *store = loaded;
+        changed
+    };
+    if changed {
+        info!("Reloaded snippets from {:?}", path);
+        let _ = app.emit("snippets-changed", ());
+    }
+}
@@ -0,0 +1,672 @@
+//! Snippet import from other text expanders
+//!
+//! - Espanso: YAML match files (`matches:` with `trigger`/`triggers`,
+//!   `replace` and optional `label`). The trigger becomes the label unless
+//!   the match has one. Matches without a plain `replace` (forms, images,
+//!   shell commands) are skipped. Only the YAML that match files use is
+//!   understood: block mappings and sequences, quoted and plain scalars,
+//!   flow sequences and `|`/`>` block scalars.
+//! - CSV: `label,value[,category]` rows, or any column order with a header
+//!   row naming them.
+//! - JSON: an array of objects (or `{ "items": [...] }`, as in
+//!   snippets.json) with `value`/`text`/`content`, `label`/`name`/`trigger`
+//!   and `category`/`folder` keys, or an array of strings.
+
+use super::SnippetItem;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+
+/// Keys and CSV headers read as the snippet value
+const VALUE_KEYS: [&str; 5] = ["value", "text", "content", "replace", "snippet"];
+
+/// Keys and CSV headers read as the label
+const LABEL_KEYS: [&str; 6] = [
+    "label",
+    "name",
+    "title",
+    "trigger",
+    "abbreviation",
+    "keyword",
+];
+
+/// Keys and CSV headers read as the category
+const CATEGORY_KEYS: [&str; 3] = ["category", "folder", "group"];
+
+/// Import file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    /// Espanso YAML match file
+    Espanso,
+    /// Comma-separated values
+    Csv,
+    /// JSON array of snippets
+    Json,
+}
+
+impl ImportFormat {
+    /// Picks the format from a file extension (`.yml`/`.yaml` for espanso,
+    /// `.csv`, anything else is JSON)
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase)
+            .as_deref()
+        {
+            Some("yml" | "yaml") => Self::Espanso,
+            Some("csv") => Self::Csv,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// Errors from parsing an import file
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("unsupported JSON layout: expected an array of snippets")]
+    JsonLayout,
+    #[error("invalid espanso file: {0}")]
+    Espanso(String),
+    #[error("invalid CSV: {0}")]
+    Csv(String),
+}
+
+/// Parses snippets exported by another tool
+///
+/// Imported snippets get new IDs. Entries with an empty value are skipped.
+pub fn parse(content: &str, format: ImportFormat) -> Result<Vec<SnippetItem>, ImportError> {
+    let items = match format {
+        ImportFormat::Espanso => parse_espanso(content)?,
+        ImportFormat::Csv => parse_csv(content)?,
+        ImportFormat::Json => parse_json(content)?,
+    };
+    Ok(items
+        .into_iter()
+        .filter(|item| !item.value.is_empty())
+        .collect())
+}
+
+/// Builds a snippet from imported fields
+fn snippet(value: String, label: Option<String>, category: Option<String>) -> SnippetItem {
+    let label = label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    let mut item = SnippetItem::new(value, label);
+    item.set_category(category);
+    item
+}
+
+fn parse_json(content: &str) -> Result<Vec<SnippetItem>, ImportError> {
+    let entries = match serde_json::from_str(content)? {
+        Value::Array(entries) => entries,
+        Value::Object(mut object) => match ["items", "snippets"]
+            .iter()
+            .find_map(|key| object.remove(*key))
+        {
+            Some(Value::Array(entries)) => entries,
+            _ => return Err(ImportError::JsonLayout),
+        },
+        _ => return Err(ImportError::JsonLayout),
+    };
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Value::String(value) => Some(snippet(value.clone(), None, None)),
+            Value::Object(object) => {
+                let field = |keys: &[&str]| {
+                    keys.iter()
+                        .find_map(|key| object.get(*key)?.as_str())
+                        .map(str::to_string)
+                };
+                Some(snippet(
+                    field(&VALUE_KEYS)?,
+                    field(&LABEL_KEYS),
+                    field(&CATEGORY_KEYS),
+                ))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+fn parse_csv(content: &str) -> Result<Vec<SnippetItem>, ImportError> {
+    let mut rows = csv_rows(content)?.into_iter().peekable();
+
+    // Columns from a header row, or label, value, category
+    let header = rows.peek().and_then(|row| {
+        let column = |keys: &[&str]| {
+            row.iter()
+                .position(|cell| keys.contains(&cell.trim().to_lowercase().as_str()))
+        };
+        column(&VALUE_KEYS).map(|value| (value, column(&LABEL_KEYS), column(&CATEGORY_KEYS)))
+    });
+    let (value, label, category) = match header {
+        Some(columns) => {
+            rows.next();
+            columns
+        }
+        None => match rows.peek().map(Vec::len) {
+            Some(1) => (0, None, None),
+            _ => (1, Some(0), Some(2)),
+        },
+    };
+
+    Ok(rows
+        .filter_map(|row| {
+            let cell = |index: Option<usize>| index.and_then(|i| row.get(i)).cloned();
+            Some(snippet(cell(Some(value))?, cell(label), cell(category)))
+        })
+        .collect())
+}
+
+/// Splits CSV into rows of cells (RFC 4180 quoting, blank lines skipped)
+fn csv_rows(content: &str) -> Result<Vec<Vec<String>>, ImportError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.next_if_eq(&'"').is_some() {
+                    cell.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if cell.is_empty() => in_quotes = true,
+            ',' if !in_quotes => row.push(std::mem::take(&mut cell)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut cell));
+                let done = std::mem::take(&mut row);
+                if done.iter().any(|cell| !cell.is_empty()) {
+                    rows.push(done);
+                }
+            }
+            c => cell.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(ImportError::Csv("unterminated quoted field".to_string()));
+    }
+    row.push(cell);
+    if row.iter().any(|cell| !cell.is_empty()) {
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn parse_espanso(content: &str) -> Result<Vec<SnippetItem>, ImportError> {
+    let lines: Vec<Line> = content.lines().map(Line::parse).collect();
+    let Some(start) = lines.iter().position(|line| {
+        line.indent == 0 && split_key(line.text).is_some_and(|(key, _)| key == "matches")
+    }) else {
+        return Err(ImportError::Espanso("no `matches:` section".to_string()));
+    };
+    let body = &lines[start + 1..];
+    let end = body
+        .iter()
+        .position(|line| line.indent == 0 && !line.is_blank() && !line.text.starts_with('-'))
+        .unwrap_or(body.len());
+
+    let mut items = Vec::new();
+    for entry in sequence_entries(&body[..end]) {
+        let fields = mapping(&entry)?;
+        let get = |key: &str| {
+            fields
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, value)| value)
+        };
+        let Some(YamlValue::Scalar(replace)) = get("replace") else {
+            continue;
+        };
+        let trigger = match get("trigger").or(get("triggers")) {
+            Some(YamlValue::Scalar(trigger)) => Some(trigger.clone()),
+            Some(YamlValue::List(triggers)) => triggers.first().cloned(),
+            _ => None,
+        };
+        let label = match get("label") {
+            Some(YamlValue::Scalar(label)) => Some(label.clone()),
+            _ => trigger,
+        };
+        items.push(snippet(replace.clone(), label, None));
+    }
+    Ok(items)
+}
+
+/// A YAML line
+#[derive(Debug, Clone)]
+struct Line<'a> {
+    /// Leading spaces (`usize::MAX` for blank lines)
+    indent: usize,
+    /// The line without indentation
+    text: &'a str,
+    /// The raw line, for block scalars
+    raw: &'a str,
+}
+
+impl<'a> Line<'a> {
+    fn parse(raw: &'a str) -> Self {
+        let text = raw.trim_start_matches(' ').trim_end();
+        let indent = if text.is_empty() {
+            usize::MAX
+        } else {
+            raw.len() - raw.trim_start_matches(' ').len()
+        };
+        Self { indent, text, raw }
+    }
+
+    /// Checks whether the line is blank or a comment (outside block
+    /// scalars, where comments are content)
+    fn is_blank(&self) -> bool {
+        self.text.is_empty() || self.text.starts_with('#')
+    }
+}
+
+/// Value of a match field
+#[derive(Debug, PartialEq)]
+enum YamlValue {
+    Scalar(String),
+    List(Vec<String>),
+    /// Nested mappings (e.g., `vars`), which are not imported
+    Other,
+}
+
+/// Splits a block sequence into its entries, each re-indented as a mapping
+///
+/// `- trigger: x` followed by `  replace: y` becomes the lines
+/// `trigger: x` and `replace: y` at the same indentation.
+fn sequence_entries<'a>(lines: &[Line<'a>]) -> Vec<Vec<Line<'a>>> {
+    let Some(indent) = lines.iter().find(|line| !line.is_blank()).map(|l| l.indent) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<Vec<Line>> = Vec::new();
+    for line in lines {
+        if line.indent == indent && line.text.starts_with('-') {
+            let rest = line.text[1..].trim_start_matches(' ');
+            let mut entry = Vec::new();
+            if !rest.is_empty() {
+                entry.push(Line {
+                    indent: line.indent + (line.text.len() - rest.len()),
+                    text: rest,
+                    raw: line.raw,
+                });
+            }
+            entries.push(entry);
+        } else if let Some(entry) = entries.last_mut() {
+            entry.push(line.clone());
+        }
+    }
+    entries
+}
+
+/// Parses a block mapping into key/value pairs
+fn mapping(lines: &[Line]) -> Result<Vec<(String, YamlValue)>, ImportError> {
+    let Some(indent) = lines.iter().find(|line| !line.is_blank()).map(|l| l.indent) else {
+        return Ok(Vec::new());
+    };
+    let mut fields = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        i += 1;
+        if line.is_blank() || line.indent != indent {
+            continue;
+        }
+        let Some((key, value)) = split_key(line.text) else {
+            return Err(ImportError::Espanso(format!(
+                "expected `key: value`, found `{}`",
+                line.text
+            )));
+        };
+
+        // Lines indented further belong to this key
+        let nested_end = lines[i..]
+            .iter()
+            .position(|l| !l.is_blank() && l.indent <= indent)
+            .map_or(lines.len(), |n| i + n);
+        let nested = &lines[i..nested_end];
+        i = nested_end;
+
+        let value = if let Some(style) = value.strip_prefix(['|', '>']) {
+            let folded = value.starts_with('>');
+            YamlValue::Scalar(block_scalar(nested, indent, folded, style))
+        } else if !value.is_empty() {
+            match value.strip_prefix('[') {
+                Some(flow) => YamlValue::List(flow_sequence(flow)?),
+                None => YamlValue::Scalar(scalar(value)?),
+            }
+        } else if nested
+            .iter()
+            .find(|l| !l.is_blank())
+            .is_some_and(|l| l.text.starts_with('-'))
+        {
+            YamlValue::List(
+                sequence_entries(nested)
+                    .iter()
+                    .filter_map(|entry| entry.first().map(|line| scalar(line.text)))
+                    .collect::<Result<_, _>>()?,
+            )
+        } else {
+            YamlValue::Other
+        };
+        fields.push((key.to_string(), value));
+    }
+    Ok(fields)
+}
+
+/// Splits `key: value` (the value may be empty)
+fn split_key(text: &str) -> Option<(&str, &str)> {
+    let (key, value) = match text.split_once(": ") {
+        Some(pair) => pair,
+        None => (text.strip_suffix(':')?, ""),
+    };
+    let key = key.trim().trim_matches(['"', '\'']);
+    (!key.is_empty()).then_some((key, strip_comment(value.trim())))
+}
+
+/// Removes a trailing `# comment` from an unquoted value
+fn strip_comment(value: &str) -> &str {
+    if value.starts_with(['"', '\'']) {
+        return value;
+    }
+    value.split(" #").next().unwrap_or_default().trim_end()
+}
+
+/// Reads a `|` (literal) or `>` (folded) block scalar
+///
+/// # Arguments
+///
+/// * `lines` - Lines indented past the key
+/// * `key_indent` - Indentation of the key
+/// * `folded` - Whether lines are joined with spaces (`>`)
+/// * `chomping` - Indicator after `|`/`>`: "-" strips the final newline,
+///   "+" keeps trailing blank lines
+fn block_scalar(lines: &[Line], key_indent: usize, folded: bool, chomping: &str) -> String {
+    let indent = lines
+        .iter()
+        .find(|line| !line.text.is_empty())
+        .map_or(key_indent + 1, |line| line.indent);
+    let texts: Vec<&str> = lines
+        .iter()
+        .map(|line| match line.text {
+            "" => "",
+            _ => line.raw.get(indent..).unwrap_or(line.text),
+        })
+        .collect();
+
+    let mut text = if folded {
+        let mut out = String::new();
+        for (n, line) in texts.iter().enumerate() {
+            if n > 0 {
+                let previous = texts[n - 1];
+                let joins = !previous.is_empty() && !line.is_empty();
+                out.push(if joins { ' ' } else { '\n' });
+                if previous.is_empty() && !line.is_empty() && n > 1 {
+                    out.pop();
+                }
+            }
+            out.push_str(line);
+        }
+        out
+    } else {
+        texts.join("\n")
+    };
+
+    let content_len = text.trim_end_matches('\n').len();
+    if chomping.contains('+') {
+        text.push('\n');
+    } else {
+        text.truncate(content_len);
+        if !chomping.contains('-') {
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Parses a flow sequence like `[":hi", ":hello"]` (after the `[`)
+fn flow_sequence(flow: &str) -> Result<Vec<String>, ImportError> {
+    let inner = flow
+        .trim_end()
+        .strip_suffix(']')
+        .ok_or_else(|| ImportError::Espanso(format!("unterminated list `[{}`", flow)))?;
+    let mut items = Vec::new();
+    let mut rest = inner.trim();
+    while !rest.is_empty() {
+        let (item, tail) = match rest.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let end = quoted_end(rest, quote).ok_or_else(|| {
+                    ImportError::Espanso(format!("unterminated string in `[{}`", flow))
+                })?;
+                (&rest[..=end], &rest[end + 1..])
+            }
+            _ => rest.split_at(rest.find(',').unwrap_or(rest.len())),
+        };
+        items.push(scalar(item.trim())?);
+        rest = tail.trim_start().trim_start_matches(',').trim_start();
+    }
+    Ok(items)
+}
+
+/// Finds the closing quote of a quoted scalar starting at index 0
+fn quoted_end(text: &str, quote: char) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1);
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' if quote == '"' => {
+                chars.next();
+            }
+            // '' is an escaped quote in single-quoted scalars
+            '\'' if quote == '\'' && text[i + 1..].starts_with('\'') => {
+                chars.next();
+            }
+            c if c == quote => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parses a single-line scalar (plain, 'single' or "double" quoted)
+fn scalar(text: &str) -> Result<String, ImportError> {
+    let unterminated = || ImportError::Espanso(format!("unterminated string `{}`", text));
+    match text.chars().next() {
+        Some('\'') => {
+            let end = quoted_end(text, '\'').ok_or_else(unterminated)?;
+            Ok(text[1..end].replace("''", "'"))
+        }
+        Some('"') => {
+            let end = quoted_end(text, '"').ok_or_else(unterminated)?;
+            Ok(unescape(&text[1..end]))
+        }
+        _ => Ok(text.to_string()),
+    }
+}
+
+/// Resolves escapes in a double-quoted scalar
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('0') => out.push('\0'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                out.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+            }
+            Some(c) => out.push(c),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(items: &[SnippetItem]) -> Vec<(Option<&str>, &str)> {
+        items
+            .iter()
+            .map(|item| (item.label.as_deref(), item.value.as_str()))
+            .collect()
+    }
+
+    const ESPANSO: &str = r#"# Personal matches
+matches:
+  # Simple
+  - trigger: ":espanso"
+    replace: "Hi there!\nBye"
+
+  - trigger: ':quote'
+    replace: 'It''s here' # comment
+    label: Quote
+
+  - triggers: [":hi", ":hello"]
+    replace: Hello world
+
+  - trigger: ":sig"
+    replace: |
+      Best regards,
+
+      # Jon
+
+  - trigger: ":fold"
+    replace: >-
+      one
+      two
+
+  - trigger: ":date"
+    replace: "{{mydate}}"
+    vars:
+      - name: mydate
+        type: date
+        params:
+          format: "%m/%d/%Y"
+
+  - trigger: ":img"
+    image_path: "/tmp/a.png"
+
+  - triggers:
+      - ":list"
+      - ":other"
+    replace: listed
+
+global_vars:
+  - name: x
+    type: echo
+"#;
+
+    #[test]
+    fn test_espanso() {
+        let items = parse(ESPANSO, ImportFormat::Espanso).unwrap();
+        assert_eq!(
+            fields(&items),
+            vec![
+                (Some(":espanso"), "Hi there!\nBye"),
+                (Some("Quote"), "It's here"),
+                (Some(":hi"), "Hello world"),
+                (Some(":sig"), "Best regards,\n\n# Jon\n"),
+                (Some(":fold"), "one two"),
+                (Some(":date"), "{{mydate}}"),
+                (Some(":list"), "listed"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_espanso_without_matches() {
+        assert!(parse("global_vars: []\n", ImportFormat::Espanso).is_err());
+        assert!(parse("matches:\n", ImportFormat::Espanso)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_csv_with_header() {
+        let csv = "Category,Text,Name\r\nWork,\"Hello, \"\"world\"\"\",greet\r\n,\"two\nlines\",\n";
+        let items = parse(csv, ImportFormat::Csv).unwrap();
+        assert_eq!(
+            fields(&items),
+            vec![(Some("greet"), "Hello, \"world\""), (None, "two\nlines")]
+        );
+        assert_eq!(items[0].category.as_deref(), Some("Work"));
+        assert!(items[1].category.is_none());
+    }
+
+    #[test]
+    fn test_csv_without_header() {
+        let items = parse("mail,me@example.com,Personal\n\nbare\n", ImportFormat::Csv).unwrap();
+        assert_eq!(fields(&items), vec![(Some("mail"), "me@example.com")]);
+        assert_eq!(items[0].category.as_deref(), Some("Personal"));
+
+        let items = parse("just a value\nanother\n", ImportFormat::Csv).unwrap();
+        assert_eq!(
+            fields(&items),
+            vec![(None, "just a value"), (None, "another")]
+        );
+
+        assert!(parse("\"open", ImportFormat::Csv).is_err());
+    }
+
+    #[test]
+    fn test_json() {
+        let json = r#"[
+            { "trigger": ";addr", "content": "Main St 1", "folder": "Home" },
+            { "value": "plain" },
+            "bare string",
+            { "label": "no value" }
+        ]"#;
+        let items = parse(json, ImportFormat::Json).unwrap();
+        assert_eq!(
+            fields(&items),
+            vec![
+                (Some(";addr"), "Main St 1"),
+                (None, "plain"),
+                (None, "bare string")
+            ]
+        );
+        assert_eq!(items[0].category.as_deref(), Some("Home"));
+
+        // snippets.json from another machine
+        let store = r#"{ "items": [{ "id": "x", "label": "L", "value": "v" }] }"#;
+        let items = parse(store, ImportFormat::Json).unwrap();
+        assert_eq!(fields(&items), vec![(Some("L"), "v")]);
+        assert_ne!(items[0].id, "x");
+
+        assert!(parse(r#"{ "a": 1 }"#, ImportFormat::Json).is_err());
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ImportFormat::from_path(Path::new("base.YML")),
+            ImportFormat::Espanso
+        );
+        assert_eq!(
+            ImportFormat::from_path(Path::new("export.csv")),
+            ImportFormat::Csv
+        );
+        assert_eq!(
+            ImportFormat::from_path(Path::new("export.json")),
+            ImportFormat::Json
+        );
+    }
+}
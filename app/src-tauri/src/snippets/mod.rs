@@ -2,12 +2,23 @@
 //!
 //! Provides persistent snippet storage for quick text access.
 //! Snippets are saved text entries that users can quickly copy to clipboard.
+//! Values may contain placeholders that are expanded on paste (see
+//! [`render`]).
 
+pub mod import;
 mod store;
+mod template;
 
-pub use store::{load_snippets, save_snippets, SnippetsStore};
+pub use store::{load_snippets, read_snippets, save_snippets, SnippetsError, SnippetsStore};
+pub use template::{
+    render, render_with_vars, variables, RenderedSnippet, SnippetVariable, TemplateContext,
+};
 
 use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Quick-access slot numbers
+pub const SLOTS: RangeInclusive<u8> = 1..=9;
 
 /// A single snippet entry
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -20,6 +31,12 @@ pub struct SnippetItem {
     pub label: Option<String>,
     /// The actual text content
     pub value: String,
+    /// Category (folder) the snippet is filed under, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Quick-access slot (1-9), unique among snippets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u8>,
 }
 
 impl SnippetItem {
@@ -29,8 +46,20 @@ impl SnippetItem {
             id: uuid::Uuid::new_v4().to_string(),
             label,
             value,
+            category: None,
+            slot: None,
         }
     }
+
+    /// Files the snippet under a category
+    ///
+    /// A blank category is treated as none. Surrounding whitespace is
+    /// removed so "Work " and "Work" are the same category.
+    pub fn set_category(&mut self, category: Option<String>) {
+        self.category = category
+            .map(|category| category.trim().to_string())
+            .filter(|category| !category.is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -72,6 +101,16 @@ mod tests {
         }"#;
         let snippet: SnippetItem = serde_json::from_str(json).unwrap();
         assert!(snippet.label.is_none());
+        assert!(snippet.category.is_none());
+    }
+
+    #[test]
+    fn test_set_category_normalizes() {
+        let mut snippet = SnippetItem::new("value".to_string(), None);
+        snippet.set_category(Some(" Work ".to_string()));
+        assert_eq!(snippet.category.as_deref(), Some("Work"));
+        snippet.set_category(Some("  ".to_string()));
+        assert!(snippet.category.is_none());
     }
 
     #[test]
@@ -79,5 +118,6 @@ mod tests {
         let snippet = SnippetItem::new("value".to_string(), None);
         let json = serde_json::to_string(&snippet).unwrap();
         assert!(!json.contains("label"));
+        assert!(!json.contains("category"));
     }
 }
@@ -1,17 +1,37 @@
 //! Snippets storage
 //!
-//! Handles loading and saving snippets to JSON file.
+//! Handles loading and saving snippets to JSON file. The file is
+//! `~/.config/uti/snippets.json` unless the store was loaded from another
+//! path (e.g., a synced folder).
 
 use super::SnippetItem;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+
+/// Errors from reading or writing the snippets file
+#[derive(Debug, thiserror::Error)]
+pub enum SnippetsError {
+    #[error("failed to write snippets: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("failed to serialize snippets: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("failed to read snippets: {0}")]
+    Read(std::io::Error),
+    #[error("invalid snippets file: {0}")]
+    Parse(serde_json::Error),
+}
 
 /// Snippets configuration file structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct SnippetsStore {
     #[serde(default)]
     pub items: Vec<SnippetItem>,
+    /// File the store was loaded from (None for the default location)
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
 }
 
 impl SnippetsStore {
@@ -22,43 +42,192 @@ impl SnippetsStore {
             .join("uti")
             .join("snippets.json")
     }
+
+    /// Gets the file the store is saved to
+    pub fn file_path(&self) -> PathBuf {
+        self.path.clone().unwrap_or_else(Self::get_storage_path)
+    }
+
+    /// Replaces a snippet's value and label
+    ///
+    /// An empty label is removed, so the value is displayed instead.
+    ///
+    /// # Returns
+    ///
+    /// The updated snippet, or None if no snippet has that ID
+    pub fn update(
+        &mut self,
+        id: &str,
+        value: String,
+        label: Option<String>,
+    ) -> Option<&SnippetItem> {
+        let item = self.items.iter_mut().find(|item| item.id == id)?;
+        item.value = value;
+        item.label = label.filter(|label| !label.trim().is_empty());
+        Some(item)
+    }
+
+    /// Removes a snippet
+    ///
+    /// # Returns
+    ///
+    /// The removed snippet, or None if no snippet has that ID
+    pub fn remove(&mut self, id: &str) -> Option<SnippetItem> {
+        let index = self.items.iter().position(|item| item.id == id)?;
+        Some(self.items.remove(index))
+    }
+
+    /// Adds imported snippets
+    ///
+    /// Snippets with the same value and label as an existing one are
+    /// skipped, so importing a file twice doesn't duplicate it.
+    ///
+    /// # Returns
+    ///
+    /// Number of snippets added
+    pub fn merge(&mut self, items: Vec<SnippetItem>) -> usize {
+        let before = self.items.len();
+        for item in items {
+            let exists = self
+                .items
+                .iter()
+                .any(|existing| existing.value == item.value && existing.label == item.label);
+            if !exists {
+                self.items.push(item);
+            }
+        }
+        self.items.len() - before
+    }
+
+    /// Moves a snippet to a category (None or blank for uncategorized)
+    ///
+    /// # Returns
+    ///
+    /// The updated snippet, or None if no snippet has that ID
+    pub fn set_category(&mut self, id: &str, category: Option<String>) -> Option<&SnippetItem> {
+        let item = self.items.iter_mut().find(|item| item.id == id)?;
+        item.set_category(category);
+        Some(item)
+    }
+
+    /// Assigns a quick-access slot to a snippet, or clears it
+    ///
+    /// A slot belongs to one snippet, so any other snippet in the slot
+    /// loses it. The caller checks that the slot is in [`SLOTS`](super::SLOTS).
+    ///
+    /// # Returns
+    ///
+    /// The updated snippet, or None if no snippet has that ID
+    pub fn set_slot(&mut self, id: &str, slot: Option<u8>) -> Option<&SnippetItem> {
+        let index = self.items.iter().position(|item| item.id == id)?;
+        if slot.is_some() {
+            for item in self.items.iter_mut().filter(|item| item.slot == slot) {
+                item.slot = None;
+            }
+        }
+        self.items[index].slot = slot;
+        Some(&self.items[index])
+    }
+
+    /// Gets the snippet in a quick-access slot
+    pub fn by_slot(&self, slot: u8) -> Option<&SnippetItem> {
+        self.items.iter().find(|item| item.slot == Some(slot))
+    }
+
+    /// Lists the categories in use, sorted and without duplicates
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self
+            .items
+            .iter()
+            .filter_map(|item| item.category.clone())
+            .collect();
+        categories.sort_by_key(|category| category.to_lowercase());
+        categories.dedup();
+        categories
+    }
+
+    /// Gets the snippets in a category, in stored order
+    ///
+    /// # Arguments
+    ///
+    /// * `category` - Category name, or None for uncategorized snippets
+    pub fn in_category(&self, category: Option<&str>) -> Vec<SnippetItem> {
+        self.items
+            .iter()
+            .filter(|item| item.category.as_deref() == category)
+            .cloned()
+            .collect()
+    }
+
+    /// Reorders snippets to follow `ids`
+    ///
+    /// Unknown IDs are ignored, and snippets missing from `ids` (e.g., added
+    /// while the list was being reordered) keep their relative order after
+    /// the listed ones.
+    pub fn reorder(&mut self, ids: &[String]) {
+        let rank = |item: &SnippetItem| {
+            ids.iter()
+                .position(|id| *id == item.id)
+                .unwrap_or(ids.len())
+        };
+        // Stable sort keeps unlisted snippets in their current order
+        self.items.sort_by_key(rank);
+    }
+}
+
+/// Reads snippets from a file
+///
+/// Unlike [`load_snippets`], a missing or invalid file is an error, so
+/// callers can keep what they have (e.g., while a sync tool is still
+/// writing the file).
+pub fn read_snippets(path: &Path) -> Result<SnippetsStore, SnippetsError> {
+    let contents = fs::read_to_string(path).map_err(SnippetsError::Read)?;
+    let mut store: SnippetsStore = serde_json::from_str(&contents).map_err(SnippetsError::Parse)?;
+    store.path = Some(path.to_path_buf());
+    Ok(store)
 }
 
 /// Load snippets from file
 ///
-/// Returns empty store if file doesn't exist or is invalid.
-pub fn load_snippets() -> SnippetsStore {
-    let path = SnippetsStore::get_storage_path();
-
-    match fs::read_to_string(&path) {
-        Ok(contents) => match serde_json::from_str::<SnippetsStore>(&contents) {
-            Ok(store) => {
-                println!("Loaded {} snippets from: {:?}", store.items.len(), path);
-                store
+/// Returns empty store if file doesn't exist or is invalid. Either way the
+/// store is saved back to `path`.
+pub fn load_snippets(path: &Path) -> SnippetsStore {
+    match read_snippets(path) {
+        Ok(store) => {
+            info!("Loaded {} snippets from: {:?}", store.items.len(), path);
+            store
+        }
+        Err(e) => {
+            match e {
+                SnippetsError::Read(e) if e.kind() == ErrorKind::NotFound => {
+                    info!("Snippets file not found at {:?}", path)
+                }
+                e => error!("Failed to load snippets: {}", e),
             }
-            Err(e) => {
-                eprintln!("Failed to parse snippets: {}", e);
-                SnippetsStore::default()
+            SnippetsStore {
+                path: Some(path.to_path_buf()),
+                ..SnippetsStore::default()
             }
-        },
-        Err(_) => {
-            println!("Snippets file not found at {:?}", path);
-            SnippetsStore::default()
         }
     }
 }
 
 /// Save snippets to file
-pub fn save_snippets(store: &SnippetsStore) -> Result<(), std::io::Error> {
-    let path = SnippetsStore::get_storage_path();
+///
+/// The file is replaced in one step, so file watchers and sync tools never
+/// see it half-written.
+pub fn save_snippets(store: &SnippetsStore) -> Result<(), SnippetsError> {
+    let path = store.file_path();
 
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
     let json = serde_json::to_string_pretty(store)?;
-    fs::write(&path, json)?;
-    println!("Saved {} snippets to: {:?}", store.items.len(), path);
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, json)?;
+    fs::rename(&temp, &path)?;
+    info!("Saved {} snippets to: {:?}", store.items.len(), path);
     Ok(())
 }
 
@@ -83,24 +252,131 @@ mod tests {
     #[test]
     fn test_save_and_load_roundtrip() {
         let temp_dir = TempDir::new().unwrap();
-        let path = temp_dir.path().join("snippets.json");
+        let path = temp_dir.path().join("sync").join("snippets.json");
 
-        let mut store = SnippetsStore::default();
+        let mut store = load_snippets(&path);
+        assert!(store.items.is_empty());
         store.items.push(SnippetItem::new(
             "test".to_string(),
             Some("Test".to_string()),
         ));
+        save_snippets(&store).unwrap();
+
+        let loaded = load_snippets(&path);
+        assert_eq!(loaded.items, store.items);
+        assert_eq!(loaded.file_path(), path);
+        assert!(!path.with_extension("json.tmp").exists());
+    }
+
+    #[test]
+    fn test_read_invalid_file_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("snippets.json");
+        std::fs::write(&path, "{ \"items\": [").unwrap();
+
+        assert!(matches!(read_snippets(&path), Err(SnippetsError::Parse(_))));
+        assert!(load_snippets(&path).items.is_empty());
+    }
+
+    fn store(values: &[&str]) -> SnippetsStore {
+        SnippetsStore {
+            items: values
+                .iter()
+                .map(|value| SnippetItem::new(value.to_string(), None))
+                .collect(),
+            path: None,
+        }
+    }
+
+    fn values(items: &[SnippetItem]) -> Vec<&str> {
+        items.iter().map(|item| item.value.as_str()).collect()
+    }
+
+    #[test]
+    fn test_update() {
+        let mut store = store(&["a", "b"]);
+        let id = store.items[1].id.clone();
+
+        let updated = store
+            .update(&id, "B".to_string(), Some("Label".to_string()))
+            .unwrap();
+        assert_eq!(updated.value, "B");
+        assert_eq!(updated.label.as_deref(), Some("Label"));
+
+        // An empty label is cleared
+        store.update(&id, "B".to_string(), Some(" ".to_string()));
+        assert!(store.items[1].label.is_none());
+
+        assert!(store.update("missing", "x".to_string(), None).is_none());
+        assert_eq!(values(&store.items), vec!["a", "B"]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut store = store(&["a", "b"]);
+        let id = store.items[0].id.clone();
+        assert_eq!(store.remove(&id).unwrap().value, "a");
+        assert!(store.remove(&id).is_none());
+        assert_eq!(values(&store.items), vec!["b"]);
+    }
+
+    #[test]
+    fn test_merge_skips_duplicates() {
+        let mut store = store(&["a"]);
+        let imported = vec![
+            SnippetItem::new("a".to_string(), None),
+            SnippetItem::new("a".to_string(), Some("Labeled".to_string())),
+            SnippetItem::new("b".to_string(), None),
+            SnippetItem::new("b".to_string(), None),
+        ];
+        assert_eq!(store.merge(imported), 2);
+        assert_eq!(values(&store.items), vec!["a", "a", "b"]);
+    }
+
+    #[test]
+    fn test_slots_are_unique() {
+        let mut store = store(&["a", "b"]);
+        let (a, b) = (store.items[0].id.clone(), store.items[1].id.clone());
+
+        store.set_slot(&a, Some(3));
+        assert_eq!(store.by_slot(3).unwrap().value, "a");
+
+        // Taking the slot moves it to the other snippet
+        store.set_slot(&b, Some(3));
+        assert_eq!(store.by_slot(3).unwrap().value, "b");
+        assert!(store.items[0].slot.is_none());
+
+        store.set_slot(&b, None);
+        assert!(store.by_slot(3).is_none());
+        assert!(store.set_slot("missing", Some(1)).is_none());
+    }
 
-        // Save
-        let json = serde_json::to_string_pretty(&store).unwrap();
-        std::fs::write(&path, json).unwrap();
+    #[test]
+    fn test_categories() {
+        let mut store = store(&["a", "b", "c", "d"]);
+        let ids: Vec<String> = store.items.iter().map(|item| item.id.clone()).collect();
+        store.set_category(&ids[0], Some("work".to_string()));
+        store.set_category(&ids[1], Some("Home".to_string()));
+        store.set_category(&ids[2], Some("work".to_string()));
+        assert!(store.set_category("missing", None).is_none());
 
-        // Load
-        let contents = std::fs::read_to_string(&path).unwrap();
-        let loaded: SnippetsStore = serde_json::from_str(&contents).unwrap();
+        assert_eq!(store.categories(), vec!["Home", "work"]);
+        assert_eq!(values(&store.in_category(Some("work"))), vec!["a", "c"]);
+        assert_eq!(values(&store.in_category(None)), vec!["d"]);
+    }
+
+    #[test]
+    fn test_reorder() {
+        let mut store = store(&["a", "b", "c", "d"]);
+        let ids = vec![
+            store.items[2].id.clone(),
+            "missing".to_string(),
+            store.items[0].id.clone(),
+        ];
 
-        assert_eq!(loaded.items.len(), 1);
-        assert_eq!(loaded.items[0].value, "test");
+        store.reorder(&ids);
+        // Unlisted snippets follow in their previous order
+        assert_eq!(values(&store.items), vec!["c", "a", "b", "d"]);
     }
 
     #[test]
@@ -110,6 +386,14 @@ mod tests {
         assert!(store.items.is_empty());
     }
 
+    #[test]
+    fn test_deserialize_snippets_without_category() {
+        let json = r#"{"items": [{"id": "1", "value": "a"}, {"id": "2", "value": "b", "category": "Work"}]}"#;
+        let store: SnippetsStore = serde_json::from_str(json).unwrap();
+        assert_eq!(store.categories(), vec!["Work"]);
+        assert_eq!(store.in_category(None)[0].id, "1");
+    }
+
     #[test]
     fn test_deserialize_missing_items_defaults_to_empty() {
         let json = r#"{}"#;
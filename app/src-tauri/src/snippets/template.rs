@@ -0,0 +1,336 @@
+//! Snippet templates
+//!
+//! Placeholders in snippet values are expanded when a snippet is pasted:
+//!
+//! | Placeholder       | Expands to                                        |
+//! | ----------------- | ------------------------------------------------- |
+//! | `{{date}}`        | Current date (`2024-01-15`)                       |
+//! | `{{time}}`        | Current time (`14:30`)                            |
+//! | `{{date:FORMAT}}` | Current date/time in a strftime format (`%d.%m.`) |
+//! | `{{clipboard}}`   | Latest clipboard history item                     |
+//! | `{{env:NAME}}`    | Environment variable (empty if unset)             |
+//! | `{{cursor}}`      | Nothing; marks where the cursor should end up     |
+//! | `{{name:prompt}}` | Value the user entered for the variable `name`    |
+//!
+//! A variable is declared with its prompt text once and can be repeated as
+//! `{{name}}`. Its values are collected before pasting (see [`variables`]
+//! and [`render_with_vars`]); [`render`] leaves variables as they are, like
+//! unknown placeholders and invalid formats.
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Placeholder names that aren't variables
+const BUILTINS: [&str; 5] = ["date", "time", "clipboard", "env", "cursor"];
+
+/// Default `{{date}}` format
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Default `{{time}}` format
+const TIME_FORMAT: &str = "%H:%M";
+
+/// Values placeholders expand to
+#[derive(Debug, Clone)]
+pub struct TemplateContext {
+    /// Time used for `{{date}}` and `{{time}}`
+    pub now: DateTime<Local>,
+    /// Latest clipboard text, for `{{clipboard}}`
+    pub clipboard: Option<String>,
+}
+
+impl TemplateContext {
+    /// Context for the current time
+    pub fn new(clipboard: Option<String>) -> Self {
+        Self {
+            now: Local::now(),
+            clipboard,
+        }
+    }
+}
+
+/// Snippet text with placeholders expanded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenderedSnippet {
+    /// Expanded text
+    pub text: String,
+    /// Cursor position in characters from the start, if the snippet has
+    /// a `{{cursor}}` marker
+    pub cursor: Option<usize>,
+}
+
+/// A variable the user is asked for before pasting
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnippetVariable {
+    /// Variable name
+    pub name: String,
+    /// Prompt text (the name if the declaration has none)
+    pub prompt: String,
+}
+
+/// Part of a snippet value
+enum Segment<'a> {
+    Text(&'a str),
+    /// Placeholder contents without braces, and the whole placeholder
+    Placeholder(&'a str, &'a str),
+}
+
+/// Splits a snippet value into text and placeholders
+fn segments(value: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+            break;
+        };
+        segments.push(Segment::Text(&rest[..start]));
+        segments.push(Segment::Placeholder(
+            rest[start + 2..end].trim(),
+            &rest[start..end + 2],
+        ));
+        rest = &rest[end + 2..];
+    }
+    segments.push(Segment::Text(rest));
+    segments
+}
+
+/// Splits a variable placeholder into its name and prompt
+///
+/// # Returns
+///
+/// None if the placeholder is a built-in or not a valid variable name
+fn variable(placeholder: &str) -> Option<(&str, Option<&str>)> {
+    let (name, prompt) = match placeholder.split_once(':') {
+        Some((name, prompt)) => (name.trim(), Some(prompt.trim())),
+        None => (placeholder, None),
+    };
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    (valid && !BUILTINS.contains(&name)).then_some((name, prompt))
+}
+
+/// Lists the variables a snippet declares, in order of appearance
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::snippets::variables;
+///
+/// let vars = variables("Dear {{name:Recipient name}}, ... {{name}}");
+/// assert_eq!(vars[0].name, "name");
+/// assert_eq!(vars[0].prompt, "Recipient name");
+/// ```
+pub fn variables(value: &str) -> Vec<SnippetVariable> {
+    let mut variables: Vec<SnippetVariable> = Vec::new();
+    for segment in segments(value) {
+        let Segment::Placeholder(placeholder, _) = segment else {
+            continue;
+        };
+        let Some((name, Some(prompt))) = variable(placeholder) else {
+            continue;
+        };
+        if variables.iter().all(|v| v.name != name) {
+            variables.push(SnippetVariable {
+                name: name.to_string(),
+                prompt: if prompt.is_empty() { name } else { prompt }.to_string(),
+            });
+        }
+    }
+    variables
+}
+
+/// Expands the placeholders in a snippet value
+///
+/// Variables are left as they are; use [`render_with_vars`] to fill them
+/// in. Only the first `{{cursor}}` sets the cursor position; further
+/// markers are removed.
+///
+/// # Examples
+///
+/// ```
+/// use uti_lib::snippets::{render, TemplateContext};
+///
+/// let context = TemplateContext::new(Some("world".to_string()));
+/// let rendered = render("Hello, {{clipboard}}!{{cursor}}", &context);
+/// assert_eq!(rendered.text, "Hello, world!");
+/// assert_eq!(rendered.cursor, Some(13));
+/// ```
+pub fn render(value: &str, context: &TemplateContext) -> RenderedSnippet {
+    expand_all(value, context, None)
+}
+
+/// Expands the placeholders in a snippet value, filling in variables
+///
+/// Declared variables missing from `values` expand to nothing.
+///
+/// # Arguments
+///
+/// * `values` - Variable values by name
+pub fn render_with_vars(
+    value: &str,
+    context: &TemplateContext,
+    values: &HashMap<String, String>,
+) -> RenderedSnippet {
+    expand_all(value, context, Some(values))
+}
+
+fn expand_all(
+    value: &str,
+    context: &TemplateContext,
+    values: Option<&HashMap<String, String>>,
+) -> RenderedSnippet {
+    let declared = variables(value);
+    let mut text = String::with_capacity(value.len());
+    let mut cursor = None;
+
+    for segment in segments(value) {
+        let (placeholder, raw) = match segment {
+            Segment::Text(part) => {
+                text.push_str(part);
+                continue;
+            }
+            Segment::Placeholder(placeholder, raw) => (placeholder, raw),
+        };
+        if placeholder == "cursor" {
+            cursor.get_or_insert_with(|| text.chars().count());
+            continue;
+        }
+
+        let expanded = match (variable(placeholder), values) {
+            (Some((name, _)), Some(values)) if declared.iter().any(|v| v.name == name) => {
+                Some(values.get(name).cloned().unwrap_or_default())
+            }
+            _ => expand(placeholder, context),
+        };
+        text.push_str(expanded.as_deref().unwrap_or(raw));
+    }
+
+    RenderedSnippet { text, cursor }
+}
+
+/// Expands one built-in placeholder (without braces)
+///
+/// # Returns
+///
+/// None for unknown placeholders and invalid date formats
+fn expand(placeholder: &str, context: &TemplateContext) -> Option<String> {
+    let (name, arg) = match placeholder.split_once(':') {
+        Some((name, arg)) => (name.trim(), Some(arg)),
+        None => (placeholder, None),
+    };
+    match (name, arg) {
+        ("date", format) => format_time(context, format.unwrap_or(DATE_FORMAT)),
+        ("time", format) => format_time(context, format.unwrap_or(TIME_FORMAT)),
+        ("clipboard", None) => Some(context.clipboard.clone().unwrap_or_default()),
+        ("env", Some(var)) => Some(std::env::var(var.trim()).unwrap_or_default()),
+        _ => None,
+    }
+}
+
+/// Formats the context time, or None if the format is invalid
+fn format_time(context: &TemplateContext, format: &str) -> Option<String> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.iter().any(|item| matches!(item, Item::Error)) {
+        return None;
+    }
+    Some(context.now.format_with_items(items.into_iter()).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn context() -> TemplateContext {
+        TemplateContext {
+            now: Local.with_ymd_and_hms(2024, 1, 15, 14, 30, 5).unwrap(),
+            clipboard: Some("copied".to_string()),
+        }
+    }
+
+    fn text(value: &str) -> String {
+        render(value, &context()).text
+    }
+
+    #[test]
+    fn test_date_and_time() {
+        assert_eq!(text("{{date}} {{time}}"), "2024-01-15 14:30");
+        assert_eq!(text("{{date:%d.%m.%Y %H:%M:%S}}"), "15.01.2024 14:30:05");
+        assert_eq!(text("{{ time:%H }}"), "14");
+    }
+
+    #[test]
+    fn test_clipboard_and_env() {
+        assert_eq!(text("[{{clipboard}}]"), "[copied]");
+        let empty = TemplateContext {
+            clipboard: None,
+            ..context()
+        };
+        assert_eq!(render("[{{clipboard}}]", &empty).text, "[]");
+
+        // Cargo sets this for test runs
+        assert_eq!(text("{{env:CARGO_PKG_NAME}}"), "uti");
+        assert_eq!(text("{{env:UTI_SURELY_UNSET_VARIABLE}}"), "");
+    }
+
+    #[test]
+    fn test_cursor() {
+        let rendered = render("<b>{{cursor}}</b>{{cursor}}", &context());
+        assert_eq!(rendered.text, "<b></b>");
+        assert_eq!(rendered.cursor, Some(3));
+
+        // Counted in characters, not bytes
+        assert_eq!(render("é{{cursor}}", &context()).cursor, Some(1));
+        assert_eq!(render("none", &context()).cursor, None);
+    }
+
+    #[test]
+    fn test_variables() {
+        let value =
+            "{{greeting:Greeting}} {{name: Your name }}, {{date}} {{name}} {{x:}} {{1a:no}}";
+        assert_eq!(
+            variables(value),
+            vec![
+                SnippetVariable {
+                    name: "greeting".to_string(),
+                    prompt: "Greeting".to_string()
+                },
+                SnippetVariable {
+                    name: "name".to_string(),
+                    prompt: "Your name".to_string()
+                },
+                SnippetVariable {
+                    name: "x".to_string(),
+                    prompt: "x".to_string()
+                },
+            ]
+        );
+        // Built-ins with arguments aren't variables
+        assert!(variables("{{date:%Y}} {{env:HOME}}").is_empty());
+    }
+
+    #[test]
+    fn test_render_with_vars() {
+        let values = HashMap::from([("name".to_string(), "Ann".to_string())]);
+        let value = "Hi {{name:Name}} ({{name}}), {{other:Other}}{{undeclared}} {{date}}";
+        assert_eq!(
+            render_with_vars(value, &context(), &values).text,
+            "Hi Ann (Ann), {{undeclared}} 2024-01-15"
+        );
+        // Without values, variables are kept
+        assert_eq!(text("{{name:Name}} {{name}}"), "{{name:Name}} {{name}}");
+    }
+
+    #[test]
+    fn test_unknown_placeholders_are_kept() {
+        assert_eq!(text("{{unknown}} {{date:%Q}}"), "{{unknown}} {{date:%Q}}");
+        assert_eq!(text("{{date"), "{{date");
+        assert_eq!(text("{} {{}}"), "{} {{}}");
+    }
+}
@@ -0,0 +1,193 @@
+//! Central application state
+//!
+//! [`AppState`] owns the data shared between commands, D-Bus services, the
+//! HTTP API and the IPC socket: clipboard history, snippets, launch and
+//! emoji usage history, pin and focus state. Service handles (`AppBus`, `PluginHost`, `HttpApiState`,
+//! `LogControl`) stay with their modules and are managed separately.
+//!
+//! Locks are `parking_lot`, which don't poison: a panic while a lock is
+//! held can't turn every later command into a panic.
+
+use crate::capture::CaptureState;
+use crate::clipboard::ClipboardStore;
+use crate::compositor::Backend;
+use crate::dbus::AppBus;
+use crate::focus::FocusState;
+use crate::launcher::FrecencyStore;
+use crate::logging::LogControl;
+use crate::plugins::PluginHost;
+use crate::snippets::SnippetsStore;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+/// Shared application state
+pub struct AppState {
+    /// Clipboard history
+    pub clipboard: RwLock<ClipboardStore>,
+    /// Snippets
+    pub snippets: RwLock<SnippetsStore>,
+    /// Launcher launch history, for frecency ranking
+    pub launches: RwLock<FrecencyStore>,
+    /// Emoji picker usage, for frecency ranking
+    pub emoji_usage: RwLock<FrecencyStore>,
+    /// Whether the window is pinned (always-on-top with auto-hide disabled)
+    ///
+    /// Shared with the window blur handler.
+    pub pinned: Arc<AtomicBool>,
+    /// Focus mode state
+    pub focus: FocusState,
+}
+
+impl AppState {
+    pub fn new(
+        clipboard: ClipboardStore,
+        snippets: SnippetsStore,
+        launches: FrecencyStore,
+        emoji_usage: FrecencyStore,
+    ) -> Self {
+        Self {
+            clipboard: RwLock::new(clipboard),
+            snippets: RwLock::new(snippets),
+            launches: RwLock::new(launches),
+            emoji_usage: RwLock::new(emoji_usage),
+            pinned: Arc::new(AtomicBool::new(false)),
+            focus: FocusState::new(),
+        }
+    }
+
+    /// Checks whether the window is pinned
+    pub fn is_pinned(&self) -> bool {
+        self.pinned.load(Ordering::SeqCst)
+    }
+
+    /// Captures the state held by `AppState` itself
+    fn snapshot(&self) -> StateSnapshot {
+        let clipboard = self.clipboard.read();
+        StateSnapshot {
+            clipboard_items: clipboard.items.len(),
+            clipboard_limit: clipboard.max_items,
+            snippets: self.snippets.read().items.len(),
+            pinned: self.is_pinned(),
+            focus_mode: self.focus.is_enabled(),
+            focus_scheduled: self.focus.is_scheduled(),
+            clipboard_capture: true,
+            plugins: Vec::new(),
+            dbus_connected: false,
+            daemon_connected: false,
+            compositor: "",
+            log_level: String::new(),
+        }
+    }
+}
+
+/// Point-in-time summary of the app state, for debugging
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshot {
+    /// Number of clipboard history items
+    pub clipboard_items: usize,
+    /// Clipboard history limit
+    pub clipboard_limit: usize,
+    /// Number of snippets
+    pub snippets: usize,
+    /// Whether the window is pinned
+    pub pinned: bool,
+    /// Whether focus mode is on
+    pub focus_mode: bool,
+    /// Whether the focus schedule was active at the last check
+    pub focus_scheduled: bool,
+    /// Whether copied text is recorded (capture not paused)
+    pub clipboard_capture: bool,
+    /// Names of the running plugins
+    pub plugins: Vec<String>,
+    /// Whether the session bus connection is established
+    pub dbus_connected: bool,
+    /// Whether the daemon is on the session bus
+    pub daemon_connected: bool,
+    /// Compositor backend in use
+    pub compositor: &'static str,
+    /// Current log level
+    pub log_level: String,
+}
+
+/// Gets a snapshot of the app state
+///
+/// Contains counts and flags only, no clipboard or snippet contents.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const snapshot = await invoke('get_state_snapshot');
+/// console.log(snapshot.clipboardItems, snapshot.pinned);
+/// ```
+#[tauri::command]
+pub fn get_state_snapshot(app: AppHandle, state: State<'_, AppState>) -> StateSnapshot {
+    let mut snapshot = state.snapshot();
+    if let Some(capture) = app.try_state::<CaptureState>() {
+        snapshot.clipboard_capture = capture.is_enabled();
+    }
+    if let Some(host) = app.try_state::<PluginHost>() {
+        snapshot.plugins = host.names();
+    }
+    if let Some(bus) = app.try_state::<AppBus>() {
+        snapshot.dbus_connected = bus.is_connected();
+        snapshot.daemon_connected = bus.is_daemon_connected();
+    }
+    if let Some(backend) = app.try_state::<Backend>() {
+        snapshot.compositor = backend.name();
+    }
+    if let Some(log) = app.try_state::<LogControl>() {
+        snapshot.log_level = log.level();
+    }
+    snapshot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_counts() {
+        let mut clipboard = ClipboardStore::new_with_limit(10);
+        clipboard.add("a".to_string());
+        clipboard.add("b".to_string());
+        let state = AppState::new(
+            clipboard,
+            SnippetsStore::default(),
+            FrecencyStore::default(),
+            FrecencyStore::default(),
+        );
+        state.pinned.store(true, Ordering::SeqCst);
+
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.clipboard_items, 2);
+        assert_eq!(snapshot.clipboard_limit, 10);
+        assert_eq!(snapshot.snippets, 0);
+        assert!(snapshot.pinned);
+        assert!(!snapshot.focus_mode);
+    }
+
+    #[test]
+    fn test_lock_survives_panic() {
+        let state = Arc::new(AppState::new(
+            ClipboardStore::new_with_limit(10),
+            SnippetsStore::default(),
+            FrecencyStore::default(),
+            FrecencyStore::default(),
+        ));
+
+        let poisoner = Arc::clone(&state);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.clipboard.write();
+            panic!("panic while holding the clipboard lock");
+        })
+        .join();
+
+        state.clipboard.write().add("still works".to_string());
+        assert_eq!(state.clipboard.read().items.len(), 1);
+    }
+}
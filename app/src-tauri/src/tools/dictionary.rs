@@ -0,0 +1,540 @@
+//! Dictionary and definition lookup
+//!
+//! Looks up words in local StarDict dictionaries and a local dictd server,
+//! falling back to an online dictionary API when nothing is found locally.
+
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tracing::{info, warn};
+
+/// Local dictd server address (RFC 2229)
+const DICTD_ADDR: &str = "127.0.0.1:2628";
+
+/// Online fallback API (English definitions)
+const ONLINE_API_URL: &str = "https://api.dictionaryapi.dev/api/v2/entries/en";
+
+/// Timeout for dictd and online lookups
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A single definition returned to the frontend
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Definition {
+    /// Dictionary the definition came from
+    pub source: String,
+    /// Headword as found in the dictionary
+    pub word: String,
+    /// Definition text
+    pub text: String,
+}
+
+/// Looks up the definition of a word
+///
+/// Searches local StarDict dictionaries and dictd first, then falls back
+/// to the online dictionary if nothing was found locally.
+///
+/// # Returns
+///
+/// Ok with the list of definitions (empty if the word is unknown),
+/// Err with error message if the term is empty or the online lookup failed
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const definitions = await invoke('define_word', { term: 'serendipity' });
+/// ```
+#[tauri::command]
+pub async fn define_word(term: String) -> Result<Vec<Definition>, String> {
+    let term = term.trim().to_string();
+    if term.is_empty() {
+        return Err("No word to look up".to_string());
+    }
+
+    let stardict_term = term.clone();
+    let mut definitions =
+        tokio::task::spawn_blocking(move || lookup_stardict(&stardict_dirs(), &stardict_term))
+            .await
+            .unwrap_or_default();
+
+    match tokio::time::timeout(LOOKUP_TIMEOUT, lookup_dictd(&term)).await {
+        Ok(Ok(found)) => definitions.extend(found),
+        Ok(Err(e)) => info!("dictd not available: {}", e),
+        Err(_) => warn!("dictd lookup timed out"),
+    }
+
+    if definitions.is_empty() {
+        definitions = lookup_online(&term).await?;
+    }
+
+    Ok(definitions)
+}
+
+/// Looks up the first line of the current clipboard text
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const definitions = await invoke('define_clipboard_selection');
+/// ```
+#[tauri::command]
+pub async fn define_clipboard_selection(app: AppHandle) -> Result<Vec<Definition>, String> {
+    let text = app
+        .clipboard()
+        .read_text()
+        .map_err(|e| format!("Failed to read clipboard: {}", e))?;
+
+    let term = text.lines().next().unwrap_or_default().to_string();
+    define_word(term).await
+}
+
+/// Directories searched for StarDict dictionaries
+fn stardict_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".stardict/dic"));
+    }
+    if let Some(data_dir) = dirs::data_dir() {
+        dirs.push(data_dir.join("stardict/dic"));
+    }
+    dirs.push(PathBuf::from("/usr/share/stardict/dic"));
+
+    dirs
+}
+
+/// Relevant fields from a StarDict .ifo file
+#[derive(Debug, PartialEq)]
+struct StarDictInfo {
+    name: String,
+    offset_bits_64: bool,
+}
+
+/// Parses a StarDict .ifo file
+fn parse_ifo(content: &str) -> Option<StarDictInfo> {
+    let mut lines = content.lines();
+    if lines.next()?.trim() != "StarDict's dict ifo file" {
+        return None;
+    }
+
+    let mut info = StarDictInfo {
+        name: String::new(),
+        offset_bits_64: false,
+    };
+
+    for line in lines {
+        match line.split_once('=') {
+            Some(("bookname", value)) => info.name = value.trim().to_string(),
+            Some(("idxoffsetbits", value)) => info.offset_bits_64 = value.trim() == "64",
+            _ => {}
+        }
+    }
+
+    Some(info)
+}
+
+/// Finds entries matching `term` (case-insensitive) in a StarDict .idx file
+///
+/// Each entry is a NUL-terminated word followed by a big-endian offset
+/// (32 or 64 bit) and a 32-bit size into the .dict file.
+fn find_in_idx(idx: &[u8], term: &str, offset_bits_64: bool) -> Vec<(String, u64, u32)> {
+    let offset_len = if offset_bits_64 { 8 } else { 4 };
+    let term = term.to_lowercase();
+    let mut matches = Vec::new();
+    let mut pos = 0;
+
+    while pos < idx.len() {
+        let Some(nul) = idx[pos..].iter().position(|&b| b == 0) else {
+            break;
+        };
+        let word = String::from_utf8_lossy(&idx[pos..pos + nul]).to_string();
+        pos += nul + 1;
+
+        if pos + offset_len + 4 > idx.len() {
+            break;
+        }
+        let offset = idx[pos..pos + offset_len]
+            .iter()
+            .fold(0u64, |acc, &b| (acc << 8) | b as u64);
+        pos += offset_len;
+        let size = u32::from_be_bytes([idx[pos], idx[pos + 1], idx[pos + 2], idx[pos + 3]]);
+        pos += 4;
+
+        if word.to_lowercase() == term {
+            matches.push((word, offset, size));
+        }
+    }
+
+    matches
+}
+
+/// Reads a file, transparently decompressing it if `<path>.dz`/`<path>.gz` exists
+fn read_maybe_compressed(path: &Path, compressed_ext: &str) -> Option<Vec<u8>> {
+    if let Ok(data) = std::fs::read(path) {
+        return Some(data);
+    }
+
+    let mut compressed = path.as_os_str().to_owned();
+    compressed.push(compressed_ext);
+    let file = std::fs::File::open(PathBuf::from(compressed)).ok()?;
+    let mut data = Vec::new();
+    GzDecoder::new(file).read_to_end(&mut data).ok()?;
+    Some(data)
+}
+
+/// Removes simple XML/HTML markup from StarDict definition text
+fn strip_markup(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    result.trim().to_string()
+}
+
+/// Looks up a term in every StarDict dictionary found in `dirs`
+fn lookup_stardict(dirs: &[PathBuf], term: &str) -> Vec<Definition> {
+    let mut definitions = Vec::new();
+
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let ifo_path = entry.path();
+            if ifo_path.extension().and_then(|s| s.to_str()) != Some("ifo") {
+                continue;
+            }
+            definitions.extend(lookup_stardict_dictionary(&ifo_path, term));
+        }
+    }
+
+    definitions
+}
+
+/// Looks up a term in a single StarDict dictionary given its .ifo path
+fn lookup_stardict_dictionary(ifo_path: &Path, term: &str) -> Vec<Definition> {
+    let Some(info) = std::fs::read_to_string(ifo_path)
+        .ok()
+        .and_then(|c| parse_ifo(&c))
+    else {
+        return Vec::new();
+    };
+
+    let Some(idx) = read_maybe_compressed(&ifo_path.with_extension("idx"), ".gz") else {
+        return Vec::new();
+    };
+
+    let matches = find_in_idx(&idx, term, info.offset_bits_64);
+    if matches.is_empty() {
+        return Vec::new();
+    }
+
+    let Some(dict) = read_maybe_compressed(&ifo_path.with_extension("dict"), ".dz") else {
+        warn!("StarDict data file missing for {:?}", ifo_path);
+        return Vec::new();
+    };
+
+    matches
+        .into_iter()
+        .filter_map(|(word, offset, size)| {
+            let Some(data) = entry_data(&dict, offset, size) else {
+                warn!(
+                    "StarDict entry {:?} in {:?} is out of range",
+                    word, ifo_path
+                );
+                return None;
+            };
+            Some(Definition {
+                source: info.name.clone(),
+                word,
+                text: strip_markup(&String::from_utf8_lossy(data)),
+            })
+        })
+        .collect()
+}
+
+/// Gets the .dict data of an .idx entry
+///
+/// Returns None if a corrupt offset or size points past the end of the data.
+fn entry_data(dict: &[u8], offset: u64, size: u32) -> Option<&[u8]> {
+    let start = usize::try_from(offset).ok()?;
+    let end = start.checked_add(usize::try_from(size).ok()?)?;
+    dict.get(start..end)
+}
+
+/// Parses a dictd `151 "word" database "description"` status line
+///
+/// Returns the headword and the database description.
+fn parse_dictd_header(line: &str) -> Option<(String, String)> {
+    let parts: Vec<&str> = line.split('"').collect();
+    let word = parts.get(1)?.to_string();
+    let source = parts
+        .get(3)
+        .map(|s| s.to_string())
+        .or_else(|| parts.get(2).map(|s| s.trim().to_string()))?;
+    Some((word, source))
+}
+
+/// Looks up a term via a local dictd server
+async fn lookup_dictd(term: &str) -> std::io::Result<Vec<Definition>> {
+    let stream = TcpStream::connect(DICTD_ADDR).await?;
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    // Server banner (220)
+    match lines.next_line().await? {
+        Some(banner) if banner.starts_with("220") => {}
+        _ => return Ok(Vec::new()),
+    }
+
+    let escaped = term.replace('"', "");
+    writer
+        .write_all(format!("DEFINE * \"{}\"\r\n", escaped).as_bytes())
+        .await?;
+
+    let mut definitions = Vec::new();
+    while let Some(line) = lines.next_line().await? {
+        if line.starts_with("151") {
+            let Some((word, source)) = parse_dictd_header(&line) else {
+                continue;
+            };
+
+            // Definition body ends with a line containing a single "."
+            let mut body = Vec::new();
+            while let Some(text) = lines.next_line().await? {
+                if text == "." {
+                    break;
+                }
+                body.push(text.strip_prefix('.').unwrap_or(&text).to_string());
+            }
+
+            definitions.push(Definition {
+                source,
+                word,
+                text: body.join("\n").trim().to_string(),
+            });
+        } else if line.starts_with("150") {
+            continue;
+        } else {
+            // 250 (done), 552 (no match) or an error status
+            break;
+        }
+    }
+
+    let _ = writer.write_all(b"QUIT\r\n").await;
+    Ok(definitions)
+}
+
+// Online API response structures
+
+#[derive(Debug, Deserialize)]
+struct OnlineEntry {
+    word: String,
+    #[serde(default)]
+    meanings: Vec<OnlineMeaning>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OnlineMeaning {
+    #[serde(default)]
+    part_of_speech: String,
+    #[serde(default)]
+    definitions: Vec<OnlineDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OnlineDefinition {
+    definition: String,
+}
+
+/// Converts the online API response into definitions (one per part of speech)
+fn parse_online_response(json: &str) -> Result<Vec<Definition>, serde_json::Error> {
+    let entries: Vec<OnlineEntry> = serde_json::from_str(json)?;
+
+    Ok(entries
+        .into_iter()
+        .flat_map(|entry| {
+            let word = entry.word;
+            entry.meanings.into_iter().map(move |meaning| {
+                let lines: Vec<String> = meaning
+                    .definitions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, d)| format!("{}. {}", i + 1, d.definition))
+                    .collect();
+                Definition {
+                    source: "dictionaryapi.dev".to_string(),
+                    word: word.clone(),
+                    text: format!("({})\n{}", meaning.part_of_speech, lines.join("\n")),
+                }
+            })
+        })
+        .collect())
+}
+
+/// Looks up a term using the online dictionary API
+async fn lookup_online(term: &str) -> Result<Vec<Definition>, String> {
+    let client = reqwest::Client::builder()
+        .timeout(LOOKUP_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let url = format!(
+        "{}/{}",
+        ONLINE_API_URL,
+        percent_encoding::utf8_percent_encode(term, percent_encoding::NON_ALPHANUMERIC)
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Online dictionary request failed: {}", e))?;
+
+    // 404 means the word is unknown, not an error
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(format!(
+            "Online dictionary returned status: {}",
+            response.status()
+        ));
+    }
+
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read online dictionary response: {}", e))?;
+
+    parse_online_response(&body).map_err(|e| format!("Failed to parse definitions: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Builds a 32-bit StarDict .idx entry
+    fn idx_entry(word: &str, offset: u32, size: u32) -> Vec<u8> {
+        let mut entry = word.as_bytes().to_vec();
+        entry.push(0);
+        entry.extend_from_slice(&offset.to_be_bytes());
+        entry.extend_from_slice(&size.to_be_bytes());
+        entry
+    }
+
+    #[test]
+    fn test_parse_ifo() {
+        let content = "StarDict's dict ifo file\nversion=2.4.2\nbookname=Test Dict\nwordcount=2\n";
+        let info = parse_ifo(content).unwrap();
+        assert_eq!(info.name, "Test Dict");
+        assert!(!info.offset_bits_64);
+    }
+
+    #[test]
+    fn test_parse_ifo_rejects_invalid_header() {
+        assert!(parse_ifo("not a dictionary\nbookname=x").is_none());
+    }
+
+    #[test]
+    fn test_find_in_idx_is_case_insensitive() {
+        let mut idx = idx_entry("apple", 0, 5);
+        idx.extend(idx_entry("Banana", 5, 6));
+
+        let matches = find_in_idx(&idx, "banana", false);
+        assert_eq!(matches, vec![("Banana".to_string(), 5, 6)]);
+        assert!(find_in_idx(&idx, "cherry", false).is_empty());
+    }
+
+    #[test]
+    fn test_entry_data_checks_bounds() {
+        let dict = b"a pet";
+        assert_eq!(entry_data(dict, 2, 3), Some(&b"pet"[..]));
+        assert_eq!(entry_data(dict, 2, 4), None);
+        assert_eq!(entry_data(dict, 6, 0), None);
+        assert_eq!(entry_data(dict, u64::MAX, u32::MAX), None);
+    }
+
+    #[test]
+    fn test_lookup_stardict_dictionary() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path().join("test");
+
+        std::fs::write(
+            base.with_extension("ifo"),
+            "StarDict's dict ifo file\nbookname=Test Dict\n",
+        )
+        .unwrap();
+        let mut idx = idx_entry("cat", 0, 12);
+        idx.extend(idx_entry("dog", 12, 14));
+        idx.extend(idx_entry("dog", u32::MAX, u32::MAX));
+        std::fs::write(base.with_extension("idx"), idx).unwrap();
+        std::fs::write(base.with_extension("dict"), "<b>a pet</b>a loyal animal").unwrap();
+
+        let definitions = lookup_stardict(&[temp_dir.path().to_path_buf()], "Dog");
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].source, "Test Dict");
+        assert_eq!(definitions[0].word, "dog");
+        assert_eq!(definitions[0].text, "a loyal animal");
+    }
+
+    #[test]
+    fn test_strip_markup() {
+        assert_eq!(
+            strip_markup("<k>word</k> <i>noun</i> meaning"),
+            "word noun meaning"
+        );
+        assert_eq!(strip_markup("plain text"), "plain text");
+    }
+
+    #[test]
+    fn test_parse_dictd_header() {
+        let line = r#"151 "apple" wn "WordNet (r) 3.0 (2006)""#;
+        let (word, source) = parse_dictd_header(line).unwrap();
+        assert_eq!(word, "apple");
+        assert_eq!(source, "WordNet (r) 3.0 (2006)");
+    }
+
+    #[test]
+    fn test_parse_online_response() {
+        let json = r#"[{
+            "word": "hello",
+            "meanings": [
+                {
+                    "partOfSpeech": "noun",
+                    "definitions": [
+                        { "definition": "A greeting." },
+                        { "definition": "An utterance of hello." }
+                    ]
+                }
+            ]
+        }]"#;
+
+        let definitions = parse_online_response(json).unwrap();
+        assert_eq!(definitions.len(), 1);
+        assert_eq!(definitions[0].word, "hello");
+        assert_eq!(
+            definitions[0].text,
+            "(noun)\n1. A greeting.\n2. An utterance of hello."
+        );
+    }
+}
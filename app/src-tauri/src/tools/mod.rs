@@ -0,0 +1,25 @@
+//! Utility tools module
+//!
+//! Small standalone utilities exposed as Tauri commands
+//! (world clock, lookups, system information, etc.).
+
+mod dictionary;
+mod network;
+mod ocr;
+mod processes;
+mod screenshot;
+mod system_stats;
+mod translate;
+mod unicode;
+mod world_clock;
+
+pub use dictionary::{define_clipboard_selection, define_word};
+pub use network::{get_network_info, get_public_ip};
+pub use ocr::ocr_screen_region;
+pub use processes::{kill_process, search_processes, terminate_process};
+pub use screenshot::take_screenshot;
+pub use system_stats::get_system_stats;
+pub use translate::{set_translation_api_key, translate_and_paste, translate_text};
+pub use unicode::inspect_text;
+pub(crate) use world_clock::parse_timezone;
+pub use world_clock::{convert_time, get_world_clock};
@@ -0,0 +1,359 @@
+//! Network information
+//!
+//! Reports local interface addresses, the default gateway and the current
+//! Wi-Fi network, plus the public IP on demand.
+
+use serde::Serialize;
+use std::ffi::CStr;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+use tracing::warn;
+use zbus::zvariant::OwnedObjectPath;
+use zbus::{proxy, Connection};
+
+/// Service used to look up the public IP address
+const PUBLIC_IP_URL: &str = "https://api.ipify.org";
+
+/// Timeout for the public IP request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// NetworkManager device type for Wi-Fi devices
+const NM_DEVICE_TYPE_WIFI: u32 = 2;
+
+/// D-Bus proxy for NetworkManager
+#[proxy(
+    interface = "org.freedesktop.NetworkManager",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager"
+)]
+trait NetworkManager {
+    #[zbus(property)]
+    fn devices(&self) -> zbus::Result<Vec<OwnedObjectPath>>;
+}
+
+/// D-Bus proxy for a NetworkManager device
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.Device",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+trait Device {
+    #[zbus(property)]
+    fn device_type(&self) -> zbus::Result<u32>;
+}
+
+/// D-Bus proxy for a NetworkManager Wi-Fi device
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.Device.Wireless",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+trait Wireless {
+    #[zbus(property)]
+    fn active_access_point(&self) -> zbus::Result<OwnedObjectPath>;
+}
+
+/// D-Bus proxy for a Wi-Fi access point
+#[proxy(
+    interface = "org.freedesktop.NetworkManager.AccessPoint",
+    default_service = "org.freedesktop.NetworkManager"
+)]
+trait AccessPoint {
+    #[zbus(property)]
+    fn ssid(&self) -> zbus::Result<Vec<u8>>;
+}
+
+/// Local network information
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInfo {
+    /// Network interfaces with at least one address (loopback excluded)
+    pub interfaces: Vec<NetworkInterface>,
+    /// Default IPv4 gateway
+    pub default_gateway: Option<String>,
+    /// Interface the default route goes through
+    pub gateway_interface: Option<String>,
+    /// SSID of the connected Wi-Fi network
+    pub wifi_ssid: Option<String>,
+}
+
+/// A network interface and its addresses
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterface {
+    /// Interface name (e.g., "wlp2s0")
+    pub name: String,
+    /// Addresses in CIDR notation (e.g., "192.168.1.5/24")
+    pub addresses: Vec<String>,
+    /// Hardware (MAC) address
+    pub mac: Option<String>,
+}
+
+/// Gets local network information
+///
+/// Each value can be copied with the clipboard plugin's `writeText`.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// import { writeText } from '@tauri-apps/plugin-clipboard-manager';
+/// const info = await invoke('get_network_info');
+/// await writeText(info.defaultGateway);
+/// ```
+#[tauri::command]
+pub async fn get_network_info() -> NetworkInfo {
+    let route = std::fs::read_to_string("/proc/net/route")
+        .ok()
+        .and_then(|s| parse_default_route(&s));
+
+    let wifi_ssid = match read_wifi_ssid().await {
+        Ok(ssid) => ssid,
+        Err(e) => {
+            warn!("Failed to read Wi-Fi SSID: {}", e);
+            None
+        }
+    };
+
+    NetworkInfo {
+        interfaces: read_interfaces(),
+        default_gateway: route.as_ref().map(|(_, gw)| gw.to_string()),
+        gateway_interface: route.map(|(iface, _)| iface),
+        wifi_ssid,
+    }
+}
+
+/// Looks up the public IP address
+///
+/// Only called on demand since it contacts an external service.
+///
+/// # Returns
+///
+/// Ok with the public IP, Err with error message if the lookup failed
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const ip = await invoke('get_public_ip');
+/// ```
+#[tauri::command]
+pub async fn get_public_ip() -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let body = client
+        .get(PUBLIC_IP_URL)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to look up public IP: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Failed to look up public IP: {}", e))?
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read public IP response: {}", e))?;
+
+    body.trim()
+        .parse::<IpAddr>()
+        .map(|ip| ip.to_string())
+        .map_err(|_| format!("Unexpected public IP response: {}", body.trim()))
+}
+
+/// Reads the SSID of the active Wi-Fi connection from NetworkManager
+async fn read_wifi_ssid() -> zbus::Result<Option<String>> {
+    let conn = Connection::system().await?;
+    let manager = NetworkManagerProxy::new(&conn).await?;
+
+    for path in manager.devices().await? {
+        let device = DeviceProxy::builder(&conn).path(&path)?.build().await?;
+        if device.device_type().await? != NM_DEVICE_TYPE_WIFI {
+            continue;
+        }
+
+        let wireless = WirelessProxy::builder(&conn).path(&path)?.build().await?;
+        let ap_path = wireless.active_access_point().await?;
+        // "/" means not connected
+        if ap_path.as_str() == "/" {
+            continue;
+        }
+
+        let ap = AccessPointProxy::builder(&conn)
+            .path(&ap_path)?
+            .build()
+            .await?;
+        let ssid = ap.ssid().await?;
+        return Ok(Some(String::from_utf8_lossy(&ssid).into_owned()));
+    }
+
+    Ok(None)
+}
+
+/// Parses the default IPv4 route from /proc/net/route
+///
+/// # Returns
+///
+/// The interface name and gateway address of the default route
+fn parse_default_route(route: &str) -> Option<(String, Ipv4Addr)> {
+    route.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [iface, destination, gateway, ..] = fields[..] else {
+            return None;
+        };
+        if destination != "00000000" {
+            return None;
+        }
+
+        // Addresses are the raw in_addr printed as a host-order integer
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        Some((iface.to_string(), Ipv4Addr::from(gateway.to_ne_bytes())))
+    })
+}
+
+/// Lists network interfaces and their addresses via getifaddrs(3)
+fn read_interfaces() -> Vec<NetworkInterface> {
+    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+    let mut addrs: *mut libc::ifaddrs = std::ptr::null_mut();
+
+    // SAFETY: addrs is a valid out pointer; freed with freeifaddrs below
+    if unsafe { libc::getifaddrs(&mut addrs) } != 0 {
+        warn!(
+            "Failed to list network interfaces: {}",
+            std::io::Error::last_os_error()
+        );
+        return interfaces;
+    }
+
+    let mut current = addrs;
+    while !current.is_null() {
+        // SAFETY: current points into the list returned by getifaddrs
+        let ifa = unsafe { &*current };
+        current = ifa.ifa_next;
+
+        // SAFETY: ifa_name is a valid NUL-terminated string
+        let name = unsafe { CStr::from_ptr(ifa.ifa_name) }
+            .to_string_lossy()
+            .into_owned();
+        if name == "lo" {
+            continue;
+        }
+
+        // SAFETY: both pointers come from getifaddrs and are checked for null
+        let Some(address) = (unsafe { format_address(ifa.ifa_addr, ifa.ifa_netmask) }) else {
+            continue;
+        };
+
+        match interfaces.iter_mut().find(|i| i.name == name) {
+            Some(iface) => iface.addresses.push(address),
+            None => interfaces.push(NetworkInterface {
+                mac: read_mac(&name),
+                name,
+                addresses: vec![address],
+            }),
+        }
+    }
+
+    // SAFETY: addrs was allocated by getifaddrs
+    unsafe { libc::freeifaddrs(addrs) };
+
+    interfaces
+}
+
+/// Formats an IPv4/IPv6 socket address and netmask as CIDR
+///
+/// # Safety
+///
+/// Both pointers must be null or point to valid sockaddr structures
+/// matching their `sa_family`.
+unsafe fn format_address(
+    addr: *const libc::sockaddr,
+    netmask: *const libc::sockaddr,
+) -> Option<String> {
+    let ip = sockaddr_to_ip(addr)?;
+    let prefix = sockaddr_to_ip(netmask).map(|mask| match mask {
+        IpAddr::V4(m) => u32::from(m).count_ones(),
+        IpAddr::V6(m) => u128::from(m).count_ones(),
+    });
+
+    Some(match prefix {
+        Some(prefix) => format!("{}/{}", ip, prefix),
+        None => ip.to_string(),
+    })
+}
+
+/// Converts a sockaddr to an IP address
+///
+/// # Safety
+///
+/// The pointer must be null or point to a valid sockaddr structure.
+unsafe fn sockaddr_to_ip(addr: *const libc::sockaddr) -> Option<IpAddr> {
+    if addr.is_null() {
+        return None;
+    }
+
+    match i32::from((*addr).sa_family) {
+        libc::AF_INET => {
+            let sin = &*(addr as *const libc::sockaddr_in);
+            Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(
+                sin.sin_addr.s_addr,
+            ))))
+        }
+        libc::AF_INET6 => {
+            let sin6 = &*(addr as *const libc::sockaddr_in6);
+            Some(IpAddr::V6(Ipv6Addr::from(sin6.sin6_addr.s6_addr)))
+        }
+        _ => None,
+    }
+}
+
+/// Reads an interface's MAC address from sysfs
+fn read_mac(name: &str) -> Option<String> {
+    let mac = std::fs::read_to_string(format!("/sys/class/net/{}/address", name)).ok()?;
+    let mac = mac.trim();
+    (!mac.is_empty() && mac != "00:00:00:00:00:00").then(|| mac.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_route() {
+        let route = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                     wlp2s0\t0001A8C0\t00000000\t0001\t0\t0\t600\t00FFFFFF\n\
+                     wlp2s0\t00000000\t0101A8C0\t0003\t0\t0\t600\t00000000\n";
+
+        assert_eq!(
+            parse_default_route(route),
+            Some(("wlp2s0".to_string(), Ipv4Addr::new(192, 168, 1, 1)))
+        );
+    }
+
+    #[test]
+    fn test_parse_default_route_none() {
+        let route = "Iface\tDestination\tGateway\n\
+                     eth0\t0001A8C0\t00000000\n";
+        assert_eq!(parse_default_route(route), None);
+    }
+
+    #[test]
+    fn test_format_address_ipv4_cidr() {
+        let sockaddr = |ip: Ipv4Addr| libc::sockaddr_in {
+            sin_family: libc::AF_INET as libc::sa_family_t,
+            sin_port: 0,
+            sin_addr: libc::in_addr {
+                s_addr: u32::from(ip).to_be(),
+            },
+            sin_zero: [0; 8],
+        };
+        let addr = sockaddr(Ipv4Addr::new(192, 168, 1, 5));
+        let mask = sockaddr(Ipv4Addr::new(255, 255, 255, 0));
+
+        let formatted = unsafe {
+            format_address(
+                &addr as *const _ as *const libc::sockaddr,
+                &mask as *const _ as *const libc::sockaddr,
+            )
+        };
+        assert_eq!(formatted, Some("192.168.1.5/24".to_string()));
+    }
+}
@@ -0,0 +1,144 @@
+//! Screen-region OCR
+//!
+//! Captures a screen region through the screenshot portal, recognizes the
+//! text with Tesseract and puts it on the clipboard.
+
+use super::screenshot;
+use crate::state::AppState;
+use std::path::Path;
+use std::process::Command;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::{error, info};
+
+/// Default Tesseract language when none is given
+const DEFAULT_LANGUAGE: &str = "eng";
+
+/// Captures a screen region and copies the recognized text
+///
+/// Hides the main window, lets the user select a region via the XDG portal,
+/// runs `tesseract` on the capture and adds the text to the clipboard
+/// history.
+///
+/// # Arguments
+///
+/// * `language` - Tesseract language(s), e.g. "eng" or "eng+jpn" (default: "eng")
+///
+/// # Returns
+///
+/// Ok with the recognized text, Err with error message if the capture was
+/// cancelled, tesseract is missing or no text was found
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const text = await invoke('ocr_screen_region', { language: 'eng+jpn' });
+/// ```
+#[tauri::command]
+pub async fn ocr_screen_region(app: AppHandle, language: Option<String>) -> Result<String, String> {
+    screenshot::hide_main_window(&app).await;
+
+    let captured = screenshot::capture(true).await?;
+    let language = language.unwrap_or_else(|| DEFAULT_LANGUAGE.to_string());
+
+    let image = captured.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || recognize(&image, &language))
+        .await
+        .map_err(|e| format!("OCR task failed: {}", e))?;
+
+    // The capture is only a temporary input unless the portal saved it
+    // into the user's Pictures folder
+    let in_pictures = dirs::picture_dir().is_some_and(|dir| captured.starts_with(dir));
+    if !in_pictures {
+        let _ = std::fs::remove_file(&captured);
+    }
+
+    let text = result?;
+    if text.is_empty() {
+        return Err("No text recognized".to_string());
+    }
+
+    app.clipboard()
+        .write_text(text.clone())
+        .map_err(|e| format!("Failed to write clipboard: {}", e))?;
+
+    let state = app.state::<AppState>();
+    if let Err(e) = state.clipboard.write().push(text.clone()) {
+        error!("Failed to save clipboard store: {}", e);
+    }
+
+    info!("OCR recognized {} characters", text.chars().count());
+    Ok(text)
+}
+
+/// Runs tesseract on an image and returns the cleaned-up text
+fn recognize(image: &Path, language: &str) -> Result<String, String> {
+    let output = Command::new("tesseract")
+        .arg(image)
+        .arg("-")
+        .args(["-l", language])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                "tesseract not found. Install it (e.g., dnf install tesseract)".to_string()
+            } else {
+                format!("Failed to run tesseract: {}", e)
+            }
+        })?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "tesseract failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(clean_text(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Normalizes OCR output
+///
+/// Trims trailing whitespace on each line, collapses runs of blank lines
+/// and strips the form feed tesseract appends after each page.
+fn clean_text(raw: &str) -> String {
+    let raw = raw.replace('\x0c', "");
+    let mut lines: Vec<&str> = Vec::new();
+
+    for line in raw.lines() {
+        let line = line.trim_end();
+        if line.is_empty() && lines.last().is_none_or(|prev| prev.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_text_strips_form_feed_and_trailing_blank_lines() {
+        assert_eq!(clean_text("Hello world  \n\n\x0c"), "Hello world");
+    }
+
+    #[test]
+    fn test_clean_text_collapses_blank_lines() {
+        assert_eq!(
+            clean_text("\n\nFirst line\n\n\n\nSecond line\n"),
+            "First line\n\nSecond line"
+        );
+    }
+
+    #[test]
+    fn test_clean_text_empty() {
+        assert_eq!(clean_text("\x0c\n  \n"), "");
+    }
+}
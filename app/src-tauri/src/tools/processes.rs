@@ -0,0 +1,373 @@
+//! Process finder and killer
+//!
+//! Lists running processes from /proc with CPU and memory usage, supports
+//! fuzzy search by name and terminates processes (SIGTERM, then SIGKILL
+//! once the user confirms).
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+/// Interval between the two CPU samples used to compute usage
+const CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long to wait for a process to exit after SIGTERM
+const TERMINATE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Maximum number of processes returned by a search
+const MAX_RESULTS: usize = 50;
+
+/// A running process
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessInfo {
+    /// Process ID
+    pub pid: u32,
+    /// Process name (from /proc/<pid>/comm)
+    pub name: String,
+    /// Full command line
+    pub command: String,
+    /// CPU usage in percent of one core
+    pub cpu_percent: f32,
+    /// Resident memory in bytes
+    pub memory_bytes: u64,
+    /// Resident memory in percent of total memory
+    pub memory_percent: f32,
+}
+
+/// Result of a terminate request
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminateResult {
+    /// Whether the process exited after SIGTERM
+    pub exited: bool,
+}
+
+/// Raw per-process values read from /proc
+#[derive(Debug, Clone, PartialEq)]
+struct ProcStat {
+    name: String,
+    /// utime + stime in clock ticks
+    cpu_ticks: u64,
+    /// Resident set size in pages
+    rss_pages: u64,
+}
+
+/// Searches running processes by name
+///
+/// Samples /proc twice to compute CPU usage. Kernel threads are skipped.
+///
+/// # Arguments
+///
+/// * `query` - Fuzzy search query (empty: all processes by CPU usage)
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const processes = await invoke('search_processes', { query: 'fire' });
+/// ```
+#[tauri::command]
+pub async fn search_processes(query: String) -> Vec<ProcessInfo> {
+    let before = read_all_stats();
+    let start = Instant::now();
+    tokio::time::sleep(CPU_SAMPLE_INTERVAL).await;
+    let after = read_all_stats();
+    let elapsed = start.elapsed().as_secs_f32();
+
+    let clock_ticks = sysconf(libc::_SC_CLK_TCK).unwrap_or(100) as f32;
+    let page_size = sysconf(libc::_SC_PAGESIZE).unwrap_or(4096);
+    let total_memory = read_total_memory().unwrap_or(0);
+
+    let mut results: Vec<(i32, ProcessInfo)> = after
+        .into_iter()
+        .filter_map(|(pid, stat)| {
+            let command = read_cmdline(pid)?;
+            let score = if query.is_empty() {
+                0
+            } else {
+                fuzzy_score(&query, &stat.name)
+                    .or_else(|| fuzzy_score(&query, &command).map(|s| s / 2))?
+            };
+
+            let prev_ticks = before.get(&pid).map_or(stat.cpu_ticks, |s| s.cpu_ticks);
+            let cpu_seconds = stat.cpu_ticks.saturating_sub(prev_ticks) as f32 / clock_ticks;
+            let memory_bytes = stat.rss_pages * page_size;
+
+            Some((
+                score,
+                ProcessInfo {
+                    pid,
+                    name: stat.name,
+                    command,
+                    cpu_percent: cpu_seconds / elapsed * 100.0,
+                    memory_bytes,
+                    memory_percent: if total_memory > 0 {
+                        memory_bytes as f32 / total_memory as f32 * 100.0
+                    } else {
+                        0.0
+                    },
+                },
+            ))
+        })
+        .collect();
+
+    // Best match first, busiest process first among equal matches
+    results.sort_by(|(sa, a), (sb, b)| {
+        sb.cmp(sa)
+            .then(b.cpu_percent.total_cmp(&a.cpu_percent))
+            .then(b.memory_bytes.cmp(&a.memory_bytes))
+    });
+    results.truncate(MAX_RESULTS);
+
+    results.into_iter().map(|(_, info)| info).collect()
+}
+
+/// Asks a process to exit with SIGTERM
+///
+/// Waits briefly for the process to exit. If it's still running, the UI
+/// should confirm with the user and call `kill_process`.
+///
+/// # Returns
+///
+/// Ok with whether the process exited, Err with error message if the
+/// signal could not be sent
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const { exited } = await invoke('terminate_process', { pid: 1234 });
+/// if (!exited && confirm('Force kill?')) {
+///   await invoke('kill_process', { pid: 1234 });
+/// }
+/// ```
+#[tauri::command]
+pub async fn terminate_process(pid: u32) -> Result<TerminateResult, String> {
+    send_signal(pid, libc::SIGTERM)?;
+    info!("Sent SIGTERM to process {}", pid);
+
+    let start = Instant::now();
+    while start.elapsed() < TERMINATE_TIMEOUT {
+        if !is_running(pid) {
+            return Ok(TerminateResult { exited: true });
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    Ok(TerminateResult {
+        exited: !is_running(pid),
+    })
+}
+
+/// Forcefully kills a process with SIGKILL
+///
+/// # Returns
+///
+/// Ok if the signal was sent, Err with error message otherwise
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('kill_process', { pid: 1234 });
+/// ```
+#[tauri::command]
+pub fn kill_process(pid: u32) -> Result<(), String> {
+    send_signal(pid, libc::SIGKILL)?;
+    info!("Sent SIGKILL to process {}", pid);
+    Ok(())
+}
+
+/// Sends a signal to a process, refusing init and uti itself
+fn send_signal(pid: u32, signal: libc::c_int) -> Result<(), String> {
+    if pid <= 1 || pid == std::process::id() {
+        return Err(format!("Refusing to signal process {}", pid));
+    }
+
+    let pid_t = libc::pid_t::try_from(pid).map_err(|_| format!("Invalid pid: {}", pid))?;
+
+    // SAFETY: kill() has no memory safety requirements
+    if unsafe { libc::kill(pid_t, signal) } != 0 {
+        return Err(format!(
+            "Failed to signal process {}: {}",
+            pid,
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks whether a process still exists (zombies count as exited)
+fn is_running(pid: u32) -> bool {
+    match fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        Ok(content) => parse_state(&content).is_some_and(|state| state != 'Z'),
+        Err(_) => false,
+    }
+}
+
+/// Reads stats for all processes in /proc
+fn read_all_stats() -> HashMap<u32, ProcStat> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return HashMap::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let content = fs::read_to_string(entry.path().join("stat")).ok()?;
+            Some((pid, parse_stat(&content)?))
+        })
+        .collect()
+}
+
+/// Reads a process's command line, or None for kernel threads
+fn read_cmdline(pid: u32) -> Option<String> {
+    let raw = fs::read(Path::new("/proc").join(pid.to_string()).join("cmdline")).ok()?;
+    let command = raw
+        .split(|&b| b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    (!command.is_empty()).then_some(command)
+}
+
+/// Reads MemTotal from /proc/meminfo in bytes
+fn read_total_memory() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Reads a sysconf value
+fn sysconf(name: libc::c_int) -> Option<u64> {
+    // SAFETY: sysconf() has no memory safety requirements
+    let value = unsafe { libc::sysconf(name) };
+    u64::try_from(value).ok().filter(|&v| v > 0)
+}
+
+/// Splits /proc/<pid>/stat into the name and the fields after it
+///
+/// The name is wrapped in parentheses and may itself contain spaces or
+/// parentheses, so split at the last ')'.
+fn split_stat(content: &str) -> Option<(&str, Vec<&str>)> {
+    let open = content.find('(')?;
+    let close = content.rfind(')')?;
+    let name = content.get(open + 1..close)?;
+    let fields = content.get(close + 1..)?.split_whitespace().collect();
+    Some((name, fields))
+}
+
+/// Parses the process state from /proc/<pid>/stat
+fn parse_state(content: &str) -> Option<char> {
+    let (_, fields) = split_stat(content)?;
+    fields.first()?.chars().next()
+}
+
+/// Parses name, CPU ticks and RSS from /proc/<pid>/stat
+fn parse_stat(content: &str) -> Option<ProcStat> {
+    let (name, fields) = split_stat(content)?;
+
+    // fields[0] is field 3 (state) in proc(5) numbering
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    let rss_pages: u64 = fields.get(21)?.parse().ok()?;
+
+    Some(ProcStat {
+        name: name.to_string(),
+        cpu_ticks: utime + stime,
+        rss_pages,
+    })
+}
+
+/// Scores how well `query` fuzzily matches `candidate`
+///
+/// All query characters must appear in order (case-insensitive).
+/// Consecutive matches and matches at the start score higher.
+///
+/// # Returns
+///
+/// Some(score) if matched, None otherwise
+fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for qc in query.to_lowercase().chars() {
+        let offset = candidate[pos..].iter().position(|&c| c == qc)?;
+        let index = pos + offset;
+
+        score += 1;
+        if index == 0 {
+            score += 10;
+        }
+        if prev_match.is_some_and(|prev| prev + 1 == index) {
+            score += 5;
+        }
+
+        prev_match = Some(index);
+        pos = index + 1;
+    }
+
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "1234 (Web Content (x)) S 1 1234 1234 0 -1 4194560 100 0 0 0 \
+                        250 50 0 0 20 0 30 0 12345 1000000 2048 18446744073709551615";
+
+    #[test]
+    fn test_parse_stat_with_parens_in_name() {
+        let stat = parse_stat(STAT).unwrap();
+        assert_eq!(stat.name, "Web Content (x)");
+        assert_eq!(stat.cpu_ticks, 300);
+        assert_eq!(stat.rss_pages, 2048);
+    }
+
+    #[test]
+    fn test_parse_state() {
+        assert_eq!(parse_state(STAT), Some('S'));
+        assert_eq!(parse_state("1 (init) Z 0"), Some('Z'));
+        assert_eq!(parse_state("garbage"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_score_matches_subsequence() {
+        assert!(fuzzy_score("ffx", "firefox").is_some());
+        assert!(fuzzy_score("FIRE", "firefox").is_some());
+        assert!(fuzzy_score("xf", "firefox").is_none());
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_prefix_and_consecutive() {
+        let prefix = fuzzy_score("fire", "firefox").unwrap();
+        let scattered = fuzzy_score("fire", "gnome-file-roller-helper").unwrap();
+        assert!(prefix > scattered);
+    }
+
+    #[test]
+    fn test_send_signal_refuses_protected_pids() {
+        assert!(send_signal(1, libc::SIGTERM).is_err());
+        assert!(send_signal(std::process::id(), libc::SIGTERM).is_err());
+    }
+
+    #[test]
+    fn test_read_own_process() {
+        let pid = std::process::id();
+        let stats = read_all_stats();
+        assert!(stats.contains_key(&pid));
+        assert!(read_cmdline(pid).is_some());
+        assert!(is_running(pid));
+    }
+}
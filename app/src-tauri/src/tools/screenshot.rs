@@ -0,0 +1,294 @@
+//! Screenshot capture
+//!
+//! Captures the screen through the XDG desktop portal
+//! (`org.freedesktop.portal.Screenshot`), saves the image to the Pictures
+//! folder and puts it on the clipboard.
+
+use crate::state::AppState;
+use futures_util::stream::StreamExt;
+use percent_encoding::percent_decode_str;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tracing::{error, info};
+use zbus::zvariant::{OwnedObjectPath, OwnedValue, Value};
+use zbus::{proxy, Connection};
+
+/// Delay after hiding the window so it doesn't appear in the capture
+const HIDE_DELAY: Duration = Duration::from_millis(200);
+
+/// D-Bus proxy for the screenshot portal
+#[proxy(
+    interface = "org.freedesktop.portal.Screenshot",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+trait Screenshot {
+    fn screenshot(
+        &self,
+        parent_window: &str,
+        options: HashMap<&str, Value<'_>>,
+    ) -> zbus::Result<OwnedObjectPath>;
+}
+
+/// D-Bus proxy for a pending portal request
+#[proxy(
+    interface = "org.freedesktop.portal.Request",
+    default_service = "org.freedesktop.portal.Desktop"
+)]
+trait Request {
+    /// Signal emitted when the user completes or cancels the request
+    #[zbus(signal)]
+    fn response(&self, response: u32, results: HashMap<String, OwnedValue>) -> zbus::Result<()>;
+}
+
+/// Screenshot capture mode
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CaptureMode {
+    /// Let the user select a screen region
+    Region,
+    /// Let the user select a window
+    Window,
+    /// Capture all screens without asking
+    Full,
+}
+
+impl CaptureMode {
+    /// Parses a mode name ('region', 'window' or 'full')
+    pub fn parse(mode: &str) -> Result<Self, String> {
+        match mode {
+            "region" => Ok(Self::Region),
+            "window" => Ok(Self::Window),
+            "full" => Ok(Self::Full),
+            _ => Err(format!("Unknown screenshot mode: {}", mode)),
+        }
+    }
+
+    /// Whether the portal should show its interactive selection dialog
+    ///
+    /// The portal API has no separate region/window flags; both are
+    /// chosen by the user in the compositor's interactive dialog.
+    fn interactive(self) -> bool {
+        self != Self::Full
+    }
+}
+
+/// Takes a screenshot and copies it to the clipboard
+///
+/// Hides the main window, captures via the XDG portal, saves the image to
+/// the Pictures folder and adds the saved path to the clipboard history.
+///
+/// # Arguments
+///
+/// * `mode` - 'region', 'window' or 'full' (default: 'region')
+///
+/// # Returns
+///
+/// Ok with the saved image path, Err with error message if the capture
+/// failed or was cancelled
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const path = await invoke('take_screenshot', { mode: 'window' });
+/// ```
+#[tauri::command]
+pub async fn take_screenshot(app: AppHandle, mode: Option<String>) -> Result<String, String> {
+    let mode = CaptureMode::parse(mode.as_deref().unwrap_or("region"))?;
+
+    hide_main_window(&app).await;
+
+    let captured = capture(mode.interactive()).await?;
+    let pictures = dirs::picture_dir()
+        .or_else(|| dirs::home_dir().map(|h| h.join("Pictures")))
+        .ok_or("Could not determine Pictures directory")?;
+    let saved = save_to_pictures(&captured, &pictures)?;
+    info!("Screenshot saved to {}", saved.display());
+
+    let image =
+        Image::from_path(&saved).map_err(|e| format!("Failed to load screenshot: {}", e))?;
+    app.clipboard()
+        .write_image(&image)
+        .map_err(|e| format!("Failed to copy screenshot to clipboard: {}", e))?;
+
+    let saved = saved.to_string_lossy().into_owned();
+    let state = app.state::<AppState>();
+    if let Err(e) = state.clipboard.write().push(saved.clone()) {
+        error!("Failed to save clipboard store: {}", e);
+    }
+
+    Ok(saved)
+}
+
+/// Hides the main window and waits until it's gone from the screen
+pub(super) async fn hide_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+        tokio::time::sleep(HIDE_DELAY).await;
+    }
+}
+
+/// Requests a screenshot from the portal and waits for the result
+///
+/// # Returns
+///
+/// Ok with the path of the file written by the portal, Err if the
+/// request failed or the user cancelled it
+pub(super) async fn capture(interactive: bool) -> Result<PathBuf, String> {
+    let conn = Connection::session()
+        .await
+        .map_err(|e| format!("Failed to connect to D-Bus: {}", e))?;
+
+    let sender = conn
+        .unique_name()
+        .ok_or("D-Bus connection has no unique name")?
+        .to_string();
+    let token = format!("uti_{}", uuid::Uuid::new_v4().simple());
+
+    // Subscribe before calling the portal so the response can't be missed
+    let request = RequestProxy::builder(&conn)
+        .path(request_path(&sender, &token))
+        .map_err(|e| format!("Invalid portal request path: {}", e))?
+        .build()
+        .await
+        .map_err(|e| format!("Failed to create portal request proxy: {}", e))?;
+    let mut responses = request
+        .receive_response()
+        .await
+        .map_err(|e| format!("Failed to subscribe to portal response: {}", e))?;
+
+    let portal = ScreenshotProxy::new(&conn)
+        .await
+        .map_err(|e| format!("Screenshot portal unavailable: {}", e))?;
+    let options = HashMap::from([
+        ("handle_token", Value::from(token.as_str())),
+        ("interactive", Value::from(interactive)),
+    ]);
+    portal
+        .screenshot("", options)
+        .await
+        .map_err(|e| format!("Screenshot request failed: {}", e))?;
+
+    let response = responses
+        .next()
+        .await
+        .ok_or("Portal closed without a response")?;
+    let args = response
+        .args()
+        .map_err(|e| format!("Invalid portal response: {}", e))?;
+
+    if args.response != 0 {
+        return Err("Screenshot cancelled".to_string());
+    }
+
+    let uri = args
+        .results
+        .get("uri")
+        .and_then(|v| <&str>::try_from(v).ok())
+        .map(str::to_string)
+        .ok_or("Portal response has no image URI")?;
+
+    uri_to_path(&uri).ok_or_else(|| format!("Unsupported screenshot URI: {}", uri))
+}
+
+/// Builds the object path of a portal request
+///
+/// The portal derives it from the caller's unique bus name (without the
+/// leading ':' and with '.' replaced by '_') and the handle token.
+fn request_path(sender: &str, token: &str) -> String {
+    format!(
+        "/org/freedesktop/portal/desktop/request/{}/{}",
+        sender.trim_start_matches(':').replace('.', "_"),
+        token
+    )
+}
+
+/// Converts a `file://` URI to a local path
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let decoded = percent_decode_str(path).decode_utf8().ok()?;
+    Some(PathBuf::from(decoded.into_owned()))
+}
+
+/// Moves a captured image into the Pictures folder
+///
+/// Files the portal already wrote somewhere under `pictures` are kept
+/// where they are.
+fn save_to_pictures(captured: &Path, pictures: &Path) -> Result<PathBuf, String> {
+    if captured.starts_with(pictures) {
+        return Ok(captured.to_path_buf());
+    }
+
+    let dir = pictures.join("Screenshots");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+    let name = chrono::Local::now()
+        .format("Screenshot from %Y-%m-%d %H-%M-%S.png")
+        .to_string();
+    let dest = dir.join(name);
+
+    std::fs::copy(captured, &dest).map_err(|e| format!("Failed to save screenshot: {}", e))?;
+    let _ = std::fs::remove_file(captured);
+
+    Ok(dest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_capture_mode() {
+        assert_eq!(CaptureMode::parse("region"), Ok(CaptureMode::Region));
+        assert_eq!(CaptureMode::parse("full"), Ok(CaptureMode::Full));
+        assert!(CaptureMode::parse("area").is_err());
+        assert!(CaptureMode::Window.interactive());
+        assert!(!CaptureMode::Full.interactive());
+    }
+
+    #[test]
+    fn test_request_path() {
+        assert_eq!(
+            request_path(":1.42", "uti_abc"),
+            "/org/freedesktop/portal/desktop/request/1_42/uti_abc"
+        );
+    }
+
+    #[test]
+    fn test_uri_to_path() {
+        assert_eq!(
+            uri_to_path("file:///tmp/Screenshot%20from%20today.png"),
+            Some(PathBuf::from("/tmp/Screenshot from today.png"))
+        );
+        assert_eq!(uri_to_path("https://example.com/a.png"), None);
+    }
+
+    #[test]
+    fn test_save_to_pictures_moves_external_file() {
+        let tmp = TempDir::new().unwrap();
+        let pictures = tmp.path().join("Pictures");
+        let captured = tmp.path().join("capture.png");
+        std::fs::write(&captured, b"png").unwrap();
+
+        let saved = save_to_pictures(&captured, &pictures).unwrap();
+
+        assert!(saved.starts_with(pictures.join("Screenshots")));
+        assert_eq!(std::fs::read(&saved).unwrap(), b"png");
+        assert!(!captured.exists());
+    }
+
+    #[test]
+    fn test_save_to_pictures_keeps_file_already_there() {
+        let tmp = TempDir::new().unwrap();
+        let captured = tmp.path().join("Screenshots").join("shot.png");
+
+        let saved = save_to_pictures(&captured, tmp.path()).unwrap();
+        assert_eq!(saved, captured);
+    }
+}
@@ -0,0 +1,382 @@
+//! System resource snapshot
+//!
+//! Samples CPU, memory, disk, battery and load average directly from
+//! /proc, /sys and statvfs(3) without spawning external tools.
+
+use serde::Serialize;
+use std::ffi::CString;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Interval between the two /proc/stat samples used for CPU usage
+const CPU_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Filesystem types that are never shown as disks
+const VIRTUAL_FILESYSTEMS: &[&str] = &[
+    "proc",
+    "sysfs",
+    "devtmpfs",
+    "devpts",
+    "tmpfs",
+    "cgroup",
+    "cgroup2",
+    "securityfs",
+    "pstore",
+    "bpf",
+    "debugfs",
+    "tracefs",
+    "configfs",
+    "fusectl",
+    "mqueue",
+    "hugetlbfs",
+    "autofs",
+    "binfmt_misc",
+    "efivarfs",
+    "overlay",
+    "squashfs",
+    "nsfs",
+    "ramfs",
+    "rpc_pipefs",
+    "fuse.portal",
+    "fuse.gvfsd-fuse",
+];
+
+/// Snapshot of system resource usage
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemStats {
+    /// Overall CPU usage in percent (all cores)
+    pub cpu_percent: f32,
+    /// Number of logical CPUs
+    pub cpu_count: usize,
+    /// Memory usage
+    pub memory: MemoryStats,
+    /// Mounted disks
+    pub disks: Vec<DiskStats>,
+    /// Battery status (None on machines without a battery)
+    pub battery: Option<BatteryStats>,
+    /// Load average over 1, 5 and 15 minutes
+    pub load_average: [f32; 3],
+}
+
+/// Memory and swap usage in bytes
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryStats {
+    pub total: u64,
+    /// Total minus available (excludes reclaimable cache)
+    pub used: u64,
+    pub available: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+}
+
+/// Usage of a mounted filesystem in bytes
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskStats {
+    /// Mount point (e.g., "/home")
+    pub mount_point: String,
+    /// Device (e.g., "/dev/nvme0n1p3")
+    pub device: String,
+    /// Filesystem type (e.g., "btrfs")
+    pub filesystem: String,
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+}
+
+/// Battery charge and state
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryStats {
+    /// Charge level in percent
+    pub percent: u8,
+    /// Status as reported by the kernel (e.g., "Charging", "Discharging")
+    pub status: String,
+}
+
+/// Mount entry parsed from /proc/mounts
+#[derive(Debug, PartialEq)]
+struct Mount {
+    device: String,
+    mount_point: String,
+    filesystem: String,
+}
+
+/// Gets a snapshot of system resource usage
+///
+/// Samples /proc/stat twice (250ms apart) to compute CPU usage.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const stats = await invoke('get_system_stats');
+/// console.log(`${stats.cpuPercent.toFixed(0)}% CPU`);
+/// ```
+#[tauri::command]
+pub async fn get_system_stats() -> SystemStats {
+    let before = read_file("/proc/stat").and_then(|s| parse_cpu_times(&s));
+    tokio::time::sleep(CPU_SAMPLE_INTERVAL).await;
+    let proc_stat = read_file("/proc/stat").unwrap_or_default();
+    let after = parse_cpu_times(&proc_stat);
+
+    let cpu_percent = match (before, after) {
+        (Some(before), Some(after)) => cpu_usage(before, after),
+        _ => 0.0,
+    };
+
+    SystemStats {
+        cpu_percent,
+        cpu_count: count_cpus(&proc_stat),
+        memory: read_file("/proc/meminfo")
+            .map(|s| parse_meminfo(&s))
+            .unwrap_or_default(),
+        disks: read_disks(),
+        battery: read_battery(Path::new("/sys/class/power_supply")),
+        load_average: read_file("/proc/loadavg")
+            .and_then(|s| parse_loadavg(&s))
+            .unwrap_or_default(),
+    }
+}
+
+/// Reads a file to a string, returning None on error
+fn read_file(path: &str) -> Option<String> {
+    fs::read_to_string(path).ok()
+}
+
+/// Parses the aggregate "cpu" line of /proc/stat into (idle, total) ticks
+fn parse_cpu_times(proc_stat: &str) -> Option<(u64, u64)> {
+    let line = proc_stat.lines().find(|l| l.starts_with("cpu "))?;
+    let values: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .filter_map(|v| v.parse().ok())
+        .collect();
+
+    // idle + iowait count as idle time
+    let idle = values.get(3)? + values.get(4).unwrap_or(&0);
+    let total = values.iter().sum();
+    Some((idle, total))
+}
+
+/// Computes CPU usage in percent between two (idle, total) samples
+fn cpu_usage(before: (u64, u64), after: (u64, u64)) -> f32 {
+    let total = after.1.saturating_sub(before.1);
+    if total == 0 {
+        return 0.0;
+    }
+    let idle = after.0.saturating_sub(before.0);
+    (total.saturating_sub(idle)) as f32 / total as f32 * 100.0
+}
+
+/// Counts per-CPU lines ("cpu0", "cpu1", ...) in /proc/stat
+fn count_cpus(proc_stat: &str) -> usize {
+    proc_stat
+        .lines()
+        .filter(|l| l.starts_with("cpu") && l.as_bytes().get(3).is_some_and(u8::is_ascii_digit))
+        .count()
+}
+
+/// Parses /proc/meminfo into memory statistics
+fn parse_meminfo(meminfo: &str) -> MemoryStats {
+    let value = |key: &str| -> u64 {
+        meminfo
+            .lines()
+            .find_map(|l| l.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|v| v.split_whitespace().next()?.parse::<u64>().ok())
+            .map_or(0, |kib| kib * 1024)
+    };
+
+    let total = value("MemTotal");
+    let available = value("MemAvailable");
+    let swap_total = value("SwapTotal");
+
+    MemoryStats {
+        total,
+        used: total.saturating_sub(available),
+        available,
+        swap_total,
+        swap_used: swap_total.saturating_sub(value("SwapFree")),
+    }
+}
+
+/// Parses the three load averages from /proc/loadavg
+fn parse_loadavg(loadavg: &str) -> Option<[f32; 3]> {
+    let mut values = loadavg.split_whitespace().map(|v| v.parse::<f32>().ok());
+    Some([values.next()??, values.next()??, values.next()??])
+}
+
+/// Parses /proc/mounts, keeping real filesystems once per device
+fn parse_mounts(mounts: &str) -> Vec<Mount> {
+    let mut result: Vec<Mount> = Vec::new();
+
+    for line in mounts.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [device, mount_point, filesystem, ..] = fields[..] else {
+            continue;
+        };
+
+        if VIRTUAL_FILESYSTEMS.contains(&filesystem) || !device.starts_with('/') {
+            continue;
+        }
+        // Btrfs subvolumes and bind mounts show the same device repeatedly
+        if result.iter().any(|m| m.device == device) {
+            continue;
+        }
+
+        result.push(Mount {
+            device: device.to_string(),
+            // Spaces in mount points are escaped as \040
+            mount_point: mount_point.replace("\\040", " "),
+            filesystem: filesystem.to_string(),
+        });
+    }
+
+    result
+}
+
+/// Reads usage of each mounted disk via statvfs(3)
+fn read_disks() -> Vec<DiskStats> {
+    let mounts = read_file("/proc/mounts").unwrap_or_default();
+
+    parse_mounts(&mounts)
+        .into_iter()
+        .filter_map(|mount| {
+            let path = CString::new(mount.mount_point.as_str()).ok()?;
+            let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+            // SAFETY: path is a valid C string and stat is a valid out pointer
+            if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+                return None;
+            }
+
+            let block = stat.f_frsize as u64;
+            let total = stat.f_blocks as u64 * block;
+            let free = stat.f_bfree as u64 * block;
+
+            (total > 0).then(|| DiskStats {
+                mount_point: mount.mount_point,
+                device: mount.device,
+                filesystem: mount.filesystem,
+                total,
+                used: total.saturating_sub(free),
+                available: stat.f_bavail as u64 * block,
+            })
+        })
+        .collect()
+}
+
+/// Reads the first battery under /sys/class/power_supply
+fn read_battery(power_supply: &Path) -> Option<BatteryStats> {
+    let mut entries: Vec<_> = fs::read_dir(power_supply).ok()?.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+
+    entries.into_iter().find_map(|entry| {
+        let dir = entry.path();
+        let kind = fs::read_to_string(dir.join("type")).ok()?;
+        if kind.trim() != "Battery" {
+            return None;
+        }
+
+        let percent = fs::read_to_string(dir.join("capacity"))
+            .ok()?
+            .trim()
+            .parse::<u8>()
+            .ok()?;
+        let status = fs::read_to_string(dir.join("status"))
+            .map(|s| s.trim().to_string())
+            .unwrap_or_else(|_| "Unknown".to_string());
+
+        Some(BatteryStats {
+            percent: percent.min(100),
+            status,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cpu_usage_between_samples() {
+        let before = parse_cpu_times("cpu  100 0 100 700 100 0 0 0 0 0\ncpu0 1 2 3 4").unwrap();
+        let after = parse_cpu_times("cpu  150 0 150 750 150 0 0 0 0 0\n").unwrap();
+
+        assert_eq!(before, (800, 1000));
+        assert_eq!(cpu_usage(before, after), 50.0);
+        assert_eq!(cpu_usage(after, after), 0.0);
+    }
+
+    #[test]
+    fn test_count_cpus() {
+        let stat = "cpu  1 2 3\ncpu0 1 2 3\ncpu1 1 2 3\nintr 5\nctxt 6";
+        assert_eq!(count_cpus(stat), 2);
+    }
+
+    #[test]
+    fn test_parse_meminfo() {
+        let meminfo = "MemTotal:       16000 kB\nMemFree:         1000 kB\n\
+                       MemAvailable:    6000 kB\nSwapTotal:       4000 kB\n\
+                       SwapFree:        3000 kB\n";
+        let memory = parse_meminfo(meminfo);
+
+        assert_eq!(memory.total, 16000 * 1024);
+        assert_eq!(memory.used, 10000 * 1024);
+        assert_eq!(memory.swap_used, 1000 * 1024);
+    }
+
+    #[test]
+    fn test_parse_loadavg() {
+        assert_eq!(
+            parse_loadavg("0.52 0.58 0.59 2/1045 12345\n"),
+            Some([0.52, 0.58, 0.59])
+        );
+        assert_eq!(parse_loadavg("garbage"), None);
+    }
+
+    #[test]
+    fn test_parse_mounts_skips_virtual_and_duplicates() {
+        let mounts = "proc /proc proc rw 0 0\n\
+                      /dev/nvme0n1p3 / btrfs rw,subvol=/root 0 0\n\
+                      tmpfs /tmp tmpfs rw 0 0\n\
+                      /dev/nvme0n1p3 /home btrfs rw,subvol=/home 0 0\n\
+                      /dev/sda1 /mnt/My\\040Disk ext4 rw 0 0\n";
+        let result = parse_mounts(mounts);
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].mount_point, "/");
+        assert_eq!(result[1].mount_point, "/mnt/My Disk");
+    }
+
+    #[test]
+    fn test_read_battery() {
+        let tmp = TempDir::new().unwrap();
+        let ac = tmp.path().join("AC");
+        let bat = tmp.path().join("BAT0");
+        fs::create_dir_all(&ac).unwrap();
+        fs::create_dir_all(&bat).unwrap();
+        fs::write(ac.join("type"), "Mains\n").unwrap();
+        fs::write(bat.join("type"), "Battery\n").unwrap();
+        fs::write(bat.join("capacity"), "87\n").unwrap();
+        fs::write(bat.join("status"), "Discharging\n").unwrap();
+
+        assert_eq!(
+            read_battery(tmp.path()),
+            Some(BatteryStats {
+                percent: 87,
+                status: "Discharging".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_read_battery_none_without_battery() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(read_battery(tmp.path()), None);
+    }
+}
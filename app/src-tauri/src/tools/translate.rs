@@ -0,0 +1,341 @@
+//! Text translation
+//!
+//! Translates text via LibreTranslate or DeepL. API keys are stored in the
+//! system keyring, never in config.json.
+
+use crate::config::{AppConfig, TranslationConfig};
+use crate::keyring;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+/// Timeout for translation requests
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// DeepL endpoint for free-tier API keys (keys ending in ":fx")
+const DEEPL_FREE_URL: &str = "https://api-free.deepl.com/v2/translate";
+
+/// DeepL endpoint for paid API keys
+const DEEPL_PRO_URL: &str = "https://api.deepl.com/v2/translate";
+
+/// Translation result returned to the frontend
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Translation {
+    /// Translated text
+    pub text: String,
+    /// Source language detected by the provider (when auto-detecting)
+    pub detected_source: Option<String>,
+    /// Provider that performed the translation
+    pub provider: String,
+}
+
+/// Translation backend resolved from configuration
+#[derive(Debug)]
+enum Provider {
+    LibreTranslate {
+        url: String,
+        api_key: Option<String>,
+    },
+    DeepL {
+        api_key: String,
+    },
+}
+
+impl Provider {
+    /// Resolves the configured provider, looking up its API key
+    fn from_config(config: &TranslationConfig, api_key: Option<String>) -> Result<Self, String> {
+        match config.provider.as_str() {
+            "deepl" => Ok(Self::DeepL {
+                api_key: api_key.ok_or_else(|| {
+                    "DeepL API key not set. Save it with set_translation_api_key.".to_string()
+                })?,
+            }),
+            _ => Ok(Self::LibreTranslate {
+                url: config.libretranslate_url.trim_end_matches('/').to_string(),
+                api_key,
+            }),
+        }
+    }
+
+    /// Translates text; `from` of None means auto-detect
+    async fn translate(
+        &self,
+        text: &str,
+        from: Option<&str>,
+        to: &str,
+    ) -> Result<Translation, String> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+        let request = match self {
+            Self::LibreTranslate { url, api_key } => {
+                let mut body = json!({
+                    "q": text,
+                    "source": from.unwrap_or("auto"),
+                    "target": to,
+                    "format": "text",
+                });
+                if let Some(key) = api_key {
+                    body["api_key"] = json!(key);
+                }
+                client.post(format!("{}/translate", url)).json(&body)
+            }
+            Self::DeepL { api_key } => {
+                let mut body = json!({
+                    "text": [text],
+                    "target_lang": to.to_uppercase(),
+                });
+                if let Some(from) = from {
+                    body["source_lang"] = json!(from.to_uppercase());
+                }
+                client
+                    .post(deepl_endpoint(api_key))
+                    .header("Authorization", format!("DeepL-Auth-Key {}", api_key))
+                    .json(&body)
+            }
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Translation request failed: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Translation provider returned status: {}",
+                response.status()
+            ));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to read translation response: {}", e))?;
+
+        match self {
+            Self::LibreTranslate { .. } => parse_libretranslate_response(&body),
+            Self::DeepL { .. } => parse_deepl_response(&body),
+        }
+    }
+}
+
+/// Picks the DeepL endpoint matching the API key tier
+fn deepl_endpoint(api_key: &str) -> &'static str {
+    if api_key.ends_with(":fx") {
+        DEEPL_FREE_URL
+    } else {
+        DEEPL_PRO_URL
+    }
+}
+
+/// Keyring key for a provider's API key
+fn api_key_name(provider: &str) -> String {
+    format!("translation.{}", provider)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LibreTranslateResponse {
+    translated_text: String,
+    #[serde(default)]
+    detected_language: Option<LibreTranslateDetected>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LibreTranslateDetected {
+    language: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepLResponse {
+    translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepLTranslation {
+    text: String,
+    #[serde(default)]
+    detected_source_language: Option<String>,
+}
+
+/// Parses a LibreTranslate `/translate` response
+fn parse_libretranslate_response(body: &str) -> Result<Translation, String> {
+    let response: LibreTranslateResponse = serde_json::from_str(body)
+        .map_err(|e| format!("Failed to parse translation response: {}", e))?;
+
+    Ok(Translation {
+        text: response.translated_text,
+        detected_source: response.detected_language.map(|d| d.language),
+        provider: "libretranslate".to_string(),
+    })
+}
+
+/// Parses a DeepL `/v2/translate` response
+fn parse_deepl_response(body: &str) -> Result<Translation, String> {
+    let response: DeepLResponse = serde_json::from_str(body)
+        .map_err(|e| format!("Failed to parse translation response: {}", e))?;
+
+    let translation = response
+        .translations
+        .into_iter()
+        .next()
+        .ok_or_else(|| "DeepL returned no translations".to_string())?;
+
+    Ok(Translation {
+        text: translation.text,
+        detected_source: translation
+            .detected_source_language
+            .map(|lang| lang.to_lowercase()),
+        provider: "deepl".to_string(),
+    })
+}
+
+/// Translates text with the configured provider
+///
+/// # Arguments
+///
+/// * `text` - Text to translate
+/// * `from` - Source language code, or None/"auto" to auto-detect
+/// * `to` - Target language code (default: `translation.targetLanguage`)
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const result = await invoke('translate_text', { text: 'Hallo', from: null, to: 'en' });
+/// console.log(result.text, result.detectedSource);
+/// ```
+#[tauri::command]
+pub async fn translate_text(
+    text: String,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Translation, String> {
+    if text.trim().is_empty() {
+        return Err("Nothing to translate".to_string());
+    }
+
+    let config = AppConfig::load().translation;
+    let api_key = keyring::lookup_secret(&api_key_name(&config.provider));
+    let provider = Provider::from_config(&config, api_key)?;
+
+    let from = from.filter(|lang| !lang.is_empty() && lang != "auto");
+    let to = to.unwrap_or(config.target_language);
+
+    provider.translate(&text, from.as_deref(), &to).await
+}
+
+/// Saves the API key for the configured translation provider in the keyring
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('set_translation_api_key', { apiKey: 'xxxx:fx' });
+/// ```
+#[tauri::command]
+pub fn set_translation_api_key(api_key: String) -> Result<(), String> {
+    let provider = AppConfig::load().translation.provider;
+    keyring::store_secret(
+        &api_key_name(&provider),
+        &format!("uti {} API key", provider),
+        api_key.trim(),
+    )
+}
+
+/// Translates the current clipboard text and pastes the result
+///
+/// Puts the translation on the clipboard, hides the main window and
+/// triggers auto-paste via the daemon.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// await invoke('translate_and_paste', { to: 'ja' });
+/// ```
+#[tauri::command]
+pub async fn translate_and_paste(app: AppHandle, to: Option<String>) -> Result<(), String> {
+    let text = app
+        .clipboard()
+        .read_text()
+        .map_err(|e| format!("Failed to read clipboard: {}", e))?;
+
+    let translation = translate_text(text, None, to).await?;
+
+    app.clipboard()
+        .write_text(translation.text)
+        .map_err(|e| format!("Failed to write clipboard: {}", e))?;
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    crate::dbus::emit_type_text(&app)
+        .await
+        .map_err(|e| format!("Failed to trigger paste: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(provider: &str) -> TranslationConfig {
+        TranslationConfig {
+            provider: provider.to_string(),
+            ..TranslationConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_deepl_endpoint_by_key_tier() {
+        assert_eq!(deepl_endpoint("abc:fx"), DEEPL_FREE_URL);
+        assert_eq!(deepl_endpoint("abc"), DEEPL_PRO_URL);
+    }
+
+    #[test]
+    fn test_provider_from_config() {
+        let provider = Provider::from_config(&config("libretranslate"), None).unwrap();
+        assert!(matches!(
+            provider,
+            Provider::LibreTranslate { api_key: None, .. }
+        ));
+
+        let provider = Provider::from_config(&config("deepl"), Some("key".to_string())).unwrap();
+        assert!(matches!(provider, Provider::DeepL { .. }));
+    }
+
+    #[test]
+    fn test_deepl_requires_api_key() {
+        assert!(Provider::from_config(&config("deepl"), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_libretranslate_response() {
+        let body =
+            r#"{"translatedText":"Hello","detectedLanguage":{"confidence":90,"language":"de"}}"#;
+        let translation = parse_libretranslate_response(body).unwrap();
+        assert_eq!(translation.text, "Hello");
+        assert_eq!(translation.detected_source, Some("de".to_string()));
+    }
+
+    #[test]
+    fn test_parse_deepl_response() {
+        let body = r#"{"translations":[{"detected_source_language":"DE","text":"Hello"}]}"#;
+        let translation = parse_deepl_response(body).unwrap();
+        assert_eq!(translation.text, "Hello");
+        assert_eq!(translation.detected_source, Some("de".to_string()));
+        assert_eq!(translation.provider, "deepl");
+    }
+
+    #[test]
+    fn test_parse_deepl_response_empty() {
+        assert!(parse_deepl_response(r#"{"translations":[]}"#).is_err());
+    }
+}
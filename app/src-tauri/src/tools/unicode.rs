@@ -0,0 +1,372 @@
+//! Unicode character inspector
+//!
+//! Breaks text down into characters with codepoint, name, block and UTF-8
+//! bytes, flagging invisible and confusable characters such as
+//! non-breaking spaces or Cyrillic letters that look like Latin ones.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Maximum number of characters inspected per call
+const MAX_CHARS: usize = 10_000;
+
+/// Locations of UnicodeData.txt on common distributions
+const UNICODE_DATA_PATHS: &[&str] = &[
+    "/usr/share/unicode/ucd/UnicodeData.txt",
+    "/usr/share/unicode/UnicodeData.txt",
+    "/usr/share/unicode-data/UnicodeData.txt",
+];
+
+/// Invisible or whitespace-like characters worth flagging, with names
+///
+/// Names are included so they're available even without UnicodeData.txt.
+const INVISIBLE: &[(char, &str)] = &[
+    ('\u{00A0}', "NO-BREAK SPACE"),
+    ('\u{00AD}', "SOFT HYPHEN"),
+    ('\u{034F}', "COMBINING GRAPHEME JOINER"),
+    ('\u{061C}', "ARABIC LETTER MARK"),
+    ('\u{115F}', "HANGUL CHOSEONG FILLER"),
+    ('\u{1160}', "HANGUL JUNGSEONG FILLER"),
+    ('\u{180E}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{2000}', "EN QUAD"),
+    ('\u{2001}', "EM QUAD"),
+    ('\u{2002}', "EN SPACE"),
+    ('\u{2003}', "EM SPACE"),
+    ('\u{2004}', "THREE-PER-EM SPACE"),
+    ('\u{2005}', "FOUR-PER-EM SPACE"),
+    ('\u{2006}', "SIX-PER-EM SPACE"),
+    ('\u{2007}', "FIGURE SPACE"),
+    ('\u{2008}', "PUNCTUATION SPACE"),
+    ('\u{2009}', "THIN SPACE"),
+    ('\u{200A}', "HAIR SPACE"),
+    ('\u{200B}', "ZERO WIDTH SPACE"),
+    ('\u{200C}', "ZERO WIDTH NON-JOINER"),
+    ('\u{200D}', "ZERO WIDTH JOINER"),
+    ('\u{200E}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200F}', "RIGHT-TO-LEFT MARK"),
+    ('\u{2028}', "LINE SEPARATOR"),
+    ('\u{2029}', "PARAGRAPH SEPARATOR"),
+    ('\u{202A}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202B}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202C}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202D}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202E}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{202F}', "NARROW NO-BREAK SPACE"),
+    ('\u{205F}', "MEDIUM MATHEMATICAL SPACE"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2061}', "FUNCTION APPLICATION"),
+    ('\u{2062}', "INVISIBLE TIMES"),
+    ('\u{2063}', "INVISIBLE SEPARATOR"),
+    ('\u{2064}', "INVISIBLE PLUS"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+    ('\u{3000}', "IDEOGRAPHIC SPACE"),
+    ('\u{3164}', "HANGUL FILLER"),
+    ('\u{FEFF}', "ZERO WIDTH NO-BREAK SPACE"),
+    ('\u{FFA0}', "HALFWIDTH HANGUL FILLER"),
+];
+
+/// Characters commonly mistaken for ASCII, with the ASCII lookalike
+///
+/// Fullwidth forms (U+FF01..U+FF5E) are handled separately.
+const CONFUSABLES: &[(char, &str)] = &[
+    // Cyrillic
+    ('\u{0410}', "A"),
+    ('\u{0412}', "B"),
+    ('\u{0415}', "E"),
+    ('\u{041A}', "K"),
+    ('\u{041C}', "M"),
+    ('\u{041D}', "H"),
+    ('\u{041E}', "O"),
+    ('\u{0420}', "P"),
+    ('\u{0421}', "C"),
+    ('\u{0422}', "T"),
+    ('\u{0425}', "X"),
+    ('\u{0430}', "a"),
+    ('\u{0435}', "e"),
+    ('\u{043E}', "o"),
+    ('\u{0440}', "p"),
+    ('\u{0441}', "c"),
+    ('\u{0443}', "y"),
+    ('\u{0445}', "x"),
+    ('\u{0455}', "s"),
+    ('\u{0456}', "i"),
+    ('\u{0458}', "j"),
+    // Greek
+    ('\u{0391}', "A"),
+    ('\u{0392}', "B"),
+    ('\u{0395}', "E"),
+    ('\u{0397}', "H"),
+    ('\u{0399}', "I"),
+    ('\u{039A}', "K"),
+    ('\u{039C}', "M"),
+    ('\u{039D}', "N"),
+    ('\u{039F}', "O"),
+    ('\u{03A1}', "P"),
+    ('\u{03A4}', "T"),
+    ('\u{03A7}', "X"),
+    ('\u{03BF}', "o"),
+    // Punctuation
+    ('\u{2010}', "-"),
+    ('\u{2011}', "-"),
+    ('\u{2012}', "-"),
+    ('\u{2013}', "-"),
+    ('\u{2014}', "-"),
+    ('\u{2212}', "-"),
+    ('\u{2018}', "'"),
+    ('\u{2019}', "'"),
+    ('\u{201A}', ","),
+    ('\u{201C}', "\""),
+    ('\u{201D}', "\""),
+    ('\u{2032}', "'"),
+    ('\u{2033}', "\""),
+    ('\u{2024}', "."),
+    ('\u{2044}', "/"),
+    ('\u{2215}', "/"),
+    ('\u{037E}', ";"),
+    ('\u{0589}', ":"),
+    ('\u{2236}', ":"),
+    ('\u{01C3}', "!"),
+];
+
+/// Unicode blocks (subset covering commonly pasted text)
+const BLOCKS: &[(u32, u32, &str)] = &[
+    (0x0000, 0x007F, "Basic Latin"),
+    (0x0080, 0x00FF, "Latin-1 Supplement"),
+    (0x0100, 0x017F, "Latin Extended-A"),
+    (0x0180, 0x024F, "Latin Extended-B"),
+    (0x0250, 0x02AF, "IPA Extensions"),
+    (0x02B0, 0x02FF, "Spacing Modifier Letters"),
+    (0x0300, 0x036F, "Combining Diacritical Marks"),
+    (0x0370, 0x03FF, "Greek and Coptic"),
+    (0x0400, 0x04FF, "Cyrillic"),
+    (0x0500, 0x052F, "Cyrillic Supplement"),
+    (0x0530, 0x058F, "Armenian"),
+    (0x0590, 0x05FF, "Hebrew"),
+    (0x0600, 0x06FF, "Arabic"),
+    (0x0900, 0x097F, "Devanagari"),
+    (0x0E00, 0x0E7F, "Thai"),
+    (0x1100, 0x11FF, "Hangul Jamo"),
+    (0x1800, 0x18AF, "Mongolian"),
+    (0x1E00, 0x1EFF, "Latin Extended Additional"),
+    (0x1F00, 0x1FFF, "Greek Extended"),
+    (0x2000, 0x206F, "General Punctuation"),
+    (0x2070, 0x209F, "Superscripts and Subscripts"),
+    (0x20A0, 0x20CF, "Currency Symbols"),
+    (0x2100, 0x214F, "Letterlike Symbols"),
+    (0x2150, 0x218F, "Number Forms"),
+    (0x2190, 0x21FF, "Arrows"),
+    (0x2200, 0x22FF, "Mathematical Operators"),
+    (0x2300, 0x23FF, "Miscellaneous Technical"),
+    (0x2500, 0x257F, "Box Drawing"),
+    (0x2580, 0x259F, "Block Elements"),
+    (0x25A0, 0x25FF, "Geometric Shapes"),
+    (0x2600, 0x26FF, "Miscellaneous Symbols"),
+    (0x2700, 0x27BF, "Dingbats"),
+    (0x3000, 0x303F, "CJK Symbols and Punctuation"),
+    (0x3040, 0x309F, "Hiragana"),
+    (0x30A0, 0x30FF, "Katakana"),
+    (0x3130, 0x318F, "Hangul Compatibility Jamo"),
+    (0x4E00, 0x9FFF, "CJK Unified Ideographs"),
+    (0xAC00, 0xD7AF, "Hangul Syllables"),
+    (0xE000, 0xF8FF, "Private Use Area"),
+    (0xFE00, 0xFE0F, "Variation Selectors"),
+    (0xFE30, 0xFE4F, "CJK Compatibility Forms"),
+    (0xFF00, 0xFFEF, "Halfwidth and Fullwidth Forms"),
+    (0xFFF0, 0xFFFF, "Specials"),
+    (0x1F300, 0x1F5FF, "Miscellaneous Symbols and Pictographs"),
+    (0x1F600, 0x1F64F, "Emoticons"),
+    (0x1F680, 0x1F6FF, "Transport and Map Symbols"),
+    (0x1F900, 0x1F9FF, "Supplemental Symbols and Pictographs"),
+    (0xE0000, 0xE007F, "Tags"),
+];
+
+/// Details about a single character
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CharInfo {
+    /// The character itself
+    pub character: String,
+    /// Codepoint in U+XXXX notation
+    pub codepoint: String,
+    /// Unicode name (None if UnicodeData.txt is unavailable)
+    pub name: Option<String>,
+    /// Unicode block (None for blocks not in the built-in table)
+    pub block: Option<String>,
+    /// UTF-8 bytes in hex (e.g., "C2 A0")
+    pub utf8: String,
+    /// Whether the character is invisible or unusual whitespace
+    pub invisible: bool,
+    /// ASCII character this is easily confused with
+    pub confusable_with: Option<String>,
+}
+
+/// Inspects each character in the given text
+///
+/// At most 10,000 characters are inspected.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const chars = await invoke('inspect_text', { text: 'a\u00A0b' });
+/// console.log(chars[1].name); // "NO-BREAK SPACE"
+/// ```
+#[tauri::command]
+pub fn inspect_text(text: String) -> Vec<CharInfo> {
+    let names = unicode_names();
+    text.chars()
+        .take(MAX_CHARS)
+        .map(|c| inspect_char(c, names))
+        .collect()
+}
+
+/// Builds the details for a single character
+fn inspect_char(c: char, names: &HashMap<u32, String>) -> CharInfo {
+    let cp = c as u32;
+    let mut buf = [0u8; 4];
+    let utf8 = c
+        .encode_utf8(&mut buf)
+        .bytes()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let builtin_name = INVISIBLE
+        .iter()
+        .find(|(ch, _)| *ch == c)
+        .map(|(_, name)| name.to_string());
+
+    CharInfo {
+        character: c.to_string(),
+        codepoint: format!("U+{:04X}", cp),
+        name: names.get(&cp).cloned().or(builtin_name),
+        block: block_of(cp).map(str::to_string),
+        utf8,
+        invisible: is_invisible(c),
+        confusable_with: confusable_with(c),
+    }
+}
+
+/// Checks whether a character is invisible or unusual whitespace
+///
+/// Newlines, carriage returns and tabs are considered normal.
+fn is_invisible(c: char) -> bool {
+    if matches!(c, '\n' | '\r' | '\t') {
+        return false;
+    }
+    c.is_control()
+        || INVISIBLE.iter().any(|(ch, _)| *ch == c)
+        // Variation selectors and tag characters
+        || matches!(c as u32, 0xFE00..=0xFE0F | 0xE0000..=0xE007F)
+}
+
+/// Returns the ASCII lookalike of a confusable character
+fn confusable_with(c: char) -> Option<String> {
+    // Fullwidth ASCII variants map to ASCII by a fixed offset
+    if let 0xFF01..=0xFF5E = c as u32 {
+        return char::from_u32(c as u32 - 0xFEE0).map(String::from);
+    }
+    CONFUSABLES
+        .iter()
+        .find(|(ch, _)| *ch == c)
+        .map(|(_, ascii)| ascii.to_string())
+}
+
+/// Finds the block containing a codepoint
+fn block_of(cp: u32) -> Option<&'static str> {
+    BLOCKS
+        .iter()
+        .find(|(start, end, _)| (*start..=*end).contains(&cp))
+        .map(|(_, _, name)| *name)
+}
+
+/// Loads character names from the system's UnicodeData.txt (once)
+fn unicode_names() -> &'static HashMap<u32, String> {
+    static NAMES: OnceLock<HashMap<u32, String>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        UNICODE_DATA_PATHS
+            .iter()
+            .find_map(|path| std::fs::read_to_string(path).ok())
+            .map(|data| parse_unicode_data(&data))
+            .unwrap_or_default()
+    })
+}
+
+/// Parses UnicodeData.txt into a codepoint -> name map
+///
+/// Control characters are named "<control>" in field 1; their real name
+/// is in field 10 (Unicode 1.0 name), which is used instead. Range markers
+/// like "<CJK Ideograph, First>" are skipped.
+fn parse_unicode_data(data: &str) -> HashMap<u32, String> {
+    data.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(';').collect();
+            let cp = u32::from_str_radix(fields.first()?, 16).ok()?;
+            let name = match *fields.get(1)? {
+                "<control>" => fields.get(10).filter(|n| !n.is_empty())?,
+                name if name.starts_with('<') => return None,
+                name => name,
+            };
+            Some((cp, name.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect_char_basic() {
+        let info = inspect_char('\u{00E9}', &HashMap::new());
+        assert_eq!(info.codepoint, "U+00E9");
+        assert_eq!(info.utf8, "C3 A9");
+        assert_eq!(info.block.as_deref(), Some("Latin-1 Supplement"));
+        assert!(!info.invisible);
+        assert!(info.confusable_with.is_none());
+    }
+
+    #[test]
+    fn test_invisible_characters() {
+        let info = inspect_char('\u{00A0}', &HashMap::new());
+        assert!(info.invisible);
+        assert_eq!(info.name.as_deref(), Some("NO-BREAK SPACE"));
+
+        assert!(is_invisible('\u{200B}'));
+        assert!(is_invisible('\u{0007}'));
+        assert!(!is_invisible('\t'));
+        assert!(!is_invisible(' '));
+    }
+
+    #[test]
+    fn test_confusable_characters() {
+        assert_eq!(confusable_with('\u{0430}').as_deref(), Some("a"));
+        assert_eq!(confusable_with('\u{FF21}').as_deref(), Some("A"));
+        assert_eq!(confusable_with('\u{201C}').as_deref(), Some("\""));
+        assert_eq!(confusable_with('a'), None);
+    }
+
+    #[test]
+    fn test_parse_unicode_data() {
+        let data = "0009;<control>;Cc;0;S;;;;;N;CHARACTER TABULATION;;;;\n\
+                    0041;LATIN CAPITAL LETTER A;Lu;0;L;;;;;N;;;;0061;\n\
+                    4E00;<CJK Ideograph, First>;Lo;0;L;;;;;N;;;;;\n";
+        let names = parse_unicode_data(data);
+
+        assert_eq!(names.get(&0x41).unwrap(), "LATIN CAPITAL LETTER A");
+        assert_eq!(names.get(&0x09).unwrap(), "CHARACTER TABULATION");
+        assert!(!names.contains_key(&0x4E00));
+    }
+
+    #[test]
+    fn test_inspect_emoji_utf8() {
+        let info = inspect_char('\u{1F600}', &HashMap::new());
+        assert_eq!(info.codepoint, "U+1F600");
+        assert_eq!(info.utf8, "F0 9F 98 80");
+        assert_eq!(info.block.as_deref(), Some("Emoticons"));
+    }
+}
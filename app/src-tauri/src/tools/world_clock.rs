@@ -0,0 +1,328 @@
+//! World clock and timezone conversion
+//!
+//! Provides current times for the configured list of IANA timezones and
+//! parses conversion queries such as "15:00 CET" or "what time is 3pm PST".
+
+use crate::config::AppConfig;
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+use tracing::warn;
+
+/// Common timezone abbreviations mapped to representative IANA zones
+///
+/// Abbreviations that chrono-tz already knows (e.g., CET, EST, UTC)
+/// are resolved directly and don't need an entry here.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("PST", "America/Los_Angeles"),
+    ("PDT", "America/Los_Angeles"),
+    ("MDT", "America/Denver"),
+    ("CST", "America/Chicago"),
+    ("CDT", "America/Chicago"),
+    ("EDT", "America/New_York"),
+    ("BST", "Europe/London"),
+    ("CEST", "Europe/Paris"),
+    ("EEST", "Europe/Athens"),
+    ("IST", "Asia/Kolkata"),
+    ("JST", "Asia/Tokyo"),
+    ("KST", "Asia/Seoul"),
+    ("AEST", "Australia/Sydney"),
+    ("AEDT", "Australia/Sydney"),
+];
+
+/// Current time in a single timezone
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZoneTime {
+    /// Timezone name (e.g., "Asia/Tokyo")
+    pub timezone: String,
+    /// Timezone abbreviation (e.g., "JST")
+    pub abbreviation: String,
+    /// Time in 24-hour format (HH:MM)
+    pub time: String,
+    /// Date (YYYY-MM-DD)
+    pub date: String,
+    /// UTC offset (e.g., "+09:00")
+    pub utc_offset: String,
+}
+
+/// Result of a time conversion query
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeConversion {
+    /// Time as given in the query
+    pub source: ZoneTime,
+    /// The same instant in the local timezone
+    pub local: ZoneTime,
+}
+
+/// Gets the current time for each configured timezone
+///
+/// Timezones are read from `worldClockTimezones` in config.json.
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const clocks = await invoke('get_world_clock');
+/// console.log(clocks[0].time); // e.g., "15:00"
+/// ```
+#[tauri::command]
+pub fn get_world_clock() -> Vec<ZoneTime> {
+    let config = AppConfig::load();
+    current_times(&config.world_clock_timezones, Utc::now())
+}
+
+/// Converts a time in another timezone to local time
+///
+/// Accepts free-form queries like "15:00 CET", "3pm PST" or
+/// "what time is 9:30 Asia/Tokyo for me".
+///
+/// # Returns
+///
+/// Ok with the source and local times, Err with error message if the
+/// query could not be parsed
+///
+/// # Examples
+///
+/// ```typescript
+/// import { invoke } from '@tauri-apps/api/core';
+/// const result = await invoke('convert_time', { query: '15:00 CET' });
+/// console.log(result.local.time);
+/// ```
+#[tauri::command]
+pub fn convert_time(query: String) -> Result<TimeConversion, String> {
+    let (time, source) = parse_conversion_query(&query)
+        .ok_or_else(|| format!("Could not find a time and timezone in '{}'", query))?;
+
+    let conversion = match local_timezone() {
+        Some(local) => convert(time, source, &local, local.name(), Utc::now()),
+        None => convert(time, source, &Local, "Local", Utc::now()),
+    };
+
+    conversion.ok_or_else(|| format!("'{}' does not exist in {}", time, source.name()))
+}
+
+/// Builds a `ZoneTime` from a date-time in any timezone
+fn zone_time<T: TimeZone>(dt: &DateTime<T>, timezone: &str) -> ZoneTime
+where
+    T::Offset: std::fmt::Display,
+{
+    ZoneTime {
+        timezone: timezone.to_string(),
+        abbreviation: dt.format("%Z").to_string(),
+        time: dt.format("%H:%M").to_string(),
+        date: dt.format("%Y-%m-%d").to_string(),
+        utc_offset: dt.format("%:z").to_string(),
+    }
+}
+
+/// Gets the time at `now` for each timezone, skipping unknown names
+fn current_times(timezones: &[String], now: DateTime<Utc>) -> Vec<ZoneTime> {
+    timezones
+        .iter()
+        .filter_map(|name| match parse_timezone(name) {
+            Some(tz) => Some(zone_time(&now.with_timezone(&tz), name)),
+            None => {
+                warn!("Unknown timezone '{}', skipping", name);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Parses an IANA timezone name or a common abbreviation
+pub(crate) fn parse_timezone(name: &str) -> Option<Tz> {
+    if let Ok(tz) = name.parse::<Tz>() {
+        return Some(tz);
+    }
+
+    let upper = name.to_uppercase();
+    ABBREVIATIONS
+        .iter()
+        .find(|(abbr, _)| *abbr == upper)
+        .and_then(|(_, zone)| zone.parse().ok())
+        .or_else(|| upper.parse().ok())
+}
+
+/// Parses a time token such as "15:00", "9:30", "3pm" or "3:30pm"
+///
+/// Bare numbers without a colon or am/pm suffix are rejected to avoid
+/// misreading unrelated words in the query.
+fn parse_time(token: &str) -> Option<NaiveTime> {
+    let token = token.to_lowercase();
+    let (body, pm) = if let Some(body) = token.strip_suffix("am") {
+        (body, Some(false))
+    } else if let Some(body) = token.strip_suffix("pm") {
+        (body, Some(true))
+    } else {
+        (token.as_str(), None)
+    };
+
+    let (hour, minute) = match body.split_once(':') {
+        Some((h, m)) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        None if pm.is_some() => (body.parse::<u32>().ok()?, 0),
+        None => return None,
+    };
+
+    let hour = match pm {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Extracts a time and a source timezone from a free-form query
+fn parse_conversion_query(query: &str) -> Option<(NaiveTime, Tz)> {
+    let tokens: Vec<&str> = query
+        .split_whitespace()
+        .map(|t| t.trim_matches(|c: char| c == '?' || c == ','))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut time = None;
+    let mut zone = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        if time.is_none() {
+            // Handle "3 pm" split across two tokens
+            let joined = tokens
+                .get(i + 1)
+                .filter(|next| next.eq_ignore_ascii_case("am") || next.eq_ignore_ascii_case("pm"))
+                .map(|next| format!("{}{}", token, next));
+
+            if let Some(t) = joined
+                .as_deref()
+                .and_then(parse_time)
+                .or_else(|| parse_time(token))
+            {
+                time = Some(t);
+                continue;
+            }
+        }
+
+        if zone.is_none() {
+            zone = parse_timezone(token);
+        }
+    }
+
+    Some((time?, zone?))
+}
+
+/// Converts a wall-clock time in `source` (on today's date there) to `target`
+///
+/// Returns None if the time doesn't exist in the source timezone
+/// (e.g., skipped by a DST transition).
+fn convert<T: TimeZone>(
+    time: NaiveTime,
+    source: Tz,
+    target: &T,
+    target_name: &str,
+    now: DateTime<Utc>,
+) -> Option<TimeConversion>
+where
+    T::Offset: std::fmt::Display,
+{
+    let date = now.with_timezone(&source).date_naive();
+    let source_dt = source
+        .from_local_datetime(&date.and_time(time))
+        .earliest()?;
+
+    Some(TimeConversion {
+        source: zone_time(&source_dt, source.name()),
+        local: zone_time(&source_dt.with_timezone(target), target_name),
+    })
+}
+
+/// Detects the system's local IANA timezone
+///
+/// Checks the `TZ` environment variable, then the `/etc/localtime` symlink.
+fn local_timezone() -> Option<Tz> {
+    if let Ok(tz) = std::env::var("TZ") {
+        if let Some(tz) = parse_timezone(tz.trim_start_matches(':')) {
+            return Some(tz);
+        }
+    }
+
+    let target = std::fs::read_link("/etc/localtime").ok()?;
+    let target = target.to_string_lossy();
+    let (_, name) = target.split_once("zoneinfo/")?;
+    name.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_time_formats() {
+        let t = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        assert_eq!(parse_time("15:00"), t(15, 0));
+        assert_eq!(parse_time("9:30"), t(9, 30));
+        assert_eq!(parse_time("3pm"), t(15, 0));
+        assert_eq!(parse_time("3:30PM"), t(15, 30));
+        assert_eq!(parse_time("12am"), t(0, 0));
+        assert_eq!(parse_time("12pm"), t(12, 0));
+    }
+
+    #[test]
+    fn test_parse_time_rejects_invalid() {
+        assert_eq!(parse_time("15"), None);
+        assert_eq!(parse_time("25:00"), None);
+        assert_eq!(parse_time("13pm"), None);
+        assert_eq!(parse_time("cet"), None);
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert_eq!(parse_timezone("Asia/Tokyo"), Some(Tz::Asia__Tokyo));
+        assert_eq!(parse_timezone("CET"), Some(Tz::CET));
+        assert_eq!(parse_timezone("pst"), Some(Tz::America__Los_Angeles));
+        assert_eq!(parse_timezone("Not/AZone"), None);
+    }
+
+    #[test]
+    fn test_parse_conversion_query() {
+        let (time, tz) = parse_conversion_query("what time is 15:00 CET for me?").unwrap();
+        assert_eq!(time, NaiveTime::from_hms_opt(15, 0, 0).unwrap());
+        assert_eq!(tz, Tz::CET);
+
+        let (time, tz) = parse_conversion_query("3 pm Asia/Tokyo").unwrap();
+        assert_eq!(time, NaiveTime::from_hms_opt(15, 0, 0).unwrap());
+        assert_eq!(tz, Tz::Asia__Tokyo);
+    }
+
+    #[test]
+    fn test_parse_conversion_query_requires_time_and_zone() {
+        assert!(parse_conversion_query("15:00").is_none());
+        assert!(parse_conversion_query("CET").is_none());
+    }
+
+    #[test]
+    fn test_convert_between_zones() {
+        let time = NaiveTime::from_hms_opt(15, 0, 0).unwrap();
+        let result = convert(time, Tz::CET, &Tz::Asia__Tokyo, "Asia/Tokyo", fixed_now()).unwrap();
+
+        assert_eq!(result.source.time, "15:00");
+        assert_eq!(result.source.utc_offset, "+01:00");
+        assert_eq!(result.local.time, "23:00");
+        assert_eq!(result.local.abbreviation, "JST");
+        assert_eq!(result.local.date, "2025-01-15");
+    }
+
+    #[test]
+    fn test_current_times_skips_unknown_zones() {
+        let zones = vec!["UTC".to_string(), "Invalid/Zone".to_string()];
+        let times = current_times(&zones, fixed_now());
+
+        assert_eq!(times.len(), 1);
+        assert_eq!(times[0].timezone, "UTC");
+        assert_eq!(times[0].time, "12:00");
+    }
+}
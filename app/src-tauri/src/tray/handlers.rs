@@ -1,9 +1,13 @@
 //! Tray menu event handlers
 
-use crate::settings::window_size;
+use crate::capture;
+use crate::error::report;
+use crate::focus;
+use crate::settings::{declined_message, install_hint, window_size};
 use crate::updater;
 use tauri::{menu::MenuEvent, AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
 use tauri_plugin_autostart::ManagerExt;
+use tracing::{error, info};
 
 /// Payload for update dialog window
 #[derive(Clone)]
@@ -11,6 +15,12 @@ struct UpdateDialogPayload {
     title: String,
     message: String,
     kind: String, // "info" | "error"
+    /// Release notes (markdown), empty for none
+    notes: String,
+    /// Offer to install the update from the dialog
+    installable: bool,
+    /// Release the user can skip or postpone, empty for none
+    version: String,
 }
 
 /// URL-encode a string for use in query parameters
@@ -24,6 +34,8 @@ fn urlencoding(s: &str) -> String {
 pub fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
     match event.id.as_ref() {
         "show_hide" => handle_show_hide(app),
+        "focus_mode" => focus::toggle(app),
+        "pause_capture" => capture::toggle(app),
         "settings" => handle_settings(app),
         "autostart" => handle_autostart(app),
         "check_update" => handle_check_update(app),
@@ -70,8 +82,12 @@ fn handle_settings(app: &AppHandle) {
         .focused(true)
         .build()
     {
-        Ok(_) => println!("Settings window created"),
-        Err(e) => eprintln!("Failed to create settings window: {:?}", e),
+        Ok(_) => info!("Settings window created"),
+        Err(e) => report(
+            app,
+            "tray",
+            format!("Failed to create settings window: {}", e),
+        ),
     }
 }
 
@@ -82,14 +98,14 @@ fn handle_autostart(app: &AppHandle) {
 
     if is_enabled {
         if let Err(e) = autostart_manager.disable() {
-            eprintln!("Failed to disable autostart: {}", e);
+            report(app, "tray", format!("Failed to disable autostart: {}", e));
         } else {
-            println!("Auto-start disabled");
+            info!("Auto-start disabled");
         }
     } else if let Err(e) = autostart_manager.enable() {
-        eprintln!("Failed to enable autostart: {}", e);
+        report(app, "tray", format!("Failed to enable autostart: {}", e));
     } else {
-        println!("Auto-start enabled");
+        info!("Auto-start enabled");
     }
 }
 
@@ -99,22 +115,44 @@ fn handle_check_update(app: &AppHandle) {
     let app_handle = app.clone();
 
     tauri::async_runtime::spawn(async move {
-        let payload = match updater::check_for_updates(&current_version).await {
+        let payload = match updater::check_for_updates(
+            &current_version,
+            updater::UpdateChannel::configured(),
+        )
+        .await
+        {
             Ok(result) => {
                 if result.update_available {
                     UpdateDialogPayload {
                         title: "Update Available".to_string(),
                         message: format!(
-                            "Update available: {} -> {}\n\nRun 'uti update' in terminal to install.",
-                            result.current_version, result.latest_version
+                            "Update available: {} -> {}\n\n{}",
+                            result.current_version,
+                            result.latest_version,
+                            install_hint()
                         ),
                         kind: "info".to_string(),
+                        notes: result.release_notes.unwrap_or_default(),
+                        installable: true,
+                        version: result.latest_version,
+                    }
+                } else if result.declined {
+                    UpdateDialogPayload {
+                        title: "Update Skipped".to_string(),
+                        message: declined_message(&result.latest_version),
+                        kind: "info".to_string(),
+                        notes: result.release_notes.unwrap_or_default(),
+                        installable: true,
+                        version: String::new(),
                     }
                 } else {
                     UpdateDialogPayload {
                         title: "No Update".to_string(),
                         message: "You are running the latest version.".to_string(),
                         kind: "info".to_string(),
+                        notes: String::new(),
+                        installable: false,
+                        version: String::new(),
                     }
                 }
             }
@@ -122,6 +160,9 @@ fn handle_check_update(app: &AppHandle) {
                 title: "Update Check Failed".to_string(),
                 message: format!("{}", e),
                 kind: "error".to_string(),
+                notes: String::new(),
+                installable: false,
+                version: String::new(),
             },
         };
 
@@ -132,15 +173,25 @@ fn handle_check_update(app: &AppHandle) {
 
         // Create new dialog window with URL parameters
         let url = format!(
-            "update-dialog.html?title={}&message={}&kind={}",
+            "update-dialog.html?title={}&message={}&kind={}&notes={}&installable={}&version={}",
             urlencoding(&payload.title),
             urlencoding(&payload.message),
-            urlencoding(&payload.kind)
+            urlencoding(&payload.kind),
+            urlencoding(&payload.notes),
+            payload.installable,
+            urlencoding(&payload.version)
         );
 
+        // Taller dialog to show the release notes
+        let height = if payload.notes.is_empty() {
+            200.0
+        } else {
+            420.0
+        };
+
         match WebviewWindowBuilder::new(&app_handle, "dialog", WebviewUrl::App(url.into()))
             .title("uti")
-            .inner_size(420.0, 200.0)
+            .inner_size(420.0, height)
             .resizable(false)
             .decorations(false)
             .transparent(true)
@@ -150,10 +201,10 @@ fn handle_check_update(app: &AppHandle) {
             .build()
         {
             Ok(dialog_window) => {
-                println!("Dialog window created: {:?}", dialog_window.label());
+                info!("Dialog window created: {:?}", dialog_window.label());
             }
             Err(e) => {
-                eprintln!("Failed to create dialog window: {:?}", e);
+                error!("Failed to create dialog window: {:?}", e);
             }
         }
     });
@@ -4,6 +4,8 @@
 
 mod handlers;
 
+use crate::capture::CaptureMenuItem;
+use crate::focus::FocusMenuItem;
 use tauri::{
     menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
@@ -39,6 +41,22 @@ pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
         None::<&str>,
     )?;
 
+    // Focus mode toggle (state is kept in sync by the focus module)
+    let focus_i =
+        CheckMenuItem::with_id(app, "focus_mode", "Focus Mode", true, false, None::<&str>)?;
+    app.manage(FocusMenuItem(focus_i.clone()));
+
+    // Clipboard capture pause (state is kept in sync by the capture module)
+    let capture_i = CheckMenuItem::with_id(
+        app,
+        "pause_capture",
+        "Pause Clipboard History",
+        true,
+        false,
+        None::<&str>,
+    )?;
+    app.manage(CaptureMenuItem(capture_i.clone()));
+
     // Settings menu item
     let settings_i = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
 
@@ -66,6 +84,8 @@ pub fn setup_tray(app: &App) -> Result<(), Box<dyn std::error::Error>> {
         app,
         &[
             &show_hide_i,
+            &focus_i,
+            &capture_i,
             &PredefinedMenuItem::separator(app)?,
             &settings_i,
             &autostart_i,
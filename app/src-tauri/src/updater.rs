@@ -2,21 +2,98 @@
 //!
 //! This module provides functions to check for updates and install them
 //! via GitHub Releases and RPM packages.
+//!
+//! Every downloaded asset is checked against the release's `SHA256SUMS`
+//! before it is installed.
+//!
+//! Inside Flatpak, RPMs can't be installed: the app is updated with
+//! `flatpak update` on the host instead, and the GNOME extension through
+//! `flatpak-spawn --host` when the sandbox may talk to the host.
 
 use reqwest::Client;
 use semver::Version;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 /// GitHub API response for a release
 #[derive(Debug, Deserialize)]
 struct Release {
     tag_name: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+    /// Release notes (markdown)
+    #[serde(default)]
+    body: Option<String>,
     assets: Vec<Asset>,
 }
 
+impl Release {
+    /// Parse the tag as a version (strip 'v' prefix if present)
+    fn version(&self) -> Option<Version> {
+        Version::parse(self.tag_name.trim_start_matches('v')).ok()
+    }
+}
+
+/// Releases the updater offers, from the `updateChannel` config option
+///
+/// Each channel includes the ones before it, so testers always get the
+/// newest build they opted into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateChannel {
+    /// Stable releases only
+    Stable,
+    /// Beta and release candidate pre-releases too (e.g., `v0.3.0-beta.1`)
+    Beta,
+    /// Nightly builds too (e.g., `v0.3.0-nightly.20260101`)
+    Nightly,
+}
+
+impl UpdateChannel {
+    /// Resolves the channel from the `updateChannel` config option
+    ///
+    /// # Arguments
+    ///
+    /// * `setting` - "stable", "beta" or "nightly"; anything else is stable
+    pub fn from_setting(setting: &str) -> Self {
+        match setting {
+            "beta" => Self::Beta,
+            "nightly" => Self::Nightly,
+            _ => Self::Stable,
+        }
+    }
+
+    /// Gets the channel from the user's config
+    pub fn configured() -> Self {
+        Self::from_setting(&crate::config::AppConfig::load().update_channel)
+    }
+
+    /// Checks whether a release belongs to the channel
+    ///
+    /// Tags whose pre-release part starts with `nightly` are nightly
+    /// builds. Other releases are pre-releases if GitHub marks them as one
+    /// or their tag has a pre-release part (e.g., `v0.2.0-beta.1`), and
+    /// belong to the beta channel.
+    fn includes(self, release: &Release, version: &Version) -> bool {
+        if release.draft {
+            return false;
+        }
+        let nightly = version.pre.as_str().starts_with("nightly");
+        let prerelease = release.prerelease || !version.pre.is_empty();
+        match self {
+            Self::Stable => !prerelease,
+            Self::Beta => !nightly,
+            Self::Nightly => true,
+        }
+    }
+}
+
 /// GitHub API response for a release asset
 #[derive(Debug, Deserialize)]
 struct Asset {
@@ -25,7 +102,9 @@ struct Asset {
 }
 
 /// Result of update check
-#[derive(Debug)]
+///
+/// Serializes with these field names for `uti update --check --json`.
+#[derive(Debug, Serialize)]
 pub struct UpdateCheckResult {
     pub current_version: String,
     pub latest_version: String,
@@ -33,6 +112,322 @@ pub struct UpdateCheckResult {
     pub uti_rpm_url: Option<String>,
     pub daemon_rpm_url: Option<String>,
     pub gnome_extension_url: Option<String>,
+    pub checksums_url: Option<String>,
+    /// What changed in the latest release (markdown), if it has notes
+    pub release_notes: Option<String>,
+    /// A newer release exists, but the user skipped it or asked to be
+    /// reminded later (`update_available` is false then)
+    pub declined: bool,
+}
+
+/// Seconds in a day, for "remind me in N days"
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// The user's answers to update offers, kept across checks
+///
+/// Stored in `~/.config/uti/update-state.json` so the tray and settings
+/// don't offer a release the user deliberately declined.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateDecisions {
+    /// Release the user chose to skip
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped_version: Option<String>,
+    /// Don't offer updates before this time (Unix seconds)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remind_after: Option<i64>,
+}
+
+impl UpdateDecisions {
+    /// Get the path to the decisions file
+    ///
+    /// Returns `~/.config/uti/update-state.json`
+    pub fn path() -> PathBuf {
+        crate::config::AppConfig::get_config_dir().join("update-state.json")
+    }
+
+    /// Load the decisions, or none if the file is missing or invalid
+    pub fn load() -> Self {
+        Self::load_from(&Self::path())
+    }
+
+    fn load_from(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the decisions
+    pub fn save(&self) -> std::io::Result<()> {
+        self.save_to(&Self::path())
+    }
+
+    fn save_to(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Stop offering a release; newer ones are offered again
+    pub fn skip(&mut self, version: &str) {
+        self.skipped_version = Some(version.to_string());
+    }
+
+    /// Stop offering updates for `days` days
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time in Unix seconds
+    pub fn remind_in(&mut self, days: u32, now: i64) {
+        self.remind_after = Some(now + i64::from(days) * DAY_SECS);
+    }
+
+    /// Checks whether the user declined being offered `version` now
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time in Unix seconds
+    pub fn declines(&self, version: &str, now: i64) -> bool {
+        self.skipped_version.as_deref() == Some(version)
+            || self.remind_after.is_some_and(|after| now < after)
+    }
+}
+
+/// UUID of the uti GNOME extension
+const GNOME_EXTENSION_UUID: &str = "uti@noppomario.github.io";
+
+/// Part of uti that an update installs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum Component {
+    /// The uti GUI
+    App,
+    /// uti-daemon (takes effect after logging out)
+    Daemon,
+    /// The GNOME Shell extension
+    Extension,
+}
+
+impl Component {
+    /// Every component, for a full update
+    pub const ALL: [Component; 3] = [Self::App, Self::Daemon, Self::Extension];
+
+    /// Human-readable name
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::App => "uti",
+            Self::Daemon => "daemon",
+            Self::Extension => "GNOME extension",
+        }
+    }
+
+    /// Version of the component installed on this system, if known
+    ///
+    /// The app reports its own version, the daemon the version of its RPM
+    /// and the extension the `version-name` in its metadata.
+    pub fn installed_version(self) -> Option<Version> {
+        match self {
+            Self::App => Version::parse(env!("CARGO_PKG_VERSION")).ok(),
+            Self::Daemon => {
+                let output = Command::new("rpm")
+                    .args(["-q", "--queryformat", "%{VERSION}", "uti-daemon"])
+                    .output()
+                    .ok()?;
+                if !output.status.success() {
+                    return None;
+                }
+                Version::parse(String::from_utf8_lossy(&output.stdout).trim()).ok()
+            }
+            Self::Extension => {
+                let user_dir = dirs::data_dir()?.join("gnome-shell/extensions");
+                let system_dir = PathBuf::from("/usr/share/gnome-shell/extensions");
+                [user_dir, system_dir]
+                    .iter()
+                    .find_map(|dir| extension_version(&dir.join(GNOME_EXTENSION_UUID)))
+            }
+        }
+    }
+}
+
+/// Read `version-name` from an installed extension's metadata.json
+fn extension_version(ext_dir: &Path) -> Option<Version> {
+    let metadata = std::fs::read_to_string(ext_dir.join("metadata.json")).ok()?;
+    let metadata: serde_json::Value = serde_json::from_str(&metadata).ok()?;
+    Version::parse(metadata.get("version-name")?.as_str()?).ok()
+}
+
+/// Checks whether two component versions can work together
+///
+/// Components are released together; they stay compatible within a
+/// release series (same major version, and same minor version before 1.0).
+pub fn compatible(a: &Version, b: &Version) -> bool {
+    a.major == b.major && (a.major > 0 || a.minor == b.minor)
+}
+
+/// Components left out of an update that won't work with `latest`
+///
+/// # Arguments
+///
+/// * `components` - Components being updated
+/// * `installed` - Looks up a component's installed version
+fn incompatible_components(
+    latest: &Version,
+    components: &[Component],
+    installed: impl Fn(Component) -> Option<Version>,
+) -> Vec<(Component, Version)> {
+    Component::ALL
+        .into_iter()
+        .filter(|component| !components.contains(component))
+        .filter_map(|component| Some((component, installed(component)?)))
+        .filter(|(_, version)| !compatible(version, latest))
+        .collect()
+}
+
+/// Refuse an update that would leave the other components incompatible
+///
+/// Components whose installed version is unknown are not checked.
+fn check_compatibility(latest_version: &str, components: &[Component]) -> Result<(), UpdateError> {
+    let latest = Version::parse(latest_version)
+        .map_err(|e| UpdateError::Parse(format!("Invalid version {}: {}", latest_version, e)))?;
+    let incompatible = incompatible_components(&latest, components, Component::installed_version);
+    if incompatible.is_empty() {
+        return Ok(());
+    }
+
+    let kept = incompatible
+        .iter()
+        .map(|(component, version)| format!("{} {}", component.label(), version))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Err(UpdateError::Incompatible(format!(
+        "{} won't work with {}; update them too",
+        kept, latest
+    )))
+}
+
+/// Version of the running daemon, asked over D-Bus
+///
+/// Differs from the installed RPM until the user logs out after updating
+/// the daemon. None if the daemon isn't running.
+pub async fn running_daemon_version() -> Option<Version> {
+    let conn = zbus::Connection::session().await.ok()?;
+    let proxy = uti_dbus::daemon::DaemonProxy::new(&conn).await.ok()?;
+    Version::parse(&proxy.get_version().await.ok()?).ok()
+}
+
+/// Components running at a version that won't work with this app
+///
+/// Compares the running daemon and the installed extension against the
+/// app's own version, e.g. after a partial update or before logging out.
+pub async fn version_mismatches() -> Vec<(Component, Version)> {
+    let Some(app_version) = Component::App.installed_version() else {
+        return Vec::new();
+    };
+    let daemon_version = running_daemon_version().await;
+    incompatible_components(
+        &app_version,
+        &[Component::App],
+        |component| match component {
+            Component::App => None,
+            Component::Daemon => daemon_version.clone(),
+            Component::Extension => component.installed_version(),
+        },
+    )
+}
+
+/// Warning for [`version_mismatches`], with how to fix them
+pub fn describe_mismatches(mismatches: &[(Component, Version)]) -> String {
+    let components = mismatches
+        .iter()
+        .map(|(component, version)| format!("{} {}", component.label(), version))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{} doesn't match uti {}. Run '{}' and log out and back in.",
+        components,
+        env!("CARGO_PKG_VERSION"),
+        manual_update_command()
+    )
+}
+
+/// Stage of an update, reported with its progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UpdatePhase {
+    /// Looking up the release and its checksums
+    Checking,
+    DownloadingDaemon,
+    DownloadingApp,
+    DownloadingExtension,
+    /// Installing packages (waits for the pkexec prompt)
+    Installing,
+}
+
+impl UpdatePhase {
+    /// Human-readable description, for the CLI
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Checking => "Checking",
+            Self::DownloadingDaemon => "Downloading daemon",
+            Self::DownloadingApp => "Downloading uti",
+            Self::DownloadingExtension => "Downloading GNOME extension",
+            Self::Installing => "Installing",
+        }
+    }
+}
+
+/// Progress of an update, sent as the `update-progress` event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProgress {
+    pub phase: UpdatePhase,
+    /// Bytes downloaded in this phase
+    pub downloaded: u64,
+    /// Size of the download, if the server announced it
+    pub total: Option<u64>,
+}
+
+impl UpdateProgress {
+    /// Progress at the start of a phase
+    pub fn start(phase: UpdatePhase) -> Self {
+        Self {
+            phase,
+            downloaded: 0,
+            total: None,
+        }
+    }
+
+    /// Renders a progress bar line for the terminal
+    ///
+    /// # Examples
+    ///
+    /// `Downloading uti [##########----------]  50%  1.0/2.0 MB`
+    pub fn bar(&self) -> String {
+        const WIDTH: u64 = 20;
+        const MB: f64 = 1024.0 * 1024.0;
+        match self.total {
+            Some(total) if total > 0 => {
+                let done = self.downloaded.min(total);
+                let filled = (done * WIDTH / total) as usize;
+                format!(
+                    "{} [{}{}] {:>3}%  {:.1}/{:.1} MB",
+                    self.phase.label(),
+                    "#".repeat(filled),
+                    "-".repeat(WIDTH as usize - filled),
+                    done * 100 / total,
+                    done as f64 / MB,
+                    total as f64 / MB
+                )
+            }
+            _ if self.downloaded > 0 => format!(
+                "{}  {:.1} MB",
+                self.phase.label(),
+                self.downloaded as f64 / MB
+            ),
+            _ => format!("{}...", self.phase.label()),
+        }
+    }
 }
 
 /// Error types for updater operations
@@ -42,6 +437,8 @@ pub enum UpdateError {
     Parse(String),
     Download(String),
     Install(String),
+    Verify(String),
+    Incompatible(String),
 }
 
 impl std::fmt::Display for UpdateError {
@@ -51,29 +448,72 @@ impl std::fmt::Display for UpdateError {
             Self::Parse(msg) => write!(f, "Parse error: {}", msg),
             Self::Download(msg) => write!(f, "Download error: {}", msg),
             Self::Install(msg) => write!(f, "Install error: {}", msg),
+            Self::Verify(msg) => write!(f, "Verification error: {}", msg),
+            Self::Incompatible(msg) => write!(f, "Incompatible versions: {}", msg),
         }
     }
 }
 
 impl std::error::Error for UpdateError {}
 
-const GITHUB_API_URL: &str = "https://api.github.com/repos/noppomario/uti/releases/latest";
+/// All releases, newest first; `/releases/latest` skips pre-releases
+const GITHUB_API_URL: &str = "https://api.github.com/repos/noppomario/uti/releases?per_page=30";
 const USER_AGENT: &str = "uti-updater";
 
+/// Minimum bytes between download progress reports
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+/// Tries per download before giving up
+const DOWNLOAD_ATTEMPTS: u32 = 5;
+
+/// Wait before retrying a failed download, doubled after each retry
+const DOWNLOAD_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Release asset listing the SHA-256 sum of every other asset
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Build the HTTP client for GitHub requests, using the configured proxy
+///
+/// Without `updateProxy` in the config, reqwest follows the `HTTPS_PROXY`,
+/// `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY` environment variables.
+fn client() -> Result<Client, UpdateError> {
+    let proxy = crate::config::AppConfig::load().update_proxy;
+    client_with_proxy(proxy.as_deref())
+}
+
+/// Build the HTTP client, sending all requests through `proxy` if set
+///
+/// # Arguments
+///
+/// * `proxy` - Proxy URL, e.g., `http://proxy.example.com:8080`
+fn client_with_proxy(proxy: Option<&str>) -> Result<Client, UpdateError> {
+    let mut builder = Client::builder().user_agent(USER_AGENT);
+    if let Some(url) = proxy {
+        let proxy = reqwest::Proxy::all(url)
+            .map_err(|e| UpdateError::Network(format!("Invalid proxy {}: {}", url, e)))?;
+        builder = builder.proxy(proxy);
+    }
+    builder
+        .build()
+        .map_err(|e| UpdateError::Network(e.to_string()))
+}
+
 /// Check for updates from GitHub Releases
 ///
 /// # Arguments
 ///
 /// * `current_version` - The current version of the application
+/// * `channel` - Which releases to consider
 ///
 /// # Returns
 ///
 /// Returns `UpdateCheckResult` with version comparison and download URLs
-pub async fn check_for_updates(current_version: &str) -> Result<UpdateCheckResult, UpdateError> {
-    let client = Client::builder()
-        .user_agent(USER_AGENT)
-        .build()
-        .map_err(|e| UpdateError::Network(e.to_string()))?;
+/// for the newest release in the channel
+pub async fn check_for_updates(
+    current_version: &str,
+    channel: UpdateChannel,
+) -> Result<UpdateCheckResult, UpdateError> {
+    let client = client()?;
 
     let response = client
         .get(GITHUB_API_URL)
@@ -88,19 +528,26 @@ pub async fn check_for_updates(current_version: &str) -> Result<UpdateCheckResul
         )));
     }
 
-    let release: Release = response
+    let releases: Vec<Release> = response
         .json()
         .await
         .map_err(|e| UpdateError::Parse(e.to_string()))?;
 
-    // Parse version (strip 'v' prefix if present)
-    let latest_version_str = release.tag_name.trim_start_matches('v');
     let current = Version::parse(current_version)
         .map_err(|e| UpdateError::Parse(format!("Invalid current version: {}", e)))?;
-    let latest = Version::parse(latest_version_str)
-        .map_err(|e| UpdateError::Parse(format!("Invalid latest version: {}", e)))?;
+    let (release, latest) = newest_release(releases, channel)
+        .ok_or_else(|| UpdateError::Parse("No release found in the update channel".to_string()))?;
 
-    let update_available = latest > current;
+    let newer = latest > current;
+    let declined = newer
+        && UpdateDecisions::load().declines(&latest.to_string(), chrono::Utc::now().timestamp());
+    let update_available = newer && !declined;
+    let release_notes = release
+        .body
+        .as_deref()
+        .map(str::trim)
+        .filter(|body| !body.is_empty())
+        .map(str::to_string);
 
     // Find RPM URLs
     let uti_rpm_url = release
@@ -121,31 +568,47 @@ pub async fn check_for_updates(current_version: &str) -> Result<UpdateCheckResul
         .find(|a| a.name == "gnome-extension.zip")
         .map(|a| a.browser_download_url.clone());
 
+    let checksums_url = release
+        .assets
+        .iter()
+        .find(|a| a.name == CHECKSUMS_ASSET)
+        .map(|a| a.browser_download_url.clone());
+
     Ok(UpdateCheckResult {
         current_version: current_version.to_string(),
-        latest_version: latest_version_str.to_string(),
+        latest_version: latest.to_string(),
         update_available,
         uti_rpm_url,
         daemon_rpm_url,
         gnome_extension_url,
+        checksums_url,
+        release_notes,
+        declined,
     })
 }
 
-/// Download a file to /tmp directory
+/// Pick the newest release in a channel
+///
+/// Releases whose tag isn't a version are skipped.
+fn newest_release(releases: Vec<Release>, channel: UpdateChannel) -> Option<(Release, Version)> {
+    releases
+        .into_iter()
+        .filter_map(|release| release.version().map(|version| (release, version)))
+        .filter(|(release, version)| channel.includes(release, version))
+        .max_by(|(_, a), (_, b)| a.cmp(b))
+}
+
+/// Download and parse the release's `SHA256SUMS`
 ///
 /// # Arguments
 ///
-/// * `url` - The URL to download from
-/// * `filename` - The filename to save as
+/// * `url` - The URL of the `SHA256SUMS` asset
 ///
 /// # Returns
 ///
-/// Returns the path to the downloaded file
-pub async fn download_rpm(url: &str, filename: &str) -> Result<PathBuf, UpdateError> {
-    let client = Client::builder()
-        .user_agent(USER_AGENT)
-        .build()
-        .map_err(|e| UpdateError::Network(e.to_string()))?;
+/// Returns the lowercase hex SHA-256 sum of each asset, by asset name
+pub async fn fetch_checksums(url: &str) -> Result<HashMap<String, String>, UpdateError> {
+    let client = client()?;
 
     let response = client
         .get(url)
@@ -155,68 +618,356 @@ pub async fn download_rpm(url: &str, filename: &str) -> Result<PathBuf, UpdateEr
 
     if !response.status().is_success() {
         return Err(UpdateError::Download(format!(
-            "Download failed with status: {}",
+            "Checksum download failed with status: {}",
             response.status()
         )));
     }
 
-    let bytes = response
-        .bytes()
+    let text = response
+        .text()
         .await
         .map_err(|e| UpdateError::Download(e.to_string()))?;
 
-    let path = PathBuf::from(format!("/tmp/{}", filename));
-    let mut file = std::fs::File::create(&path)
-        .map_err(|e| UpdateError::Download(format!("Failed to create file: {}", e)))?;
+    Ok(parse_checksums(&text))
+}
 
-    file.write_all(&bytes)
-        .map_err(|e| UpdateError::Download(format!("Failed to write file: {}", e)))?;
+/// Parse `sha256sum` output (`<hex>  <name>`, or `<hex> *<name>` in binary
+/// mode)
+///
+/// Lines that don't hold a 64-digit hex sum are skipped.
+fn parse_checksums(text: &str) -> HashMap<String, String> {
+    text.lines()
+        .filter_map(|line| {
+            let (sum, name) = line.trim().split_once(char::is_whitespace)?;
+            let name = name.trim_start();
+            let name = name.strip_prefix('*').unwrap_or(name);
+            let valid = sum.len() == 64 && sum.chars().all(|c| c.is_ascii_hexdigit());
+            (valid && !name.is_empty()).then(|| (name.to_string(), sum.to_ascii_lowercase()))
+        })
+        .collect()
+}
 
-    Ok(path)
+/// Get the asset name from its download URL (the last path segment)
+fn asset_name(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
 }
 
-/// Download a file to /tmp directory (generic version for any file type)
+/// Check a downloaded asset against the release's checksums
+///
+/// Deletes the file if it doesn't match, so it can't be installed by
+/// mistake.
+///
+/// # Arguments
+///
+/// * `path` - The downloaded file
+/// * `url` - The URL it was downloaded from, which names the asset
+/// * `checksums` - Sums from [`fetch_checksums`]
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the file's SHA-256 sum matches
+pub fn verify_download(
+    path: &Path,
+    url: &str,
+    checksums: &HashMap<String, String>,
+) -> Result<(), UpdateError> {
+    let name = asset_name(url);
+    let expected = checksums
+        .get(name)
+        .ok_or_else(|| UpdateError::Verify(format!("No checksum published for {}", name)))?;
+
+    let bytes = std::fs::read(path)
+        .map_err(|e| UpdateError::Verify(format!("Failed to read {}: {}", path.display(), e)))?;
+    let actual = format!("{:x}", Sha256::digest(&bytes));
+
+    if &actual != expected {
+        let _ = std::fs::remove_file(path);
+        return Err(UpdateError::Verify(format!(
+            "Checksum mismatch for {}: expected {}, got {}",
+            name, expected, actual
+        )));
+    }
+
+    Ok(())
+}
+
+/// Download an RPM package to the private update directory
 ///
 /// # Arguments
 ///
 /// * `url` - The URL to download from
 /// * `filename` - The filename to save as
 ///
+/// * `on_progress` - Called with the bytes downloaded and the total
+///
 /// # Returns
 ///
 /// Returns the path to the downloaded file
-pub async fn download_file(url: &str, filename: &str) -> Result<PathBuf, UpdateError> {
-    let client = Client::builder()
-        .user_agent(USER_AGENT)
-        .build()
-        .map_err(|e| UpdateError::Network(e.to_string()))?;
+pub async fn download_rpm(
+    url: &str,
+    filename: &str,
+    on_progress: &(dyn Fn(u64, Option<u64>) + Send + Sync),
+) -> Result<PathBuf, UpdateError> {
+    download_file(url, filename, on_progress).await
+}
 
-    let response = client
-        .get(url)
+/// Private directory for update downloads
+///
+/// Returns `~/.cache/uti/updates`, created (or tightened) to mode 0700 so
+/// other local users can't plant or swap the partial files resumed from.
+fn download_dir() -> Result<PathBuf, UpdateError> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let dir = dirs::cache_dir()
+        .ok_or_else(|| UpdateError::Download("Could not find cache directory".to_string()))?
+        .join("uti")
+        .join("updates");
+    let io_error = |e: std::io::Error| {
+        UpdateError::Download(format!("Failed to prepare {}: {}", dir.display(), e))
+    };
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(&dir)
+        .map_err(io_error)?;
+    std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)).map_err(io_error)?;
+    Ok(dir)
+}
+
+/// Length of a partial download to resume from
+///
+/// Only a regular file owned by the current user counts; anything else at
+/// that path is removed and the download starts over.
+fn resumable_len(part: &Path) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+
+    match std::fs::symlink_metadata(part) {
+        // SAFETY: getuid has no preconditions and can't fail
+        Ok(meta) if meta.is_file() && meta.uid() == unsafe { libc::getuid() } => meta.len(),
+        Ok(_) => {
+            tracing::warn!("Ignoring partial download not owned by us: {:?}", part);
+            let _ = std::fs::remove_file(part);
+            0
+        }
+        Err(_) => 0,
+    }
+}
+
+/// Download a file to the private update directory (any file type)
+///
+/// The download goes to `<filename>.part` first and resumes from it with
+/// a `Range` request when the connection drops, so a flaky network doesn't
+/// restart large RPMs from zero. The file is only renamed into place once
+/// its length matches what the server announced.
+///
+/// # Arguments
+///
+/// * `url` - The URL to download from
+/// * `filename` - The filename to save as
+/// * `on_progress` - Called with the bytes downloaded and the total
+///
+/// # Returns
+///
+/// Returns the path to the downloaded file
+pub async fn download_file(
+    url: &str,
+    filename: &str,
+    on_progress: &(dyn Fn(u64, Option<u64>) + Send + Sync),
+) -> Result<PathBuf, UpdateError> {
+    let path = download_dir()?.join(filename);
+    download_to(url, &path, DOWNLOAD_RETRY_DELAY, on_progress).await?;
+    Ok(path)
+}
+
+/// Download `url` to `path`, retrying and resuming from the partial file
+///
+/// # Arguments
+///
+/// * `url` - The URL to download from
+/// * `path` - Where to save the file
+/// * `retry_delay` - Wait before the first retry, doubled after each one
+/// * `on_progress` - Called with the bytes downloaded and the total
+async fn download_to(
+    url: &str,
+    path: &Path,
+    retry_delay: Duration,
+    on_progress: &(dyn Fn(u64, Option<u64>) + Send + Sync),
+) -> Result<(), UpdateError> {
+    let client = client()?;
+
+    let mut part = path.as_os_str().to_owned();
+    part.push(".part");
+    let part = PathBuf::from(part);
+
+    let mut delay = retry_delay;
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match download_attempt(&client, url, &part, on_progress).await {
+            Ok(()) => {
+                return std::fs::rename(&part, path).map_err(|e| {
+                    UpdateError::Download(format!("Failed to move download into place: {}", e))
+                });
+            }
+            Err(Attempt::Fatal(e)) => return Err(e),
+            Err(Attempt::Retry(e)) if attempt == DOWNLOAD_ATTEMPTS => return Err(e),
+            Err(Attempt::Retry(e)) => {
+                tracing::warn!(
+                    "Download attempt {}/{} failed, retrying: {}",
+                    attempt,
+                    DOWNLOAD_ATTEMPTS,
+                    e
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+    }
+    unreachable!("the last attempt returns")
+}
+
+/// Outcome of a failed download attempt
+enum Attempt {
+    /// Worth retrying: network errors, server errors, short reads
+    Retry(UpdateError),
+    /// Retrying won't help, e.g., 404 or a local I/O error
+    Fatal(UpdateError),
+}
+
+/// Download the rest of `url` into `part`, appending to what it holds
+///
+/// Progress is reported every [`PROGRESS_STEP`] bytes and at the end.
+async fn download_attempt(
+    client: &Client,
+    url: &str,
+    part: &Path,
+    on_progress: &(dyn Fn(u64, Option<u64>) + Send + Sync),
+) -> Result<(), Attempt> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let offset = resumable_len(part);
+
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let mut response = request
         .send()
         .await
-        .map_err(|e| UpdateError::Download(e.to_string()))?;
+        .map_err(|e| Attempt::Retry(UpdateError::Download(e.to_string())))?;
 
-    if !response.status().is_success() {
-        return Err(UpdateError::Download(format!(
-            "Download failed with status: {}",
-            response.status()
-        )));
-    }
+    let status = response.status();
+    let content_range = response
+        .headers()
+        .get(reqwest::header::CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_content_range);
 
-    let bytes = response
-        .bytes()
+    // Where this response starts in the file, and the full length if known
+    let (start, total) = match status {
+        reqwest::StatusCode::PARTIAL_CONTENT => match content_range {
+            Some(ContentRange::Bytes { start, total }) if start == offset => (start, total),
+            _ => {
+                // Not the range we asked for; start over
+                let _ = std::fs::remove_file(part);
+                return Err(Attempt::Retry(UpdateError::Download(
+                    "Server sent an unexpected range".to_string(),
+                )));
+            }
+        },
+        reqwest::StatusCode::RANGE_NOT_SATISFIABLE => {
+            // The partial file is already complete, or stale
+            if content_range == Some(ContentRange::Unsatisfied { total: offset }) {
+                on_progress(offset, Some(offset));
+                return Ok(());
+            }
+            let _ = std::fs::remove_file(part);
+            return Err(Attempt::Retry(UpdateError::Download(
+                "Partial download doesn't match the file on the server".to_string(),
+            )));
+        }
+        // The server ignored the range, so the body is the whole file
+        status if status.is_success() => (0, response.content_length()),
+        status => {
+            let e = UpdateError::Download(format!("Download failed with status: {}", status));
+            return Err(if status.is_server_error() {
+                Attempt::Retry(e)
+            } else {
+                Attempt::Fatal(e)
+            });
+        }
+    };
+
+    let io_error = |e: std::io::Error| {
+        Attempt::Fatal(UpdateError::Download(format!(
+            "Failed to write file: {}",
+            e
+        )))
+    };
+    // Never follow a symlink swapped in for the partial file
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(start == 0)
+        .append(start > 0)
+        .mode(0o600)
+        .custom_flags(libc::O_NOFOLLOW)
+        .open(part)
+        .map_err(io_error)?;
+
+    let mut written = start;
+    let mut reported = start;
+    on_progress(written, total);
+    while let Some(chunk) = response
+        .chunk()
         .await
-        .map_err(|e| UpdateError::Download(e.to_string()))?;
+        .map_err(|e| Attempt::Retry(UpdateError::Download(e.to_string())))?
+    {
+        file.write_all(&chunk).map_err(io_error)?;
+        written += chunk.len() as u64;
+        if written - reported >= PROGRESS_STEP {
+            on_progress(written, total);
+            reported = written;
+        }
+    }
+    file.flush().map_err(io_error)?;
+    if written != reported {
+        on_progress(written, total);
+    }
 
-    let path = PathBuf::from(format!("/tmp/{}", filename));
-    let mut file = std::fs::File::create(&path)
-        .map_err(|e| UpdateError::Download(format!("Failed to create file: {}", e)))?;
+    match total {
+        Some(total) if written != total => Err(Attempt::Retry(UpdateError::Download(format!(
+            "Download incomplete: got {} of {} bytes",
+            written, total
+        )))),
+        _ => Ok(()),
+    }
+}
 
-    file.write_all(&bytes)
-        .map_err(|e| UpdateError::Download(format!("Failed to write file: {}", e)))?;
+/// A parsed `Content-Range` response header
+#[derive(Debug, PartialEq)]
+enum ContentRange {
+    /// `bytes <start>-<end>/<total>`, total `*` if unknown
+    Bytes { start: u64, total: Option<u64> },
+    /// `bytes */<total>`, sent with 416 Range Not Satisfiable
+    Unsatisfied { total: u64 },
+}
 
-    Ok(path)
+/// Parse a `Content-Range` header value
+fn parse_content_range(value: &str) -> Option<ContentRange> {
+    let (range, total) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+    if range == "*" {
+        return Some(ContentRange::Unsatisfied {
+            total: total.parse().ok()?,
+        });
+    }
+    let (start, _end) = range.split_once('-')?;
+    let total = match total {
+        "*" => None,
+        total => Some(total.parse().ok()?),
+    };
+    Some(ContentRange::Bytes {
+        start: start.parse().ok()?,
+        total,
+    })
 }
 
 /// Install GNOME extension from a zip file
@@ -229,11 +980,10 @@ pub async fn download_file(url: &str, filename: &str) -> Result<PathBuf, UpdateE
 ///
 /// Returns `Ok(())` if installation was successful
 pub fn install_gnome_extension(zip_path: &PathBuf) -> Result<(), UpdateError> {
-    let ext_uuid = "uti@noppomario.github.io";
     let ext_dir = dirs::data_dir()
         .ok_or_else(|| UpdateError::Install("Could not find data directory".to_string()))?
         .join("gnome-shell/extensions")
-        .join(ext_uuid);
+        .join(GNOME_EXTENSION_UUID);
 
     // Create extension directory
     std::fs::create_dir_all(&ext_dir).map_err(|e| {
@@ -303,54 +1053,222 @@ pub fn install_rpms(rpm_paths: &[PathBuf]) -> Result<(), UpdateError> {
     Ok(())
 }
 
-/// Perform a full update (download and install RPMs and GNOME extension)
+/// Flatpak app ID, if uti runs inside Flatpak
+pub fn flatpak_app_id() -> Option<String> {
+    if !Path::new("/.flatpak-info").exists() {
+        return None;
+    }
+    std::env::var("FLATPAK_ID").ok().filter(|id| !id.is_empty())
+}
+
+/// Command users run to update uti themselves
+///
+/// # Returns
+///
+/// `flatpak update <app id>` inside Flatpak, `uti update` otherwise
+pub fn manual_update_command() -> String {
+    match flatpak_app_id() {
+        Some(app_id) => format!("flatpak update {}", app_id),
+        None => "uti update".to_string(),
+    }
+}
+
+/// Run a command on the host from inside Flatpak
+///
+/// Needs the `--talk-name=org.freedesktop.Flatpak` permission.
+fn run_on_host(args: &[&str]) -> Result<(), String> {
+    let output = Command::new("flatpak-spawn")
+        .arg("--host")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run flatpak-spawn: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+/// Update a Flatpak install: `flatpak update` on the host, then the GNOME
+/// extension if the host can be reached
+///
+/// The extension zip is downloaded to `$XDG_RUNTIME_DIR/app/<app id>`,
+/// which the host sees at the same path.
+async fn perform_flatpak_update(
+    result: &UpdateCheckResult,
+    app_id: &str,
+    components: &[Component],
+    on_progress: &(dyn Fn(UpdateProgress) + Send + Sync),
+) -> Result<bool, UpdateError> {
+    if components.contains(&Component::App) {
+        on_progress(UpdateProgress::start(UpdatePhase::Installing));
+        run_on_host(&["flatpak", "update", "-y", "--noninteractive", app_id]).map_err(|e| {
+            UpdateError::Install(format!(
+                "flatpak update failed ({}). Run 'flatpak update {}' on the host.",
+                e, app_id
+            ))
+        })?;
+    }
+
+    if !components.contains(&Component::Extension) {
+        return Ok(false);
+    }
+
+    let (Some(ext_url), Some(checksums_url)) = (&result.gnome_extension_url, &result.checksums_url)
+    else {
+        return Ok(false);
+    };
+    let Some(runtime_dir) = dirs::runtime_dir() else {
+        return Ok(false);
+    };
+    if !is_gnome_environment() {
+        return Ok(false);
+    }
+
+    on_progress(UpdateProgress::start(UpdatePhase::DownloadingExtension));
+    let checksums = fetch_checksums(checksums_url).await?;
+    let dir = runtime_dir.join("app").join(app_id);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| UpdateError::Download(format!("Failed to create {}: {}", dir.display(), e)))?;
+    let path = dir.join("gnome-extension.zip");
+    download_to(
+        ext_url,
+        &path,
+        DOWNLOAD_RETRY_DELAY,
+        &|downloaded, total| {
+            on_progress(UpdateProgress {
+                phase: UpdatePhase::DownloadingExtension,
+                downloaded,
+                total,
+            })
+        },
+    )
+    .await?;
+    verify_download(&path, ext_url, &checksums)?;
+
+    on_progress(UpdateProgress::start(UpdatePhase::Installing));
+    let installed = run_on_host(&[
+        "gnome-extensions",
+        "install",
+        "--force",
+        &path.to_string_lossy(),
+    ]);
+    let _ = std::fs::remove_file(&path);
+    match installed {
+        Ok(()) => Ok(true),
+        Err(e) => {
+            // The app is updated; the extension can be updated by hand
+            tracing::warn!("Failed to install the GNOME extension on the host: {}", e);
+            Ok(false)
+        }
+    }
+}
+
+/// Perform an update (download and install RPMs and GNOME extension)
 ///
 /// # Arguments
 ///
 /// * `result` - The update check result containing download URLs
+/// * `components` - Components to update ([`Component::ALL`] for all)
+/// * `on_progress` - Called when a phase starts and as downloads progress
 ///
 /// # Returns
 ///
-/// Returns `Ok(())` if update was successful
-pub async fn perform_update(result: &UpdateCheckResult) -> Result<(), UpdateError> {
+/// Returns `Ok(true)` if the GNOME extension was updated too, or
+/// [`UpdateError::Incompatible`] if the components left out won't work
+/// with the new version
+pub async fn perform_update_with_progress(
+    result: &UpdateCheckResult,
+    components: &[Component],
+    on_progress: &(dyn Fn(UpdateProgress) + Send + Sync),
+) -> Result<bool, UpdateError> {
+    on_progress(UpdateProgress::start(UpdatePhase::Checking));
+    check_compatibility(&result.latest_version, components)?;
+
+    if let Some(app_id) = flatpak_app_id() {
+        return perform_flatpak_update(result, &app_id, components, on_progress).await;
+    }
+
+    // Nothing is installed without checksums to verify it against
+    let checksums_url = result
+        .checksums_url
+        .as_deref()
+        .ok_or_else(|| UpdateError::Verify(format!("Release has no {} asset", CHECKSUMS_ASSET)))?;
+    let checksums = fetch_checksums(checksums_url).await?;
+
+    // Reports download progress for a phase
+    let downloading = |phase: UpdatePhase| {
+        move |downloaded: u64, total: Option<u64>| {
+            on_progress(UpdateProgress {
+                phase,
+                downloaded,
+                total,
+            })
+        }
+    };
+
     let mut rpm_paths: Vec<PathBuf> = Vec::new();
 
+    // Only the selected components are downloaded
+    let selected = |component: Component, url: &Option<String>| {
+        url.clone().filter(|_| components.contains(&component))
+    };
+
     // Download daemon RPM
-    if let Some(ref daemon_url) = result.daemon_rpm_url {
-        println!("Downloading daemon RPM...");
+    if let Some(ref daemon_url) = selected(Component::Daemon, &result.daemon_rpm_url) {
+        on_progress(UpdateProgress::start(UpdatePhase::DownloadingDaemon));
         let daemon_path = download_rpm(
             daemon_url,
             &format!("uti-daemon-{}.rpm", result.latest_version),
+            &downloading(UpdatePhase::DownloadingDaemon),
         )
         .await?;
+        verify_download(&daemon_path, daemon_url, &checksums)?;
         rpm_paths.push(daemon_path);
     }
 
     // Download uti app RPM
-    if let Some(ref uti_url) = result.uti_rpm_url {
-        println!("Downloading uti RPM...");
-        let uti_path = download_rpm(uti_url, &format!("uti-{}.rpm", result.latest_version)).await?;
+    if let Some(ref uti_url) = selected(Component::App, &result.uti_rpm_url) {
+        on_progress(UpdateProgress::start(UpdatePhase::DownloadingApp));
+        let uti_path = download_rpm(
+            uti_url,
+            &format!("uti-{}.rpm", result.latest_version),
+            &downloading(UpdatePhase::DownloadingApp),
+        )
+        .await?;
+        verify_download(&uti_path, uti_url, &checksums)?;
         rpm_paths.push(uti_path);
     }
 
+    // Download the GNOME extension (if available and on GNOME) before
+    // installing, so everything is verified first
+    let mut ext_path = None;
+    if let Some(ref ext_url) = selected(Component::Extension, &result.gnome_extension_url) {
+        if is_gnome_environment() {
+            on_progress(UpdateProgress::start(UpdatePhase::DownloadingExtension));
+            let path = download_file(
+                ext_url,
+                "gnome-extension.zip",
+                &downloading(UpdatePhase::DownloadingExtension),
+            )
+            .await?;
+            verify_download(&path, ext_url, &checksums)?;
+            ext_path = Some(path);
+        }
+    }
+
+    on_progress(UpdateProgress::start(UpdatePhase::Installing));
+
     // Install all RPMs in a single pkexec session (one authentication prompt)
     if !rpm_paths.is_empty() {
-        println!("Installing RPM packages...");
         install_rpms(&rpm_paths)?;
     }
 
-    // Download and install GNOME extension (if available and on GNOME)
-    if let Some(ref ext_url) = result.gnome_extension_url {
-        if is_gnome_environment() {
-            println!("Downloading GNOME extension...");
-            let ext_path = download_file(ext_url, "gnome-extension.zip").await?;
-            println!("Installing GNOME extension...");
-            install_gnome_extension(&ext_path)?;
-            println!("GNOME extension updated. Log out and log back in to apply changes.");
-        }
+    if let Some(ref path) = ext_path {
+        install_gnome_extension(path)?;
     }
 
-    Ok(())
+    Ok(ext_path.is_some())
 }
 
 /// Check if running in a GNOME environment
@@ -375,4 +1293,384 @@ mod tests {
         let v3 = Version::parse("1.0.0").unwrap();
         assert!(v3 > v2);
     }
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            draft: false,
+            prerelease,
+            body: None,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_newest_release_in_channel() {
+        let releases = || {
+            vec![
+                release("v0.3.0-nightly.20260101", true),
+                release("v0.3.0-beta.1", false),
+                release("v0.2.1", true),
+                release("nightly", true),
+                release("v0.2.0", false),
+                release("v0.1.3", false),
+            ]
+        };
+
+        let (stable, version) = newest_release(releases(), UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.tag_name, "v0.2.0");
+        assert_eq!(version, Version::parse("0.2.0").unwrap());
+
+        let (beta, _) = newest_release(releases(), UpdateChannel::Beta).unwrap();
+        assert_eq!(beta.tag_name, "v0.3.0-beta.1");
+
+        let (nightly, _) = newest_release(releases(), UpdateChannel::Nightly).unwrap();
+        assert_eq!(nightly.tag_name, "v0.3.0-nightly.20260101");
+
+        let mut drafts = releases();
+        drafts.iter_mut().for_each(|r| r.draft = true);
+        assert!(newest_release(drafts, UpdateChannel::Nightly).is_none());
+    }
+
+    #[test]
+    fn test_channel_includes_by_tag() {
+        let includes = |channel: UpdateChannel, tag: &str, prerelease: bool| {
+            let release = release(tag, prerelease);
+            let version = release.version().unwrap();
+            channel.includes(&release, &version)
+        };
+
+        assert!(includes(UpdateChannel::Stable, "v1.0.0", false));
+        assert!(!includes(UpdateChannel::Stable, "v1.0.0", true));
+        assert!(!includes(UpdateChannel::Stable, "v1.1.0-rc.1", false));
+
+        assert!(includes(UpdateChannel::Beta, "v1.0.0", false));
+        assert!(includes(UpdateChannel::Beta, "v1.1.0-beta.2", true));
+        assert!(includes(UpdateChannel::Beta, "v1.1.0-rc.1", false));
+        assert!(!includes(
+            UpdateChannel::Beta,
+            "v1.1.0-nightly.20260101",
+            true
+        ));
+
+        assert!(includes(UpdateChannel::Nightly, "v1.0.0", false));
+        assert!(includes(UpdateChannel::Nightly, "v1.1.0-beta.2", true));
+        assert!(includes(
+            UpdateChannel::Nightly,
+            "v1.1.0-nightly.20260101",
+            true
+        ));
+    }
+
+    #[test]
+    fn test_update_channel_from_setting() {
+        assert_eq!(UpdateChannel::from_setting("stable"), UpdateChannel::Stable);
+        assert_eq!(UpdateChannel::from_setting("beta"), UpdateChannel::Beta);
+        assert_eq!(
+            UpdateChannel::from_setting("nightly"),
+            UpdateChannel::Nightly
+        );
+        assert_eq!(UpdateChannel::from_setting("weekly"), UpdateChannel::Stable);
+    }
+
+    #[test]
+    fn test_progress_bar() {
+        let progress = UpdateProgress {
+            phase: UpdatePhase::DownloadingApp,
+            downloaded: 1024 * 1024,
+            total: Some(2 * 1024 * 1024),
+        };
+        assert_eq!(
+            progress.bar(),
+            "Downloading uti [##########----------]  50%  1.0/2.0 MB"
+        );
+
+        let unknown = UpdateProgress {
+            total: None,
+            ..progress
+        };
+        assert_eq!(unknown.bar(), "Downloading uti  1.0 MB");
+        assert_eq!(
+            UpdateProgress::start(UpdatePhase::Installing).bar(),
+            "Installing..."
+        );
+    }
+
+    #[test]
+    fn test_progress_serializes_for_frontend() {
+        let json =
+            serde_json::to_value(UpdateProgress::start(UpdatePhase::DownloadingDaemon)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "phase": "downloading-daemon", "downloaded": 0, "total": null })
+        );
+    }
+
+    #[test]
+    fn test_manual_update_command_outside_flatpak() {
+        if Path::new("/.flatpak-info").exists() {
+            return;
+        }
+        assert_eq!(flatpak_app_id(), None);
+        assert_eq!(manual_update_command(), "uti update");
+    }
+
+    #[test]
+    fn test_compatible() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        assert!(compatible(&v("0.1.3"), &v("0.1.0")));
+        assert!(!compatible(&v("0.1.3"), &v("0.2.0")));
+        assert!(compatible(&v("1.2.0"), &v("1.5.1")));
+        assert!(!compatible(&v("1.2.0"), &v("2.0.0")));
+    }
+
+    #[test]
+    fn test_incompatible_components() {
+        let v = |s: &str| Version::parse(s).unwrap();
+        let installed = |component| match component {
+            Component::App => Some(v("0.1.3")),
+            Component::Daemon => Some(v("0.1.3")),
+            Component::Extension => None,
+        };
+
+        // Leaving the daemon behind across a minor release isn't allowed
+        assert_eq!(
+            incompatible_components(&v("0.2.0"), &[Component::App], installed),
+            vec![(Component::Daemon, v("0.1.3"))]
+        );
+        // Patch releases mix, and unknown versions aren't checked
+        assert!(incompatible_components(&v("0.1.4"), &[Component::App], installed).is_empty());
+        assert!(incompatible_components(&v("0.2.0"), &Component::ALL, installed).is_empty());
+    }
+
+    #[test]
+    fn test_update_check_result_json() {
+        let result = UpdateCheckResult {
+            current_version: "0.1.3".to_string(),
+            latest_version: "0.2.0".to_string(),
+            update_available: true,
+            uti_rpm_url: Some("https://example.com/uti.rpm".to_string()),
+            daemon_rpm_url: None,
+            gnome_extension_url: None,
+            checksums_url: None,
+            release_notes: None,
+            declined: false,
+        };
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["current_version"], "0.1.3");
+        assert_eq!(json["latest_version"], "0.2.0");
+        assert_eq!(json["update_available"], true);
+        assert_eq!(json["uti_rpm_url"], "https://example.com/uti.rpm");
+        assert!(json["daemon_rpm_url"].is_null());
+    }
+
+    #[test]
+    fn test_describe_mismatches() {
+        let message = describe_mismatches(&[(Component::Daemon, Version::new(9, 0, 0))]);
+        assert!(message.starts_with(&format!(
+            "daemon 9.0.0 doesn't match uti {}.",
+            env!("CARGO_PKG_VERSION")
+        )));
+    }
+
+    #[test]
+    fn test_extension_version() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(extension_version(dir.path()), None);
+
+        std::fs::write(
+            dir.path().join("metadata.json"),
+            r#"{"uuid": "uti@noppomario.github.io", "version-name": "0.1.3"}"#,
+        )
+        .unwrap();
+        assert_eq!(extension_version(dir.path()), Version::parse("0.1.3").ok());
+    }
+
+    #[test]
+    fn test_update_decisions() {
+        const NOW: i64 = 1_700_000_000;
+
+        let mut decisions = UpdateDecisions::default();
+        assert!(!decisions.declines("0.2.0", NOW));
+
+        decisions.skip("0.2.0");
+        assert!(decisions.declines("0.2.0", NOW));
+        assert!(!decisions.declines("0.2.1", NOW));
+
+        decisions.remind_in(3, NOW);
+        assert!(decisions.declines("0.2.1", NOW + 3 * DAY_SECS - 1));
+        assert!(!decisions.declines("0.2.1", NOW + 3 * DAY_SECS));
+    }
+
+    #[test]
+    fn test_update_decisions_persist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("uti").join("update-state.json");
+        assert_eq!(
+            UpdateDecisions::load_from(&path),
+            UpdateDecisions::default()
+        );
+
+        let mut decisions = UpdateDecisions::default();
+        decisions.skip("0.2.0");
+        decisions.save_to(&path).unwrap();
+        assert_eq!(UpdateDecisions::load_from(&path), decisions);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(
+            UpdateDecisions::load_from(&path),
+            UpdateDecisions::default()
+        );
+    }
+
+    #[test]
+    fn test_client_with_proxy() {
+        assert!(client_with_proxy(None).is_ok());
+        assert!(client_with_proxy(Some("http://proxy.example.com:8080")).is_ok());
+        assert!(matches!(
+            client_with_proxy(Some("not a proxy url")),
+            Err(UpdateError::Network(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_content_range() {
+        assert_eq!(
+            parse_content_range("bytes 4-9/10"),
+            Some(ContentRange::Bytes {
+                start: 4,
+                total: Some(10)
+            })
+        );
+        assert_eq!(
+            parse_content_range("bytes 0-99/*"),
+            Some(ContentRange::Bytes {
+                start: 0,
+                total: None
+            })
+        );
+        assert_eq!(
+            parse_content_range("bytes */10"),
+            Some(ContentRange::Unsatisfied { total: 10 })
+        );
+        assert_eq!(parse_content_range("items 0-1/2"), None);
+        assert_eq!(parse_content_range("bytes x-9/10"), None);
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_after_dropped_connection() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/uti.rpm", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let responses: [&[u8]; 2] = [
+                // Announces 10 bytes but drops the connection after 4
+                b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\n0123",
+                b"HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 4-9/10\r\nContent-Length: 6\r\n\r\n456789",
+            ];
+            let mut requests = Vec::new();
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    request.extend_from_slice(&buf[..n]);
+                }
+                requests.push(String::from_utf8(request).unwrap().to_lowercase());
+                stream.write_all(response).await.unwrap();
+            }
+            requests
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("uti.rpm");
+        let progress = std::sync::Mutex::new(Vec::new());
+        download_to(
+            &url,
+            &path,
+            Duration::from_millis(10),
+            &|downloaded, total| progress.lock().unwrap().push((downloaded, total)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), b"0123456789");
+        // The resumed attempt starts from the partial file's length
+        let progress = progress.into_inner().unwrap();
+        assert_eq!(progress.last(), Some(&(10, Some(10))));
+        assert!(progress.contains(&(4, Some(10))));
+        assert!(!dir.path().join("uti.rpm.part").exists());
+
+        let requests = server.await.unwrap();
+        assert!(!requests[0].contains("range:"));
+        assert!(requests[1].contains("range: bytes=4-"));
+    }
+
+    #[test]
+    fn test_resumable_len() {
+        let dir = tempfile::tempdir().unwrap();
+        let part = dir.path().join("uti.rpm.part");
+        assert_eq!(resumable_len(&part), 0);
+
+        std::fs::write(&part, b"0123").unwrap();
+        assert_eq!(resumable_len(&part), 4);
+
+        // A symlink isn't resumed from, and is removed
+        let target = dir.path().join("elsewhere");
+        std::fs::write(&target, b"0123456").unwrap();
+        std::fs::remove_file(&part).unwrap();
+        std::os::unix::fs::symlink(&target, &part).unwrap();
+        assert_eq!(resumable_len(&part), 0);
+        assert!(std::fs::symlink_metadata(&part).is_err());
+        assert_eq!(std::fs::read(&target).unwrap(), b"0123456");
+    }
+
+    const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+    #[test]
+    fn test_parse_checksums() {
+        let text = format!(
+            "{}  uti-0.2.0-1.x86_64.rpm\n{} *gnome-extension.zip\n\nnot a checksum line\n",
+            HELLO_SHA256,
+            HELLO_SHA256.to_uppercase()
+        );
+        let checksums = parse_checksums(&text);
+
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(checksums["uti-0.2.0-1.x86_64.rpm"], HELLO_SHA256);
+        assert_eq!(checksums["gnome-extension.zip"], HELLO_SHA256);
+    }
+
+    #[test]
+    fn test_verify_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let url =
+            "https://github.com/noppomario/uti/releases/download/v0.2.0/uti-0.2.0-1.x86_64.rpm";
+        let checksums = HashMap::from([(
+            "uti-0.2.0-1.x86_64.rpm".to_string(),
+            HELLO_SHA256.to_string(),
+        )]);
+
+        let good = dir.path().join("good.rpm");
+        std::fs::write(&good, "hello").unwrap();
+        assert!(verify_download(&good, url, &checksums).is_ok());
+
+        // A mismatch removes the file
+        let bad = dir.path().join("bad.rpm");
+        std::fs::write(&bad, "tampered").unwrap();
+        assert!(matches!(
+            verify_download(&bad, url, &checksums),
+            Err(UpdateError::Verify(_))
+        ));
+        assert!(!bad.exists());
+
+        // Assets without a published sum are rejected
+        assert!(matches!(
+            verify_download(&good, "https://example.com/other.rpm", &checksums),
+            Err(UpdateError::Verify(_))
+        ));
+    }
 }
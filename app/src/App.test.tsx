@@ -30,6 +30,10 @@ vi.mock('./hooks/useClipboard', () => ({
   useClipboard: vi.fn(),
 }));
 
+vi.mock('./hooks/useDaemonStatus', () => ({
+  useDaemonStatus: vi.fn(() => true),
+}));
+
 describe('App', () => {
   beforeEach(() => {
     vi.clearAllMocks();
@@ -80,16 +84,16 @@ describe('App', () => {
     const { listen } = await import('@tauri-apps/api/event');
 
     const mockHistory = [
-      { text: 'Test item 1', timestamp: 123 },
-      { text: 'Test item 2', timestamp: 456 },
+      { id: '1', text: 'Test item 1', timestamp: 123 },
+      { id: '2', text: 'Test item 2', timestamp: 456 },
     ];
 
     vi.mocked(invoke).mockResolvedValueOnce(mockHistory);
 
-    let doubleCtrlHandler: (() => void) | undefined;
+    let doubleCtrlHandler: ((event: { payload: unknown }) => void) | undefined;
     vi.mocked(listen).mockImplementation((event, handler) => {
       if (event === 'double-ctrl-pressed') {
-        doubleCtrlHandler = handler as () => void;
+        doubleCtrlHandler = handler as (event: { payload: unknown }) => void;
       }
       return Promise.resolve(() => {});
     });
@@ -102,7 +106,7 @@ describe('App', () => {
     // Trigger double ctrl event
     await act(async () => {
       if (doubleCtrlHandler) {
-        doubleCtrlHandler();
+        doubleCtrlHandler({ payload: null });
       }
     });
 
@@ -110,6 +114,35 @@ describe('App', () => {
     expect(invoke).toHaveBeenCalledWith('toggle_window');
   });
 
+  it('ignores stale daemon triggers', async () => {
+    // Arrange
+    const { invoke } = await import('@tauri-apps/api/core');
+    const { listen } = await import('@tauri-apps/api/event');
+
+    let doubleCtrlHandler: ((event: { payload: unknown }) => void) | undefined;
+    vi.mocked(listen).mockImplementation((event, handler) => {
+      if (event === 'double-ctrl-pressed') {
+        doubleCtrlHandler = handler as (event: { payload: unknown }) => void;
+      }
+      return Promise.resolve(() => {});
+    });
+
+    await act(async () => {
+      render(<App />);
+    });
+    vi.mocked(invoke).mockClear();
+
+    // Act - a trigger queued during suspend arrives a minute late
+    await act(async () => {
+      doubleCtrlHandler?.({
+        payload: { source: 'Keyboard', gesture: 'double-tap', timestampMs: Date.now() - 60_000 },
+      });
+    });
+
+    // Assert
+    expect(invoke).not.toHaveBeenCalledWith('toggle_window');
+  });
+
   describe('keyboard shortcuts', () => {
     it('focuses search bar with Ctrl+F', async () => {
       // Arrange
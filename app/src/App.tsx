@@ -5,23 +5,117 @@ import { writeText } from '@tauri-apps/plugin-clipboard-manager';
 import type React from 'react';
 import { useCallback, useEffect, useMemo, useRef, useState } from 'react';
 import { ClipboardHistory, type ClipboardItem } from './components/ClipboardHistory';
+import { ErrorBanner } from './components/ErrorBanner';
 import type { RecentFile } from './components/JumpList';
 import { Launcher, type LauncherItem } from './components/Launcher';
 import { PinButton } from './components/PinButton';
 import { Prompt } from './components/Prompt';
 import { SearchBar } from './components/SearchBar';
+import { ShellOutput } from './components/ShellOutput';
 import { type SnippetItem, Snippets } from './components/Snippets';
 import { TabBar, type TabType } from './components/TabBar';
+import { useBackendErrors } from './hooks/useBackendErrors';
 import { useClipboard } from './hooks/useClipboard';
-import { useLauncher } from './hooks/useLauncher';
+import { useDaemonStatus } from './hooks/useDaemonStatus';
+import { parseWebSearch, useLauncher } from './hooks/useLauncher';
+import { parseShellCommand, useShellCommand } from './hooks/useShellCommand';
+
+/**
+ * Gesture that toggled the window, sent with daemon triggers
+ *
+ * Null for other sources (tray, D-Bus ToggleWindow).
+ */
+interface TriggerEvent {
+  /** Keyboard name, empty for the daemon's TriggerNow */
+  source: string;
+  /** 'double-tap', 'triple-tap', 'hold' or 'manual' */
+  gesture: string;
+  /** When the daemon recognized the gesture (Unix epoch milliseconds) */
+  timestampMs: number;
+}
+
+/** Triggers older than this were delivered late (e.g., after resume) */
+const STALE_TRIGGER_MS = 2000;
 
 /** Desktop application from .desktop file search */
 interface DesktopApp {
   id: string;
   name: string;
+  /** Exec line as written, including field codes (e.g., "firefox %u") */
   exec: string;
   icon: string | null;
   comment: string | null;
+  genericName: string | null;
+  keywords: string[];
+  /** Whether the app runs in a terminal (Terminal=true) */
+  terminal: boolean;
+  /** Where the app is installed; Flatpak apps are launched with `flatpak run` */
+  source: 'native' | 'flatpak';
+  flatpakId: string | null;
+  /** Fuzzy match score for the query, higher is better */
+  score: number;
+}
+
+/** Id of the launcher item that runs the typed shell command */
+const SHELL_ITEM_ID = 'shell-command';
+
+/** Id of the launcher item that opens the typed web search */
+const WEB_SEARCH_ITEM_ID = 'web-search';
+
+/** Launcher query prefix that searches files instead of apps (e.g., "'report") */
+const FILE_SEARCH_PREFIX = "'";
+
+/** Prefix of launcher item ids for file search results */
+const FILE_ITEM_PREFIX = 'file:';
+
+/** Launcher query prefix that searches emojis (e.g., ":tada") */
+const EMOJI_PREFIX = ':';
+
+/** Prefix of launcher item ids for emoji results */
+const EMOJI_ITEM_PREFIX = 'emoji:';
+
+/** Launcher query prefix that lists systemd user services (e.g., "!sync") */
+const SERVICE_PREFIX = '!';
+
+/** Prefix of launcher item ids for service actions ("service:restart:syncthing.service") */
+const SERVICE_ITEM_PREFIX = 'service:';
+
+/** Prefix of launcher item ids for script provider results ("script:3") */
+const SCRIPT_ITEM_PREFIX = 'script:';
+
+/** Launcher result returned by a `scriptProviders` script */
+interface ScriptResult {
+  provider: string;
+  title: string;
+  subtitle: string | null;
+  icon: string | null;
+  action:
+    | { type: 'open'; target: string }
+    | { type: 'copy'; text: string }
+    | { type: 'run'; command: string; args: string[] };
+}
+
+/** systemd user service found by `list_systemd_units` */
+interface SystemdUnit {
+  name: string;
+  description: string;
+  activeState: string;
+  subState: string;
+}
+
+/** Emoji found by `search_emoji` */
+interface EmojiMatch {
+  emoji: string;
+  name: string;
+  shortcodes: string[];
+  score: number;
+}
+
+/** File found by `search_files` */
+interface FileMatch {
+  path: string;
+  name: string;
+  isDir: boolean;
 }
 
 /** Inline styles using CSS variables for theme-based sizing */
@@ -30,6 +124,41 @@ const headerStyles: React.CSSProperties = {
   boxShadow: 'var(--shadow-header)',
 };
 
+/**
+ * Records a launch so frequently used entries rank higher
+ *
+ * @param id - Launcher entry or desktop app id
+ */
+function recordLaunch(id: string) {
+  invoke('record_launch', { id }).catch(err => {
+    console.error('Failed to record launch:', err);
+  });
+}
+
+/**
+ * Records a file opened from a jump list in the item's XBEL history
+ *
+ * Only `recently-used` sources are XBEL files; the other histories are kept
+ * by the apps themselves.
+ *
+ * @param item - The launcher item that opened the file
+ * @param filePath - The opened file path
+ */
+function recordRecentFile(item: LauncherItem, filePath: string) {
+  const source = item.historySource;
+  if (source?.type !== 'recently-used') {
+    return;
+  }
+  invoke('record_recent_file', {
+    filePath,
+    appName: source.appName ?? item.command,
+    command: item.command,
+    xbelPath: source.path ?? null,
+  }).catch(err => {
+    console.error('Failed to record recent file:', err);
+  });
+}
+
 /**
  * Main application component
  *
@@ -40,7 +169,7 @@ const headerStyles: React.CSSProperties = {
 function App() {
   const [history, setHistory] = useState<ClipboardItem[]>([]);
   const [snippets, setSnippets] = useState<SnippetItem[]>([]);
-  const [pendingPins, setPendingPins] = useState<Set<number>>(new Set());
+  const [pendingPins, setPendingPins] = useState<Set<string>>(new Set());
   const [activeTab, setActiveTab] = useState<TabType>('clipboard');
   const [expandedItemId, setExpandedItemId] = useState<string | undefined>();
   const [recentFiles, setRecentFiles] = useState<RecentFile[]>([]);
@@ -50,12 +179,24 @@ function App() {
     launcher: '',
   });
   const [desktopApps, setDesktopApps] = useState<DesktopApp[]>([]);
+  const [fileMatches, setFileMatches] = useState<FileMatch[]>([]);
+  const [emojiMatches, setEmojiMatches] = useState<EmojiMatch[]>([]);
+  const [systemdUnits, setSystemdUnits] = useState<SystemdUnit[]>([]);
+  const [scriptResults, setScriptResults] = useState<ScriptResult[]>([]);
   const [isPinned, setIsPinned] = useState(false);
   const searchInputRef = useRef<HTMLInputElement>(null);
   const listContainerRef = useRef<HTMLElement>(null);
 
-  // Start clipboard monitoring
-  useClipboard();
+  // Surface backend failures to the user
+  const { error: backendError, showError, dismiss: dismissError } = useBackendErrors();
+
+  // Tell the user when gestures stop working because the daemon is gone
+  const daemonConnected = useDaemonStatus();
+  useEffect(() => {
+    if (daemonConnected === false) {
+      showError('uti-daemon is not running, keyboard gestures are unavailable');
+    }
+  }, [daemonConnected, showError]);
 
   /**
    * Handles tab change and updates window mode for Prompt tab
@@ -93,14 +234,23 @@ function App() {
   }, [snippets, searchQueries.snippets]);
 
   // Load launcher configuration
-  const { commands: launcherItems } = useLauncher();
+  const { commands: launcherItems, searchProviders } = useLauncher();
+
+  // Shell command typed after the prefix (e.g., "> ls"), if any
+  const shellCommand = parseShellCommand(searchQueries.launcher);
+  const shell = useShellCommand();
+  const runShell = shell.run;
+  const showShellOutput = shell.running || shell.exitCode !== null || shell.lines.length > 0;
 
   // Search desktop apps when launcher search query changes (debounced)
   useEffect(() => {
     const query = searchQueries.launcher;
 
-    // Clear results if query is empty
-    if (!query) {
+    // Clear results if query is empty, a shell command, or a file, emoji or service search
+    const prefixed = [FILE_SEARCH_PREFIX, EMOJI_PREFIX, SERVICE_PREFIX].some(prefix =>
+      query.startsWith(prefix)
+    );
+    if (!query || parseShellCommand(query) !== null || prefixed) {
       setDesktopApps([]);
       return;
     }
@@ -119,6 +269,100 @@ function App() {
     return () => clearTimeout(timer);
   }, [searchQueries.launcher]);
 
+  // Search files when the launcher query starts with the file search prefix (debounced)
+  useEffect(() => {
+    const query = searchQueries.launcher;
+    const fileQuery = query.startsWith(FILE_SEARCH_PREFIX)
+      ? query.slice(FILE_SEARCH_PREFIX.length).trim()
+      : '';
+
+    if (!fileQuery) {
+      setFileMatches([]);
+      return;
+    }
+
+    const timer = setTimeout(async () => {
+      try {
+        setFileMatches(await invoke<FileMatch[]>('search_files', { query: fileQuery }));
+      } catch (err) {
+        // e.g., neither fd nor plocate is installed
+        showError(err);
+        setFileMatches([]);
+      }
+    }, 250);
+
+    return () => clearTimeout(timer);
+  }, [searchQueries.launcher, showError]);
+
+  // Run script providers alongside the desktop app search (debounced)
+  useEffect(() => {
+    const query = searchQueries.launcher;
+    const prefixed = [FILE_SEARCH_PREFIX, EMOJI_PREFIX, SERVICE_PREFIX].some(prefix =>
+      query.startsWith(prefix)
+    );
+    if (!query.trim() || parseShellCommand(query) !== null || prefixed) {
+      setScriptResults([]);
+      return;
+    }
+
+    const timer = setTimeout(async () => {
+      try {
+        setScriptResults(await invoke<ScriptResult[]>('search_script_providers', { query }));
+      } catch (err) {
+        console.error('Failed to run script providers:', err);
+        setScriptResults([]);
+      }
+    }, 200);
+
+    return () => clearTimeout(timer);
+  }, [searchQueries.launcher]);
+
+  // List systemd user services when the launcher query starts with the service prefix
+  useEffect(() => {
+    const query = searchQueries.launcher;
+    if (!query.startsWith(SERVICE_PREFIX)) {
+      setSystemdUnits([]);
+      return;
+    }
+
+    const timer = setTimeout(async () => {
+      try {
+        setSystemdUnits(
+          await invoke<SystemdUnit[]>('list_systemd_units', {
+            query: query.slice(SERVICE_PREFIX.length),
+          })
+        );
+      } catch (err) {
+        showError(err);
+        setSystemdUnits([]);
+      }
+    }, 150);
+
+    return () => clearTimeout(timer);
+  }, [searchQueries.launcher, showError]);
+
+  // Search emojis when the launcher query starts with the emoji prefix
+  useEffect(() => {
+    const query = searchQueries.launcher;
+    if (!query.startsWith(EMOJI_PREFIX)) {
+      setEmojiMatches([]);
+      return;
+    }
+
+    const timer = setTimeout(async () => {
+      try {
+        setEmojiMatches(
+          await invoke<EmojiMatch[]>('search_emoji', { query: query.slice(EMOJI_PREFIX.length) })
+        );
+      } catch (err) {
+        console.error('Failed to search emojis:', err);
+        setEmojiMatches([]);
+      }
+    }, 100);
+
+    return () => clearTimeout(timer);
+  }, [searchQueries.launcher]);
+
   // Convert DesktopApp to LauncherItem for display
   const desktopAppsAsLauncherItems: LauncherItem[] = useMemo(
     () =>
@@ -126,14 +370,100 @@ function App() {
         id: app.id,
         name: app.name,
         command: app.exec.split(' ')[0], // First part of exec
-        args: app.exec.split(' ').slice(1), // Rest as args
+        args: [], // Launched via execute_desktop_entry, which substitutes field codes
         historySource: null,
+        terminal: app.terminal,
+        desktopEntry: true,
       })),
     [desktopApps]
   );
 
   // Determine which items to show in launcher
-  const launcherDisplayItems = searchQueries.launcher ? desktopAppsAsLauncherItems : launcherItems;
+  const launcherDisplayItems: LauncherItem[] = useMemo(() => {
+    if (shellCommand !== null) {
+      return shellCommand
+        ? [
+            {
+              id: SHELL_ITEM_ID,
+              name: `Run: ${shellCommand}`,
+              command: shellCommand,
+              args: [],
+              historySource: null,
+            },
+          ]
+        : [];
+    }
+    if (!searchQueries.launcher) return launcherItems;
+
+    // Emojis are copied to the clipboard
+    if (searchQueries.launcher.startsWith(EMOJI_PREFIX)) {
+      return emojiMatches.map(match => ({
+        id: `${EMOJI_ITEM_PREFIX}${match.emoji}`,
+        name: `${match.emoji}  ${match.name}`,
+        command: match.emoji,
+        args: [],
+        historySource: null,
+      }));
+    }
+
+    // Running services can be restarted or stopped, others started
+    if (searchQueries.launcher.startsWith(SERVICE_PREFIX)) {
+      return systemdUnits.flatMap(unit => {
+        const running = unit.activeState === 'active';
+        const actions = running ? ['restart', 'stop'] : ['start'];
+        const label = unit.description ? `${unit.name} (${unit.description})` : unit.name;
+        return actions.map(action => ({
+          id: `${SERVICE_ITEM_PREFIX}${action}:${unit.name}`,
+          name: `${action[0].toUpperCase()}${action.slice(1)} ${label} [${unit.subState}]`,
+          command: unit.name,
+          args: [action],
+          historySource: null,
+        }));
+      });
+    }
+
+    // Files open with their default app
+    if (searchQueries.launcher.startsWith(FILE_SEARCH_PREFIX)) {
+      return fileMatches.map(file => ({
+        id: `${FILE_ITEM_PREFIX}${file.path}`,
+        name: file.path,
+        command: 'xdg-open',
+        args: [file.path],
+        historySource: null,
+      }));
+    }
+
+    // Script results follow the apps
+    const scriptItems: LauncherItem[] = scriptResults.map((result, index) => ({
+      id: `${SCRIPT_ITEM_PREFIX}${index}`,
+      name: result.subtitle ? `${result.title} — ${result.subtitle}` : result.title,
+      command: result.provider,
+      args: [],
+      historySource: null,
+    }));
+
+    // "gh tokio" with a "gh" search provider offers the web search first
+    const webSearch = parseWebSearch(searchQueries.launcher, searchProviders);
+    if (!webSearch) return [...desktopAppsAsLauncherItems, ...scriptItems];
+    const webSearchItem: LauncherItem = {
+      id: WEB_SEARCH_ITEM_ID,
+      name: `Search ${webSearch.provider.name}: ${webSearch.terms}`,
+      command: webSearch.provider.keyword,
+      args: [webSearch.terms],
+      historySource: null,
+    };
+    return [webSearchItem, ...desktopAppsAsLauncherItems, ...scriptItems];
+  }, [
+    shellCommand,
+    searchQueries.launcher,
+    searchProviders,
+    fileMatches,
+    emojiMatches,
+    systemdUnits,
+    scriptResults,
+    desktopAppsAsLauncherItems,
+    launcherItems,
+  ]);
 
   /**
    * Switch to the next tab (stops at ends, doesn't cycle)
@@ -165,6 +495,9 @@ function App() {
     }
   }, []);
 
+  // Reload history when the backend records a copy
+  useClipboard(loadHistory);
+
   /**
    * Loads snippets from backend
    */
@@ -182,31 +515,51 @@ function App() {
    */
   const handleTogglePin = useCallback(
     async (index: number) => {
-      const item = history[index];
+      const item = filteredHistory[index];
       if (!item) return;
 
-      const isCurrentlyPinned = pendingPins.has(index);
+      const isCurrentlyPinned = pendingPins.has(item.id);
 
       if (isCurrentlyPinned) {
         // Unpin: just remove from pending set
         setPendingPins(prev => {
           const newSet = new Set(prev);
-          newSet.delete(index);
+          newSet.delete(item.id);
           return newSet;
         });
       } else {
         // Pin: add to pending set and add to snippets immediately
-        setPendingPins(prev => new Set(prev).add(index));
+        setPendingPins(prev => new Set(prev).add(item.id));
         try {
           await invoke('add_snippet', { value: item.text, label: null });
           console.log('Added to snippets:', item.text.substring(0, 50));
           await loadSnippets();
         } catch (err) {
-          console.error('Failed to add to snippets:', err);
+          showError(err);
         }
       }
     },
-    [history, pendingPins, loadSnippets]
+    [filteredHistory, pendingPins, loadSnippets, showError]
+  );
+
+  /**
+   * Toggles whether a clipboard item is kept when the history limit is reached
+   */
+  const handleToggleKeep = useCallback(
+    async (index: number) => {
+      const item = filteredHistory[index];
+      if (!item) return;
+
+      try {
+        await invoke(item.pinned ? 'unpin_clipboard_item' : 'pin_clipboard_item', {
+          id: item.id,
+        });
+        await loadHistory();
+      } catch (err) {
+        showError(err);
+      }
+    },
+    [filteredHistory, loadHistory, showError]
   );
 
   /**
@@ -216,20 +569,17 @@ function App() {
   const processPendingPins = useCallback(async () => {
     if (pendingPins.size === 0) return;
 
-    // Sort indices in descending order to avoid index shifting during removal
-    const sortedIndices = Array.from(pendingPins).sort((a, b) => b - a);
-
-    for (const index of sortedIndices) {
+    for (const id of pendingPins) {
       try {
-        await invoke('remove_clipboard_item', { index });
+        await invoke('remove_clipboard_item_by_id', { id });
       } catch (err) {
-        console.error('Failed to remove clipboard item:', err);
+        showError(err);
       }
     }
 
     setPendingPins(new Set());
     await loadHistory();
-  }, [pendingPins, loadHistory]);
+  }, [pendingPins, loadHistory, showError]);
 
   /**
    * Toggles window pinned state (always-on-top with auto-hide disabled)
@@ -240,9 +590,9 @@ function App() {
       await invoke('set_pinned', { pinned: newPinned });
       setIsPinned(newPinned);
     } catch (err) {
-      console.error('Failed to set pinned state:', err);
+      showError(err);
     }
-  }, [isPinned]);
+  }, [isPinned, showError]);
 
   // Load clipboard history and snippets on initial mount
   useEffect(() => {
@@ -294,6 +644,20 @@ function App() {
     };
   }, [activeTab]);
 
+  // Reload snippets when the snippets file is changed elsewhere (e.g., synced)
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen('snippets-changed', () => {
+      loadSnippets();
+    }).then(fn => {
+      unlisten = fn;
+    });
+
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [loadSnippets]);
+
   // Listen for double Ctrl press to toggle window
   useEffect(() => {
     /**
@@ -303,7 +667,12 @@ function App() {
      */
     const setupListener = async () => {
       console.log('Setting up double-ctrl-pressed event listener...');
-      const unlisten = await listen('double-ctrl-pressed', () => {
+      const unlisten = await listen<TriggerEvent | null>('double-ctrl-pressed', event => {
+        const trigger = event.payload;
+        if (trigger && Date.now() - trigger.timestampMs > STALE_TRIGGER_MS) {
+          console.log('Ignoring stale trigger from', trigger.source);
+          return;
+        }
         console.log('Double Ctrl event received from Rust backend');
         console.log('Invoking toggle_window command...');
         invoke('toggle_window')
@@ -337,6 +706,22 @@ function App() {
     };
   }, []);
 
+  // Switch tabs when asked over D-Bus (OpenTab)
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<TabType>('open-tab', event => {
+      handleTabChange(event.payload);
+    })
+      .then(fn => {
+        unlisten = fn;
+      })
+      .catch(err => {
+        console.error('Failed to setup open-tab listener:', err);
+      });
+
+    return () => unlisten?.();
+  }, [handleTabChange]);
+
   // Global keyboard shortcut: Ctrl+F to focus search bar
   useEffect(() => {
     const handleKeyDown = (e: KeyboardEvent) => {
@@ -359,16 +744,16 @@ function App() {
    */
   const handleClipboardSelect = useCallback(async (text: string) => {
     try {
-      // Write to system clipboard
-      await writeText(text);
+      // Write to system clipboard (with HTML when the history has it)
+      await invoke('paste_item', { text });
       console.log('Clipboard updated:', text);
 
       // Hide window after selection
       await invoke('toggle_window');
     } catch (err) {
-      console.error('Failed to paste item:', err);
+      showError(err);
     }
-  }, []);
+  }, [showError]);
 
   /**
    * Handles snippet item selection
@@ -384,9 +769,9 @@ function App() {
       // Hide window after selection
       await invoke('toggle_window');
     } catch (err) {
-      console.error('Failed to copy snippet:', err);
+      showError(err);
     }
-  }, []);
+  }, [showError]);
 
   /**
    * Handles launcher item selection
@@ -395,15 +780,66 @@ function App() {
    */
   const handleLauncherSelect = useCallback(async (item: LauncherItem) => {
     try {
-      await invoke('execute_command', { command: item.command, args: item.args });
+      if (item.id === SHELL_ITEM_ID) {
+        // Keep the window open to show the output
+        await runShell(item.command);
+        return;
+      }
+
+      if (item.id.startsWith(EMOJI_ITEM_PREFIX)) {
+        await invoke('copy_emoji', { emoji: item.command });
+        await invoke('toggle_window');
+        return;
+      }
+
+      if (item.id.startsWith(SCRIPT_ITEM_PREFIX)) {
+        const result = scriptResults[Number(item.id.slice(SCRIPT_ITEM_PREFIX.length))];
+        if (result) {
+          await invoke('activate_script_result', { action: result.action });
+          await invoke('toggle_window');
+        }
+        return;
+      }
+
+      if (item.id.startsWith(SERVICE_ITEM_PREFIX)) {
+        await invoke('systemd_unit_action', { unit: item.command, action: item.args[0] });
+        await invoke('toggle_window');
+        return;
+      }
+
+      if (item.id === WEB_SEARCH_ITEM_ID) {
+        await invoke('open_web_search', { keyword: item.command, query: item.args[0] });
+        await invoke('toggle_window');
+        return;
+      }
+
+      if (!item.id.startsWith(FILE_ITEM_PREFIX)) recordLaunch(item.id);
+      if (item.action) {
+        // Built-in actions manage the window themselves (e.g., screenshot hides it)
+        await invoke('run_launcher_action', { action: item.action });
+        console.log('Ran action:', item.action.type);
+        return;
+      }
+
+      if (item.desktopEntry) {
+        await invoke('execute_desktop_entry', { id: item.id, files: [] });
+      } else {
+        await invoke('execute_command', {
+          command: item.command,
+          args: item.args,
+          terminal: item.terminal ?? false,
+          env: item.env ?? null,
+          cwd: item.cwd ?? null,
+        });
+      }
       console.log('Launched:', item.command);
 
       // Hide window after launching
       await invoke('toggle_window');
     } catch (err) {
-      console.error('Failed to launch:', err);
+      showError(err);
     }
-  }, []);
+  }, [runShell, scriptResults, showError]);
 
   /**
    * Handles launcher item expansion to show recent files
@@ -418,6 +854,7 @@ function App() {
 
     try {
       let files: RecentFile[];
+      const maxItems = item.maxItems ?? null;
 
       if (item.historySource.type === 'recently-used') {
         // Use recently-used.xbel with optional appName and custom path
@@ -432,7 +869,13 @@ function App() {
           return;
         }
 
-        files = await invoke<RecentFile[]>('get_recent_files', { appName, xbelPath });
+        const includeMissing = item.historySource.includeMissing ?? false;
+        files = await invoke<RecentFile[]>('get_recent_files', {
+          appName,
+          xbelPath,
+          includeMissing,
+          maxItems,
+        });
         console.log(
           'Loaded recent files for:',
           appName ?? 'all',
@@ -446,8 +889,47 @@ function App() {
           console.warn('No path configured for VSCode:', item.name);
           return;
         }
-        files = await invoke<RecentFile[]>('get_vscode_recent_files', { storagePath });
+        const includeMissing = item.historySource.includeMissing ?? false;
+        files = await invoke<RecentFile[]>('get_vscode_recent_files', {
+          storagePath,
+          includeMissing,
+          maxItems,
+        });
         console.log('Loaded VSCode recent files from:', storagePath, files.length);
+      } else if (item.historySource.type === 'sublime') {
+        // Use Sublime Text's Session.sublime_session
+        const sessionPath = item.historySource.path;
+        if (!sessionPath) {
+          console.warn('No path configured for Sublime Text:', item.name);
+          return;
+        }
+        const includeMissing = item.historySource.includeMissing ?? false;
+        files = await invoke<RecentFile[]>('get_sublime_recent_files', {
+          sessionPath,
+          includeMissing,
+          maxItems,
+        });
+        console.log('Loaded Sublime Text recent files from:', sessionPath, files.length);
+      } else if (item.historySource.type === 'sqlite') {
+        // Custom query against the app's own SQLite recents database
+        const { path: dbPath, query } = item.historySource;
+        if (!dbPath || !query) {
+          console.warn('No path or query configured for SQLite history:', item.name);
+          return;
+        }
+        files = await invoke<RecentFile[]>('get_sqlite_recent_files', {
+          dbPath,
+          query,
+          uriColumn: item.historySource.uriColumn ?? null,
+          includeMissing: item.historySource.includeMissing ?? false,
+          maxItems,
+        });
+        console.log('Loaded SQLite recent files from:', dbPath, files.length);
+      } else if (item.historySource.type === 'zoxide') {
+        // Frecent directories, opened with the item's command (file manager, terminal, ...)
+        const query = item.historySource.query ?? null;
+        files = await invoke<RecentFile[]>('get_zoxide_dirs', { query, maxItems });
+        console.log('Loaded zoxide directories:', files.length);
       } else {
         console.warn('Unknown historySource type:', item.historySource);
         return;
@@ -564,10 +1046,10 @@ function App() {
           await invoke('show_window');
         }
       } catch (err) {
-        console.error('Failed to submit prompt:', err);
+        showError(err);
       }
     },
-    [isPinned]
+    [isPinned, showError]
   );
 
   /**
@@ -577,18 +1059,27 @@ function App() {
    * @param filePath - The selected file path
    */
   const handleLauncherSelectWithFile = async (item: LauncherItem, filePath: string) => {
+    recordLaunch(item.id);
     try {
       // Execute command with file path as argument
-      await invoke('execute_command', {
-        command: item.command,
-        args: [...item.args, filePath],
-      });
+      if (item.desktopEntry) {
+        await invoke('execute_desktop_entry', { id: item.id, files: [filePath] });
+      } else {
+        await invoke('execute_command', {
+          command: item.command,
+          args: [...item.args, filePath],
+          terminal: item.terminal ?? false,
+          env: item.env ?? null,
+          cwd: item.cwd ?? null,
+        });
+      }
       console.log('Launched:', item.command, 'with file:', filePath);
+      recordRecentFile(item, filePath);
 
       // Hide window after launching
       await invoke('toggle_window');
     } catch (err) {
-      console.error('Failed to launch with file:', err);
+      showError(err);
     }
   };
 
@@ -625,6 +1116,7 @@ function App() {
           </div>
         )}
       </div>
+      {backendError && <ErrorBanner message={backendError} onDismiss={dismissError} />}
       <div className="flex-1 min-h-0">
         {activeTab === 'prompt' && (
           <Prompt onSubmit={handlePromptSubmit} onSwitchToNextTab={() => switchTab('right')} />
@@ -639,6 +1131,7 @@ function App() {
             listContainerRef={listContainerRef}
             pendingPins={pendingPins}
             onTogglePin={handleTogglePin}
+            onToggleKeep={handleToggleKeep}
           />
         )}
         {activeTab === 'snippets' && (
@@ -665,6 +1158,14 @@ function App() {
             listContainerRef={listContainerRef}
           />
         )}
+        {activeTab === 'launcher' && showShellOutput && (
+          <ShellOutput
+            lines={shell.lines}
+            running={shell.running}
+            exitCode={shell.exitCode}
+            onStop={shell.kill}
+          />
+        )}
       </div>
     </div>
   );
@@ -10,9 +10,9 @@ import { ClipboardHistory } from './ClipboardHistory';
 
 describe('ClipboardHistory', () => {
   const mockItems = [
-    { text: 'Item 1', timestamp: 1735567200 },
-    { text: 'Item 2', timestamp: 1735567100 },
-    { text: 'Item 3', timestamp: 1735567000 },
+    { id: '1', text: 'Item 1', timestamp: 1735567200 },
+    { id: '2', text: 'Item 2', timestamp: 1735567100 },
+    { id: '3', text: 'Item 3', timestamp: 1735567000 },
   ];
 
   it('should render empty state when no items', () => {
@@ -133,7 +133,7 @@ describe('ClipboardHistory', () => {
     const longText = 'A'.repeat(100);
     const onSelect = vi.fn();
     const { container } = render(
-      <ClipboardHistory items={[{ text: longText, timestamp: 123 }]} onSelect={onSelect} />
+      <ClipboardHistory items={[{ id: '1', text: longText, timestamp: 123 }]} onSelect={onSelect} />
     );
 
     const item = container.querySelector('[data-clipboard-item]');
@@ -232,7 +232,7 @@ describe('ClipboardHistory', () => {
           items={mockItems}
           onSelect={onSelect}
           onTogglePin={onTogglePin}
-          pendingPins={new Set([1])} // Second item is pinned
+          pendingPins={new Set(['2'])} // Second item is pinned
         />
       );
 
@@ -357,6 +357,37 @@ describe('ClipboardHistory', () => {
     });
   });
 
+  describe('lock button (keep in history)', () => {
+    it('should show kept state for pinned items', () => {
+      const items = [mockItems[0], { ...mockItems[1], pinned: true }];
+      const { container } = render(
+        <ClipboardHistory items={items} onSelect={vi.fn()} onToggleKeep={vi.fn()} />
+      );
+
+      expect(container.querySelectorAll('[aria-label="Keep in history"]').length).toBe(1);
+      const kept = container.querySelectorAll('[aria-label="Allow removal from history"]');
+      expect(kept.length).toBe(1);
+      expect(kept[0]?.className).toContain('text-app-accent');
+    });
+
+    it('should toggle keep with K key on selected item', () => {
+      const onSelect = vi.fn();
+      const onToggleKeep = vi.fn();
+      const { container } = render(
+        <ClipboardHistory items={mockItems} onSelect={onSelect} onToggleKeep={onToggleKeep} />
+      );
+
+      const list = container.querySelector('ul');
+      if (!list) throw new Error('List not found');
+
+      fireEvent.keyDown(list, { key: 'ArrowDown' });
+      fireEvent.keyDown(list, { key: 'k' });
+
+      expect(onToggleKeep).toHaveBeenCalledWith(1);
+      expect(onSelect).not.toHaveBeenCalled();
+    });
+  });
+
   describe('number key selection', () => {
     it('should select item with number key 1-9', () => {
       const onSelect = vi.fn();
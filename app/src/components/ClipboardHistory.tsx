@@ -3,17 +3,24 @@
  *
  * Displays clipboard history items with keyboard navigation support.
  * Users can select items with mouse click or keyboard (ArrowUp/Down + Enter).
+ * Kept (pinned) items are exempt from the history limit.
  * Uses CSS variables for sizing to support theme-based scaling.
  */
-import { Star } from 'lucide-react';
+import { Lock, Star } from 'lucide-react';
 import type React from 'react';
 import { useCallback, useEffect, useRef, useState } from 'react';
 import { useListKeyboardNavigation } from '../hooks/useListKeyboardNavigation';
 import { ListItem } from './ListItem';
 
 export interface ClipboardItem {
+  /** Stable ID (stays the same when the text is copied again) */
+  id: string;
   text: string;
   timestamp: number;
+  /** Whether the item is kept when the history limit is reached */
+  pinned?: boolean;
+  /** HTML copied alongside the text, if any */
+  html?: string;
 }
 
 export interface ClipboardHistoryProps {
@@ -29,10 +36,12 @@ export interface ClipboardHistoryProps {
   onUpAtTop?: () => void;
   /** Ref for the list container (for focus management) */
   listContainerRef?: React.RefObject<HTMLElement | null>;
-  /** Set of item indices that are pinned (will be moved to snippets on window close) */
-  pendingPins?: Set<number>;
+  /** Set of item IDs that are pinned (will be moved to snippets on window close) */
+  pendingPins?: Set<string>;
   /** Callback when star button is clicked */
   onTogglePin?: (index: number) => void;
+  /** Callback when lock button is clicked (keep item in history) */
+  onToggleKeep?: (index: number) => void;
 }
 
 /** Inline styles using CSS variables for theme-based sizing */
@@ -86,6 +95,43 @@ function StarButton({ isPinned, onClick, isVisible }: StarButtonProps) {
   );
 }
 
+interface KeepButtonProps {
+  isKept: boolean;
+  onClick: () => void;
+  isVisible: boolean;
+}
+
+/**
+ * Lock button that keeps an item from being evicted from history
+ * Uses span with role="button" to avoid invalid button nesting in ListItem
+ */
+function KeepButton({ isKept, onClick, isVisible }: KeepButtonProps) {
+  return (
+    // biome-ignore lint/a11y/useSemanticElements: Cannot use button to avoid invalid nesting (button inside ListItem button)
+    <span
+      role="button"
+      tabIndex={-1}
+      aria-label={isKept ? 'Allow removal from history' : 'Keep in history'}
+      onClick={e => {
+        e.stopPropagation();
+        onClick();
+      }}
+      onKeyDown={e => {
+        if (e.key === 'Enter' || e.key === ' ') {
+          e.preventDefault();
+          e.stopPropagation();
+          onClick();
+        }
+      }}
+      className={`inline-flex items-center rounded transition-opacity cursor-pointer hover:bg-app-item-hover ${
+        isKept ? 'text-app-accent' : 'text-app-text-muted hover:text-app-text'
+      } ${isVisible || isKept ? 'opacity-100' : 'opacity-0'}`}
+    >
+      <Lock size={ICON_SIZE} aria-hidden="true" />
+    </span>
+  );
+}
+
 /**
  * Renders clipboard history with keyboard navigation
  *
@@ -106,6 +152,7 @@ export function ClipboardHistory({
   listContainerRef,
   pendingPins,
   onTogglePin,
+  onToggleKeep,
 }: ClipboardHistoryProps) {
   const itemRefs = useRef<(HTMLButtonElement | null)[]>([]);
   const internalContainerRef = useRef<HTMLElement | null>(null);
@@ -122,7 +169,7 @@ export function ClipboardHistory({
   });
 
   /**
-   * Extended keyboard handler with number key and star toggle support
+   * Extended keyboard handler with number key, star and keep toggle support
    */
   const handleKeyDown = useCallback(
     (e: React.KeyboardEvent) => {
@@ -134,6 +181,14 @@ export function ClipboardHistory({
           return;
         }
       }
+      // Keep toggle with K key
+      if (e.key === 'k' || e.key === 'K') {
+        if (onToggleKeep && items.length > 0) {
+          e.preventDefault();
+          onToggleKeep(selectedIndex);
+          return;
+        }
+      }
       // Number key selection (1-9)
       if (e.key >= '1' && e.key <= '9') {
         const index = Number.parseInt(e.key, 10) - 1;
@@ -146,7 +201,7 @@ export function ClipboardHistory({
       // Fall through to base handler
       baseHandleKeyDown(e);
     },
-    [items, onSelect, baseHandleKeyDown, onTogglePin, selectedIndex]
+    [items, onSelect, baseHandleKeyDown, onTogglePin, onToggleKeep, selectedIndex]
   );
 
   // Scroll selected item into view when selectedIndex changes
@@ -193,10 +248,10 @@ export function ClipboardHistory({
       style={listStyles}
     >
       {items.map((item, index) => {
-        const isPinned = pendingPins?.has(index) ?? false;
+        const isPinned = pendingPins?.has(item.id) ?? false;
         const isVisible = hoveredIndex === index || selectedIndex === index;
         return (
-          <li key={`${item.timestamp}-${index}`}>
+          <li key={item.id}>
             <ListItem
               selected={index === selectedIndex}
               index={index}
@@ -207,12 +262,23 @@ export function ClipboardHistory({
               buttonRef={setItemRef(index)}
               dataAttributes={{ 'data-clipboard-item': true }}
               suffix={
-                onTogglePin ? (
-                  <StarButton
-                    isPinned={isPinned}
-                    onClick={() => onTogglePin(index)}
-                    isVisible={isVisible}
-                  />
+                onTogglePin || onToggleKeep ? (
+                  <>
+                    {onToggleKeep && (
+                      <KeepButton
+                        isKept={item.pinned ?? false}
+                        onClick={() => onToggleKeep(index)}
+                        isVisible={isVisible}
+                      />
+                    )}
+                    {onTogglePin && (
+                      <StarButton
+                        isPinned={isPinned}
+                        onClick={() => onTogglePin(index)}
+                        isVisible={isVisible}
+                      />
+                    )}
+                  </>
                 ) : undefined
               }
             >
@@ -0,0 +1,27 @@
+/**
+ * Tests for ErrorBanner component
+ *
+ * Shows the latest backend error with a dismiss button.
+ */
+
+import { fireEvent, render, screen } from '@testing-library/react';
+import { describe, expect, it, vi } from 'vitest';
+import { ErrorBanner } from './ErrorBanner';
+
+describe('ErrorBanner', () => {
+  it('renders the message as an alert', () => {
+    render(<ErrorBanner message="Failed to save snippets" onDismiss={() => {}} />);
+
+    const alert = screen.getByRole('alert');
+    expect(alert.textContent).toContain('Failed to save snippets');
+  });
+
+  it('calls onDismiss when the dismiss button is clicked', () => {
+    const handleDismiss = vi.fn();
+    render(<ErrorBanner message="Oops" onDismiss={handleDismiss} />);
+
+    fireEvent.click(screen.getByRole('button', { name: /dismiss error/i }));
+
+    expect(handleDismiss).toHaveBeenCalledTimes(1);
+  });
+});
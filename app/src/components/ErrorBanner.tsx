@@ -0,0 +1,47 @@
+import { X } from 'lucide-react';
+
+/**
+ * Props for the ErrorBanner component
+ */
+interface ErrorBannerProps {
+  /** Error message to show */
+  message: string;
+  /** Callback when the banner is dismissed */
+  onDismiss: () => void;
+}
+
+/** Icon size using em units to scale with text size */
+const ICON_SIZE = '1em';
+
+/** Inline styles using CSS variables for theme-based sizing */
+const bannerStyles: React.CSSProperties = {
+  fontSize: 'var(--size-font-base)',
+  padding: 'var(--size-padding-y) var(--size-padding-x)',
+  gap: 'var(--size-gap)',
+};
+
+/**
+ * Banner showing a backend error
+ *
+ * @param props - Component props
+ * @returns The ErrorBanner UI
+ */
+export function ErrorBanner({ message, onDismiss }: ErrorBannerProps) {
+  return (
+    <div
+      role="alert"
+      className="flex items-center justify-between bg-app-item text-app-accent-error border-b border-app-header-border"
+      style={bannerStyles}
+    >
+      <span className="truncate">{message}</span>
+      <button
+        type="button"
+        onClick={onDismiss}
+        className="text-app-text-muted hover:text-app-text transition-colors"
+        aria-label="Dismiss error"
+      >
+        <X size={ICON_SIZE} aria-hidden="true" />
+      </button>
+    </div>
+  );
+}
@@ -35,6 +35,15 @@ describe('JumpList', () => {
     expect(screen.getByText('config.ts')).toBeDefined();
   });
 
+  it('marks missing files', () => {
+    const files: RecentFile[] = [{ ...mockFiles[0], missing: true }, mockFiles[1]];
+    render(<JumpList files={files} onSelect={() => {}} onClose={() => {}} />);
+
+    expect(screen.getByText('main.rs').className).toContain('line-through');
+    expect(screen.getByText('config.ts').className).not.toContain('line-through');
+    expect(screen.getAllByText('(missing)')).toHaveLength(1);
+  });
+
   it('calls onSelect with file path when clicked', () => {
     const handleSelect = vi.fn();
     render(<JumpList files={mockFiles} onSelect={handleSelect} onClose={() => {}} />);
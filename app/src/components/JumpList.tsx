@@ -16,6 +16,8 @@ export interface RecentFile {
   path: string;
   name: string;
   timestamp: string;
+  /** File no longer exists (only listed with `includeMissing`) */
+  missing?: boolean;
 }
 
 /**
@@ -88,7 +90,10 @@ export function JumpList({ files, onSelect, onClose }: JumpListProps) {
             title={file.path}
             onClick={() => onSelect(file.path)}
           >
-            {file.name}
+            <span className={file.missing ? 'line-through text-app-text-muted' : undefined}>
+              {file.name}
+            </span>
+            {file.missing && <span className="text-app-text-muted"> (missing)</span>}
           </ListItem>
         </li>
       ))}
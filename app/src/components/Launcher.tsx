@@ -16,11 +16,24 @@ import { ListItem } from './ListItem';
  * History source configuration for jump list
  */
 export interface HistorySource {
-  type: 'recently-used' | 'vscode';
+  type: 'recently-used' | 'vscode' | 'sublime' | 'sqlite' | 'zoxide';
   appName?: string;
   path?: string;
+  /** zoxide keywords filtering the directories, or the SQL query for `sqlite` */
+  query?: string;
+  /** Column of the SQL query holding file URIs or paths */
+  uriColumn?: string;
+  /** Show recent files that no longer exist, marked as missing */
+  includeMissing?: boolean;
 }
 
+/**
+ * Built-in action run instead of an external command
+ */
+export type LauncherAction =
+  | { type: 'screenshot'; mode?: 'region' | 'window' | 'full' }
+  | { type: 'ocr'; language?: string };
+
 /**
  * Launcher item from backend
  */
@@ -30,6 +43,17 @@ export interface LauncherItem {
   command: string;
   args: string[];
   historySource: HistorySource | null;
+  action?: LauncherAction | null;
+  /** Run the command inside a terminal emulator */
+  terminal?: boolean;
+  /** Extra environment variables for the command */
+  env?: Record<string, string>;
+  /** Working directory for the command (`~/` is expanded) */
+  cwd?: string | null;
+  /** Number of recent files in the jump list (default 10) */
+  maxItems?: number | null;
+  /** Launch the desktop entry `id` instead of `command` (desktop app results) */
+  desktopEntry?: boolean;
 }
 
 /**
@@ -0,0 +1,43 @@
+/**
+ * Tests for ShellOutput component
+ *
+ * Shows the output of a shell command run from the launcher.
+ */
+
+import { fireEvent, render, screen } from '@testing-library/react';
+import { describe, expect, it, vi } from 'vitest';
+import { ShellOutput } from './ShellOutput';
+
+describe('ShellOutput', () => {
+  it('renders output lines and the running status', () => {
+    render(
+      <ShellOutput
+        lines={[
+          { stream: 'stdout', line: 'hello' },
+          { stream: 'stderr', line: 'warning: careful' },
+        ]}
+        running={true}
+        exitCode={null}
+      />
+    );
+
+    expect(screen.getByText('hello')).toBeDefined();
+    expect(screen.getByText('warning: careful').className).toContain('text-app-accent-error');
+    expect(screen.getByRole('status').textContent).toBe('Running…');
+  });
+
+  it('shows the exit code once finished', () => {
+    render(<ShellOutput lines={[]} running={false} exitCode={2} />);
+
+    expect(screen.getByRole('status').textContent).toBe('Exited with 2');
+    expect(screen.queryByText('Stop')).toBeNull();
+  });
+
+  it('stops the running command', () => {
+    const onStop = vi.fn();
+    render(<ShellOutput lines={[]} running={true} exitCode={null} onStop={onStop} />);
+
+    fireEvent.click(screen.getByText('Stop'));
+    expect(onStop).toHaveBeenCalled();
+  });
+});
@@ -0,0 +1,73 @@
+/**
+ * ShellOutput component
+ *
+ * Shows the streamed output of a shell command run from the launcher.
+ * Uses CSS variables for sizing to support theme-based scaling.
+ */
+
+import type React from 'react';
+import type { ShellLine } from '../hooks/useShellCommand';
+
+/**
+ * Props for ShellOutput component
+ */
+interface ShellOutputProps {
+  /** Output lines, in the order they were written */
+  lines: ShellLine[];
+  /** Whether the command is still running */
+  running: boolean;
+  /** Exit code once the command has finished */
+  exitCode: number | null;
+  /** Kills the running command */
+  onStop?: () => void;
+}
+
+/** Inline styles using CSS variables for theme-based sizing */
+const outputStyles: React.CSSProperties = {
+  fontSize: 'var(--size-font-base)',
+  padding: 'var(--size-padding-y) var(--size-padding-x)',
+};
+
+/**
+ * Command output with stderr lines highlighted
+ *
+ * @param props - Component props
+ * @returns The ShellOutput UI
+ */
+export function ShellOutput({ lines, running, exitCode, onStop }: ShellOutputProps) {
+  const status = running ? 'Running…' : `Exited with ${exitCode ?? 'signal'}`;
+
+  return (
+    <section
+      aria-label="Command output"
+      className="border-t border-app-header-border overflow-auto"
+      style={outputStyles}
+    >
+      <pre className="font-mono whitespace-pre-wrap break-all">
+        {lines.map((line, index) => (
+          // biome-ignore lint/suspicious/noArrayIndexKey: Output lines are append-only
+          <div
+            key={index}
+            className={line.stream === 'stderr' ? 'text-app-accent-error' : 'text-app-text'}
+          >
+            {line.line}
+          </div>
+        ))}
+      </pre>
+      <div className="flex items-center gap-2">
+        <p className="text-app-text-muted" role="status">
+          {status}
+        </p>
+        {running && onStop && (
+          <button
+            type="button"
+            onClick={onStop}
+            className="text-app-accent-error hover:underline"
+          >
+            Stop
+          </button>
+        )}
+      </div>
+    </section>
+  );
+}
@@ -14,6 +14,10 @@ export interface SnippetItem {
   id: string;
   label: string | null;
   value: string;
+  /** Category (folder) the snippet is filed under */
+  category?: string;
+  /** Quick-access slot (1-9) */
+  slot?: number;
 }
 
 export interface SnippetsProps {
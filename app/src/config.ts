@@ -38,6 +38,77 @@ export interface ThemeConfig {
   accentColor?: string;
 }
 
+/**
+ * Translation configuration (API keys are stored in the system keyring)
+ */
+export interface TranslationConfig {
+  /** Provider: 'libretranslate' or 'deepl' */
+  provider: 'libretranslate' | 'deepl';
+  /** LibreTranslate server URL */
+  libretranslateUrl: string;
+  /** Default target language code */
+  targetLanguage: string;
+}
+
+/**
+ * Daily focus mode schedule (local time, 'HH:MM')
+ */
+export interface FocusSchedule {
+  /** Start time, e.g. '22:00' */
+  start: string;
+  /** End time, e.g. '07:00' (before start runs overnight) */
+  end: string;
+}
+
+/**
+ * Log level for the backend log file
+ */
+export type LogLevel = 'error' | 'warn' | 'info' | 'debug' | 'trace';
+
+/**
+ * Compositor integration used for window placement and always-on-top
+ */
+export type Compositor = 'auto' | 'gnome' | 'kwin' | 'sway' | 'hyprland' | 'none';
+
+/**
+ * Releases offered by the updater
+ */
+export type UpdateChannel = 'stable' | 'beta' | 'nightly';
+
+/**
+ * Local HTTP API configuration (the token is stored in the system keyring)
+ */
+export interface HttpApiConfig {
+  /** Whether the HTTP API server is started */
+  enabled: boolean;
+  /** Port on 127.0.0.1 to listen on */
+  port: number;
+}
+
+/**
+ * Rules for copied text that is never stored in clipboard history
+ */
+export interface ClipboardFilterConfig {
+  /** Skip text that looks like a password, card number or private key */
+  detectSecrets: boolean;
+  /** Regex patterns; matching text is skipped */
+  patterns: string[];
+  /** App IDs or window classes (e.g. "keepassxc") whose copies are skipped */
+  apps: string[];
+}
+
+/**
+ * How the daemon pastes into the focused window
+ */
+export interface PasteConfig {
+  /** Shortcut: 'ctrl-shift-v' also works in terminals */
+  shortcut: 'ctrl-v' | 'ctrl-shift-v';
+  /** Press Enter after pasting */
+  enter: boolean;
+  /** Milliseconds between key events (0 uses the daemon's key_delay_ms) */
+  keyDelayMs: number;
+}
+
 export interface AppConfig {
   /** Theme configuration */
   theme: ThemeConfig;
@@ -45,8 +116,53 @@ export interface AppConfig {
   /** Maximum number of clipboard items to store */
   clipboardHistoryLimit: number;
 
+  /** Maximum size of one clipboard item in bytes (0 for no limit) */
+  clipboardItemMaxBytes: number;
+
+  /** Handling of items over the size limit */
+  clipboardOversize: 'truncate' | 'skip';
+
+  /** Days after which unpinned clipboard items are dropped (0 to keep them) */
+  clipboardItemTtl: number;
+
+  /** Encrypt clipboard history on disk (key kept in the system keyring) */
+  clipboardEncryption: boolean;
+
   /** UI language: 'en' or 'ja' */
   language: Language;
+
+  /** IANA timezones shown in the world clock (e.g., 'Asia/Tokyo') */
+  worldClockTimezones: string[];
+
+  /** Translation provider settings */
+  translation: TranslationConfig;
+
+  /** Daily schedule for automatic focus mode */
+  focusSchedule?: FocusSchedule;
+
+  /** Snippets file path, e.g., in a synced folder (`~/` is expanded) */
+  snippetsPath?: string;
+
+  /** Local HTTP API settings */
+  httpApi: HttpApiConfig;
+
+  /** Log level */
+  logLevel: LogLevel;
+
+  /** Compositor integration ('auto' detects it from the session) */
+  compositor: Compositor;
+
+  /** Releases offered by the updater (each channel includes the ones before it) */
+  updateChannel: UpdateChannel;
+
+  /** Proxy URL for update checks and downloads (HTTPS_PROXY/HTTP_PROXY when unset) */
+  updateProxy?: string;
+
+  /** Clipboard capture filter */
+  clipboardFilter: ClipboardFilterConfig;
+
+  /** Auto-paste settings */
+  paste: PasteConfig;
 }
 
 /**
@@ -61,7 +177,34 @@ export const defaultConfig: AppConfig = {
     size: 'normal',
   },
   clipboardHistoryLimit: 50,
+  clipboardItemMaxBytes: 1048576,
+  clipboardOversize: 'truncate',
+  clipboardItemTtl: 0,
+  clipboardEncryption: false,
   language: 'en',
+  worldClockTimezones: ['UTC', 'America/New_York', 'Europe/London', 'Asia/Tokyo'],
+  translation: {
+    provider: 'libretranslate',
+    libretranslateUrl: 'https://libretranslate.com',
+    targetLanguage: 'en',
+  },
+  httpApi: {
+    enabled: false,
+    port: 47917,
+  },
+  logLevel: 'info',
+  compositor: 'auto',
+  updateChannel: 'stable',
+  clipboardFilter: {
+    detectSecrets: true,
+    patterns: [],
+    apps: [],
+  },
+  paste: {
+    shortcut: 'ctrl-shift-v',
+    enter: true,
+    keyDelayMs: 0,
+  },
 };
 
 /**
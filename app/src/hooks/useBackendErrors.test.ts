@@ -0,0 +1,96 @@
+/**
+ * Tests for useBackendErrors hook
+ *
+ * This hook listens for backend-error events and tracks the latest error.
+ */
+
+import { act, renderHook } from '@testing-library/react';
+import { afterEach, beforeEach, describe, expect, it, vi } from 'vitest';
+import { useBackendErrors } from './useBackendErrors';
+
+// Mock Tauri APIs
+vi.mock('@tauri-apps/api/core', () => ({
+  invoke: vi.fn(() => Promise.resolve([])),
+}));
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(() => Promise.resolve(() => {})),
+}));
+
+describe('useBackendErrors', () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+    vi.useFakeTimers();
+    vi.spyOn(console, 'error').mockImplementation(() => {});
+  });
+
+  afterEach(() => {
+    vi.useRealTimers();
+  });
+
+  it('should listen for backend-error events', async () => {
+    const { listen } = await import('@tauri-apps/api/event');
+
+    renderHook(() => useBackendErrors());
+
+    expect(listen).toHaveBeenCalledWith('backend-error', expect.any(Function));
+  });
+
+  it('should show backend-error event messages', async () => {
+    const { listen } = await import('@tauri-apps/api/event');
+    let handler: ((event: { payload: { source: string; message: string } }) => void) | undefined;
+    vi.mocked(listen).mockImplementation((_event, callback) => {
+      handler = callback as typeof handler;
+      return Promise.resolve(() => {});
+    });
+
+    const { result } = renderHook(() => useBackendErrors());
+
+    act(() => {
+      handler?.({ payload: { source: 'dbus', message: 'D-Bus error' } });
+    });
+    expect(result.current.error).toBe('D-Bus error');
+  });
+
+  it('should show errors from startup once listening', async () => {
+    const { invoke } = await import('@tauri-apps/api/core');
+    vi.mocked(invoke).mockResolvedValueOnce([
+      { source: 'clipboard', message: 'Failed to open clipboard history' },
+    ]);
+
+    const { result } = renderHook(() => useBackendErrors());
+
+    await act(async () => {
+      await Promise.resolve();
+      await Promise.resolve();
+    });
+    expect(invoke).toHaveBeenCalledWith('take_backend_errors');
+    expect(result.current.error).toBe('Failed to open clipboard history');
+  });
+
+  it('should clear the error after 5 seconds', () => {
+    const { result } = renderHook(() => useBackendErrors());
+
+    act(() => {
+      result.current.showError('Failed to execute foo');
+    });
+    expect(result.current.error).toBe('Failed to execute foo');
+
+    act(() => {
+      vi.advanceTimersByTime(5000);
+    });
+    expect(result.current.error).toBeNull();
+  });
+
+  it('should clear the error on dismiss', () => {
+    const { result } = renderHook(() => useBackendErrors());
+
+    act(() => {
+      result.current.showError('Oops');
+    });
+    act(() => {
+      result.current.dismiss();
+    });
+    expect(result.current.error).toBeNull();
+  });
+});
@@ -0,0 +1,90 @@
+/**
+ * Custom hook for surfacing backend errors
+ *
+ * Listens for `backend-error` events (failures in background work such as
+ * D-Bus services or the tray) and lets components report failed commands.
+ * Errors from startup, sent before the listener existed, are fetched once
+ * it is registered.
+ * The latest error is shown until dismissed or for 5 seconds.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { useCallback, useEffect, useRef, useState } from 'react';
+
+const DISMISS_AFTER = 5000; // 5 seconds
+
+/**
+ * Payload of the `backend-error` event
+ */
+interface BackendErrorPayload {
+  /** Area that failed (e.g., "dbus", "tray") */
+  source: string;
+  /** Human-readable message */
+  message: string;
+}
+
+/**
+ * Tracks the latest backend error
+ *
+ * @returns The current error message, a function to show an error, and a dismiss function
+ *
+ * @example
+ * ```tsx
+ * const { error, showError, dismiss } = useBackendErrors();
+ * invoke('execute_command', { command, args }).catch(showError);
+ * ```
+ */
+export function useBackendErrors() {
+  const [error, setError] = useState<string | null>(null);
+  const timerRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+
+  const dismiss = useCallback(() => {
+    if (timerRef.current) {
+      clearTimeout(timerRef.current);
+      timerRef.current = null;
+    }
+    setError(null);
+  }, []);
+
+  const showError = useCallback(
+    (err: unknown) => {
+      console.error('Backend error:', err);
+      if (timerRef.current) {
+        clearTimeout(timerRef.current);
+      }
+      setError(String(err));
+      timerRef.current = setTimeout(dismiss, DISMISS_AFTER);
+    },
+    [dismiss]
+  );
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    listen<BackendErrorPayload>('backend-error', event => {
+      showError(event.payload.message);
+    })
+      .then(fn => {
+        unlisten = fn;
+        return invoke<BackendErrorPayload[]>('take_backend_errors');
+      })
+      .then(errors => {
+        const latest = errors?.at(-1);
+        if (latest) {
+          showError(latest.message);
+        }
+      })
+      .catch(err => {
+        console.error('Failed to setup backend-error listener:', err);
+      });
+
+    return () => {
+      unlisten?.();
+      if (timerRef.current) {
+        clearTimeout(timerRef.current);
+      }
+    };
+  }, [showError]);
+
+  return { error, showError, dismiss };
+}
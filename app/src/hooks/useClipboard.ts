@@ -1,57 +1,50 @@
 /**
- * Custom hook for monitoring clipboard changes
+ * Custom hook for following clipboard history changes
  *
- * Polls the clipboard every 1 second and adds new items to history.
- * Ignores duplicate or empty content.
+ * The backend watches the clipboard and records new items itself, then
+ * emits `clipboard-changed` with the item. Items dropped past their TTL
+ * are announced with `clipboard-expired` and the number removed. This hook
+ * reloads the history on either event.
  */
 
-import { invoke } from '@tauri-apps/api/core';
-import { readText } from '@tauri-apps/plugin-clipboard-manager';
-import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { useEffect } from 'react';
 
-const POLL_INTERVAL = 1000; // 1 second
+/** Events after which the history has to be reloaded */
+const CHANGE_EVENTS = ['clipboard-changed', 'clipboard-expired'];
 
 /**
- * Monitors clipboard changes and adds new items to history
+ * Calls `onChange` whenever the backend records or expires clipboard items
+ *
+ * @param onChange - Called after items are stored or dropped (e.g., reload history)
  *
  * @example
  * ```tsx
  * function App() {
- *   useClipboard(); // Start monitoring clipboard
+ *   useClipboard(loadHistory);
  *   return <div>App content</div>;
  * }
  * ```
  */
-export function useClipboard() {
-  const lastClipboardRef = useRef<string | null>(null);
-
+export function useClipboard(onChange: () => void) {
   useEffect(() => {
-    const pollClipboard = async () => {
-      try {
-        const current = await readText();
-
-        // Ignore null or empty content
-        if (!current || current.trim() === '') {
-          return;
-        }
-
-        // Ignore duplicates
-        if (current === lastClipboardRef.current) {
-          return;
-        }
-
-        // Add to history
-        await invoke('add_clipboard_item', { text: current });
-        lastClipboardRef.current = current;
-      } catch (error) {
-        console.error('Failed to read clipboard:', error);
-      }
-    };
-
-    const interval = setInterval(pollClipboard, POLL_INTERVAL);
+    const unlisteners: (() => void)[] = [];
+    for (const event of CHANGE_EVENTS) {
+      listen(event, () => {
+        onChange();
+      })
+        .then(fn => {
+          unlisteners.push(fn);
+        })
+        .catch(err => {
+          console.error(`Failed to setup ${event} listener:`, err);
+        });
+    }
 
     return () => {
-      clearInterval(interval);
+      for (const unlisten of unlisteners) {
+        unlisten();
+      }
     };
-  }, []);
+  }, [onChange]);
 }
@@ -0,0 +1,61 @@
+/**
+ * Tests for useDaemonStatus hook
+ *
+ * This hook reads the daemon status and follows daemon-status events.
+ */
+
+import { act, renderHook, waitFor } from '@testing-library/react';
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import { useDaemonStatus } from './useDaemonStatus';
+
+// Mock Tauri APIs
+vi.mock('@tauri-apps/api/core', () => ({
+  invoke: vi.fn(() => Promise.resolve(true)),
+}));
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(() => Promise.resolve(() => {})),
+}));
+
+describe('useDaemonStatus', () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it('should read the initial status', async () => {
+    const { invoke } = await import('@tauri-apps/api/core');
+
+    const { result } = renderHook(() => useDaemonStatus());
+
+    expect(invoke).toHaveBeenCalledWith('get_daemon_status');
+    await waitFor(() => expect(result.current).toBe(true));
+  });
+
+  it('should follow daemon-status events', async () => {
+    const { listen } = await import('@tauri-apps/api/event');
+    let handler: ((event: { payload: { connected: boolean } }) => void) | undefined;
+    vi.mocked(listen).mockImplementation((_event, callback) => {
+      handler = callback as typeof handler;
+      return Promise.resolve(() => {});
+    });
+
+    const { result } = renderHook(() => useDaemonStatus());
+    await waitFor(() => expect(result.current).toBe(true));
+    act(() => handler?.({ payload: { connected: false } }));
+
+    expect(listen).toHaveBeenCalledWith('daemon-status', expect.any(Function));
+    expect(result.current).toBe(false);
+  });
+
+  it('should stop listening on unmount', async () => {
+    const { listen } = await import('@tauri-apps/api/event');
+    const unlisten = vi.fn();
+    vi.mocked(listen).mockResolvedValue(unlisten);
+
+    const { unmount } = renderHook(() => useDaemonStatus());
+    await Promise.resolve();
+    unmount();
+
+    expect(unlisten).toHaveBeenCalledTimes(1);
+  });
+});
@@ -0,0 +1,64 @@
+/**
+ * Custom hook for following whether uti-daemon is running
+ *
+ * Reads the status with `get_daemon_status`, then follows the
+ * `daemon-status` events the backend emits when the daemon appears on or
+ * leaves the session bus (e.g., while it restarts).
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { useEffect, useState } from 'react';
+
+/**
+ * Payload of the `daemon-status` event
+ */
+interface DaemonStatusPayload {
+  /** Whether the daemon owns its bus name */
+  connected: boolean;
+}
+
+/**
+ * Tracks whether the daemon is running
+ *
+ * @returns Whether the daemon is on the bus, or null until known
+ *
+ * @example
+ * ```tsx
+ * const daemonConnected = useDaemonStatus();
+ * if (daemonConnected === false) console.warn('Gestures are unavailable');
+ * ```
+ */
+export function useDaemonStatus() {
+  const [connected, setConnected] = useState<boolean | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+
+    listen<DaemonStatusPayload>('daemon-status', event => {
+      setConnected(event.payload.connected);
+    })
+      .then(fn => {
+        unlisten = fn;
+      })
+      .catch(err => {
+        console.error('Failed to setup daemon-status listener:', err);
+      });
+
+    invoke<boolean>('get_daemon_status')
+      .then(status => {
+        if (!cancelled) setConnected(current => current ?? status);
+      })
+      .catch(err => {
+        console.error('Failed to get daemon status:', err);
+      });
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  return connected;
+}
@@ -6,7 +6,7 @@
 
 import { act, renderHook, waitFor } from '@testing-library/react';
 import { beforeEach, describe, expect, it, vi } from 'vitest';
-import { useLauncher } from './useLauncher';
+import { parseWebSearch, useLauncher } from './useLauncher';
 
 // Mock Tauri API
 vi.mock('@tauri-apps/api/core', () => ({
@@ -97,3 +97,21 @@ describe('useLauncher', () => {
     expect(invoke).toHaveBeenCalledTimes(2);
   });
 });
+
+describe('parseWebSearch', () => {
+  const providers = [
+    { name: 'GitHub', keyword: 'gh', url: 'https://github.com/search?q={query}' },
+  ];
+
+  it('splits the keyword from the search terms', () => {
+    const result = parseWebSearch('GH  tokio runtime', providers);
+    expect(result?.provider.name).toBe('GitHub');
+    expect(result?.terms).toBe('tokio runtime');
+  });
+
+  it('returns null without a known keyword or terms', () => {
+    expect(parseWebSearch('firefox nightly', providers)).toBeNull();
+    expect(parseWebSearch('gh', providers)).toBeNull();
+    expect(parseWebSearch('gh ', providers)).toBeNull();
+  });
+});
@@ -12,11 +12,24 @@ import { useCallback, useEffect, useState } from 'react';
  * History source configuration for jump list
  */
 export interface HistorySource {
-  type: 'recently-used' | 'vscode';
+  type: 'recently-used' | 'vscode' | 'sublime' | 'sqlite' | 'zoxide';
   appName?: string;
   path?: string;
+  /** zoxide keywords filtering the directories, or the SQL query for `sqlite` */
+  query?: string;
+  /** Column of the SQL query holding file URIs or paths */
+  uriColumn?: string;
+  /** Show recent files that no longer exist, marked as missing */
+  includeMissing?: boolean;
 }
 
+/**
+ * Built-in action run instead of an external command
+ */
+export type LauncherAction =
+  | { type: 'screenshot'; mode?: 'region' | 'window' | 'full' }
+  | { type: 'ocr'; language?: string };
+
 /**
  * Launcher command item
  */
@@ -26,6 +39,25 @@ export interface LauncherItem {
   command: string;
   args: string[];
   historySource: HistorySource | null;
+  action?: LauncherAction | null;
+  /** Run the command inside a terminal emulator */
+  terminal?: boolean;
+  /** Extra environment variables for the command */
+  env?: Record<string, string>;
+  /** Working directory for the command (`~/` is expanded) */
+  cwd?: string | null;
+  /** Number of recent files in the jump list (default 10) */
+  maxItems?: number | null;
+}
+
+/**
+ * Web search shortcut (e.g., "gh tokio" searches GitHub)
+ */
+export interface SearchProvider {
+  name: string;
+  keyword: string;
+  /** URL template containing `{query}` */
+  url: string;
 }
 
 /**
@@ -33,6 +65,7 @@ export interface LauncherItem {
  */
 interface LauncherConfig {
   commands: LauncherItem[];
+  searchProviders: SearchProvider[];
 }
 
 /**
@@ -41,6 +74,8 @@ interface LauncherConfig {
 interface UseLauncherResult {
   /** List of launcher commands */
   commands: LauncherItem[];
+  /** Web search shortcuts */
+  searchProviders: SearchProvider[];
   /** Whether the config is being loaded */
   isLoading: boolean;
   /** Error message if loading failed */
@@ -49,6 +84,24 @@ interface UseLauncherResult {
   reload: () => Promise<void>;
 }
 
+/**
+ * Matches a launcher query against the web search keywords
+ *
+ * @param query - Launcher search query (e.g., "gh tokio")
+ * @param providers - Configured search providers
+ * @returns The provider and search terms, or null if the first word isn't a keyword
+ */
+export function parseWebSearch(
+  query: string,
+  providers: SearchProvider[]
+): { provider: SearchProvider; terms: string } | null {
+  const match = query.trim().match(/^(\S+)\s+(.+)$/);
+  if (!match) return null;
+  const keyword = match[1].toLowerCase();
+  const provider = providers.find(p => p.keyword.toLowerCase() === keyword);
+  return provider ? { provider, terms: match[2] } : null;
+}
+
 /**
  * Hook for managing launcher configuration
  *
@@ -66,6 +119,7 @@ interface UseLauncherResult {
  */
 export function useLauncher(): UseLauncherResult {
   const [commands, setCommands] = useState<LauncherItem[]>([]);
+  const [searchProviders, setSearchProviders] = useState<SearchProvider[]>([]);
   const [isLoading, setIsLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
 
@@ -76,10 +130,12 @@ export function useLauncher(): UseLauncherResult {
     try {
       const config = await invoke<LauncherConfig>('get_launcher_config');
       setCommands(config.commands);
+      setSearchProviders(config.searchProviders ?? []);
     } catch (err) {
       console.error('Failed to load launcher config:', err);
       setError('Failed to load launcher config');
       setCommands([]);
+      setSearchProviders([]);
     } finally {
       setIsLoading(false);
     }
@@ -91,6 +147,7 @@ export function useLauncher(): UseLauncherResult {
 
   return {
     commands,
+    searchProviders,
     isLoading,
     error,
     reload: loadConfig,
@@ -0,0 +1,113 @@
+/**
+ * Tests for useShellCommand hook
+ *
+ * Runs shell commands and collects their streamed output.
+ */
+
+import { act, renderHook } from '@testing-library/react';
+import { beforeEach, describe, expect, it, vi } from 'vitest';
+import { parseShellCommand, useShellCommand } from './useShellCommand';
+
+// Mock Tauri APIs
+vi.mock('@tauri-apps/api/core', () => ({
+  invoke: vi.fn(),
+}));
+
+vi.mock('@tauri-apps/api/event', () => ({
+  listen: vi.fn(() => Promise.resolve(() => {})),
+}));
+
+type Handler = (event: { payload: unknown }) => void;
+
+/** Captures the handlers registered with listen */
+async function captureHandlers(): Promise<Record<string, Handler>> {
+  const { listen } = await import('@tauri-apps/api/event');
+  const handlers: Record<string, Handler> = {};
+  vi.mocked(listen).mockImplementation((event, callback) => {
+    handlers[event] = callback as Handler;
+    return Promise.resolve(() => {});
+  });
+  return handlers;
+}
+
+describe('parseShellCommand', () => {
+  it('returns the command after the prefix', () => {
+    expect(parseShellCommand('> ls -la')).toBe('ls -la');
+    expect(parseShellCommand('  >echo hi ')).toBe('echo hi');
+    expect(parseShellCommand('>')).toBe('');
+  });
+
+  it('returns null for other queries', () => {
+    expect(parseShellCommand('firefox')).toBeNull();
+    expect(parseShellCommand('a > b')).toBeNull();
+  });
+});
+
+describe('useShellCommand', () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it('runs the command and collects its output', async () => {
+    const { invoke } = await import('@tauri-apps/api/core');
+    vi.mocked(invoke).mockResolvedValue(7);
+    const handlers = await captureHandlers();
+
+    const { result } = renderHook(() => useShellCommand());
+    await act(() => result.current.run('echo hi'));
+
+    expect(invoke).toHaveBeenCalledWith('run_shell_command', { cmd: 'echo hi' });
+    expect(result.current.running).toBe(true);
+
+    act(() => {
+      handlers['shell-output']({ payload: { id: 7, stream: 'stdout', line: 'hi' } });
+      handlers['shell-output']({ payload: { id: 3, stream: 'stdout', line: 'old run' } });
+      handlers['shell-exit']({ payload: { id: 7, code: 0 } });
+    });
+
+    expect(result.current.lines).toEqual([{ stream: 'stdout', line: 'hi' }]);
+    expect(result.current.running).toBe(false);
+    expect(result.current.exitCode).toBe(0);
+  });
+
+  it('keeps output that arrives before the run id', async () => {
+    const { invoke } = await import('@tauri-apps/api/core');
+    const handlers = await captureHandlers();
+    vi.mocked(invoke).mockImplementation(async () => {
+      handlers['shell-output']({ payload: { id: 2, stream: 'stderr', line: 'fast' } });
+      handlers['shell-exit']({ payload: { id: 2, code: 1 } });
+      return 2;
+    });
+
+    const { result } = renderHook(() => useShellCommand());
+    await act(() => result.current.run('false'));
+
+    expect(result.current.lines).toEqual([{ stream: 'stderr', line: 'fast' }]);
+    expect(result.current.running).toBe(false);
+    expect(result.current.exitCode).toBe(1);
+  });
+
+  it('stops running when the command fails to start', async () => {
+    const { invoke } = await import('@tauri-apps/api/core');
+    vi.mocked(invoke).mockRejectedValue('Shell command is empty');
+
+    const { result } = renderHook(() => useShellCommand());
+    await act(async () => {
+      await expect(result.current.run('')).rejects.toBe('Shell command is empty');
+    });
+
+    expect(result.current.running).toBe(false);
+  });
+
+  it('kills the latest run', async () => {
+    const { invoke } = await import('@tauri-apps/api/core');
+    vi.mocked(invoke).mockResolvedValue(5);
+    await captureHandlers();
+
+    const { result } = renderHook(() => useShellCommand());
+    await act(() => result.current.run('sleep 100'));
+    await act(() => result.current.kill());
+
+    expect(invoke).toHaveBeenCalledWith('kill_shell_command', { id: 5 });
+  });
+});
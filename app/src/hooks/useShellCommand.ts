@@ -0,0 +1,179 @@
+/**
+ * Custom hook for running shell commands from the launcher
+ *
+ * The backend runs the command and streams its output as `shell-output`
+ * events, then emits `shell-exit`. This hook collects the output of the
+ * latest run.
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+import { useCallback, useEffect, useRef, useState } from 'react';
+
+/** Launcher query prefix that runs the rest as a shell command */
+export const SHELL_PREFIX = '>';
+
+/**
+ * A line of command output
+ */
+export interface ShellLine {
+  stream: 'stdout' | 'stderr';
+  line: string;
+}
+
+/** Payload of `shell-output` events */
+interface ShellOutputEvent extends ShellLine {
+  id: number;
+}
+
+/** Payload of `shell-exit` events */
+interface ShellExitEvent {
+  id: number;
+  code: number | null;
+}
+
+/** Output or exit event of a run */
+type ShellEvent = ({ kind: 'output' } & ShellOutputEvent) | ({ kind: 'exit' } & ShellExitEvent);
+
+/**
+ * State returned by useShellCommand hook
+ */
+interface UseShellCommandResult {
+  /** Output lines of the latest command */
+  lines: ShellLine[];
+  /** Whether the latest command is still running */
+  running: boolean;
+  /** Exit code of the latest command (null while running or if killed) */
+  exitCode: number | null;
+  /** Runs a command, replacing the previous output */
+  run: (cmd: string) => Promise<void>;
+  /** Kills the latest command if it's still running */
+  kill: () => Promise<void>;
+  /** Clears the output, killing the latest command if it's still running */
+  clear: () => void;
+}
+
+/**
+ * Extracts the shell command from a launcher query
+ *
+ * @param query - Launcher search query
+ * @returns The command, or null if the query isn't a shell command
+ */
+export function parseShellCommand(query: string): string | null {
+  const trimmed = query.trimStart();
+  if (!trimmed.startsWith(SHELL_PREFIX)) return null;
+  return trimmed.slice(SHELL_PREFIX.length).trim();
+}
+
+/**
+ * Hook for running shell commands and collecting their output
+ *
+ * @returns Output state and controls
+ *
+ * @example
+ * ```tsx
+ * const shell = useShellCommand();
+ * await shell.run('ls -la');
+ * return shell.lines.map(l => <div>{l.line}</div>);
+ * ```
+ */
+export function useShellCommand(): UseShellCommandResult {
+  const [lines, setLines] = useState<ShellLine[]>([]);
+  const [running, setRunning] = useState(false);
+  const [exitCode, setExitCode] = useState<number | null>(null);
+  // Id of the latest run; events of older runs are ignored
+  const runId = useRef<number | null>(null);
+  // Events received while the latest run's id isn't known yet (a fast
+  // command can finish before `run_shell_command` returns)
+  const pending = useRef<ShellEvent[] | null>(null);
+
+  const apply = useCallback((event: ShellEvent) => {
+    if (event.kind === 'output') {
+      setLines(prev => [...prev, { stream: event.stream, line: event.line }]);
+    } else {
+      setRunning(false);
+      setExitCode(event.code);
+    }
+  }, []);
+
+  useEffect(() => {
+    const unlisteners: (() => void)[] = [];
+    let disposed = false;
+    const keep = (fn: () => void) => {
+      if (disposed) fn();
+      else unlisteners.push(fn);
+    };
+    const receive = (event: ShellEvent) => {
+      if (event.id === runId.current) apply(event);
+      else pending.current?.push(event);
+    };
+
+    listen<ShellOutputEvent>('shell-output', event => {
+      receive({ kind: 'output', ...event.payload });
+    })
+      .then(keep)
+      .catch(err => {
+        console.error('Failed to setup shell-output listener:', err);
+      });
+
+    listen<ShellExitEvent>('shell-exit', event => {
+      receive({ kind: 'exit', ...event.payload });
+    })
+      .then(keep)
+      .catch(err => {
+        console.error('Failed to setup shell-exit listener:', err);
+      });
+
+    return () => {
+      disposed = true;
+      for (const unlisten of unlisteners) unlisten();
+    };
+  }, [apply]);
+
+  const run = useCallback(
+    async (cmd: string) => {
+      setLines([]);
+      setExitCode(null);
+      setRunning(true);
+      runId.current = null;
+      pending.current = [];
+      try {
+        const id = await invoke<number>('run_shell_command', { cmd });
+        runId.current = id;
+        for (const event of pending.current ?? []) {
+          if (event.id === id) apply(event);
+        }
+      } catch (err) {
+        setRunning(false);
+        throw err;
+      } finally {
+        pending.current = null;
+      }
+    },
+    [apply]
+  );
+
+  const kill = useCallback(async () => {
+    if (runId.current === null) return;
+    try {
+      await invoke('kill_shell_command', { id: runId.current });
+    } catch (err) {
+      console.error('Failed to kill shell command:', err);
+    }
+  }, []);
+
+  const clear = useCallback(() => {
+    if (runId.current !== null) {
+      invoke('kill_shell_command', { id: runId.current }).catch(err => {
+        console.error('Failed to kill shell command:', err);
+      });
+    }
+    runId.current = null;
+    pending.current = null;
+    setLines([]);
+    setRunning(false);
+    setExitCode(null);
+  }, []);
+
+  return { lines, running, exitCode, run, kill, clear };
+}
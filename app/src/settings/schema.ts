@@ -238,6 +238,18 @@ export const settingsSchema: SettingsSchema = [
         type: 'text',
         valueKey: 'version',
       },
+      {
+        key: 'updateChannel',
+        labelKey: 'about.updateChannel.label',
+        descriptionKey: 'about.updateChannel.description',
+        type: 'select',
+        configPath: 'updateChannel',
+        options: [
+          { value: 'stable', labelKey: 'about.updateChannel.options.stable' },
+          { value: 'beta', labelKey: 'about.updateChannel.options.beta' },
+          { value: 'nightly', labelKey: 'about.updateChannel.options.nightly' },
+        ],
+      },
       {
         key: 'checkForUpdates',
         labelKey: 'about.checkForUpdates.label',
@@ -2,9 +2,14 @@
  * Update dialog entry point
  *
  * Separate entry point for displaying update check results.
- * Parses URL parameters (title, message, kind) and shows a themed dialog.
+ * Parses URL parameters (title, message, kind, notes, installable, version)
+ * and shows a themed dialog. Installable updates get an Install button that
+ * shows `update-progress` events while the update runs. Offered versions
+ * can be skipped or postponed.
  */
 
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { getCurrentWindow } from '@tauri-apps/api/window';
 import type React from 'react';
 import { useEffect, useState } from 'react';
@@ -18,6 +23,60 @@ interface DialogParams {
   title: string;
   message: string;
   kind: DialogKind;
+  /** Release notes (markdown), empty for none */
+  notes: string;
+  /** Offer to install the update */
+  installable: boolean;
+  /** Release the user can skip or postpone, empty for none */
+  version: string;
+}
+
+/** Days to wait before offering updates again after "Later" */
+const REMIND_LATER_DAYS = 7;
+
+/** Update phase, see `UpdatePhase` in src-tauri/src/updater.rs */
+type UpdatePhase =
+  | 'checking'
+  | 'downloading-daemon'
+  | 'downloading-app'
+  | 'downloading-extension'
+  | 'installing';
+
+/** Payload of `update-progress` events */
+interface UpdateProgress {
+  phase: UpdatePhase;
+  downloaded: number;
+  total: number | null;
+}
+
+/** Install state shown in place of the buttons */
+type InstallState =
+  | { status: 'idle' }
+  | { status: 'running'; progress: UpdateProgress | null }
+  | { status: 'done'; message: string }
+  | { status: 'failed'; message: string };
+
+const PHASE_LABELS: Record<UpdatePhase, string> = {
+  checking: 'Checking...',
+  'downloading-daemon': 'Downloading daemon',
+  'downloading-app': 'Downloading uti',
+  'downloading-extension': 'Downloading GNOME extension',
+  installing: 'Installing...',
+};
+
+/**
+ * Describe update progress, e.g., "Downloading uti 1.2/27.0 MB"
+ */
+function describeProgress(progress: UpdateProgress): string {
+  const label = PHASE_LABELS[progress.phase];
+  const mb = (bytes: number) => (bytes / (1024 * 1024)).toFixed(1);
+  if (progress.total) {
+    return `${label} ${mb(progress.downloaded)}/${mb(progress.total)} MB`;
+  }
+  if (progress.downloaded > 0) {
+    return `${label} ${mb(progress.downloaded)} MB`;
+  }
+  return label;
 }
 
 /**
@@ -29,6 +88,9 @@ function parseDialogParams(): DialogParams {
     title: params.get('title') || 'Dialog',
     message: params.get('message') || '',
     kind: (params.get('kind') as DialogKind) || 'info',
+    notes: params.get('notes') || '',
+    installable: params.get('installable') === 'true',
+    version: params.get('version') || '',
   };
 }
 
@@ -72,6 +134,7 @@ const dialogStyles = {
   container: { borderRadius: '0.75rem' } as React.CSSProperties, // 12px
   heading: { fontSize: '1rem' } as React.CSSProperties, // 16px - matches size-normal heading
   message: { fontSize: '0.875rem' } as React.CSSProperties, // 14px - matches size-normal base
+  notes: { fontSize: '0.8125rem', borderRadius: '0.375rem' } as React.CSSProperties, // 13px
   button: {
     fontSize: '0.875rem', // 14px - matches size-normal base
     padding: '0.5rem 0.75rem', // 8px 12px - matches size-normal padding
@@ -87,6 +150,7 @@ const dialogStyles = {
  */
 function DialogPage() {
   const [params] = useState<DialogParams>(parseDialogParams);
+  const [install, setInstall] = useState<InstallState>({ status: 'idle' });
 
   // Handle Escape key to close window
   useEffect(() => {
@@ -104,6 +168,44 @@ function DialogPage() {
     getCurrentWindow().close();
   };
 
+  const handleInstall = async () => {
+    setInstall({ status: 'running', progress: null });
+    const unlisten = await listen<UpdateProgress>('update-progress', (event) => {
+      setInstall({ status: 'running', progress: event.payload });
+    });
+    try {
+      const installed = await invoke<boolean>('install_update');
+      setInstall({
+        status: 'done',
+        message: installed
+          ? 'Update installed. Log out and log back in to apply changes.'
+          : 'You are running the latest version.',
+      });
+    } catch (e) {
+      setInstall({ status: 'failed', message: String(e) });
+    } finally {
+      unlisten();
+    }
+  };
+
+  // Record the decision and close, so the release isn't offered again
+  const handleDecline = async (command: 'skip_update' | 'remind_update_later') => {
+    try {
+      const args =
+        command === 'skip_update' ? { version: params.version } : { days: REMIND_LATER_DAYS };
+      await invoke(command, args);
+      getCurrentWindow().close();
+    } catch (e) {
+      setInstall({ status: 'failed', message: String(e) });
+    }
+  };
+
+  const progress = install.status === 'running' ? install.progress : null;
+  const percent =
+    progress?.total && progress.total > 0
+      ? Math.min(100, Math.round((progress.downloaded / progress.total) * 100))
+      : null;
+
   return (
     <div
       className="flex h-screen flex-col overflow-hidden border border-app-header-border bg-app-bg"
@@ -145,19 +247,96 @@ function DialogPage() {
         </h1>
 
         {/* Message */}
-        <p className="mb-5 flex-1 whitespace-pre-wrap text-app-text" style={dialogStyles.message}>
+        <p
+          className={`whitespace-pre-wrap text-app-text ${params.notes ? 'mb-3' : 'mb-5 flex-1'}`}
+          style={dialogStyles.message}
+        >
           {params.message}
         </p>
 
-        {/* OK button */}
-        <button
-          type="button"
-          onClick={handleClose}
-          className="w-full bg-app-item text-app-text hover:bg-app-item-hover"
-          style={dialogStyles.button}
-        >
-          OK
-        </button>
+        {/* Release notes, shown as written (markdown source) */}
+        {params.notes && (
+          <pre
+            className="mb-5 min-h-0 flex-1 overflow-y-auto whitespace-pre-wrap bg-app-item p-3 font-sans text-app-text-muted"
+            style={dialogStyles.notes}
+          >
+            {params.notes}
+          </pre>
+        )}
+
+        {/* Install progress */}
+        {install.status === 'running' && (
+          <div className="mb-3" role="status">
+            <p className="mb-2 text-app-text-muted" style={dialogStyles.message}>
+              {progress ? describeProgress(progress) : PHASE_LABELS.checking}
+            </p>
+            <div className="h-1.5 w-full overflow-hidden rounded bg-app-item">
+              <div
+                className="h-full bg-app-accent-info transition-all"
+                style={{ width: `${percent ?? 0}%` }}
+              />
+            </div>
+          </div>
+        )}
+        {(install.status === 'done' || install.status === 'failed') && (
+          <p
+            className={`mb-3 whitespace-pre-wrap ${
+              install.status === 'failed' ? 'text-app-accent-error' : 'text-app-accent-info'
+            }`}
+            style={dialogStyles.message}
+          >
+            {install.message}
+          </p>
+        )}
+
+        <div className="flex gap-2">
+          {/* Install button */}
+          {params.installable && install.status === 'idle' && (
+            <button
+              type="button"
+              onClick={handleInstall}
+              className="w-full bg-app-item-selected text-app-text hover:bg-app-item-hover"
+              style={dialogStyles.button}
+            >
+              Install
+            </button>
+          )}
+
+          {/* Skip / postpone buttons */}
+          {params.version && install.status === 'idle' && (
+            <>
+              <button
+                type="button"
+                onClick={() => handleDecline('skip_update')}
+                title={`Don't offer version ${params.version} again`}
+                className="w-full bg-app-item text-app-text hover:bg-app-item-hover"
+                style={dialogStyles.button}
+              >
+                Skip
+              </button>
+              <button
+                type="button"
+                onClick={() => handleDecline('remind_update_later')}
+                title={`Remind me in ${REMIND_LATER_DAYS} days`}
+                className="w-full bg-app-item text-app-text hover:bg-app-item-hover"
+                style={dialogStyles.button}
+              >
+                Later
+              </button>
+            </>
+          )}
+
+          {/* OK button */}
+          <button
+            type="button"
+            onClick={handleClose}
+            disabled={install.status === 'running'}
+            className="w-full bg-app-item text-app-text hover:bg-app-item-hover disabled:opacity-50"
+            style={dialogStyles.button}
+          >
+            OK
+          </button>
+        </div>
       </div>
     </div>
   );
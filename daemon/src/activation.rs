@@ -0,0 +1,110 @@
+//! Starting the app through D-Bus activation
+//!
+//! Signals don't start services, so a gesture does nothing while uti isn't
+//! running. When nobody owns the app bus name, the daemon asks the bus to
+//! start uti (its activation file runs `uti --minimized`) and then calls
+//! the App method matching the signal, so the gesture that started it
+//! still opens the window.
+
+use log::{debug, info, warn};
+use std::time::Duration;
+use uti_dbus::app::AppProxy;
+use zbus::fdo::DBusProxy;
+use zbus::names::{BusName, WellKnownName};
+use zbus::Connection;
+
+/// Time between attempts to reach a starting app
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// How long to wait for a starting app to serve its interface
+const START_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// App method standing in for a signal the app missed
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AppCall {
+    /// `ShowWindow`, for `Triggered`: the app starts hidden, so toggling
+    /// means showing
+    ShowWindow,
+    /// `OpenTab`, for `Triggered<Tab>`
+    OpenTab(&'static str),
+}
+
+impl AppCall {
+    /// Gets the call for a signal name, or None if it's unknown
+    fn for_signal(signal: &str) -> Option<Self> {
+        match signal {
+            "Triggered" => Some(Self::ShowWindow),
+            "TriggeredPrompt" => Some(Self::OpenTab("prompt")),
+            "TriggeredClipboard" => Some(Self::OpenTab("clipboard")),
+            "TriggeredSnippets" => Some(Self::OpenTab("snippets")),
+            "TriggeredLauncher" => Some(Self::OpenTab("launcher")),
+            _ => None,
+        }
+    }
+
+    async fn send(self, app: &AppProxy<'_>) -> zbus::Result<()> {
+        match self {
+            Self::ShowWindow => app.show_window().await,
+            Self::OpenTab(tab) => app.open_tab(tab).await,
+        }
+    }
+}
+
+/// Starts the app if it isn't running and delivers a signal to it
+///
+/// Does nothing when the app owns its bus name, since it received the
+/// signal. Failures are logged: without an activation file (e.g., a
+/// build not installed from the package) the bus can't start the app.
+pub async fn deliver(conn: Connection, signal: String) {
+    if let Err(e) = try_deliver(&conn, &signal).await {
+        warn!("Failed to start uti for {}: {}", signal, e);
+    }
+}
+
+async fn try_deliver(conn: &Connection, signal: &str) -> zbus::Result<()> {
+    let Some(call) = AppCall::for_signal(signal) else {
+        return Ok(());
+    };
+    let dbus = DBusProxy::new(conn).await?;
+    let name = BusName::try_from(uti_dbus::APP_BUS_NAME)?;
+    if dbus.name_has_owner(name).await? {
+        return Ok(());
+    }
+
+    info!("uti is not running, starting it through D-Bus activation");
+    let name = WellKnownName::try_from(uti_dbus::APP_BUS_NAME)?;
+    dbus.start_service_by_name(name, 0).await?;
+
+    // The app owns its name before it serves the interface
+    let app = AppProxy::new(conn).await?;
+    let deadline = tokio::time::Instant::now() + START_TIMEOUT;
+    loop {
+        match call.send(&app).await {
+            Ok(()) => return Ok(()),
+            Err(e) if tokio::time::Instant::now() < deadline => {
+                debug!("uti not ready yet: {}", e);
+                tokio::time::sleep(RETRY_DELAY).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_for_every_signal() {
+        assert_eq!(AppCall::for_signal("Triggered"), Some(AppCall::ShowWindow));
+        assert_eq!(
+            AppCall::for_signal("TriggeredLauncher"),
+            Some(AppCall::OpenTab("launcher"))
+        );
+        assert_eq!(AppCall::for_signal("TriggeredSettings"), None);
+
+        for signal in crate::config::SIGNALS {
+            assert!(AppCall::for_signal(signal).is_some(), "{}", signal);
+        }
+    }
+}
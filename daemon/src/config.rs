@@ -0,0 +1,419 @@
+//! Daemon configuration
+//!
+//! Read from `~/.config/uti/daemon.toml`. A missing file or field uses the
+//! defaults: double Ctrl within 300ms emits `Triggered`.
+//!
+//! ```toml
+//! interval_ms = 300
+//! hold_ms = 500
+//! key_delay_ms = 10
+//!
+//! [[bindings]]
+//! key = "ctrl"
+//! signal = "Triggered"
+//!
+//! [[bindings]]
+//! key = "ctrl"
+//! gesture = "triple-tap"
+//! signal = "TriggeredLauncher"
+//!
+//! [[bindings]]
+//! key = "shift"
+//! gesture = "hold"
+//! signal = "TriggeredSnippets"
+//!
+//! [devices]
+//! exclude = ["Foot Pedal", "/dev/input/by-id/usb-Macro_Pad-event-kbd"]
+//!
+//! [keyboard]
+//! layout = "de"
+//! variant = "nodeadkeys"
+//!
+//! [macros]
+//! paste-submit = [{ keys = "ctrl+shift+v" }, { keys = "enter", delay_ms = 100 }]
+//! ```
+
+use crate::gesture::Gesture;
+use crate::macros::Step;
+use evdev::Key;
+use log::{info, warn};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Signals a binding can emit on the `io.github.noppomario.uti.DoubleTap`
+/// interface
+pub use uti_dbus::double_tap::SIGNALS;
+
+/// Longest accepted delay between virtual key events, in milliseconds
+pub const MAX_KEY_DELAY_MS: u64 = 1000;
+
+/// Modifier key that can be double-tapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Modifier {
+    Ctrl,
+    Shift,
+    Alt,
+    Super,
+}
+
+impl Modifier {
+    /// Gets the modifier for a key, either left or right
+    pub fn from_key(key: Key) -> Option<Self> {
+        match key {
+            Key::KEY_LEFTCTRL | Key::KEY_RIGHTCTRL => Some(Self::Ctrl),
+            Key::KEY_LEFTSHIFT | Key::KEY_RIGHTSHIFT => Some(Self::Shift),
+            Key::KEY_LEFTALT | Key::KEY_RIGHTALT => Some(Self::Alt),
+            Key::KEY_LEFTMETA | Key::KEY_RIGHTMETA => Some(Self::Super),
+            _ => None,
+        }
+    }
+}
+
+/// A gesture on a modifier and the signal it emits
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Binding {
+    pub key: Modifier,
+    #[serde(default = "default_gesture")]
+    pub gesture: Gesture,
+    /// One of [`SIGNALS`]
+    #[serde(default = "default_signal")]
+    pub signal: String,
+}
+
+fn default_gesture() -> Gesture {
+    Gesture::DoubleTap
+}
+
+fn default_signal() -> String {
+    SIGNALS[0].to_string()
+}
+
+/// Which keyboards to monitor
+///
+/// Entries starting with `/` are device paths, compared after resolving
+/// symlinks so `/dev/input/by-id/...` works. Other entries match device
+/// names, case-insensitively, as substrings.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct DeviceFilter {
+    /// If not empty, only matching devices are monitored
+    pub include: Vec<String>,
+    /// Matching devices are never monitored
+    pub exclude: Vec<String>,
+}
+
+impl DeviceFilter {
+    /// Whether a device should be monitored
+    pub fn allows(&self, name: &str, path: &Path) -> bool {
+        let matches = |entry: &String| {
+            if entry.starts_with('/') {
+                let entry = Path::new(entry);
+                entry == path || entry.canonicalize().is_ok_and(|entry| entry == path)
+            } else {
+                name.to_lowercase().contains(&entry.to_lowercase())
+            }
+        };
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// XKB layout used to type text
+///
+/// An empty layout uses the session's (see `layout::session_layout`), else
+/// `XKB_DEFAULT_LAYOUT` and `XKB_DEFAULT_VARIANT` or the system default.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct KeyboardLayout {
+    pub layout: String,
+    pub variant: String,
+}
+
+/// Daemon configuration file structure
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Maximum time between two taps, in milliseconds
+    pub interval_ms: u64,
+    /// How long the second press of a tap-then-hold lasts, in milliseconds
+    pub hold_ms: u64,
+    /// Delay between virtual key events when pasting, typing and playing
+    /// macros, in milliseconds; raise it for targets that drop events
+    /// (e.g., remote desktops and VMs)
+    pub key_delay_ms: u64,
+    /// Gestures to detect
+    pub bindings: Vec<Binding>,
+    /// Keyboards to monitor
+    pub devices: DeviceFilter,
+    /// Layout for typing text
+    pub keyboard: KeyboardLayout,
+    /// Key sequences `PlayMacro` plays, by name (see [`crate::macros`])
+    pub macros: HashMap<String, Vec<Step>>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            interval_ms: crate::DOUBLE_TAP_INTERVAL.as_millis() as u64,
+            hold_ms: 500,
+            key_delay_ms: crate::uinput::KEY_EVENT_DELAY.as_millis() as u64,
+            bindings: vec![Binding {
+                key: Modifier::Ctrl,
+                gesture: default_gesture(),
+                signal: default_signal(),
+            }],
+            devices: DeviceFilter::default(),
+            keyboard: KeyboardLayout::default(),
+            macros: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Gets the path to the config file
+    ///
+    /// Returns `~/.config/uti/daemon.toml`
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("uti").join("daemon.toml"))
+    }
+
+    /// Loads the config file, falling back to defaults if it's missing or
+    /// invalid
+    pub fn load() -> Self {
+        Self::read().unwrap_or_else(|e| {
+            warn!("{}, using defaults", e);
+            Self::default()
+        })
+    }
+
+    /// Reads the config file, using defaults if it's missing
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the file can't be read or is invalid
+    pub fn read() -> Result<Self, String> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        let config = Self::parse(&content)
+            .map_err(|e| format!("Invalid config {}: {}", path.display(), e))?;
+        info!("Loaded config from {}", path.display());
+        Ok(config)
+    }
+
+    /// Parses and validates config file content
+    fn parse(content: &str) -> Result<Self, toml::de::Error> {
+        let mut config: Self = toml::from_str(content)?;
+        config.validate();
+        Ok(config)
+    }
+
+    /// Drops bindings with an unknown signal, later bindings for the same
+    /// gesture and empty macros, and caps the key and macro step delays,
+    /// with warnings
+    fn validate(&mut self) {
+        if self.key_delay_ms > MAX_KEY_DELAY_MS {
+            warn!(
+                "key_delay_ms {} is too long, using {}",
+                self.key_delay_ms, MAX_KEY_DELAY_MS
+            );
+            self.key_delay_ms = MAX_KEY_DELAY_MS;
+        }
+        self.macros.retain(|name, steps| {
+            if steps.is_empty() {
+                warn!("Ignoring macro {:?}: it has no steps", name);
+            }
+            for step in steps.iter_mut() {
+                if step.delay_ms > MAX_KEY_DELAY_MS {
+                    warn!(
+                        "Macro {:?}: delay_ms {} is too long, using {}",
+                        name, step.delay_ms, MAX_KEY_DELAY_MS
+                    );
+                    step.delay_ms = MAX_KEY_DELAY_MS;
+                }
+            }
+            !steps.is_empty()
+        });
+
+        let mut gestures = HashSet::new();
+        self.bindings.retain(|binding| {
+            if !SIGNALS.contains(&binding.signal.as_str()) {
+                warn!(
+                    "Ignoring {:?} binding for {:?}: unknown signal {:?}",
+                    binding.gesture, binding.key, binding.signal
+                );
+                return false;
+            }
+            if !gestures.insert((binding.key, binding.gesture)) {
+                warn!(
+                    "Ignoring {:?} binding for {:?}: gesture is already bound",
+                    binding.gesture, binding.key
+                );
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Maximum time between two taps
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+
+    /// How long the second press of a tap-then-hold lasts
+    pub fn hold(&self) -> Duration {
+        Duration::from_millis(self.hold_ms)
+    }
+
+    /// Delay between virtual key events
+    pub fn key_delay(&self) -> Duration {
+        Duration::from_millis(self.key_delay_ms)
+    }
+
+    /// Gets the binding for a gesture on a modifier, if it's bound
+    pub fn binding(&self, modifier: Modifier, gesture: Gesture) -> Option<&Binding> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.key == modifier && binding.gesture == gesture)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.interval(), Duration::from_millis(300));
+        assert_eq!(config.hold(), Duration::from_millis(500));
+        assert_eq!(config.key_delay(), Duration::from_millis(10));
+        assert_eq!(
+            Config::parse("key_delay_ms = 5000").unwrap().key_delay_ms,
+            MAX_KEY_DELAY_MS
+        );
+        let ctrl = config.binding(Modifier::Ctrl, Gesture::DoubleTap).unwrap();
+        assert_eq!(ctrl.signal, "Triggered");
+        assert!(config
+            .binding(Modifier::Shift, Gesture::DoubleTap)
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_bindings() {
+        let config = Config::parse(
+            r#"
+            interval_ms = 250
+
+            [[bindings]]
+            key = "ctrl"
+
+            [[bindings]]
+            key = "shift"
+            signal = "TriggeredSnippets"
+
+            [[bindings]]
+            key = "alt"
+            signal = "Unknown"
+
+            [[bindings]]
+            key = "shift"
+            signal = "TriggeredLauncher"
+
+            [[bindings]]
+            key = "shift"
+            gesture = "hold"
+            signal = "TriggeredLauncher"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.interval_ms, 250);
+        assert_eq!(config.bindings.len(), 3);
+        let double = |key| config.binding(key, Gesture::DoubleTap);
+        assert_eq!(double(Modifier::Ctrl).unwrap().signal, "Triggered");
+        assert_eq!(double(Modifier::Shift).unwrap().signal, "TriggeredSnippets");
+        assert!(double(Modifier::Alt).is_none());
+        assert_eq!(
+            config
+                .binding(Modifier::Shift, Gesture::Hold)
+                .unwrap()
+                .signal,
+            "TriggeredLauncher"
+        );
+
+        assert!(Config::parse("[[bindings]]\nkey = \"hyper\"").is_err());
+        assert!(Config::parse("[[bindings]]\nkey = \"ctrl\"\ngesture = \"swipe\"").is_err());
+    }
+
+    #[test]
+    fn test_device_filter() {
+        let path = Path::new("/dev/input/event3");
+        let all = DeviceFilter::default();
+        assert!(all.allows("AT Translated Set 2 keyboard", path));
+
+        let filter = Config::parse(
+            r#"
+            [devices]
+            exclude = ["foot pedal", "/dev/input/event7"]
+            "#,
+        )
+        .unwrap()
+        .devices;
+        assert!(filter.allows("AT Translated Set 2 keyboard", path));
+        assert!(!filter.allows("PCsensor FootSwitch Foot Pedal", path));
+        assert!(!filter.allows("Keyboard", Path::new("/dev/input/event7")));
+
+        let only = DeviceFilter {
+            include: vec!["Keychron".to_string()],
+            exclude: vec!["Mouse".to_string()],
+        };
+        assert!(only.allows("Keychron K2", path));
+        assert!(!only.allows("Keychron K2 Mouse", path));
+        assert!(!only.allows("AT Translated Set 2 keyboard", path));
+    }
+
+    #[test]
+    fn test_parse_macros() {
+        let config = Config::parse(
+            r#"
+            [macros]
+            submit = [{ keys = "ctrl+v" }, { keys = "enter", delay_ms = 50 }]
+            empty = []
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.macros.len(), 1);
+        assert_eq!(config.macros["submit"].len(), 2);
+        assert_eq!(
+            config.macros["submit"][1].delay(),
+            Duration::from_millis(50)
+        );
+
+        assert!(Config::parse("[macros]\nx = [{ keys = \"ctrl+hyper\" }]").is_err());
+
+        let config =
+            Config::parse("[macros]\nslow = [{ keys = \"enter\", delay_ms = 4294967295 }]")
+                .unwrap();
+        assert_eq!(
+            config.macros["slow"][0].delay(),
+            Duration::from_millis(MAX_KEY_DELAY_MS)
+        );
+    }
+
+    #[test]
+    fn test_modifier_from_key() {
+        assert_eq!(Modifier::from_key(Key::KEY_RIGHTCTRL), Some(Modifier::Ctrl));
+        assert_eq!(Modifier::from_key(Key::KEY_LEFTMETA), Some(Modifier::Super));
+        assert_eq!(Modifier::from_key(Key::KEY_A), None);
+    }
+}
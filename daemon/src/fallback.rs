@@ -0,0 +1,146 @@
+//! Unix socket fallback, used when there is no session bus
+//!
+//! Serves [`uti_dbus::socket::socket_path`] with the messages of
+//! [`uti_dbus::socket`]: gesture signals go to every connected client, and
+//! `TypeText` and `TypeString` from clients go to the input task like
+//! their D-Bus counterparts.
+
+use log::{debug, error, info, warn};
+use std::os::unix::fs::PermissionsExt;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{broadcast, mpsc};
+use uti_dbus::socket::Message;
+
+/// Binds the socket, replacing one left by a crashed daemon
+///
+/// # Errors
+///
+/// Fails if `XDG_RUNTIME_DIR` is not set, another daemon serves the socket,
+/// or it can't be created
+pub async fn bind() -> std::io::Result<UnixListener> {
+    let path = uti_dbus::socket::socket_path().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "XDG_RUNTIME_DIR not set")
+    })?;
+
+    if path.exists() {
+        if UnixStream::connect(&path).await.is_ok() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AddrInUse,
+                format!("{} is served by another daemon", path.display()),
+            ));
+        }
+        std::fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    // Only the user may send input requests
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+    info!("Listening on {}", path.display());
+    Ok(listener)
+}
+
+/// Accepts clients until the daemon exits
+///
+/// # Arguments
+///
+/// * `listener` - Socket from [`bind`]
+/// * `triggers` - Gesture signals to send to every client
+/// * `input` - Where `TypeText` and `TypeString` from clients go
+pub async fn serve(
+    listener: UnixListener,
+    triggers: broadcast::Sender<Message>,
+    input: mpsc::Sender<Message>,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                info!("Socket client connected");
+                tokio::spawn(handle_client(stream, triggers.subscribe(), input.clone()));
+            }
+            Err(e) => error!("Failed to accept socket client: {}", e),
+        }
+    }
+}
+
+/// Relays messages between one client and the daemon until it disconnects
+async fn handle_client(
+    stream: UnixStream,
+    mut triggers: broadcast::Receiver<Message>,
+    input: mpsc::Sender<Message>,
+) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    loop {
+        tokio::select! {
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => match Message::from_line(&line) {
+                    Ok(Message::Trigger { name, .. }) => {
+                        warn!("Ignoring {} from a socket client", name);
+                    }
+                    Ok(message) => {
+                        if input.send(message).await.is_err() {
+                            debug!("No virtual keyboard, input request dropped");
+                        }
+                    }
+                    Err(e) => warn!("Invalid socket message: {}", e),
+                },
+                Ok(None) => break,
+                Err(e) => {
+                    warn!("Failed to read from socket client: {}", e);
+                    break;
+                }
+            },
+            trigger = triggers.recv() => match trigger {
+                Ok(message) => {
+                    if let Err(e) = writer.write_all(message.to_line().as_bytes()).await {
+                        warn!("Failed to write to socket client: {}", e);
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Socket client too slow, {} signal(s) dropped", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            },
+        }
+    }
+    info!("Socket client disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uti_dbus::double_tap::TriggerInfo;
+
+    #[tokio::test]
+    async fn test_handle_client_relays_messages() {
+        let (daemon_end, client_end) = UnixStream::pair().unwrap();
+        let (triggers, _) = broadcast::channel(4);
+        let (input, mut requests) = mpsc::channel(4);
+        tokio::spawn(handle_client(daemon_end, triggers.subscribe(), input));
+
+        let (reader, mut writer) = client_end.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        // Input requests from the client reach the input task
+        writer
+            .write_all(b"{\"signal\":\"TypeString\",\"text\":\"hi\"}\n")
+            .await
+            .unwrap();
+        assert_eq!(
+            requests.recv().await,
+            Some(Message::TypeString {
+                text: "hi".to_string()
+            })
+        );
+
+        // Gesture signals reach the client
+        let trigger = Message::trigger("TriggeredLauncher", &TriggerInfo::now("kbd", "hold"));
+        triggers.send(trigger.clone()).unwrap();
+        let line = lines.next_line().await.unwrap().unwrap();
+        assert_eq!(Message::from_line(&line).unwrap(), trigger);
+    }
+}
@@ -0,0 +1,455 @@
+//! Modifier gesture recognition
+//!
+//! [`Recognizer`] turns modifier presses and releases from all keyboards
+//! into gestures: double tap, triple tap, and tap-then-hold. When a key has
+//! both a double and a triple tap bound, the double tap is only reported
+//! once the interval passes without a third tap, so the monitors wait for
+//! [`Recognizer::deadline`] and call [`Recognizer::timeout`].
+//!
+//! Only bare taps count: a modifier used in a chord (Ctrl+C, Ctrl+Shift)
+//! doesn't, and any other key ends the sequence.
+
+use crate::config::{Config, Modifier};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// A gesture on a modifier key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Gesture {
+    /// Two taps, each released within the interval of the previous one
+    DoubleTap,
+    /// Three taps
+    TripleTap,
+    /// A tap, then pressing again and holding for the hold time
+    Hold,
+}
+
+impl Gesture {
+    /// Name used in `daemon.toml` and trigger signals
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::DoubleTap => "double-tap",
+            Self::TripleTap => "triple-tap",
+            Self::Hold => "hold",
+        }
+    }
+
+    /// Number of taps for tap gestures
+    fn taps(self) -> Option<u32> {
+        match self {
+            Self::DoubleTap => Some(2),
+            Self::TripleTap => Some(3),
+            Self::Hold => None,
+        }
+    }
+
+    /// Tap gesture with the given number of taps
+    fn from_taps(taps: u32) -> Option<Self> {
+        match taps {
+            2 => Some(Self::DoubleTap),
+            3 => Some(Self::TripleTap),
+            _ => None,
+        }
+    }
+}
+
+/// Recognizes bound gestures from modifier key events
+#[derive(Debug)]
+pub struct Recognizer {
+    interval: Duration,
+    hold: Duration,
+    /// Bound gestures per modifier
+    bound: HashMap<Modifier, Vec<Gesture>>,
+    /// Modifier of the current sequence
+    modifier: Option<Modifier>,
+    /// Completed taps in the current sequence
+    taps: u32,
+    last_release: Option<Instant>,
+    /// When the modifier was pressed, while it's down
+    pressed_at: Option<Instant>,
+    /// Whether the current press was already reported as a hold
+    held: bool,
+    /// Whether another key was pressed during the current press
+    chord: bool,
+}
+
+impl Recognizer {
+    /// Creates a recognizer for the gestures bound in a config
+    pub fn new(config: &Config) -> Self {
+        let mut bound: HashMap<Modifier, Vec<Gesture>> = HashMap::new();
+        for binding in &config.bindings {
+            bound.entry(binding.key).or_default().push(binding.gesture);
+        }
+        Self {
+            interval: config.interval(),
+            hold: config.hold(),
+            bound,
+            modifier: None,
+            taps: 0,
+            last_release: None,
+            pressed_at: None,
+            held: false,
+            chord: false,
+        }
+    }
+
+    /// Maximum time between two taps
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Changes the maximum time between two taps
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    fn is_bound(&self, modifier: Modifier, gesture: Gesture) -> bool {
+        self.bound
+            .get(&modifier)
+            .is_some_and(|gestures| gestures.contains(&gesture))
+    }
+
+    /// Most taps any tap gesture bound to the modifier needs
+    fn max_taps(&self, modifier: Modifier) -> u32 {
+        self.bound
+            .get(&modifier)
+            .into_iter()
+            .flatten()
+            .filter_map(|gesture| gesture.taps())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether the last release is recent enough to continue the sequence
+    fn continues(&self, modifier: Modifier, now: Instant) -> bool {
+        self.modifier == Some(modifier)
+            && self.taps > 0
+            && self
+                .last_release
+                .is_some_and(|last| now.duration_since(last) < self.interval)
+    }
+
+    /// Tap gesture waiting to see if another tap follows, if any
+    fn pending(&self) -> Option<(Modifier, Gesture)> {
+        let modifier = self.modifier?;
+        let gesture = Gesture::from_taps(self.taps)?;
+        (self.pressed_at.is_none() && self.is_bound(modifier, gesture))
+            .then_some((modifier, gesture))
+    }
+
+    fn reset(&mut self) {
+        self.modifier = None;
+        self.taps = 0;
+        self.last_release = None;
+        self.pressed_at = None;
+        self.held = false;
+        self.chord = false;
+    }
+
+    /// Handles a modifier press
+    ///
+    /// # Returns
+    ///
+    /// A pending tap gesture of another sequence that this press ends
+    pub fn press(&mut self, modifier: Modifier, now: Instant) -> Option<(Modifier, Gesture)> {
+        // Pressed while another modifier is held: a chord for both
+        let chord = self.pressed_at.is_some() && self.modifier != Some(modifier);
+        let mut ended = None;
+        if !self.continues(modifier, now) {
+            if self.modifier != Some(modifier) {
+                ended = self.pending();
+            }
+            self.reset();
+        }
+        self.modifier = Some(modifier);
+        self.pressed_at = Some(now);
+        self.held = false;
+        self.chord = chord;
+        ended
+    }
+
+    /// Handles a press of any key other than a modifier
+    ///
+    /// While a modifier is held, the press makes it a chord and its release
+    /// won't count as a tap. Otherwise, it ends the sequence.
+    ///
+    /// # Returns
+    ///
+    /// A pending tap gesture that this press ends
+    pub fn other_key_press(&mut self) -> Option<(Modifier, Gesture)> {
+        if self.pressed_at.is_some() {
+            self.chord = true;
+            return None;
+        }
+        let ended = self.pending();
+        self.reset();
+        ended
+    }
+
+    /// Handles a modifier release
+    ///
+    /// # Returns
+    ///
+    /// The gesture completed by this release, if it needs no more taps
+    pub fn release(&mut self, modifier: Modifier, now: Instant) -> Option<(Modifier, Gesture)> {
+        if self.modifier != Some(modifier) || self.pressed_at.is_none() {
+            return None;
+        }
+        if self.held || self.chord {
+            // The hold was already reported, or the press was part of a chord
+            self.reset();
+            return None;
+        }
+
+        self.taps = if self.continues(modifier, now) {
+            self.taps + 1
+        } else {
+            1
+        };
+        self.last_release = Some(now);
+        self.pressed_at = None;
+
+        let gesture = Gesture::from_taps(self.taps)?;
+        if self.taps >= self.max_taps(modifier) {
+            self.reset();
+            return self
+                .is_bound(modifier, gesture)
+                .then_some((modifier, gesture));
+        }
+        None
+    }
+
+    /// When [`Recognizer::timeout`] should be called next, if at all
+    pub fn deadline(&self) -> Option<Instant> {
+        let modifier = self.modifier?;
+        match self.pressed_at {
+            // Pressed again after a tap: a hold if it lasts
+            Some(pressed_at)
+                if self.taps > 0
+                    && !self.held
+                    && !self.chord
+                    && self.is_bound(modifier, Gesture::Hold) =>
+            {
+                Some(pressed_at + self.hold)
+            }
+            Some(_) => None,
+            None => {
+                self.pending()?;
+                Some(self.last_release? + self.interval)
+            }
+        }
+    }
+
+    /// Reports gestures completed by time passing
+    ///
+    /// # Returns
+    ///
+    /// A hold that lasted long enough, or a tap gesture no further tap
+    /// followed
+    pub fn timeout(&mut self, now: Instant) -> Option<(Modifier, Gesture)> {
+        let deadline = self.deadline()?;
+        if now < deadline {
+            return None;
+        }
+        let modifier = self.modifier?;
+        if self.pressed_at.is_some() {
+            self.held = true;
+            return Some((modifier, Gesture::Hold));
+        }
+        let pending = self.pending();
+        self.reset();
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Binding;
+
+    const CTRL: Modifier = Modifier::Ctrl;
+
+    fn recognizer(bindings: &[(Modifier, Gesture)]) -> Recognizer {
+        let config = Config {
+            bindings: bindings
+                .iter()
+                .map(|&(key, gesture)| Binding {
+                    key,
+                    gesture,
+                    signal: "Triggered".to_string(),
+                })
+                .collect(),
+            ..Config::default()
+        };
+        Recognizer::new(&config)
+    }
+
+    fn ms(start: Instant, ms: u64) -> Instant {
+        start + Duration::from_millis(ms)
+    }
+
+    /// Taps the modifier at a time, returning the gesture reported on release
+    fn tap(r: &mut Recognizer, modifier: Modifier, at: Instant) -> Option<(Modifier, Gesture)> {
+        r.press(modifier, at);
+        r.release(modifier, at + Duration::from_millis(30))
+    }
+
+    #[test]
+    fn test_gesture_names_match_config() {
+        for gesture in [Gesture::DoubleTap, Gesture::TripleTap, Gesture::Hold] {
+            let value = toml::Value::String(gesture.name().to_string());
+            assert_eq!(value.try_into::<Gesture>().unwrap(), gesture);
+        }
+    }
+
+    #[test]
+    fn test_double_tap_reported_on_release() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::DoubleTap)]);
+        assert_eq!(tap(&mut r, CTRL, t), None);
+        assert_eq!(
+            tap(&mut r, CTRL, ms(t, 150)),
+            Some((CTRL, Gesture::DoubleTap))
+        );
+        assert_eq!(r.deadline(), None);
+    }
+
+    #[test]
+    fn test_slow_taps_are_not_a_double_tap() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::DoubleTap)]);
+        assert_eq!(tap(&mut r, CTRL, t), None);
+        assert_eq!(tap(&mut r, CTRL, ms(t, 400)), None);
+        // The slow tap starts a new sequence
+        assert_eq!(
+            tap(&mut r, CTRL, ms(t, 600)),
+            Some((CTRL, Gesture::DoubleTap))
+        );
+    }
+
+    #[test]
+    fn test_different_modifiers_do_not_combine() {
+        let t = Instant::now();
+        let mut r = recognizer(&[
+            (CTRL, Gesture::DoubleTap),
+            (Modifier::Shift, Gesture::DoubleTap),
+        ]);
+        assert_eq!(tap(&mut r, CTRL, t), None);
+        assert_eq!(tap(&mut r, Modifier::Shift, ms(t, 100)), None);
+        assert_eq!(tap(&mut r, CTRL, ms(t, 200)), None);
+    }
+
+    #[test]
+    fn test_double_tap_waits_for_possible_triple_tap() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::DoubleTap), (CTRL, Gesture::TripleTap)]);
+        tap(&mut r, CTRL, t);
+        assert_eq!(tap(&mut r, CTRL, ms(t, 150)), None);
+        let deadline = r.deadline().unwrap();
+        assert_eq!(deadline, ms(t, 180 + 300));
+
+        assert_eq!(r.timeout(ms(t, 300)), None);
+        assert_eq!(r.timeout(deadline), Some((CTRL, Gesture::DoubleTap)));
+        assert_eq!(r.deadline(), None);
+
+        // A third tap in time makes it a triple tap instead
+        tap(&mut r, CTRL, ms(t, 1000));
+        tap(&mut r, CTRL, ms(t, 1150));
+        assert_eq!(
+            tap(&mut r, CTRL, ms(t, 1300)),
+            Some((CTRL, Gesture::TripleTap))
+        );
+        assert_eq!(r.deadline(), None);
+    }
+
+    #[test]
+    fn test_pending_double_tap_ends_on_other_modifier() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::DoubleTap), (CTRL, Gesture::TripleTap)]);
+        tap(&mut r, CTRL, t);
+        tap(&mut r, CTRL, ms(t, 150));
+        assert_eq!(
+            r.press(Modifier::Alt, ms(t, 200)),
+            Some((CTRL, Gesture::DoubleTap))
+        );
+    }
+
+    #[test]
+    fn test_tap_then_hold() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::Hold), (CTRL, Gesture::DoubleTap)]);
+        tap(&mut r, CTRL, t);
+        r.press(CTRL, ms(t, 150));
+        let deadline = r.deadline().unwrap();
+        assert_eq!(deadline, ms(t, 150 + 500));
+        assert_eq!(r.timeout(deadline), Some((CTRL, Gesture::Hold)));
+        assert_eq!(r.deadline(), None);
+
+        // Releasing after the hold reports nothing more
+        assert_eq!(r.release(CTRL, ms(t, 900)), None);
+        assert_eq!(tap(&mut r, CTRL, ms(t, 1000)), None);
+    }
+
+    #[test]
+    fn test_short_second_press_is_a_tap_not_a_hold() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::Hold), (CTRL, Gesture::DoubleTap)]);
+        tap(&mut r, CTRL, t);
+        assert_eq!(
+            tap(&mut r, CTRL, ms(t, 150)),
+            Some((CTRL, Gesture::DoubleTap))
+        );
+    }
+
+    #[test]
+    fn test_chords_are_not_taps() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::DoubleTap)]);
+
+        // Ctrl+C quickly followed by Ctrl+V
+        r.press(CTRL, t);
+        assert_eq!(r.other_key_press(), None);
+        assert_eq!(r.release(CTRL, ms(t, 50)), None);
+        r.press(CTRL, ms(t, 100));
+        r.other_key_press();
+        assert_eq!(r.release(CTRL, ms(t, 150)), None);
+
+        // Ctrl+Shift doesn't count for either modifier
+        r.press(CTRL, ms(t, 200));
+        r.press(Modifier::Shift, ms(t, 210));
+        assert_eq!(r.release(Modifier::Shift, ms(t, 240)), None);
+        assert_eq!(r.release(CTRL, ms(t, 250)), None);
+
+        // Bare taps still count
+        tap(&mut r, CTRL, ms(t, 300));
+        assert_eq!(
+            tap(&mut r, CTRL, ms(t, 450)),
+            Some((CTRL, Gesture::DoubleTap))
+        );
+    }
+
+    #[test]
+    fn test_other_key_between_taps_ends_sequence() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::DoubleTap), (CTRL, Gesture::TripleTap)]);
+        tap(&mut r, CTRL, t);
+        assert_eq!(r.other_key_press(), None);
+        assert_eq!(tap(&mut r, CTRL, ms(t, 100)), None);
+
+        // A pending double tap is reported instead of dropped
+        assert_eq!(tap(&mut r, CTRL, ms(t, 200)), None);
+        assert_eq!(r.other_key_press(), Some((CTRL, Gesture::DoubleTap)));
+        assert_eq!(r.deadline(), None);
+    }
+
+    #[test]
+    fn test_hold_without_tap_is_ignored() {
+        let t = Instant::now();
+        let mut r = recognizer(&[(CTRL, Gesture::Hold)]);
+        r.press(CTRL, t);
+        assert_eq!(r.deadline(), None);
+        assert_eq!(r.timeout(ms(t, 2000)), None);
+    }
+}
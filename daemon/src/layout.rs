@@ -0,0 +1,269 @@
+//! Keyboard layout lookup for typing text
+//!
+//! uinput emits key codes, not characters, so typing text needs the key
+//! that produces each character in the user's XKB layout. [`Layout`] builds
+//! that table from an xkbcommon keymap by asking which character every key
+//! gives with no modifier, Shift, AltGr and Shift+AltGr.
+//!
+//! Unless `daemon.toml` names a layout, the session's layout is read with
+//! [`session_layout`]: GNOME's input sources, KDE's `kxkbrc`, then the
+//! system X11 keyboard config written by `localectl`.
+
+use evdev::Key;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use xkbcommon::xkb;
+
+/// Keyboard config written by `localectl set-x11-keymap`
+const X11_KEYBOARD_CONF: &str = "/etc/X11/xorg.conf.d/00-keyboard.conf";
+
+/// XKB layout and variant names, e.g. ("de", "nodeadkeys")
+pub type LayoutNames = (String, String);
+
+/// Reads the layout the user's session types with
+///
+/// Only the first (most recently used) layout counts, so typing follows a
+/// layout switch the next time text is typed.
+///
+/// # Returns
+///
+/// None if no source names a layout; xkbcommon then uses
+/// `XKB_DEFAULT_LAYOUT` or the system default
+pub fn session_layout() -> Option<LayoutNames> {
+    gnome_layout()
+        .or_else(|| parse_kxkbrc(&read(&dirs::config_dir()?.join("kxkbrc"))?))
+        .or_else(|| parse_x11_keyboard_conf(&read(Path::new(X11_KEYBOARD_CONF))?))
+}
+
+fn read(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+/// GNOME's current input source, from gsettings
+fn gnome_layout() -> Option<LayoutNames> {
+    let get = |key: &str| {
+        let output = Command::new("gsettings")
+            .args(["get", "org.gnome.desktop.input-sources", key])
+            .output()
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    get("mru-sources")
+        .and_then(|sources| parse_gnome_sources(&sources))
+        .or_else(|| parse_gnome_sources(&get("sources")?))
+}
+
+/// First XKB source in a gsettings list like `[('xkb', 'de+nodeadkeys')]`
+///
+/// Input method sources (e.g., `('ibus', 'mozc-jp')`) are skipped.
+fn parse_gnome_sources(sources: &str) -> Option<LayoutNames> {
+    sources.split("('xkb', '").skip(1).find_map(|rest| {
+        let (name, _) = rest.split_once('\'')?;
+        let (layout, variant) = name.split_once('+').unwrap_or((name, ""));
+        (!layout.is_empty()).then(|| (layout.to_string(), variant.to_string()))
+    })
+}
+
+/// First layout of KDE's `LayoutList` and its `VariantList` entry
+fn parse_kxkbrc(contents: &str) -> Option<LayoutNames> {
+    let value = |key: &str| {
+        contents.lines().find_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            (name.trim() == key).then(|| value.trim().to_string())
+        })
+    };
+    let first = |list: String| list.split(',').next().unwrap_or("").trim().to_string();
+    let layout = first(value("LayoutList")?);
+    let variant = value("VariantList").map(first).unwrap_or_default();
+    (!layout.is_empty()).then_some((layout, variant))
+}
+
+/// First layout of the `XkbLayout` option and its `XkbVariant` entry
+fn parse_x11_keyboard_conf(contents: &str) -> Option<LayoutNames> {
+    let option = |name: &str| {
+        contents.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix("Option")?.trim();
+            let rest = rest.strip_prefix(&format!("\"{}\"", name))?.trim();
+            let value = rest.strip_prefix('"')?.split('"').next()?;
+            Some(value.split(',').next().unwrap_or("").to_string())
+        })
+    };
+    let layout = option("XkbLayout")?;
+    let variant = option("XkbVariant").unwrap_or_default();
+    (!layout.is_empty()).then_some((layout, variant))
+}
+
+/// Offset between XKB keycodes and evdev key codes
+const EVDEV_OFFSET: u32 = 8;
+
+/// A key press that types one character
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stroke {
+    pub key: Key,
+    pub shift: bool,
+    pub altgr: bool,
+}
+
+/// Characters typeable with an XKB layout and the keys that type them
+pub struct Layout {
+    strokes: HashMap<char, Stroke>,
+}
+
+impl Layout {
+    /// Builds the table for an XKB layout and variant
+    ///
+    /// Empty names use `XKB_DEFAULT_LAYOUT` and `XKB_DEFAULT_VARIANT`, else
+    /// the system default; see [`session_layout`] for the session's.
+    ///
+    /// # Returns
+    ///
+    /// None if xkbcommon can't compile the keymap (e.g., unknown layout)
+    pub fn new(layout: &str, variant: &str) -> Option<Self> {
+        let context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let keymap = xkb::Keymap::new_from_names(
+            &context,
+            "",
+            "",
+            layout,
+            variant,
+            None,
+            xkb::KEYMAP_COMPILE_NO_FLAGS,
+        )?;
+
+        // Unmodified keys come first so they win over modified ones
+        let shift = 1 << keymap.mod_get_index(xkb::MOD_NAME_SHIFT);
+        let mut levels = vec![(false, false, 0), (true, false, shift)];
+        let level3 = keymap.mod_get_index(xkb::MOD_NAME_ISO_LEVEL3_SHIFT);
+        if level3 != xkb::MOD_INVALID {
+            let altgr = 1 << level3;
+            levels.extend([(false, true, altgr), (true, true, shift | altgr)]);
+        }
+
+        let mut strokes = HashMap::new();
+        strokes.insert(
+            '\n',
+            Stroke {
+                key: Key::KEY_ENTER,
+                shift: false,
+                altgr: false,
+            },
+        );
+        let keycodes = keymap.min_keycode().raw().max(EVDEV_OFFSET)..=keymap.max_keycode().raw();
+        for (shift, altgr, mask) in levels {
+            let mut state = xkb::State::new(&keymap);
+            state.update_mask(mask, 0, 0, 0, 0, 0);
+            for keycode in keycodes.clone() {
+                let Ok(code) = u16::try_from(keycode - EVDEV_OFFSET) else {
+                    break;
+                };
+                let typed = char::from_u32(state.key_get_utf32(keycode.into()))
+                    .filter(|c| !c.is_control() || *c == '\t');
+                if let Some(c) = typed {
+                    strokes.entry(c).or_insert(Stroke {
+                        key: Key::new(code),
+                        shift,
+                        altgr,
+                    });
+                }
+            }
+        }
+        Some(Self { strokes })
+    }
+
+    /// Gets the key presses that type a text
+    ///
+    /// # Returns
+    ///
+    /// None if any character isn't on the layout (e.g., emoji or dead key
+    /// compositions)
+    pub fn strokes(&self, text: &str) -> Option<Vec<Stroke>> {
+        text.chars()
+            .map(|c| self.strokes.get(&c).copied())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stroke(key: Key, shift: bool, altgr: bool) -> Stroke {
+        Stroke { key, shift, altgr }
+    }
+
+    fn names(layout: &str, variant: &str) -> Option<LayoutNames> {
+        Some((layout.to_string(), variant.to_string()))
+    }
+
+    #[test]
+    fn test_parse_gnome_sources() {
+        assert_eq!(
+            parse_gnome_sources("[('ibus', 'mozc-jp'), ('xkb', 'de+nodeadkeys'), ('xkb', 'us')]\n"),
+            names("de", "nodeadkeys")
+        );
+        assert_eq!(parse_gnome_sources("[('xkb', 'jp')]"), names("jp", ""));
+        assert_eq!(parse_gnome_sources("@a(ss) []"), None);
+    }
+
+    #[test]
+    fn test_parse_kxkbrc() {
+        let contents =
+            "[Layout]\nDisplayNames=,\nLayoutList=de,us\nUse=true\nVariantList=nodeadkeys,\n";
+        assert_eq!(parse_kxkbrc(contents), names("de", "nodeadkeys"));
+        assert_eq!(parse_kxkbrc("[Layout]\nLayoutList=fr\n"), names("fr", ""));
+        assert_eq!(parse_kxkbrc("[Layout]\nUse=false\n"), None);
+    }
+
+    #[test]
+    fn test_parse_x11_keyboard_conf() {
+        let contents = r#"Section "InputClass"
+        Identifier "system-keyboard"
+        MatchIsKeyboard "on"
+        Option "XkbLayout" "fr,us"
+        Option "XkbVariant" "azerty,"
+EndSection
+"#;
+        assert_eq!(parse_x11_keyboard_conf(contents), names("fr", "azerty"));
+        assert_eq!(
+            parse_x11_keyboard_conf("Section \"InputClass\"\nEndSection\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_us_layout() {
+        // xkeyboard-config data may be missing in minimal test environments
+        let Some(us) = Layout::new("us", "") else {
+            return;
+        };
+        assert_eq!(
+            us.strokes("aA@\n").unwrap(),
+            [
+                stroke(Key::KEY_A, false, false),
+                stroke(Key::KEY_A, true, false),
+                stroke(Key::KEY_2, true, false),
+                stroke(Key::KEY_ENTER, false, false),
+            ]
+        );
+        assert_eq!(us.strokes("naïve"), None);
+    }
+
+    #[test]
+    fn test_german_layout() {
+        let Some(de) = Layout::new("de", "") else {
+            return;
+        };
+        assert_eq!(
+            de.strokes("z@ü").unwrap(),
+            [
+                stroke(Key::KEY_Y, false, false),
+                stroke(Key::KEY_Q, false, true),
+                stroke(Key::KEY_LEFTBRACE, false, false),
+            ]
+        );
+    }
+}
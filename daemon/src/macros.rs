@@ -0,0 +1,146 @@
+//! Key macros
+//!
+//! `[macros]` in `daemon.toml` names key sequences that `PlayMacro(name)` on
+//! the Daemon interface plays through the virtual keyboard. Each step
+//! presses a key combination, after an optional delay:
+//!
+//! ```toml
+//! [macros]
+//! paste-submit = [
+//!     { keys = "ctrl+shift+v" },
+//!     { keys = "enter", delay_ms = 100 },
+//! ]
+//! ```
+//!
+//! Key names are evdev names without `KEY_` (`a`, `f5`, `pageup`, ...) or
+//! one of the aliases in [`key_by_name`]. The auto-paste shortcuts are
+//! built from the same steps (see `PasteShortcut::steps`).
+
+use crate::uinput::MAX_KEY_CODE;
+use evdev::Key;
+use serde::Deserialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Finds a key by name, case-insensitively
+///
+/// Accepts evdev key names without the `KEY_` prefix and the aliases
+/// `ctrl`, `shift`, `alt`, `altgr`, `super`, `return` and `escape`. Keys
+/// the virtual keyboard can't emit (e.g., mouse buttons) aren't found.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(key_by_name("Ctrl"), Some(Key::KEY_LEFTCTRL));
+/// assert_eq!(key_by_name("pagedown"), Some(Key::KEY_PAGEDOWN));
+/// ```
+pub fn key_by_name(name: &str) -> Option<Key> {
+    let name = name.trim().to_uppercase();
+    let key = match name.as_str() {
+        "CTRL" | "CONTROL" => Key::KEY_LEFTCTRL,
+        "SHIFT" => Key::KEY_LEFTSHIFT,
+        "ALT" => Key::KEY_LEFTALT,
+        "ALTGR" => Key::KEY_RIGHTALT,
+        "SUPER" | "META" => Key::KEY_LEFTMETA,
+        "RETURN" => Key::KEY_ENTER,
+        "ESCAPE" => Key::KEY_ESC,
+        _ => Key::from_str(&format!("KEY_{}", name)).ok()?,
+    };
+    (1..=MAX_KEY_CODE).contains(&key.code()).then_some(key)
+}
+
+/// A key pressed while holding other keys, like Ctrl+Shift+V
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Combo {
+    /// Keys held down, in press order
+    pub modifiers: Vec<Key>,
+    pub key: Key,
+}
+
+impl Combo {
+    /// A single key without modifiers
+    pub fn key(key: Key) -> Self {
+        Self {
+            modifiers: Vec::new(),
+            key,
+        }
+    }
+}
+
+impl FromStr for Combo {
+    type Err = String;
+
+    /// Parses keys joined with `+`, e.g., "ctrl+shift+v"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut keys = s
+            .split('+')
+            .map(|name| key_by_name(name).ok_or_else(|| format!("unknown key {:?}", name.trim())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let key = keys.pop().ok_or("no key")?;
+        Ok(Self {
+            modifiers: keys,
+            key,
+        })
+    }
+}
+
+impl TryFrom<String> for Combo {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// One step of a macro
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Step {
+    pub keys: Combo,
+    /// Time to wait before pressing the keys, in milliseconds (at most
+    /// `MAX_KEY_DELAY_MS`, see `Config::validate`)
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+impl Step {
+    /// A step pressing keys right away
+    pub fn new(keys: Combo) -> Self {
+        Self { keys, delay_ms: 0 }
+    }
+
+    /// Time to wait before pressing the keys
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_by_name() {
+        assert_eq!(key_by_name("Ctrl"), Some(Key::KEY_LEFTCTRL));
+        assert_eq!(key_by_name("return"), Some(Key::KEY_ENTER));
+        assert_eq!(key_by_name("f5"), Some(Key::KEY_F5));
+        assert_eq!(key_by_name(" pagedown "), Some(Key::KEY_PAGEDOWN));
+        assert_eq!(key_by_name("hyper"), None);
+        // Beyond the keys the virtual keyboard supports
+        assert_eq!(key_by_name("btn_left"), None);
+    }
+
+    #[test]
+    fn test_parse_combo() {
+        assert_eq!(
+            "ctrl+shift+v".parse::<Combo>(),
+            Ok(Combo {
+                modifiers: vec![Key::KEY_LEFTCTRL, Key::KEY_LEFTSHIFT],
+                key: Key::KEY_V,
+            })
+        );
+        assert_eq!("Enter".parse::<Combo>(), Ok(Combo::key(Key::KEY_ENTER)));
+        assert!("ctrl+".parse::<Combo>().is_err());
+        assert!("ctrl+nope".parse::<Combo>().is_err());
+    }
+}
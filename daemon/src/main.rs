@@ -3,26 +3,63 @@
 //! This daemon monitors keyboard input devices for double Ctrl key presses
 //! and sends D-Bus signals to notify the Tauri application.
 //!
-//! It also listens for TypeText signals to simulate keyboard input.
-
+//! It also listens for TypeText and TypeString signals to simulate keyboard
+//! input (see [`layout`] for typing text), and serves a small D-Bus
+//! interface (see [`service`]) for focus mode and key macros (see
+//! [`macros`]).
+//!
+//! Which modifier gestures are watched and the signals they emit are set in
+//! `~/.config/uti/daemon.toml` (see [`config`]); [`gesture`] recognizes
+//! them. If uti isn't running, [`activation`] starts it.
+//!
+//! Without a session bus, the daemon serves the same signals on a Unix
+//! socket instead (see [`fallback`]). Sending SIGHUP or calling `Reload()`
+//! applies changes to the file without a restart.
+
+mod activation;
+mod config;
+mod fallback;
+mod gesture;
+mod layout;
+mod macros;
+mod service;
+mod stats;
+mod systemd;
 mod uinput;
 
+use config::{Config, DeviceFilter, Modifier};
 use evdev::{Device, EventType, Key};
 use futures_util::StreamExt;
-use log::{debug, error, info};
+use gesture::{Gesture, Recognizer};
+use layout::Layout;
+use log::{debug, error, info, warn};
+use stats::Stats;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::{broadcast, mpsc, watch, Mutex};
+use tokio::task::{AbortHandle, JoinSet};
+use uti_dbus::app::{TypeString, TypeText};
+use uti_dbus::double_tap::{DoubleTap, TriggerInfo};
+use uti_dbus::socket::Message;
+use zbus::object_server::SignalContext;
 use zbus::Connection;
 
-/// Maximum time interval between two Ctrl presses to be considered a double tap
+/// Default maximum time interval between two presses to be considered a double tap
 const DOUBLE_TAP_INTERVAL: Duration = Duration::from_millis(300);
 
-/// Sends a D-Bus signal to notify listeners of a double Ctrl press event
+/// Sends a D-Bus signal to notify listeners of a gesture
+///
+/// Starts the app through D-Bus activation if it isn't running.
 ///
 /// # Arguments
 ///
 /// * `conn` - The D-Bus connection to use for sending the signal
+/// * `signal` - Signal name, one of [`config::SIGNALS`]
+/// * `info` - Keyboard, gesture and time sent with the signal
 ///
 /// # Returns
 ///
@@ -31,43 +68,110 @@ const DOUBLE_TAP_INTERVAL: Duration = Duration::from_millis(300);
 /// # Examples
 ///
 /// ```no_run
+/// # use uti_dbus::double_tap::TriggerInfo;
 /// # use zbus::Connection;
 /// # async fn example() -> zbus::Result<()> {
 /// let conn = Connection::session().await?;
-/// notify_double_ctrl(&conn).await?;
+/// notify_trigger(&conn, "Triggered", &TriggerInfo::now("Keyboard", "double-tap")).await?;
 /// # Ok(())
 /// # }
 /// ```
-async fn notify_double_ctrl(conn: &Connection) -> zbus::Result<()> {
-    conn.emit_signal(
-        None::<()>,
-        "/io/github/noppomario/uti/DoubleTap",
-        "io.github.noppomario.uti.DoubleTap",
-        "Triggered",
-        &(),
-    )
-    .await?;
-    info!("D-Bus signal sent: Triggered");
+async fn notify_trigger(conn: &Connection, signal: &str, info: &TriggerInfo) -> zbus::Result<()> {
+    let ctxt = SignalContext::new(conn, uti_dbus::DOUBLE_TAP_PATH)?;
+    DoubleTap::emit(&ctxt, signal, info).await?;
+    info!("D-Bus signal sent: {}", signal);
+    tokio::spawn(activation::deliver(conn.clone(), signal.to_string()));
     Ok(())
 }
 
-/// Listens for TypeText D-Bus signals and simulates paste
+/// Where gesture signals are sent
+#[derive(Clone)]
+enum Notifier {
+    /// DoubleTap signals on the session bus
+    DBus(Arc<Connection>),
+    /// Lines to the clients of the socket fallback
+    Socket(broadcast::Sender<Message>),
+}
+
+impl Notifier {
+    /// Sends a gesture signal, see [`notify_trigger`]
+    async fn notify(&self, signal: &str, info: &TriggerInfo) -> zbus::Result<()> {
+        match self {
+            Self::DBus(conn) => notify_trigger(conn, signal, info).await,
+            Self::Socket(clients) => {
+                // Fails only when no client is connected to receive it
+                if clients.send(Message::trigger(signal, info)).is_err() {
+                    debug!("No socket client for {}", signal);
+                }
+                info!("Socket signal sent: {}", signal);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Names of the layout to type with: `daemon.toml`'s, else the session's
+fn layout_names(config: &Config) -> layout::LayoutNames {
+    let keyboard = &config.keyboard;
+    if !keyboard.layout.is_empty() {
+        return (keyboard.layout.clone(), keyboard.variant.clone());
+    }
+    layout::session_layout().unwrap_or_default()
+}
+
+/// Builds the keyboard layout table for typing text, logging failures
+fn load_layout((layout, variant): &layout::LayoutNames) -> Option<Arc<Layout>> {
+    info!(
+        "Typing with XKB layout {:?} (variant {:?})",
+        layout, variant
+    );
+    let table = Layout::new(layout, variant);
+    if table.is_none() {
+        error!(
+            "Failed to load XKB layout {:?} (variant {:?}), text will be pasted instead of typed",
+            layout, variant
+        );
+    }
+    table.map(Arc::new)
+}
+
+/// Whether a message was sent by the current owner of the app bus name
+async fn sent_by_app(dbus: &zbus::fdo::DBusProxy<'_>, msg: &zbus::Message) -> bool {
+    let header = msg.header();
+    let Some(sender) = header.sender() else {
+        return false;
+    };
+    let Ok(name) = zbus::names::BusName::try_from(uti_dbus::APP_BUS_NAME) else {
+        return false;
+    };
+    match dbus.get_name_owner(name).await {
+        Ok(owner) => owner.as_str() == sender.as_str(),
+        Err(_) => false,
+    }
+}
+
+/// Forwards TypeText and TypeString D-Bus signals to the input task
 ///
-/// When a TypeText signal is received, this function uses the virtual keyboard
-/// to simulate Ctrl+Shift+V to paste the content from clipboard.
+/// Only signals from the owner of the app bus name are accepted, since
+/// TypeString types arbitrary text into the focused window. TypeText
+/// without arguments, from older app versions, pastes with Ctrl+Shift+V
+/// and Enter as before.
 ///
 /// # Arguments
 ///
 /// * `conn` - The D-Bus connection to listen on
-async fn listen_for_type_text(conn: Arc<Connection>) {
+/// * `input` - Requests for [`simulate_input`]
+async fn listen_for_type_text(conn: Arc<Connection>, input: mpsc::Sender<Message>) {
     info!("Setting up TypeText signal listener...");
 
-    // Create a rule to match TypeText signals
+    // Create a rule to match TypeText and TypeString signals from the app
     let rule = zbus::MatchRule::builder()
         .msg_type(zbus::message::Type::Signal)
-        .interface("io.github.noppomario.uti.DoubleTap")
+        .sender(uti_dbus::APP_BUS_NAME)
+        .unwrap()
+        .interface(uti_dbus::APP_INTERFACE)
         .unwrap()
-        .member("TypeText")
+        .path(uti_dbus::APP_PATH)
         .unwrap()
         .build();
 
@@ -80,30 +184,129 @@ async fn listen_for_type_text(conn: Arc<Connection>) {
         }
     };
 
-    info!("TypeText signal listener started");
-
-    // Create virtual keyboard once
-    let mut virtual_kb = match uinput::VirtualKeyboard::new() {
-        Ok(kb) => kb,
+    let dbus = match zbus::fdo::DBusProxy::new(&conn).await {
+        Ok(proxy) => proxy,
         Err(e) => {
-            error!("Failed to create virtual keyboard: {}", e);
-            error!("TypeText functionality will be disabled");
+            error!("Failed to create D-Bus proxy for TypeText: {}", e);
             return;
         }
     };
 
+    info!("TypeText signal listener started");
+
     while let Some(msg) = stream.next().await {
-        match msg {
-            Ok(_) => {
-                debug!("Received TypeText signal");
-                // Simulate Ctrl+Shift+V paste
-                if let Err(e) = virtual_kb.paste() {
+        let msg = match msg {
+            Ok(msg) => msg,
+            Err(e) => {
+                error!("Error receiving TypeText signal: {}", e);
+                continue;
+            }
+        };
+        if !sent_by_app(&dbus, &msg).await {
+            warn!(
+                "Ignoring signal from {:?}, which doesn't own {}",
+                msg.header().sender(),
+                uti_dbus::APP_BUS_NAME
+            );
+            continue;
+        }
+        let request = if let Some(signal) = TypeText::from_message(msg.clone()) {
+            debug!("Received TypeText signal");
+            match signal.args() {
+                Ok(args) => Message::TypeText {
+                    shortcut: args.shortcut.to_string(),
+                    enter: args.enter,
+                    key_delay_ms: args.key_delay_ms,
+                },
+                Err(_) => Message::TypeText {
+                    shortcut: "ctrl-shift-v".to_string(),
+                    enter: true,
+                    key_delay_ms: 0,
+                },
+            }
+        } else if let Some(signal) = TypeString::from_message(msg) {
+            debug!("Received TypeString signal");
+            match signal.args() {
+                Ok(args) => Message::TypeString {
+                    text: args.text.to_string(),
+                },
+                Err(e) => {
+                    error!("Invalid TypeString signal: {}", e);
+                    continue;
+                }
+            }
+        } else {
+            continue;
+        };
+        if input.send(request).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Simulates input for TypeText and TypeString requests
+///
+/// A TypeText request makes the virtual keyboard paste the content from
+/// clipboard with the shortcut it names, then press Enter if asked, with
+/// the key delay it gives (0 for `key_delay_ms` from the config). A
+/// TypeString request carries text to type key by key instead, for apps
+/// that block pasting. Key events are emitted on a blocking thread (see
+/// [`uinput::run_blocking`]), one request at a time.
+///
+/// # Arguments
+///
+/// * `requests` - Requests from D-Bus signals or socket clients
+/// * `config` - Config with the keyboard layout, updated on reload
+/// * `virtual_kb` - Virtual keyboard, shared with `PlayMacro`
+async fn simulate_input(
+    mut requests: mpsc::Receiver<Message>,
+    mut config: watch::Receiver<Arc<Config>>,
+    virtual_kb: Arc<Mutex<uinput::VirtualKeyboard>>,
+) {
+    let mut names = layout_names(&config.borrow_and_update());
+    let mut layout = load_layout(&names);
+
+    while let Some(request) = requests.recv().await {
+        match request {
+            Message::TypeText {
+                shortcut,
+                enter,
+                key_delay_ms,
+            } => {
+                let shortcut = uinput::PasteShortcut::from_name(&shortcut).unwrap_or_else(|| {
+                    warn!("Unknown paste shortcut {:?}, using ctrl-shift-v", shortcut);
+                    uinput::PasteShortcut::CtrlShiftV
+                });
+                let delay = match u64::from(key_delay_ms) {
+                    0 => config.borrow().key_delay(),
+                    ms => Duration::from_millis(ms.min(config::MAX_KEY_DELAY_MS)),
+                };
+                let result = uinput::run_blocking(&virtual_kb, move |keyboard| {
+                    keyboard.paste(shortcut, enter, delay)
+                })
+                .await;
+                if let Err(e) = result {
                     error!("Failed to simulate paste: {}", e);
                 }
             }
-            Err(e) => {
-                error!("Error receiving TypeText signal: {}", e);
+            Message::TypeString { text } => {
+                // Follow config reloads and session layout switches
+                let current = layout_names(&config.borrow_and_update());
+                if current != names {
+                    layout = load_layout(&current);
+                    names = current;
+                }
+                let delay = config.borrow().key_delay();
+                let layout = layout.clone();
+                let result = uinput::run_blocking(&virtual_kb, move |keyboard| {
+                    keyboard.type_string(&text, layout.as_deref(), delay)
+                })
+                .await;
+                if let Err(e) = result {
+                    error!("Failed to type text: {}", e);
+                }
             }
+            Message::Trigger { .. } => {}
         }
     }
 }
@@ -111,12 +314,17 @@ async fn listen_for_type_text(conn: Arc<Connection>) {
 /// Finds all available keyboard devices
 ///
 /// Searches through `/dev/input/event*` devices to find keyboards by checking
-/// if the device supports the 'A' key.
+/// if the device supports the 'A' key. uti's own virtual keyboard is skipped,
+/// so pastes it emits can't feed back into hotkey detection.
+///
+/// # Arguments
+///
+/// * `filter` - Devices to include or exclude, from the config
 ///
 /// # Returns
 ///
 /// Returns a list of keyboard device paths.
-fn find_keyboard_devices() -> std::io::Result<Vec<std::path::PathBuf>> {
+fn find_keyboard_devices(filter: &DeviceFilter) -> std::io::Result<Vec<std::path::PathBuf>> {
     let mut keyboards = Vec::new();
 
     for entry in std::fs::read_dir("/dev/input")? {
@@ -124,12 +332,21 @@ fn find_keyboard_devices() -> std::io::Result<Vec<std::path::PathBuf>> {
         if let Some(name) = path.file_name() {
             if name.to_string_lossy().starts_with("event") {
                 if let Ok(device) = Device::open(&path) {
-                    if device
+                    if uinput::is_virtual_keyboard(device.name(), &device.input_id()) {
+                        debug!("Skipping uti's virtual keyboard: {:?}", path);
+                        continue;
+                    }
+                    if !device
                         .supported_keys()
                         .is_some_and(|keys| keys.contains(Key::KEY_A))
                     {
-                        keyboards.push(path);
+                        continue;
+                    }
+                    if !filter.allows(device.name().unwrap_or_default(), &path) {
+                        info!("Skipping filtered device: {:?}", path);
+                        continue;
                     }
+                    keyboards.push(path);
                 }
             }
         }
@@ -145,89 +362,125 @@ fn find_keyboard_devices() -> std::io::Result<Vec<std::path::PathBuf>> {
     Ok(keyboards)
 }
 
-/// Monitors a single keyboard device for double Ctrl presses
+/// Sleeps until a gesture deadline, or forever if there is none
+async fn wait_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Sends the signal bound to a recognized gesture, unless focus mode is on
+async fn trigger(
+    device_name: &str,
+    (modifier, gesture): (Modifier, Gesture),
+    config: &Config,
+    notifier: &Notifier,
+    focus_mode: &AtomicBool,
+    stats: &Stats,
+) {
+    let Some(binding) = config.binding(modifier, gesture) else {
+        return;
+    };
+    info!("[{}] {:?} {:?} detected!", device_name, gesture, modifier);
+    stats.record_gesture();
+    if focus_mode.load(Ordering::SeqCst) {
+        info!("[{}] Focus mode on, trigger suppressed", device_name);
+        return;
+    }
+    let info = TriggerInfo::now(device_name, gesture.name());
+    match notifier.notify(&binding.signal, &info).await {
+        Ok(()) => stats.record_signal(),
+        Err(e) => error!("[{}] Failed to send D-Bus signal: {}", device_name, e),
+    }
+}
+
+/// Monitors a single keyboard device for gestures of the bound modifiers
 ///
 /// # Arguments
 ///
 /// * `device_path` - Path to the keyboard device
 /// * `device_name` - Name of the keyboard device
-/// * `last_ctrl_release` - Shared state for tracking last Ctrl release time
-/// * `conn` - D-Bus connection for sending signals
+/// * `config` - Bindings and timings, updated on reload
+/// * `recognizer` - Gesture recognizer shared by all keyboards
+/// * `notifier` - Where gesture signals are sent
+/// * `focus_mode` - Shared focus mode flag; gestures are ignored while set
+/// * `stats` - Counters for `GetStats()`
 async fn monitor_device(
     device_path: std::path::PathBuf,
     device_name: String,
-    last_ctrl_release: Arc<Mutex<Option<Instant>>>,
-    conn: Arc<Connection>,
+    config: watch::Receiver<Arc<Config>>,
+    recognizer: Arc<Mutex<Recognizer>>,
+    notifier: Notifier,
+    focus_mode: Arc<AtomicBool>,
+    stats: Arc<Stats>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let device = Device::open(&device_path)?;
     let mut stream = device.into_event_stream()?;
     info!("[{}] Monitoring started (async)", device_name);
 
     loop {
-        match stream.next_event().await {
-            Ok(event) => {
-                // Log all key events for debugging (debug level)
-                if event.event_type() == EventType::KEY {
-                    let key = Key::new(event.code());
-                    debug!(
-                        "[{}] Key event: {:?} value={} (type={:?})",
-                        device_name,
-                        key,
-                        event.value(),
-                        event.event_type()
-                    );
+        let deadline = recognizer.lock().await.deadline();
+        let result = tokio::select! {
+            result = stream.next_event() => result,
+            () = wait_until(deadline) => {
+                // A pending double tap or a hold completed by time passing
+                let recognized = recognizer.lock().await.timeout(Instant::now());
+                if let Some(recognized) = recognized {
+                    let config = Arc::clone(&config.borrow());
+                    trigger(
+                        &device_name,
+                        recognized,
+                        &config,
+                        &notifier,
+                        &focus_mode,
+                        &stats,
+                    )
+                    .await;
                 }
+                continue;
+            }
+        };
 
+        match result {
+            Ok(event) => {
+                stats.record_event(&device_name);
                 if event.event_type() != EventType::KEY {
                     continue;
                 }
 
+                // Log all key events for debugging (debug level)
                 let key = Key::new(event.code());
-                if key != Key::KEY_LEFTCTRL && key != Key::KEY_RIGHTCTRL {
-                    continue;
-                }
+                debug!(
+                    "[{}] Key event: {:?} value={} (type={:?})",
+                    device_name,
+                    key,
+                    event.value(),
+                    event.event_type()
+                );
 
-                let key_name = if key == Key::KEY_LEFTCTRL {
-                    "LEFT"
-                } else {
-                    "RIGHT"
+                // Press (1) and release (0); auto-repeat (2) is ignored.
+                // Other keys make chords (Ctrl+C) so they aren't taps.
+                let now = Instant::now();
+                let mut gestures = recognizer.lock().await;
+                let recognized = match (Modifier::from_key(key), event.value()) {
+                    (Some(modifier), 1) => gestures.press(modifier, now),
+                    (Some(modifier), 0) => gestures.release(modifier, now),
+                    (None, 1) => gestures.other_key_press(),
+                    _ => None,
                 };
-
-                // Key press event (value == 1)
-                if event.value() == 1 {
-                    debug!("[{}] Ctrl key pressed ({})", device_name, key_name);
-                }
-
-                // Key release event (value == 0)
-                if event.value() == 0 {
-                    let now = Instant::now();
-                    let mut last_release = last_ctrl_release.lock().await;
-
-                    if let Some(last) = *last_release {
-                        let interval = now.duration_since(last);
-                        debug!(
-                            "[{}] Ctrl key released ({}) - {}ms since last release",
-                            device_name,
-                            key_name,
-                            interval.as_millis()
-                        );
-
-                        if interval < DOUBLE_TAP_INTERVAL {
-                            info!("[{}] Double Ctrl detected!", device_name);
-                            if let Err(e) = notify_double_ctrl(&conn).await {
-                                error!("[{}] Failed to send D-Bus signal: {}", device_name, e);
-                            }
-                            *last_release = None;
-                            continue;
-                        }
-                    } else {
-                        debug!(
-                            "[{}] Ctrl key released ({}) - first press",
-                            device_name, key_name
-                        );
-                    }
-
-                    *last_release = Some(now);
+                drop(gestures);
+                if let Some(recognized) = recognized {
+                    let config = Arc::clone(&config.borrow());
+                    trigger(
+                        &device_name,
+                        recognized,
+                        &config,
+                        &notifier,
+                        &focus_mode,
+                        &stats,
+                    )
+                    .await;
                 }
             }
             Err(e) => {
@@ -247,16 +500,131 @@ async fn monitor_device(
     }
 }
 
+/// Logs the gestures the daemon watches
+fn log_bindings(config: &Config) {
+    for binding in &config.bindings {
+        info!(
+            "Monitoring all keyboard devices for {:?} {:?} -> {}",
+            binding.gesture, binding.key, binding.signal
+        );
+    }
+}
+
+/// Keyboard monitoring tasks and the state they share
+struct Monitors {
+    /// All daemon tasks; the daemon exits when one ends
+    tasks: JoinSet<()>,
+    /// Monitoring task of each device
+    devices: HashMap<PathBuf, AbortHandle>,
+    config: watch::Sender<Arc<Config>>,
+    recognizer: Arc<Mutex<Recognizer>>,
+    notifier: Notifier,
+    focus_mode: Arc<AtomicBool>,
+    stats: Arc<Stats>,
+}
+
+impl Monitors {
+    /// Starts a monitoring task for a keyboard device
+    fn spawn(&mut self, path: PathBuf) {
+        let device_name = if let Ok(dev) = Device::open(&path) {
+            dev.name().unwrap_or("unknown").to_string()
+        } else {
+            path.display().to_string()
+        };
+
+        let config = self.config.subscribe();
+        let recognizer = Arc::clone(&self.recognizer);
+        let notifier = self.notifier.clone();
+        let focus_mode = Arc::clone(&self.focus_mode);
+        let stats = Arc::clone(&self.stats);
+        let device_path = path.clone();
+
+        let task = self.tasks.spawn(async move {
+            if let Err(e) = monitor_device(
+                device_path,
+                device_name.clone(),
+                config,
+                recognizer,
+                notifier,
+                focus_mode,
+                stats,
+            )
+            .await
+            {
+                error!("[{}] Monitoring task failed: {}", device_name, e);
+            }
+        });
+        self.devices.insert(path, task);
+    }
+
+    /// Monitors exactly the given devices, stopping and starting tasks as
+    /// needed
+    fn set_devices(&mut self, keyboards: Vec<PathBuf>) {
+        self.devices.retain(|path, task| {
+            let keep = keyboards.contains(path);
+            if !keep {
+                info!("Stopped monitoring {}", path.display());
+                task.abort();
+            }
+            keep
+        });
+        for path in keyboards {
+            if !self.devices.contains_key(&path) {
+                self.spawn(path);
+            }
+        }
+    }
+
+    /// Reloads the config file and applies it to the running monitors
+    ///
+    /// # Errors
+    ///
+    /// Returns a message if the file can't be read or is invalid; the
+    /// current config stays in effect
+    async fn reload(&mut self) -> Result<(), String> {
+        let config = Config::read()?;
+        let keyboards = find_keyboard_devices(&config.devices).unwrap_or_else(|e| {
+            warn!("No keyboard to monitor after reload: {}", e);
+            Vec::new()
+        });
+
+        *self.recognizer.lock().await = Recognizer::new(&config);
+        log_bindings(&config);
+        self.config.send_replace(Arc::new(config));
+        self.set_devices(keyboards);
+        info!("Config reloaded");
+        Ok(())
+    }
+}
+
+/// Serves the daemon interfaces and takes the daemon bus name
+async fn serve_dbus(conn: &Connection, service: service::DaemonService) -> zbus::Result<()> {
+    // Serve the daemon interfaces before taking the bus name
+    conn.object_server()
+        .at(uti_dbus::DOUBLE_TAP_PATH, DoubleTap)
+        .await?;
+    conn.object_server()
+        .at(uti_dbus::DAEMON_PATH, service)
+        .await?;
+
+    // Request the bus name so other applications can connect to us
+    conn.request_name(uti_dbus::DAEMON_BUS_NAME).await?;
+    info!("Connected to D-Bus session bus");
+    info!("Registered bus name: {}", uti_dbus::DAEMON_BUS_NAME);
+    Ok(())
+}
+
 /// Main daemon entry point
 ///
-/// Initializes the keyboard device monitoring, connects to D-Bus, and spawns
-/// monitoring tasks for all keyboard devices.
+/// Initializes the keyboard device monitoring, connects to D-Bus (or serves
+/// the socket fallback without a session bus), and spawns monitoring tasks
+/// for all keyboard devices.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - No keyboard device is found
-/// - Failed to connect to D-Bus session bus
+/// - Failed to serve the daemon on D-Bus, or the socket without D-Bus
 /// - Failed to spawn monitoring tasks
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -265,7 +633,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     info!("Double Ctrl daemon starting...");
 
-    let keyboards = find_keyboard_devices()?;
+    let config = Config::load();
+
+    let keyboards = find_keyboard_devices(&config.devices)?;
     info!("Found {} keyboard device(s):", keyboards.len());
 
     for (i, path) in keyboards.iter().enumerate() {
@@ -279,50 +649,102 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
-    let conn = Arc::new(Connection::session().await?);
+    // Gestures can span keyboards, so they share one recognizer
+    let recognizer = Arc::new(Mutex::new(Recognizer::new(&config)));
 
-    // Request the bus name so other applications can connect to us
-    conn.request_name("io.github.noppomario.uti").await?;
-    info!("Connected to D-Bus session bus");
-    info!("Registered bus name: io.github.noppomario.uti");
-    info!("Monitoring all keyboard devices for double Ctrl press...");
+    // Create the virtual keyboard once; typing and macros share it
+    let virtual_kb = match uinput::VirtualKeyboard::new() {
+        Ok(kb) => Some(Arc::new(Mutex::new(kb))),
+        Err(e) => {
+            error!("Failed to create virtual keyboard: {}", e);
+            error!("TypeText, TypeString and macros will be disabled");
+            None
+        }
+    };
 
-    // Shared state for last Ctrl release time across all keyboards
-    let last_ctrl_release = Arc::new(Mutex::new(None));
+    let focus_mode = Arc::new(AtomicBool::new(false));
+    let stats = Arc::new(Stats::default());
+    let (reload_tx, mut reload_requests) = mpsc::channel(1);
+    let config = watch::Sender::new(Arc::new(config));
+    let (input_tx, input_rx) = mpsc::channel(16);
+    let mut tasks = JoinSet::new();
+
+    let notifier = match Connection::session().await {
+        Ok(conn) => {
+            let conn = Arc::new(conn);
+            let service = service::DaemonService::new(
+                Arc::clone(&focus_mode),
+                Arc::clone(&recognizer),
+                reload_tx,
+                config.subscribe(),
+                virtual_kb.clone(),
+                Arc::clone(&stats),
+            );
+            serve_dbus(&conn, service).await?;
+            if virtual_kb.is_some() {
+                tasks.spawn(listen_for_type_text(Arc::clone(&conn), input_tx));
+            }
+            Notifier::DBus(conn)
+        }
+        Err(e) => {
+            warn!("No D-Bus session bus ({}), serving the socket fallback", e);
+            let listener = fallback::bind().await?;
+            let (clients, _) = broadcast::channel(16);
+            tasks.spawn(fallback::serve(listener, clients.clone(), input_tx));
+            Notifier::Socket(clients)
+        }
+    };
+    log_bindings(&config.borrow());
 
-    // Spawn a monitoring task for each keyboard device
-    let mut tasks = tokio::task::JoinSet::new();
+    // Simulate input requested over D-Bus or the socket
+    if let Some(virtual_kb) = virtual_kb {
+        tasks.spawn(simulate_input(input_rx, config.subscribe(), virtual_kb));
+    }
 
-    for path in keyboards {
-        let device_name = if let Ok(dev) = Device::open(&path) {
-            dev.name().unwrap_or("unknown").to_string()
-        } else {
-            path.display().to_string()
-        };
+    // Spawn a monitoring task for each keyboard device
+    let mut monitors = Monitors {
+        tasks,
+        devices: HashMap::new(),
+        config,
+        recognizer: Arc::clone(&recognizer),
+        notifier,
+        focus_mode,
+        stats,
+    };
+    monitors.set_devices(keyboards);
 
-        let last_release_clone = Arc::clone(&last_ctrl_release);
-        let conn_clone = Arc::clone(&conn);
+    // Devices are monitored: report ready and keep the watchdog fed
+    systemd::notify_ready();
+    monitors
+        .tasks
+        .spawn(systemd::run_watchdog(Arc::clone(&recognizer)));
 
-        tasks.spawn(async move {
-            if let Err(e) =
-                monitor_device(path, device_name.clone(), last_release_clone, conn_clone).await
-            {
-                error!("[{}] Monitoring task failed: {}", device_name, e);
+    // Exit when any task completes (device disconnect triggers systemd
+    // restart); reload the config on SIGHUP or a Reload() call
+    let mut hangup = signal(SignalKind::hangup())?;
+    loop {
+        tokio::select! {
+            Some(result) = monitors.tasks.join_next() => match result {
+                // Stopped because a reload excluded its device
+                Err(e) if e.is_cancelled() => {}
+                Ok(()) => {
+                    info!("A monitoring task exited, shutting down for restart");
+                    break;
+                }
+                Err(e) => {
+                    error!("A monitoring task panicked: {}", e);
+                    break;
+                }
+            },
+            _ = hangup.recv() => {
+                info!("SIGHUP received, reloading config");
+                if let Err(e) = monitors.reload().await {
+                    error!("Failed to reload config: {}", e);
+                }
+            }
+            Some(reply) = reload_requests.recv() => {
+                let _ = reply.send(monitors.reload().await);
             }
-        });
-    }
-
-    // Spawn TypeText signal listener
-    let conn_for_type_text = Arc::clone(&conn);
-    tasks.spawn(async move {
-        listen_for_type_text(conn_for_type_text).await;
-    });
-
-    // Exit when any task completes (device disconnect triggers systemd restart)
-    if let Some(result) = tasks.join_next().await {
-        match result {
-            Ok(()) => info!("A monitoring task exited, shutting down for restart"),
-            Err(e) => error!("A monitoring task panicked: {}", e),
         }
     }
 
@@ -392,7 +814,7 @@ mod tests {
     fn test_find_keyboard_devices_path_format() {
         // This test verifies that the function looks for event devices
         // In CI/test environments without /dev/input access, this is expected to fail
-        let result = find_keyboard_devices();
+        let result = find_keyboard_devices(&DeviceFilter::default());
 
         match result {
             Ok(keyboards) => {
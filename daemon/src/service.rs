@@ -0,0 +1,254 @@
+//! Daemon D-Bus service
+//!
+//! Exposes methods the Tauri application and other tools can call on the
+//! daemon, served at `/io/github/noppomario/uti/Daemon`. zbus also serves
+//! `org.freedesktop.DBus.Introspectable` there, so `busctl introspect` lists
+//! them. Clients use `uti_dbus::daemon::DaemonProxy`, which must match the
+//! methods here.
+
+use crate::config::Config;
+use crate::gesture::Recognizer;
+use crate::stats::Stats;
+use crate::uinput::VirtualKeyboard;
+use log::info;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch, Mutex};
+use uti_dbus::daemon::DaemonStats;
+use uti_dbus::double_tap::TriggerInfo;
+use zbus::{interface, Connection, SignalContext};
+
+/// A request to reload the config, answered with the outcome
+pub type ReloadRequest = oneshot::Sender<Result<(), String>>;
+
+/// Accepted range for `SetInterval`, in milliseconds
+const INTERVAL_RANGE_MS: std::ops::RangeInclusive<u32> = 50..=2000;
+
+/// D-Bus interface implementation for the daemon
+pub struct DaemonService {
+    /// Whether focus mode is on (double Ctrl triggers are suppressed)
+    focus_mode: Arc<AtomicBool>,
+    /// Gesture recognizer shared with the monitors
+    recognizer: Arc<Mutex<Recognizer>>,
+    /// Sends reload requests to the main loop, which owns the monitors
+    reload: mpsc::Sender<ReloadRequest>,
+    /// Current config, for macros
+    config: watch::Receiver<Arc<Config>>,
+    /// Virtual keyboard shared with the TypeText listener, if it could be
+    /// created
+    keyboard: Option<Arc<Mutex<VirtualKeyboard>>>,
+    /// Counters shared with the monitors
+    stats: Arc<Stats>,
+}
+
+impl DaemonService {
+    /// Creates the service sharing focus mode and gesture state with the
+    /// monitors
+    pub fn new(
+        focus_mode: Arc<AtomicBool>,
+        recognizer: Arc<Mutex<Recognizer>>,
+        reload: mpsc::Sender<ReloadRequest>,
+        config: watch::Receiver<Arc<Config>>,
+        keyboard: Option<Arc<Mutex<VirtualKeyboard>>>,
+        stats: Arc<Stats>,
+    ) -> Self {
+        Self {
+            focus_mode,
+            recognizer,
+            reload,
+            config,
+            keyboard,
+            stats,
+        }
+    }
+}
+
+#[interface(name = "io.github.noppomario.uti.Daemon")]
+impl DaemonService {
+    /// Enables or disables focus mode
+    ///
+    /// While enabled, double Ctrl presses don't emit `Triggered`.
+    async fn set_focus_mode(
+        &self,
+        enabled: bool,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> zbus::fdo::Result<()> {
+        let previous = self.focus_mode.swap(enabled, Ordering::SeqCst);
+        if previous != enabled {
+            info!(
+                "Focus mode {}",
+                if enabled { "enabled" } else { "disabled" }
+            );
+            self.focus_mode_changed(&ctxt).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether focus mode is on
+    #[zbus(property)]
+    fn focus_mode(&self) -> bool {
+        self.focus_mode.load(Ordering::SeqCst)
+    }
+
+    /// Does nothing; lets callers check the daemon is alive
+    fn ping(&self) {}
+
+    /// Gets the daemon version
+    fn get_version(&self) -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+
+    /// Changes the maximum time between taps until the daemon restarts or
+    /// reloads its config
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgs` if `ms` is outside 50-2000
+    async fn set_interval(
+        &self,
+        ms: u32,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> zbus::fdo::Result<()> {
+        if !INTERVAL_RANGE_MS.contains(&ms) {
+            return Err(zbus::fdo::Error::InvalidArgs(format!(
+                "interval must be {}-{}ms, got {}",
+                INTERVAL_RANGE_MS.start(),
+                INTERVAL_RANGE_MS.end(),
+                ms
+            )));
+        }
+        self.recognizer
+            .lock()
+            .await
+            .set_interval(Duration::from_millis(ms.into()));
+        info!("Tap interval set to {}ms", ms);
+        self.interval_changed(&ctxt).await?;
+        Ok(())
+    }
+
+    /// Maximum time between taps, in milliseconds
+    #[zbus(property)]
+    async fn interval(&self) -> u32 {
+        self.recognizer.lock().await.interval().as_millis() as u32
+    }
+
+    /// Reloads `daemon.toml`: bindings, timings and devices
+    ///
+    /// # Errors
+    ///
+    /// Returns `Failed` if the file can't be read or is invalid; the
+    /// current config stays in effect
+    async fn reload(&self) -> zbus::fdo::Result<()> {
+        let (reply, outcome) = oneshot::channel();
+        let shutting_down = || zbus::fdo::Error::Failed("daemon is shutting down".to_string());
+        self.reload.send(reply).await.map_err(|_| shutting_down())?;
+        outcome
+            .await
+            .map_err(|_| shutting_down())?
+            .map_err(zbus::fdo::Error::Failed)
+    }
+
+    /// Plays a macro from the `[macros]` section of `daemon.toml`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgs` if no macro has that name and `Failed` if the
+    /// virtual keyboard is unavailable or can't emit the keys
+    ///
+    /// The keys are emitted on a blocking thread (see
+    /// [`crate::uinput::run_blocking`]), so other calls are served meanwhile.
+    async fn play_macro(&self, name: &str) -> zbus::fdo::Result<()> {
+        let (steps, delay) = {
+            let config = self.config.borrow();
+            (config.macros.get(name).cloned(), config.key_delay())
+        };
+        let steps = steps
+            .ok_or_else(|| zbus::fdo::Error::InvalidArgs(format!("unknown macro {:?}", name)))?;
+        let keyboard = self.keyboard.as_ref().ok_or_else(|| {
+            zbus::fdo::Error::Failed("virtual keyboard is not available".to_string())
+        })?;
+        crate::uinput::run_blocking(keyboard, move |keyboard| keyboard.play(&steps, delay))
+            .await
+            .map_err(|e| zbus::fdo::Error::Failed(e.to_string()))?;
+        info!("Played macro {:?}", name);
+        Ok(())
+    }
+
+    /// Emits `Triggered` as if Ctrl had been double-tapped
+    ///
+    /// Unlike a gesture, this works in focus mode: it's an explicit request.
+    async fn trigger_now(&self, #[zbus(connection)] conn: &Connection) -> zbus::fdo::Result<()> {
+        let info = TriggerInfo::now("", "manual");
+        crate::notify_trigger(conn, crate::config::SIGNALS[0], &info).await?;
+        self.stats.record_signal();
+        Ok(())
+    }
+
+    /// Gets counters since the daemon started
+    ///
+    /// Keys: `uptime_secs`, `events`, `gestures`, `signals` (all `t`) and
+    /// `devices` (`a{st}`, events per keyboard name).
+    fn get_stats(&self) -> DaemonStats {
+        self.stats.snapshot()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(focus_mode: &Arc<AtomicBool>) -> DaemonService {
+        let recognizer = Recognizer::new(&Config::default());
+        let (reload, _) = mpsc::channel(1);
+        let (_, config) = watch::channel(Arc::new(Config::default()));
+        DaemonService::new(
+            Arc::clone(focus_mode),
+            Arc::new(Mutex::new(recognizer)),
+            reload,
+            config,
+            None,
+            Arc::new(Stats::default()),
+        )
+    }
+
+    #[test]
+    fn test_interface_name() {
+        assert_eq!(
+            <DaemonService as zbus::Interface>::name(),
+            uti_dbus::DAEMON_INTERFACE
+        );
+    }
+
+    #[test]
+    fn test_focus_mode_shares_state() {
+        let focus_mode = Arc::new(AtomicBool::new(false));
+        let service = service(&focus_mode);
+        assert!(!service.focus_mode());
+
+        focus_mode.store(true, Ordering::SeqCst);
+        assert!(service.focus_mode());
+    }
+
+    #[tokio::test]
+    async fn test_interval_reads_recognizer() {
+        let service = service(&Arc::new(AtomicBool::new(false)));
+        assert_eq!(service.interval().await, 300);
+
+        service
+            .recognizer
+            .lock()
+            .await
+            .set_interval(Duration::from_millis(450));
+        assert_eq!(service.interval().await, 450);
+    }
+
+    #[tokio::test]
+    async fn test_play_unknown_macro() {
+        let service = service(&Arc::new(AtomicBool::new(false)));
+        assert!(matches!(
+            service.play_macro("nope").await,
+            Err(zbus::fdo::Error::InvalidArgs(_))
+        ));
+    }
+}
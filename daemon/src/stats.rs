@@ -0,0 +1,91 @@
+//! Daemon statistics
+//!
+//! Counters since the daemon started, served by `GetStats()` on the Daemon
+//! interface so the app and scripts can check the daemon is healthy, e.g.,
+//! that a keyboard still sends events after resume.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use uti_dbus::daemon::DaemonStats;
+
+/// Counters shared by the monitors and the D-Bus service
+pub struct Stats {
+    started: Instant,
+    events: AtomicU64,
+    gestures: AtomicU64,
+    signals: AtomicU64,
+    /// Events read from each keyboard, by device name
+    devices: Mutex<HashMap<String, u64>>,
+}
+
+impl Default for Stats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            events: AtomicU64::new(0),
+            gestures: AtomicU64::new(0),
+            signals: AtomicU64::new(0),
+            devices: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Stats {
+    /// Counts an input event read from a keyboard
+    pub fn record_event(&self, device_name: &str) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+        let mut devices = self.devices.lock().unwrap();
+        match devices.get_mut(device_name) {
+            Some(count) => *count += 1,
+            None => {
+                devices.insert(device_name.to_string(), 1);
+            }
+        }
+    }
+
+    /// Counts a recognized gesture that has a binding
+    pub fn record_gesture(&self) {
+        self.gestures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Counts an emitted gesture signal
+    pub fn record_signal(&self) {
+        self.signals.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Gets the current counters
+    pub fn snapshot(&self) -> DaemonStats {
+        DaemonStats {
+            uptime_secs: self.started.elapsed().as_secs(),
+            events: self.events.load(Ordering::Relaxed),
+            gestures: self.gestures.load(Ordering::Relaxed),
+            signals: self.signals.load(Ordering::Relaxed),
+            devices: self.devices.lock().unwrap().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_counts() {
+        let stats = Stats::default();
+        stats.record_event("Keychron K2");
+        stats.record_event("Keychron K2");
+        stats.record_event("AT Translated Set 2 keyboard");
+        stats.record_gesture();
+        stats.record_signal();
+
+        let snapshot = stats.snapshot();
+        assert_eq!(snapshot.uptime_secs, 0);
+        assert_eq!(snapshot.events, 3);
+        assert_eq!(snapshot.gestures, 1);
+        assert_eq!(snapshot.signals, 1);
+        assert_eq!(snapshot.devices["Keychron K2"], 2);
+        assert_eq!(snapshot.devices.len(), 2);
+    }
+}
@@ -0,0 +1,50 @@
+//! systemd service notifications
+//!
+//! `uti-daemon.service` is `Type=notify` with a watchdog: the daemon reports
+//! `READY=1` once its devices are monitored, then pings the watchdog while
+//! its runtime and shared state stay responsive. If it wedges, systemd
+//! restarts it. Outside systemd, `NOTIFY_SOCKET` is unset and these do
+//! nothing.
+
+use crate::gesture::Recognizer;
+use log::{debug, info, warn};
+use sd_notify::NotifyState;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Tells systemd the daemon is up
+pub fn notify_ready() {
+    if let Err(e) = sd_notify::notify(false, &[NotifyState::Ready]) {
+        warn!("Failed to notify systemd: {}", e);
+    }
+}
+
+/// Gets how often to ping the watchdog: half its timeout
+///
+/// Returns None if the watchdog isn't enabled for this process.
+fn ping_interval() -> Option<Duration> {
+    let mut usec = 0;
+    sd_notify::watchdog_enabled(false, &mut usec).then(|| Duration::from_micros(usec) / 2)
+}
+
+/// Pings the systemd watchdog until the daemon exits
+///
+/// Each ping first takes the recognizer lock the monitors share, so a
+/// deadlocked monitor stops the pings as well as a stalled runtime.
+pub async fn run_watchdog(recognizer: Arc<Mutex<Recognizer>>) {
+    let Some(interval) = ping_interval() else {
+        debug!("systemd watchdog not enabled");
+        return std::future::pending().await;
+    };
+    info!("Pinging systemd watchdog every {:?}", interval);
+
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        ticker.tick().await;
+        drop(recognizer.lock().await);
+        if let Err(e) = sd_notify::notify(false, &[NotifyState::Watchdog]) {
+            warn!("Failed to ping systemd watchdog: {}", e);
+        }
+    }
+}
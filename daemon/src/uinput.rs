@@ -3,15 +3,86 @@
 //! This module provides a virtual keyboard implementation using evdev uinput
 //! to simulate keyboard input events.
 
+use crate::layout::Layout;
+use crate::macros::{Combo, Step};
 use evdev::uinput::VirtualDeviceBuilder;
-use evdev::{AttributeSet, InputEvent, Key};
+use evdev::{AttributeSet, BusType, InputEvent, InputId, Key};
 use log::{debug, error, info};
 use std::io;
+use std::sync::Arc;
 use std::thread::sleep;
 use std::time::Duration;
+use tokio::sync::Mutex;
 
-/// Delay between key events to ensure proper handling
-const KEY_EVENT_DELAY: Duration = Duration::from_millis(10);
+/// Default delay between key events to ensure proper handling
+pub const KEY_EVENT_DELAY: Duration = Duration::from_millis(10);
+
+/// Name of the virtual keyboard device
+pub const DEVICE_NAME: &str = "uti Virtual Keyboard";
+
+/// Vendor ID of the virtual keyboard device ("ut")
+const VENDOR_ID: u16 = 0x7574;
+
+/// Product ID of the virtual keyboard device
+const PRODUCT_ID: u16 = 0x0001;
+
+/// Highest key code the virtual keyboard emits (`KEY_MICMUTE`); covers every
+/// key a layout can type with
+pub const MAX_KEY_CODE: u16 = 248;
+
+/// Checks whether a device is uti's own virtual keyboard
+///
+/// Matches by name or by bus, vendor and product ID, so the daemon never
+/// monitors the key presses it emits itself.
+///
+/// # Arguments
+///
+/// * `name` - Device name
+/// * `id` - Device bus, vendor and product ID
+pub fn is_virtual_keyboard(name: Option<&str>, id: &InputId) -> bool {
+    name == Some(DEVICE_NAME)
+        || (id.bus_type() == BusType::BUS_VIRTUAL
+            && id.vendor() == VENDOR_ID
+            && id.product() == PRODUCT_ID)
+}
+
+/// Keyboard shortcut used to paste
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PasteShortcut {
+    /// Ctrl+V, for GUI apps
+    CtrlV,
+    /// Ctrl+Shift+V, which also works in terminals and pastes plain text in
+    /// GUI apps
+    CtrlShiftV,
+}
+
+impl PasteShortcut {
+    /// Parses a shortcut name from the TypeText signal: "ctrl-v" or
+    /// "ctrl-shift-v"
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ctrl-v" => Some(Self::CtrlV),
+            "ctrl-shift-v" => Some(Self::CtrlShiftV),
+            _ => None,
+        }
+    }
+
+    /// Macro steps that paste with this shortcut, then press Enter if asked
+    pub fn steps(self, enter: bool) -> Vec<Step> {
+        let modifiers = match self {
+            Self::CtrlV => vec![Key::KEY_LEFTCTRL],
+            Self::CtrlShiftV => vec![Key::KEY_LEFTCTRL, Key::KEY_LEFTSHIFT],
+        };
+        let mut steps = vec![Step::new(Combo {
+            modifiers,
+            key: Key::KEY_V,
+        })];
+        if enter {
+            steps.push(Step::new(Combo::key(Key::KEY_ENTER)));
+        }
+        steps
+    }
+}
 
 /// Virtual keyboard device for simulating key presses
 ///
@@ -35,15 +106,15 @@ impl VirtualKeyboard {
     /// - No permission to access /dev/uinput
     /// - Failed to create the virtual device
     pub fn new() -> io::Result<Self> {
-        // Define which keys the virtual device supports
+        // Support every keyboard key so any layout's keys can be typed
         let mut keys = AttributeSet::<Key>::new();
-        keys.insert(Key::KEY_LEFTCTRL);
-        keys.insert(Key::KEY_LEFTSHIFT);
-        keys.insert(Key::KEY_V);
-        keys.insert(Key::KEY_ENTER);
+        for code in 1..=MAX_KEY_CODE {
+            keys.insert(Key::new(code));
+        }
 
         let device = VirtualDeviceBuilder::new()?
-            .name("uti Virtual Keyboard")
+            .name(DEVICE_NAME)
+            .input_id(InputId::new(BusType::BUS_VIRTUAL, VENDOR_ID, PRODUCT_ID, 1))
             .with_keys(&keys)?
             .build()?;
 
@@ -51,61 +122,107 @@ impl VirtualKeyboard {
         Ok(Self { device })
     }
 
-    /// Simulates Ctrl+Shift+V (paste) followed by Enter
-    ///
-    /// Uses Ctrl+Shift+V to work in both terminals (where Ctrl+V doesn't work)
-    /// and GUI applications (where it pastes as plain text).
-    /// After pasting, sends Enter to submit the text (useful for terminals).
+    /// Simulates a paste shortcut, optionally followed by Enter
     ///
     /// Emits the key events in the following sequence:
-    /// 1. Press Ctrl, Shift, V (paste)
-    /// 2. Release V, Shift, Ctrl
-    /// 3. Press Enter (submit)
-    /// 4. Release Enter
+    /// 1. Press the shortcut's modifiers and V, then release them in reverse
+    /// 2. If `enter` is set, press and release Enter (e.g., to run a command
+    ///    in a terminal)
+    ///
+    /// # Arguments
+    ///
+    /// * `shortcut` - Paste shortcut to press
+    /// * `enter` - Whether to press Enter after pasting
+    /// * `delay` - Time between key events
     ///
     /// # Returns
     ///
     /// Returns `Ok(())` if the keystroke was simulated successfully,
     /// or an `io::Error` if it failed.
-    pub fn paste(&mut self) -> io::Result<()> {
-        debug!("Simulating Ctrl+Shift+V + Enter");
+    pub fn paste(
+        &mut self,
+        shortcut: PasteShortcut,
+        enter: bool,
+        delay: Duration,
+    ) -> io::Result<()> {
+        debug!(
+            "Simulating {:?} (Enter: {}, delay: {:?})",
+            shortcut, enter, delay
+        );
 
-        // Key press values: 1 = press, 0 = release
-        const PRESS: i32 = 1;
-        const RELEASE: i32 = 0;
+        self.play(&shortcut.steps(enter), delay)?;
 
-        // Press Ctrl
-        self.emit_key(Key::KEY_LEFTCTRL, PRESS)?;
-        sleep(KEY_EVENT_DELAY);
-
-        // Press Shift
-        self.emit_key(Key::KEY_LEFTSHIFT, PRESS)?;
-        sleep(KEY_EVENT_DELAY);
-
-        // Press V
-        self.emit_key(Key::KEY_V, PRESS)?;
-        sleep(KEY_EVENT_DELAY);
-
-        // Release V
-        self.emit_key(Key::KEY_V, RELEASE)?;
-        sleep(KEY_EVENT_DELAY);
-
-        // Release Shift
-        self.emit_key(Key::KEY_LEFTSHIFT, RELEASE)?;
-        sleep(KEY_EVENT_DELAY);
+        info!("{:?} simulated successfully", shortcut);
+        Ok(())
+    }
 
-        // Release Ctrl
-        self.emit_key(Key::KEY_LEFTCTRL, RELEASE)?;
-        sleep(KEY_EVENT_DELAY);
+    /// Plays a key macro
+    ///
+    /// Waits for each step's delay, then presses its keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - Macro steps, see [`crate::macros`]
+    /// * `delay` - Time between key events
+    pub fn play(&mut self, steps: &[Step], delay: Duration) -> io::Result<()> {
+        for step in steps {
+            sleep(step.delay());
+            self.tap(&step.keys.modifiers, step.keys.key, delay)?;
+        }
+        Ok(())
+    }
 
-        // Press Enter
-        self.emit_key(Key::KEY_ENTER, PRESS)?;
-        sleep(KEY_EVENT_DELAY);
+    /// Types text by pressing the keys for each character
+    ///
+    /// Falls back to pasting with Ctrl+Shift+V (without Enter) if the layout
+    /// can't type some character or isn't available, so the text must also
+    /// be on the clipboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to type
+    /// * `layout` - Keyboard layout of the focused session
+    /// * `delay` - Time between key events
+    pub fn type_string(
+        &mut self,
+        text: &str,
+        layout: Option<&Layout>,
+        delay: Duration,
+    ) -> io::Result<()> {
+        let Some(strokes) = layout.and_then(|layout| layout.strokes(text)) else {
+            info!("Text can't be typed with the keyboard layout, pasting instead");
+            return self.paste(PasteShortcut::CtrlShiftV, false, delay);
+        };
 
-        // Release Enter
-        self.emit_key(Key::KEY_ENTER, RELEASE)?;
+        debug!("Typing {} characters", strokes.len());
+        for stroke in strokes {
+            let mut modifiers = Vec::new();
+            if stroke.shift {
+                modifiers.push(Key::KEY_LEFTSHIFT);
+            }
+            if stroke.altgr {
+                modifiers.push(Key::KEY_RIGHTALT);
+            }
+            self.tap(&modifiers, stroke.key, delay)?;
+        }
+        info!("Text typed successfully");
+        Ok(())
+    }
 
-        info!("Ctrl+Shift+V + Enter simulated successfully");
+    /// Presses modifiers and a key, then releases them in reverse order
+    fn tap(&mut self, modifiers: &[Key], key: Key, delay: Duration) -> io::Result<()> {
+        for &modifier in modifiers {
+            self.emit_key(modifier, 1)?;
+            sleep(delay);
+        }
+        self.emit_key(key, 1)?;
+        sleep(delay);
+        self.emit_key(key, 0)?;
+        sleep(delay);
+        for &modifier in modifiers.iter().rev() {
+            self.emit_key(modifier, 0)?;
+            sleep(delay);
+        }
         Ok(())
     }
 
@@ -132,6 +249,26 @@ impl VirtualKeyboard {
     }
 }
 
+/// Runs work on the virtual keyboard on a blocking thread
+///
+/// Key events are spaced with [`sleep`], which mustn't run on the async
+/// runtime. The keyboard stays locked until the work is done, so pastes,
+/// typed text and macros don't interleave.
+///
+/// # Arguments
+///
+/// * `keyboard` - Virtual keyboard shared by the input task and `PlayMacro`
+/// * `work` - Key events to emit
+pub async fn run_blocking<F>(keyboard: &Arc<Mutex<VirtualKeyboard>>, work: F) -> io::Result<()>
+where
+    F: FnOnce(&mut VirtualKeyboard) -> io::Result<()> + Send + 'static,
+{
+    let keyboard = Arc::clone(keyboard);
+    tokio::task::spawn_blocking(move || work(&mut keyboard.blocking_lock()))
+        .await
+        .unwrap_or_else(|e| Err(io::Error::other(e)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +277,39 @@ mod tests {
     fn test_key_event_delay_constant() {
         assert_eq!(KEY_EVENT_DELAY, Duration::from_millis(10));
     }
+
+    #[test]
+    fn test_paste_shortcut_from_name() {
+        assert_eq!(
+            PasteShortcut::from_name("ctrl-v"),
+            Some(PasteShortcut::CtrlV)
+        );
+        assert_eq!(PasteShortcut::from_name("ctrl-insert"), None);
+    }
+
+    #[test]
+    fn test_paste_shortcut_steps() {
+        assert_eq!(
+            PasteShortcut::CtrlShiftV.steps(true),
+            [
+                Step::new("ctrl+shift+v".parse().unwrap()),
+                Step::new(Combo::key(Key::KEY_ENTER)),
+            ]
+        );
+        assert_eq!(
+            PasteShortcut::CtrlV.steps(false),
+            [Step::new("ctrl+v".parse().unwrap())]
+        );
+    }
+
+    #[test]
+    fn test_is_virtual_keyboard() {
+        let own_id = InputId::new(BusType::BUS_VIRTUAL, VENDOR_ID, PRODUCT_ID, 1);
+        let usb_id = InputId::new(BusType::BUS_USB, 0x046d, 0xc52b, 0x0111);
+
+        assert!(is_virtual_keyboard(Some(DEVICE_NAME), &usb_id));
+        assert!(is_virtual_keyboard(None, &own_id));
+        assert!(!is_virtual_keyboard(Some("Logitech USB Receiver"), &usb_id));
+        assert!(!is_virtual_keyboard(None, &usb_id));
+    }
 }
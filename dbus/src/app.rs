@@ -0,0 +1,59 @@
+//! `io.github.noppomario.uti.App`: methods the app serves and signals it
+//! emits
+//!
+//! The app implements the interface (its state lives there). The daemon
+//! reads the `TypeText` and `TypeString` signals with the types generated
+//! here, e.g., [`TypeText::from_message`], and tools call the methods
+//! through [`AppProxy`].
+
+use zbus::proxy;
+
+/// Client side of the interface
+#[proxy(
+    interface = "io.github.noppomario.uti.App",
+    default_service = "io.github.noppomario.uti.App",
+    default_path = "/io/github/noppomario/uti/App"
+)]
+pub trait App {
+    /// Toggles the main window, like a double Ctrl press
+    fn toggle_window(&self) -> zbus::Result<()>;
+
+    /// Shows and focuses the main window, e.g., when uti is launched again
+    fn show_window(&self) -> zbus::Result<()>;
+
+    /// Shows the main window on a tab: "prompt", "clipboard", "snippets"
+    /// or "launcher"
+    fn open_tab(&self, tab: &str) -> zbus::Result<()>;
+
+    /// Pastes a clipboard history item, by id, into the focused window
+    fn paste_item(&self, id: &str) -> zbus::Result<()>;
+
+    /// Adds text to the clipboard history
+    fn add_clip(&self, text: &str) -> zbus::Result<()>;
+
+    /// Records the focused window (GNOME extension)
+    fn set_active_window(&self, title: &str, app_id: &str) -> zbus::Result<()>;
+
+    /// Shows the main window next to the pointer at `x`, `y` (GNOME
+    /// extension)
+    fn show_at(&self, x: i32, y: i32) -> zbus::Result<()>;
+
+    /// Reloads config.json after it was changed outside the app
+    fn reload_config(&self) -> zbus::Result<()>;
+
+    /// Whether the window is pinned (kept above other windows)
+    #[zbus(property)]
+    fn pinned(&self) -> zbus::Result<bool>;
+
+    /// Asks the daemon to paste the clipboard into the focused window
+    ///
+    /// `shortcut` is "ctrl-v" or "ctrl-shift-v"; `enter` presses Enter
+    /// after pasting; `key_delay_ms` is the time between key events, 0 for
+    /// the daemon's default.
+    #[zbus(signal)]
+    fn type_text(&self, shortcut: &str, enter: bool, key_delay_ms: u32) -> zbus::Result<()>;
+
+    /// Asks the daemon to type text key by key into the focused window
+    #[zbus(signal)]
+    fn type_string(&self, text: &str) -> zbus::Result<()>;
+}
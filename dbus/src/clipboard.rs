@@ -0,0 +1,28 @@
+//! `io.github.noppomario.uti.Clipboard`: clipboard history and snippets
+//!
+//! The app serves the interface so scripts and launchers (e.g., rofi) can
+//! read the history, copy text and paste snippets without the window.
+//! Clients call it through [`ClipboardProxy`].
+
+use zbus::proxy;
+
+/// Client side of the interface
+#[proxy(
+    interface = "io.github.noppomario.uti.Clipboard",
+    default_service = "io.github.noppomario.uti.App",
+    default_path = "/io/github/noppomario/uti/Clipboard"
+)]
+pub trait Clipboard {
+    /// Gets the text of the newest history items, newest first; 0 for all
+    fn get_history(&self, limit: u32) -> zbus::Result<Vec<String>>;
+
+    /// Puts text on the system clipboard and adds it to the history
+    fn copy(&self, text: &str) -> zbus::Result<()>;
+
+    /// Lists snippets as (id, label) pairs; the label is the value when
+    /// the snippet has none
+    fn get_snippets(&self) -> zbus::Result<Vec<(String, String)>>;
+
+    /// Expands a snippet and pastes it into the focused window
+    fn paste_snippet(&self, id: &str) -> zbus::Result<()>;
+}
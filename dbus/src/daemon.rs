@@ -0,0 +1,66 @@
+//! `io.github.noppomario.uti.Daemon`: methods the daemon serves
+//!
+//! The daemon implements the interface (its state lives there); the app,
+//! scripts and tests call it through [`DaemonProxy`].
+
+use std::collections::HashMap;
+use zbus::proxy;
+use zbus::zvariant::{DeserializeDict, SerializeDict, Type};
+
+/// Counters returned by `GetStats`, sent as an `a{sv}` dictionary
+#[derive(Debug, Clone, Default, PartialEq, SerializeDict, DeserializeDict, Type)]
+#[zvariant(signature = "a{sv}")]
+pub struct DaemonStats {
+    /// Seconds since the daemon started
+    pub uptime_secs: u64,
+    /// Input events read from all keyboards
+    pub events: u64,
+    /// Bound gestures recognized, including ones suppressed by focus mode
+    pub gestures: u64,
+    /// Gesture signals emitted
+    pub signals: u64,
+    /// Events read from each keyboard, by device name; keyboards with the
+    /// same name are counted together
+    pub devices: HashMap<String, u64>,
+}
+
+/// Client side of the interface
+#[proxy(
+    interface = "io.github.noppomario.uti.Daemon",
+    default_service = "io.github.noppomario.uti",
+    default_path = "/io/github/noppomario/uti/Daemon"
+)]
+pub trait Daemon {
+    /// Enables or disables focus mode, which suppresses gesture signals
+    fn set_focus_mode(&self, enabled: bool) -> zbus::Result<()>;
+
+    /// Does nothing; checks the daemon is alive
+    fn ping(&self) -> zbus::Result<()>;
+
+    /// Gets the daemon version
+    fn get_version(&self) -> zbus::Result<String>;
+
+    /// Changes the maximum time between taps (50-2000ms) until the daemon
+    /// restarts or reloads its config
+    fn set_interval(&self, ms: u32) -> zbus::Result<()>;
+
+    /// Reloads `daemon.toml`
+    fn reload(&self) -> zbus::Result<()>;
+
+    /// Plays a macro from the `[macros]` section of `daemon.toml`
+    fn play_macro(&self, name: &str) -> zbus::Result<()>;
+
+    /// Emits `Triggered`, even in focus mode
+    fn trigger_now(&self) -> zbus::Result<()>;
+
+    /// Gets counters since the daemon started
+    fn get_stats(&self) -> zbus::Result<DaemonStats>;
+
+    /// Whether focus mode is on
+    #[zbus(property)]
+    fn focus_mode(&self) -> zbus::Result<bool>;
+
+    /// Maximum time between taps, in milliseconds
+    #[zbus(property)]
+    fn interval(&self) -> zbus::Result<u32>;
+}
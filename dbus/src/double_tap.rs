@@ -0,0 +1,227 @@
+//! `io.github.noppomario.uti.DoubleTap`: gesture signals
+//!
+//! The daemon serves [`DoubleTap`] at [`crate::DOUBLE_TAP_PATH`] so the
+//! signals show up in `busctl introspect`, and emits the one bound to a
+//! recognized gesture in `daemon.toml`. The app and the GNOME extension
+//! listen for them.
+//!
+//! Every signal carries a [`TriggerInfo`]: the keyboard, the gesture and
+//! when it happened, so listeners can tell gestures apart and drop signals
+//! delivered late (e.g., queued while the system was suspended).
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use zbus::message::Message;
+use zbus::object_server::SignalContext;
+use zbus::{interface, proxy};
+
+/// Signal names, in the order bindings list them
+///
+/// `Triggered` toggles the app window; the others open it on a tab.
+pub const SIGNALS: [&str; 5] = [
+    "Triggered",
+    "TriggeredPrompt",
+    "TriggeredClipboard",
+    "TriggeredSnippets",
+    "TriggeredLauncher",
+];
+
+/// Where and when a trigger happened, the arguments of every signal
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerInfo {
+    /// Name of the keyboard, empty for `TriggerNow`
+    pub source: String,
+    /// `double-tap`, `triple-tap`, `hold`, or `manual` for `TriggerNow`
+    pub gesture: String,
+    /// When the gesture was recognized, in milliseconds since the Unix epoch
+    pub timestamp_ms: u64,
+}
+
+impl TriggerInfo {
+    /// Info for a trigger happening now
+    pub fn now(source: &str, gesture: &str) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        Self {
+            source: source.to_string(),
+            gesture: gesture.to_string(),
+            timestamp_ms,
+        }
+    }
+
+    /// Reads the arguments of a DoubleTap signal
+    ///
+    /// # Returns
+    ///
+    /// None if the signal has no arguments (sent by an older daemon)
+    pub fn from_message(msg: &Message) -> Option<Self> {
+        let (source, gesture, timestamp_ms) =
+            msg.body().deserialize::<(String, String, u64)>().ok()?;
+        Some(Self {
+            source,
+            gesture,
+            timestamp_ms,
+        })
+    }
+}
+
+/// Server side of the interface; it only has signals
+pub struct DoubleTap;
+
+#[interface(name = "io.github.noppomario.uti.DoubleTap")]
+impl DoubleTap {
+    /// Toggle the app window
+    #[zbus(signal)]
+    pub async fn triggered(
+        ctxt: &SignalContext<'_>,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+
+    /// Open the app on the prompt tab
+    #[zbus(signal)]
+    pub async fn triggered_prompt(
+        ctxt: &SignalContext<'_>,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+
+    /// Open the app on the clipboard tab
+    #[zbus(signal)]
+    pub async fn triggered_clipboard(
+        ctxt: &SignalContext<'_>,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+
+    /// Open the app on the snippets tab
+    #[zbus(signal)]
+    pub async fn triggered_snippets(
+        ctxt: &SignalContext<'_>,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+
+    /// Open the app on the launcher tab
+    #[zbus(signal)]
+    pub async fn triggered_launcher(
+        ctxt: &SignalContext<'_>,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+}
+
+impl DoubleTap {
+    /// Emits a signal by name
+    ///
+    /// # Errors
+    ///
+    /// Returns `Failure` if `signal` isn't one of [`SIGNALS`], or the error
+    /// sending it
+    pub async fn emit(
+        ctxt: &SignalContext<'_>,
+        signal: &str,
+        info: &TriggerInfo,
+    ) -> zbus::Result<()> {
+        let TriggerInfo {
+            source,
+            gesture,
+            timestamp_ms,
+        } = info;
+        match signal {
+            "Triggered" => Self::triggered(ctxt, source, gesture, *timestamp_ms).await,
+            "TriggeredPrompt" => Self::triggered_prompt(ctxt, source, gesture, *timestamp_ms).await,
+            "TriggeredClipboard" => {
+                Self::triggered_clipboard(ctxt, source, gesture, *timestamp_ms).await
+            },
+            "TriggeredSnippets" => {
+                Self::triggered_snippets(ctxt, source, gesture, *timestamp_ms).await
+            },
+            "TriggeredLauncher" => {
+                Self::triggered_launcher(ctxt, source, gesture, *timestamp_ms).await
+            },
+            _ => Err(zbus::Error::Failure(format!("unknown signal {:?}", signal))),
+        }
+    }
+}
+
+/// Client side of the interface, for listening to the signals
+#[proxy(
+    interface = "io.github.noppomario.uti.DoubleTap",
+    default_service = "io.github.noppomario.uti",
+    default_path = "/io/github/noppomario/uti/DoubleTap"
+)]
+pub trait DoubleTap {
+    #[zbus(signal)]
+    fn triggered(&self, source: &str, gesture: &str, timestamp_ms: u64) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn triggered_prompt(&self, source: &str, gesture: &str, timestamp_ms: u64) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn triggered_clipboard(
+        &self,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn triggered_snippets(
+        &self,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    fn triggered_launcher(
+        &self,
+        source: &str,
+        gesture: &str,
+        timestamp_ms: u64,
+    ) -> zbus::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zbus::Interface;
+
+    #[test]
+    fn test_interface_name() {
+        assert_eq!(DoubleTap::name(), crate::DOUBLE_TAP_INTERFACE);
+    }
+
+    #[test]
+    fn test_trigger_info_from_message() {
+        let msg = Message::signal(
+            crate::DOUBLE_TAP_PATH,
+            crate::DOUBLE_TAP_INTERFACE,
+            "TriggeredLauncher",
+        )
+        .unwrap()
+        .build(&("Keychron K2", "hold", 1_700_000_000_000u64))
+        .unwrap();
+
+        let info = TriggerInfo::from_message(&msg).unwrap();
+        assert_eq!(info.source, "Keychron K2");
+        assert_eq!(info.gesture, "hold");
+        assert_eq!(info.timestamp_ms, 1_700_000_000_000);
+
+        // Same layout as the proxy's signal arguments
+        let launcher = TriggeredLauncher::from_message(msg).unwrap();
+        assert_eq!(launcher.args().unwrap().gesture, "hold");
+
+        let old = Message::signal(crate::DOUBLE_TAP_PATH, crate::DOUBLE_TAP_INTERFACE, "Triggered")
+            .unwrap()
+            .build(&())
+            .unwrap();
+        assert_eq!(TriggerInfo::from_message(&old), None);
+    }
+}
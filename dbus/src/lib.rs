@@ -0,0 +1,77 @@
+//! D-Bus interfaces shared by uti and uti-daemon
+//!
+//! Both binaries take bus names, object paths and signal signatures from
+//! here instead of copying strings, so a change to an interface is a
+//! compile error on the other side rather than a silently ignored signal.
+//!
+//! - [`double_tap`]: gesture signals the daemon emits
+//! - [`daemon`]: methods the daemon serves
+//! - [`app`]: methods the app serves and signals it emits
+//! - [`clipboard`]: clipboard history and snippets the app serves
+//! - [`socket`]: the same signals over a Unix socket, when there is no
+//!   session bus
+//!
+//! zbus attributes need string literals, so the names are repeated in the
+//! `#[interface]` and `#[proxy]` attributes; the tests check they match the
+//! constants below.
+
+pub mod app;
+pub mod clipboard;
+pub mod daemon;
+pub mod double_tap;
+pub mod socket;
+
+/// Bus name owned by the daemon
+pub const DAEMON_BUS_NAME: &str = "io.github.noppomario.uti";
+
+/// Interface of the daemon's methods
+pub const DAEMON_INTERFACE: &str = "io.github.noppomario.uti.Daemon";
+
+/// Object path of the daemon's methods
+pub const DAEMON_PATH: &str = "/io/github/noppomario/uti/Daemon";
+
+/// Interface of the daemon's gesture signals
+pub const DOUBLE_TAP_INTERFACE: &str = "io.github.noppomario.uti.DoubleTap";
+
+/// Object path of the daemon's gesture signals
+pub const DOUBLE_TAP_PATH: &str = "/io/github/noppomario/uti/DoubleTap";
+
+/// Bus name owned by the app
+pub const APP_BUS_NAME: &str = "io.github.noppomario.uti.App";
+
+/// Interface of the app's methods and signals
+pub const APP_INTERFACE: &str = "io.github.noppomario.uti.App";
+
+/// Object path of the app interface
+pub const APP_PATH: &str = "/io/github/noppomario/uti/App";
+
+/// Interface of the app's clipboard and snippets methods
+pub const CLIPBOARD_INTERFACE: &str = "io.github.noppomario.uti.Clipboard";
+
+/// Object path of the clipboard interface
+pub const CLIPBOARD_PATH: &str = "/io/github/noppomario/uti/Clipboard";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zbus::proxy::ProxyDefault;
+
+    #[test]
+    fn test_proxy_defaults_match_constants() {
+        assert_eq!(daemon::DaemonProxy::INTERFACE, Some(DAEMON_INTERFACE));
+        assert_eq!(daemon::DaemonProxy::DESTINATION, Some(DAEMON_BUS_NAME));
+        assert_eq!(daemon::DaemonProxy::PATH, Some(DAEMON_PATH));
+
+        assert_eq!(double_tap::DoubleTapProxy::INTERFACE, Some(DOUBLE_TAP_INTERFACE));
+        assert_eq!(double_tap::DoubleTapProxy::DESTINATION, Some(DAEMON_BUS_NAME));
+        assert_eq!(double_tap::DoubleTapProxy::PATH, Some(DOUBLE_TAP_PATH));
+
+        assert_eq!(app::AppProxy::INTERFACE, Some(APP_INTERFACE));
+        assert_eq!(app::AppProxy::DESTINATION, Some(APP_BUS_NAME));
+        assert_eq!(app::AppProxy::PATH, Some(APP_PATH));
+
+        assert_eq!(clipboard::ClipboardProxy::INTERFACE, Some(CLIPBOARD_INTERFACE));
+        assert_eq!(clipboard::ClipboardProxy::DESTINATION, Some(APP_BUS_NAME));
+        assert_eq!(clipboard::ClipboardProxy::PATH, Some(CLIPBOARD_PATH));
+    }
+}
@@ -0,0 +1,109 @@
+//! Unix socket fallback for sessions without a usable D-Bus session bus
+//!
+//! Some minimal setups (no systemd, nested sessions) have no session bus.
+//! The daemon then serves a socket at [`socket_path`] and sends the same
+//! signals over it: one [`Message`] per line, as JSON.
+//!
+//! ```text
+//! {"signal":"Trigger","name":"Triggered","source":"Keychron K2","gesture":"double-tap","timestamp_ms":1700000000000}
+//! {"signal":"TypeText","shortcut":"ctrl-v","enter":false,"key_delay_ms":0}
+//! {"signal":"TypeString","text":"hello"}
+//! ```
+//!
+//! The daemon sends `Trigger` to every connected client; the app sends
+//! `TypeText` and `TypeString`.
+
+use crate::double_tap::TriggerInfo;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Socket file name inside `$XDG_RUNTIME_DIR`
+pub const SOCKET_NAME: &str = "uti-daemon.sock";
+
+/// Gets the path of the daemon's socket
+///
+/// # Returns
+///
+/// None if `XDG_RUNTIME_DIR` is not set
+pub fn socket_path() -> Option<PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR").map(|dir| PathBuf::from(dir).join(SOCKET_NAME))
+}
+
+/// A signal sent over the socket, with the arguments of its D-Bus
+/// counterpart
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "signal")]
+pub enum Message {
+    /// One of the DoubleTap signals, named by `name` (see
+    /// [`crate::double_tap::SIGNALS`])
+    Trigger {
+        name: String,
+        source: String,
+        gesture: String,
+        timestamp_ms: u64,
+    },
+    /// The App interface's `TypeText`
+    TypeText {
+        shortcut: String,
+        enter: bool,
+        key_delay_ms: u32,
+    },
+    /// The App interface's `TypeString`
+    TypeString { text: String },
+}
+
+impl Message {
+    /// Message for a DoubleTap signal
+    pub fn trigger(name: &str, info: &TriggerInfo) -> Self {
+        Self::Trigger {
+            name: name.to_string(),
+            source: info.source.clone(),
+            gesture: info.gesture.clone(),
+            timestamp_ms: info.timestamp_ms,
+        }
+    }
+
+    /// Encodes the message as a line, including the newline
+    pub fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self).expect("messages serialize");
+        line.push('\n');
+        line
+    }
+
+    /// Decodes a line
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error for a line that isn't a known message
+    pub fn from_line(line: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(line.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_round_trip() {
+        let info = TriggerInfo {
+            source: "Keychron K2".to_string(),
+            gesture: "double-tap".to_string(),
+            timestamp_ms: 1_700_000_000_000,
+        };
+        let message = Message::trigger("Triggered", &info);
+        let line = message.to_line();
+        assert!(line.ends_with('\n'));
+        assert!(line.starts_with(r#"{"signal":"Trigger","name":"Triggered""#));
+        assert_eq!(Message::from_line(&line).unwrap(), message);
+
+        let typed = Message::from_line(r#"{"signal":"TypeString","text":"hello"}"#).unwrap();
+        assert_eq!(
+            typed,
+            Message::TypeString {
+                text: "hello".to_string()
+            }
+        );
+        assert!(Message::from_line(r#"{"signal":"Reboot"}"#).is_err());
+    }
+}
@@ -4,10 +4,10 @@
  * Features:
  * 1. StatusNotifierWatcher service (replaces AppIndicator extension)
  * 2. StatusNotifierItem host for uti's tray icon
- * 3. Positions uti window at cursor location on Ctrl double-tap
+ * 3. Shows uti window at cursor location on Ctrl double-tap (App.ShowAt)
  *
  * Architecture:
- *   daemon (evdev) --D-Bus--> Extension --move window--> Tauri app
+ *   daemon (evdev) --D-Bus--> Extension --ShowAt(x, y)--> Tauri app
  *   Tauri app --RegisterStatusNotifierItem--> Extension (Watcher)
  *   Extension (Watcher) --create indicator--> GNOME Shell panel
  */
@@ -24,6 +24,10 @@ import * as PopupMenu from 'resource:///org/gnome/shell/ui/popupMenu.js';
 const DAEMON_BUS_NAME = 'io.github.noppomario.uti';
 const DAEMON_OBJECT_PATH = '/io/github/noppomario/uti/DoubleTap';
 const DAEMON_INTERFACE = 'io.github.noppomario.uti.DoubleTap';
+const APP_BUS_NAME = 'io.github.noppomario.uti.App';
+const APP_OBJECT_PATH = '/io/github/noppomario/uti/App';
+const APP_INTERFACE = 'io.github.noppomario.uti.App';
+const PROPERTIES_INTERFACE = 'org.freedesktop.DBus.Properties';
 const UTI_WM_CLASS = 'uti';
 
 // StatusNotifierItem interface
@@ -388,6 +392,9 @@ export default class UtiExtension extends Extension {
         this._settings = null;
         this._settingsChangedId = null;
         this._sniWatcher = null;
+        this._focusWindowId = null;
+        this._appWatcherId = null;
+        this._windowCreatedId = null;
     }
 
     enable() {
@@ -402,6 +409,7 @@ export default class UtiExtension extends Extension {
 
         // Always connect to D-Bus for cursor positioning
         this._connectToDbus();
+        this._watchFocus();
 
         // Start StatusNotifierWatcher if tray icon is enabled
         if (this._settings.get_boolean('enable-tray-icon')) {
@@ -415,6 +423,8 @@ export default class UtiExtension extends Extension {
         console.log('[uti] Extension disabled');
         this._removeIndicator();
         this._unwatchSNI();
+        this._unwatchFocus();
+        this._disconnectWindowCreated();
         this._disconnectFromDbus();
 
         if (this._sniWatcher) {
@@ -567,28 +577,32 @@ export default class UtiExtension extends Extension {
         try {
             this._dbusConnection = Gio.bus_get_sync(Gio.BusType.SESSION, null);
 
-            // Listen for Triggered signal (double Ctrl press)
+            // Listen for Triggered signals (double Ctrl press, or the
+            // Triggered<Tab> variants bound in daemon.toml)
             this._signalSubscriptionId = this._dbusConnection.signal_subscribe(
                 DAEMON_BUS_NAME,
                 DAEMON_INTERFACE,
-                'Triggered',
+                null,
                 DAEMON_OBJECT_PATH,
                 null,
                 Gio.DBusSignalFlags.NONE,
-                this._onTriggered.bind(this)
+                (_conn, _sender, _path, _iface, signalName) => {
+                    if (signalName.startsWith('Triggered'))
+                        this._onTriggered();
+                }
             );
 
-            // Listen for SetAlwaysOnTop signal (pin button toggle)
+            // Follow the app's Pinned property (pin button toggle)
             // This handles always-on-top on GNOME/Wayland where Tauri's
             // set_always_on_top() is ignored by Mutter
-            this._alwaysOnTopSubscriptionId = this._dbusConnection.signal_subscribe(
+            this._pinnedSubscriptionId = this._dbusConnection.signal_subscribe(
                 null, // Accept from any sender (the Tauri app)
-                DAEMON_INTERFACE,
-                'SetAlwaysOnTop',
-                DAEMON_OBJECT_PATH,
-                null,
+                PROPERTIES_INTERFACE,
+                'PropertiesChanged',
+                APP_OBJECT_PATH,
+                APP_INTERFACE, // arg0: interface whose properties changed
                 Gio.DBusSignalFlags.NONE,
-                this._onSetAlwaysOnTop.bind(this)
+                this._onAppPropertiesChanged.bind(this)
             );
 
             console.log('[uti] D-Bus connected');
@@ -602,20 +616,116 @@ export default class UtiExtension extends Extension {
             this._dbusConnection.signal_unsubscribe(this._signalSubscriptionId);
             this._signalSubscriptionId = null;
         }
-        if (this._alwaysOnTopSubscriptionId && this._dbusConnection) {
-            this._dbusConnection.signal_unsubscribe(this._alwaysOnTopSubscriptionId);
-            this._alwaysOnTopSubscriptionId = null;
+        if (this._pinnedSubscriptionId && this._dbusConnection) {
+            this._dbusConnection.signal_unsubscribe(this._pinnedSubscriptionId);
+            this._pinnedSubscriptionId = null;
         }
         this._dbusConnection = null;
     }
 
     /**
-     * Handle SetAlwaysOnTop signal from uti app
+     * Report the focused window to uti whenever focus changes
+     * uti can't query it on GNOME/Wayland, and needs it to skip copies
+     * made in apps excluded from clipboard history
+     */
+    _watchFocus() {
+        this._focusWindowId = global.display.connect(
+            'notify::focus-window',
+            this._reportFocusWindow.bind(this)
+        );
+
+        // Report the current window when uti starts, and catch up on its
+        // pin state (e.g., after the extension reloads)
+        this._appWatcherId = Gio.bus_watch_name(
+            Gio.BusType.SESSION,
+            APP_BUS_NAME,
+            Gio.BusNameWatcherFlags.NONE,
+            () => {
+                this._reportFocusWindow();
+                this._syncPinned();
+            },
+            null
+        );
+    }
+
+    _unwatchFocus() {
+        if (this._focusWindowId) {
+            global.display.disconnect(this._focusWindowId);
+            this._focusWindowId = null;
+        }
+        if (this._appWatcherId) {
+            Gio.bus_unwatch_name(this._appWatcherId);
+            this._appWatcherId = null;
+        }
+    }
+
+    _reportFocusWindow() {
+        if (!this._dbusConnection) {
+            return;
+        }
+        const window = global.display.focus_window;
+        const title = window?.get_title() ?? '';
+        const appId = window?.get_wm_class() ?? '';
+
+        // uti may not be running; errors are ignored
+        this._dbusConnection.call(
+            APP_BUS_NAME,
+            APP_OBJECT_PATH,
+            APP_INTERFACE,
+            'SetActiveWindow',
+            new GLib.Variant('(ss)', [title, appId]),
+            null,
+            Gio.DBusCallFlags.NO_AUTO_START,
+            -1,
+            null,
+            null
+        );
+    }
+
+    /**
+     * Read the app's Pinned property and apply it
+     */
+    _syncPinned() {
+        if (!this._dbusConnection) {
+            return;
+        }
+        this._dbusConnection.call(
+            APP_BUS_NAME,
+            APP_OBJECT_PATH,
+            PROPERTIES_INTERFACE,
+            'Get',
+            new GLib.Variant('(ss)', [APP_INTERFACE, 'Pinned']),
+            new GLib.VariantType('(v)'),
+            Gio.DBusCallFlags.NO_AUTO_START,
+            -1,
+            null,
+            (conn, result) => {
+                try {
+                    const [value] = conn.call_finish(result).deep_unpack();
+                    this._setAlwaysOnTop(value.unpack());
+                } catch (e) {
+                    console.log(`[uti] Failed to read Pinned: ${e.message}`);
+                }
+            }
+        );
+    }
+
+    /**
+     * Handle PropertiesChanged from uti app
+     */
+    _onAppPropertiesChanged(_conn, _sender, _path, _iface, _signal, params) {
+        const [, changed] = params.deep_unpack();
+        if ('Pinned' in changed) {
+            this._setAlwaysOnTop(changed.Pinned.unpack());
+        }
+    }
+
+    /**
+     * Keep uti's window above others, or stop doing so
      * Uses Meta.Window.make_above() to set window layer on GNOME/Mutter
      */
-    _onSetAlwaysOnTop(_conn, _sender, _path, _iface, _signal, params) {
-        const [enabled] = params.deep_unpack();
-        console.log(`[uti] SetAlwaysOnTop signal received: ${enabled}`);
+    _setAlwaysOnTop(enabled) {
+        console.log(`[uti] Pinned changed: ${enabled}`);
 
         const window = this._findUtiWindow();
         if (window) {
@@ -627,26 +737,70 @@ export default class UtiExtension extends Extension {
                 console.log('[uti] Window removed from always-on-top');
             }
         } else {
-            console.log('[uti] Window not found for SetAlwaysOnTop');
+            console.log('[uti] Window not found for Pinned');
         }
     }
 
     _onTriggered() {
         console.log('[uti] Triggered signal received');
+
+        // A visible window is hidden by the app's own toggle
+        if (this._findUtiWindow() || !this._dbusConnection) {
+            return;
+        }
+
+        // Report the pointer; the app shows the window there and replies
         const [x, y] = global.get_pointer();
+        this._dbusConnection.call(
+            APP_BUS_NAME,
+            APP_OBJECT_PATH,
+            APP_INTERFACE,
+            'ShowAt',
+            new GLib.Variant('(ii)', [x, y]),
+            null,
+            Gio.DBusCallFlags.NO_AUTO_START,
+            -1,
+            null,
+            (conn, result) => {
+                try {
+                    conn.call_finish(result);
+                } catch (e) {
+                    console.log(`[uti] ShowAt failed: ${e.message}`);
+                    return;
+                }
+                this._placeShownWindow(x, y);
+            }
+        );
+    }
+
+    /**
+     * Move the window shown by ShowAt to the pointer and activate it
+     * Mutter ignores client positioning on Wayland, so the extension moves
+     * the window once it's mapped
+     */
+    _placeShownWindow(x, y) {
         const window = this._findUtiWindow();
         if (window) {
             this._moveWindowToCursor(window, x, y);
             this._activateWindow(window);
-        } else {
-            GLib.timeout_add(GLib.PRIORITY_DEFAULT, 100, () => {
-                const win = this._findUtiWindow();
-                if (win) {
-                    const [cx, cy] = global.get_pointer();
-                    this._moveWindowToCursor(win, cx, cy);
-                }
-                return GLib.SOURCE_REMOVE;
-            });
+            return;
+        }
+
+        this._disconnectWindowCreated();
+        this._windowCreatedId = global.display.connect('window-created', (_display, win) => {
+            const wmClass = win.get_wm_class();
+            if (wmClass && wmClass.toLowerCase() === UTI_WM_CLASS) {
+                this._disconnectWindowCreated();
+                this._moveWindowToCursor(win, x, y);
+                this._activateWindow(win);
+            }
+        });
+    }
+
+    _disconnectWindowCreated() {
+        if (this._windowCreatedId) {
+            global.display.disconnect(this._windowCreatedId);
+            this._windowCreatedId = null;
         }
     }
 